@@ -0,0 +1,194 @@
+//! Account-abstraction activity: EIP-7702 set-code transactions and their authorizations,
+//! and top-level calls into the ERC-4337 EntryPoints, classified from full block bodies
+use crate::config::AA_WINDOW_BLOCKS;
+use crate::tx_types::TxType;
+use serde_json::Value;
+use std::collections::VecDeque;
+
+/// The canonical ERC-4337 EntryPoint deployments, by version.
+pub const ENTRY_POINTS: [(&str, &str); 3] = [
+    ("v0.6", "0x5ff137d4b0fdcd49dca30c7cf57e578a026d2789"),
+    ("v0.7", "0x0000000071727de22e5e9d8baf0edac6f37da032"),
+    ("v0.8", "0x4337084d9e255ff0702461cf8895ce9e3b5ff108"),
+];
+
+/// `handleOps(UserOperation[],address)` (v0.6) and `handleOps(PackedUserOperation[],address)`
+/// (v0.7 and later): the user operation count is the length of the first argument.
+const HANDLE_OPS: [&str; 2] = ["1fad948c", "765e827f"];
+
+/// What one block's transactions did with account abstraction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AaBlock {
+    pub number: u64,
+    pub transactions: usize,
+    pub set_code: usize,       // type-4 transactions
+    pub authorizations: usize, // entries across their authorization lists
+    pub entry_point: usize,    // transactions sent straight to an EntryPoint
+    pub user_ops: usize,       // decoded from the `handleOps` among them
+}
+
+impl AaBlock {
+    /// Classify a block fetched with full transaction objects; `None` for hash-only bodies.
+    pub fn of_block(block: &Value) -> Option<Self> {
+        let number = block
+            .get("number")
+            .and_then(Value::as_str)
+            .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())?;
+        let transactions = block.get("transactions")?.as_array()?;
+        if transactions.iter().any(Value::is_string) {
+            return None;
+        }
+        let mut counts = Self {
+            number,
+            transactions: transactions.len(),
+            ..Self::default()
+        };
+        for tx in transactions {
+            if TxType::of(tx) == Some(TxType::SetCode) {
+                counts.set_code += 1;
+                counts.authorizations += tx
+                    .get("authorizationList")
+                    .and_then(Value::as_array)
+                    .map_or(0, Vec::len);
+            }
+            let to = tx.get("to").and_then(Value::as_str).unwrap_or_default();
+            if ENTRY_POINTS
+                .iter()
+                .any(|(_, address)| address.eq_ignore_ascii_case(to))
+            {
+                counts.entry_point += 1;
+                let input = tx.get("input").and_then(Value::as_str).unwrap_or_default();
+                counts.user_ops += handle_ops_count(input).unwrap_or(0);
+            }
+        }
+        Some(counts)
+    }
+
+    pub fn is_aa(&self) -> bool {
+        self.set_code + self.entry_point > 0
+    }
+}
+
+/// The `ops` array length of a `handleOps` call: the word at the offset its first argument
+/// points to. `None` for other selectors or truncated calldata.
+fn handle_ops_count(input: &str) -> Option<usize> {
+    let hex = input.strip_prefix("0x").unwrap_or(input);
+    let (selector, args) = (hex.get(..8)?, hex.get(8..)?);
+    if !HANDLE_OPS
+        .iter()
+        .any(|known| known.eq_ignore_ascii_case(selector))
+    {
+        return None;
+    }
+    let word = |at: usize| -> Option<usize> {
+        let word = args.get(at * 2..at * 2 + 64)?;
+        // Only the low 8 bytes can hold a plausible offset or length
+        if word[..48].bytes().any(|b| b != b'0') {
+            return None;
+        }
+        usize::from_str_radix(&word[48..], 16).ok()
+    };
+    word(word(0)?)
+}
+
+/// The newest [`AA_WINDOW_BLOCKS`] blocks classified, newest first.
+#[derive(Debug, Clone, Default)]
+pub struct AaActivity {
+    pub blocks: VecDeque<AaBlock>,
+}
+
+impl AaActivity {
+    pub fn record(&mut self, block: AaBlock) {
+        if self.blocks.iter().any(|seen| seen.number == block.number) {
+            return;
+        }
+        self.blocks.push_front(block);
+        self.blocks.truncate(AA_WINDOW_BLOCKS);
+    }
+
+    /// Sums over the window; `number` is the newest block's.
+    pub fn totals(&self) -> AaBlock {
+        self.blocks.iter().fold(
+            AaBlock {
+                number: self.blocks.front().map_or(0, |block| block.number),
+                ..AaBlock::default()
+            },
+            |sum, block| AaBlock {
+                number: sum.number,
+                transactions: sum.transactions + block.transactions,
+                set_code: sum.set_code + block.set_code,
+                authorizations: sum.authorizations + block.authorizations,
+                entry_point: sum.entry_point + block.entry_point,
+                user_ops: sum.user_ops + block.user_ops,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AaActivity, AaBlock, handle_ops_count};
+    use crate::config::AA_WINDOW_BLOCKS;
+    use serde_json::json;
+
+    fn word(value: usize) -> String {
+        format!("{:064x}", value)
+    }
+
+    #[test]
+    fn classifies_set_code_and_entry_point_transactions() {
+        // handleOps(ops = 3 entries at offset 0x40, beneficiary)
+        let handle_ops = format!("0x765e827f{}{}{}", word(0x40), word(0xbeef), word(3));
+        let block = json!({
+            "number": "0x20",
+            "transactions": [
+                {"type": "0x4", "authorizationList": [{}, {}]},
+                {"type": "0x4", "authorizationList": [{}]},
+                {"type": "0x2", "to": "0x0000000071727De22E5E9d8BAf0edAc6f37da032", "input": handle_ops},
+                {"type": "0x2", "to": "0x5ff137d4b0fdcd49dca30c7cf57e578a026d2789", "input": "0x"},
+                {"type": "0x2", "to": "0x00000000000000000000000000000000000000aa"},
+                {"type": "0x0", "to": null}
+            ]
+        });
+        assert_eq!(
+            AaBlock::of_block(&block),
+            Some(AaBlock {
+                number: 32,
+                transactions: 6,
+                set_code: 2,
+                authorizations: 3,
+                entry_point: 2,
+                user_ops: 3,
+            })
+        );
+        assert_eq!(
+            AaBlock::of_block(&json!({"number": "0x1", "transactions": ["0xabc"]})),
+            None
+        );
+        assert_eq!(handle_ops_count("0x765e827f"), None);
+        assert_eq!(handle_ops_count("0xa9059cbb"), None);
+    }
+
+    #[test]
+    fn sums_a_window_of_recent_blocks() {
+        let mut activity = AaActivity::default();
+        for number in 0..AA_WINDOW_BLOCKS as u64 + 2 {
+            activity.record(AaBlock {
+                number,
+                transactions: 10,
+                set_code: 1,
+                ..AaBlock::default()
+            });
+        }
+        activity.record(AaBlock {
+            number: AA_WINDOW_BLOCKS as u64 + 1,
+            ..AaBlock::default()
+        });
+        let totals = activity.totals();
+        assert_eq!(activity.blocks.len(), AA_WINDOW_BLOCKS);
+        assert_eq!(totals.number, AA_WINDOW_BLOCKS as u64 + 1);
+        assert_eq!(totals.set_code, AA_WINDOW_BLOCKS);
+        assert_eq!(totals.transactions, AA_WINDOW_BLOCKS * 10);
+        assert!(totals.is_aa());
+    }
+}
@@ -0,0 +1,137 @@
+//! Address labels loaded from `addressbook.toml`, re-read when the file changes
+use crate::error::{FutureSightError, Result};
+use alloy::primitives::Address;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// `"0x…" = "label"` pairs naming builders, contracts, and tx-pool senders and recipients.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AddressBook {
+    labels: HashMap<Address, String>,
+}
+
+impl AddressBook {
+    pub fn parse(raw: &str) -> Result<Self> {
+        Ok(Self {
+            labels: toml::from_str(raw).map_err(|e| FutureSightError::Config(e.to_string()))?,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path).map_err(|e| {
+            FutureSightError::Config(format!(
+                "cannot read address book '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::parse(&raw).map_err(|e| {
+            FutureSightError::Config(format!("invalid address book '{}': {}", path.display(), e))
+        })
+    }
+
+    pub fn label(&self, address: Address) -> Option<&str> {
+        self.labels.get(&address).map(String::as_str)
+    }
+
+    /// The address's label, or its shortened hex.
+    pub fn name(&self, address: Address) -> String {
+        self.label(address)
+            .map(str::to_string)
+            .unwrap_or_else(|| short_address(address))
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (Address, String)> + '_ {
+        self.labels
+            .iter()
+            .map(|(address, label)| (*address, label.clone()))
+    }
+}
+
+/// An address book path and the modification time of the version last loaded.
+#[derive(Debug)]
+pub struct AddressBookFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl AddressBookFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            modified: None,
+        }
+    }
+
+    /// Re-read the file if its modification time changed since the last poll. A file that
+    /// disappears yields an empty book; `None` means nothing changed.
+    pub fn poll(&mut self) -> Option<Result<AddressBook>> {
+        let modified = fs::metadata(&self.path)
+            .and_then(|meta| meta.modified())
+            .ok();
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        Some(match modified {
+            Some(_) => AddressBook::load(&self.path),
+            None => Ok(AddressBook::default()),
+        })
+    }
+}
+
+/// `addressbook.toml` next to the default `config.toml`.
+pub fn default_address_book_path() -> Option<PathBuf> {
+    crate::settings::default_config_path().map(|path| path.with_file_name("addressbook.toml"))
+}
+
+/// `0x1234…abcd`
+pub fn short_address(address: Address) -> String {
+    let hex = address.to_string();
+    format!("{}…{}", &hex[..6], &hex[hex.len() - 4..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AddressBook, AddressBookFile};
+    use alloy::primitives::Address;
+
+    #[test]
+    fn labels_known_addresses_and_shortens_the_rest() {
+        let book = AddressBook::parse(
+            r#"
+            "0x00000000000000000000000000000000000000aa" = "Uniswap router"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(book.name(Address::with_last_byte(0xaa)), "Uniswap router");
+        assert_eq!(book.name(Address::with_last_byte(0xbb)), "0x0000…00bb");
+
+        assert!(AddressBook::parse(r#""not an address" = "x""#).is_err());
+    }
+
+    #[test]
+    fn file_is_reloaded_only_when_it_changes() {
+        let dir = std::env::temp_dir().join(format!("futuresight-book-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("addressbook.toml");
+        let mut file = AddressBookFile::new(path.clone());
+        assert!(file.poll().is_none());
+
+        std::fs::write(
+            &path,
+            r#""0x00000000000000000000000000000000000000aa" = "a""#,
+        )
+        .unwrap();
+        let book = file.poll().unwrap().unwrap();
+        assert_eq!(book.label(Address::with_last_byte(0xaa)), Some("a"));
+        assert!(file.poll().is_none());
+
+        std::fs::remove_dir_all(dir).unwrap();
+        assert_eq!(file.poll().unwrap().unwrap(), AddressBook::default());
+    }
+}
@@ -0,0 +1,871 @@
+//! Alert conditions and the history of every alert fired this session
+use crate::config::{
+    ALERT_HISTORY, BLOCK_SIZE_ALERT_BLOCKS, GAS_LIMIT_ALERT_SECS, LARGE_TRANSFER_ALERT_SECS,
+    REORG_ALERT_SECS,
+};
+use crate::data::{ConnectionStatus, SignetMetrics};
+use crate::proofs::ProofOutcome;
+use crate::units;
+use alloy::primitives::U256;
+use clap::ValueEnum;
+use std::collections::{HashMap, VecDeque};
+
+/// Critical alerts show in red and ring the `--bell`; warnings show in yellow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum AlertKind {
+    ConnectionLost,
+    BlockDelay,
+    BlockJitter,
+    BlockSize,
+    HighBaseFee,
+    FeeSpike,
+    Reorg,
+    SequencerStalled,
+    GasLimitChanged,
+    LargeTransfer,
+    StateProofMismatch,
+    EndpointDivergence,
+}
+
+impl AlertKind {
+    pub const ALL: [AlertKind; 12] = [
+        AlertKind::ConnectionLost,
+        AlertKind::BlockDelay,
+        AlertKind::BlockJitter,
+        AlertKind::BlockSize,
+        AlertKind::SequencerStalled,
+        AlertKind::HighBaseFee,
+        AlertKind::FeeSpike,
+        AlertKind::Reorg,
+        AlertKind::GasLimitChanged,
+        AlertKind::LargeTransfer,
+        AlertKind::StateProofMismatch,
+        AlertKind::EndpointDivergence,
+    ];
+
+    /// Kebab-case name, as accepted by `--on-alert-kinds`.
+    pub fn name(self) -> &'static str {
+        match self {
+            AlertKind::ConnectionLost => "connection-lost",
+            AlertKind::BlockDelay => "block-delay",
+            AlertKind::BlockJitter => "block-jitter",
+            AlertKind::BlockSize => "block-size",
+            AlertKind::HighBaseFee => "high-base-fee",
+            AlertKind::FeeSpike => "fee-spike",
+            AlertKind::Reorg => "reorg",
+            AlertKind::SequencerStalled => "sequencer-stalled",
+            AlertKind::GasLimitChanged => "gas-limit-changed",
+            AlertKind::LargeTransfer => "large-transfer",
+            AlertKind::StateProofMismatch => "state-proof-mismatch",
+            AlertKind::EndpointDivergence => "endpoint-divergence",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AlertKind::ConnectionLost => "connection lost",
+            AlertKind::BlockDelay => "block delay",
+            AlertKind::BlockJitter => "block jitter",
+            AlertKind::BlockSize => "blocks near size limit",
+            AlertKind::HighBaseFee => "high base fee",
+            AlertKind::FeeSpike => "fee spike",
+            AlertKind::Reorg => "reorg",
+            AlertKind::SequencerStalled => "sequencer stalled",
+            AlertKind::GasLimitChanged => "gas limit changed",
+            AlertKind::LargeTransfer => "large transfer",
+            AlertKind::StateProofMismatch => "state proof mismatch",
+            AlertKind::EndpointDivergence => "endpoints diverged",
+        }
+    }
+
+    /// How serious a reading of `value` is. Block delay escalates from a warning at its
+    /// warning threshold to critical past its critical one, and endpoint divergence from a
+    /// lagging endpoint to one on a different fork; other kinds have one level.
+    fn severity(self, value: f64, metrics: &SignetMetrics) -> Severity {
+        match self {
+            AlertKind::EndpointDivergence
+                if metrics
+                    .comparison
+                    .as_ref()
+                    .is_some_and(|comparison| comparison.mismatch.is_some()) =>
+            {
+                Severity::Critical
+            }
+            AlertKind::BlockDelay if value <= metrics.block_delay.critical as f64 => {
+                Severity::Warning
+            }
+            AlertKind::ConnectionLost
+            | AlertKind::BlockDelay
+            | AlertKind::Reorg
+            | AlertKind::SequencerStalled
+            | AlertKind::StateProofMismatch => Severity::Critical,
+            AlertKind::BlockJitter
+            | AlertKind::BlockSize
+            | AlertKind::HighBaseFee
+            | AlertKind::FeeSpike
+            | AlertKind::GasLimitChanged
+            | AlertKind::LargeTransfer
+            | AlertKind::EndpointDivergence => Severity::Warning,
+        }
+    }
+
+    /// Format a peak value in this alert's unit.
+    pub fn fmt_value(self, value: f64) -> String {
+        match self {
+            AlertKind::ConnectionLost => String::new(),
+            AlertKind::BlockDelay | AlertKind::SequencerStalled => format!("{:.0}s", value),
+            AlertKind::BlockJitter => format!("{:.1}s σ", value),
+            AlertKind::BlockSize => units::format_bytes(value as u64),
+            AlertKind::HighBaseFee => format!("{:.2} gwei", value),
+            AlertKind::FeeSpike => format!("x{:.2}", value),
+            AlertKind::Reorg => format!("{:.0} blocks deep", value),
+            AlertKind::GasLimitChanged => fmt_gas_millions(value as u64),
+            AlertKind::LargeTransfer => format!("{:.2} ETH", value),
+            AlertKind::StateProofMismatch => format!("{:.0} accounts", value),
+            AlertKind::EndpointDivergence => format!("{:.0} blocks apart", value),
+        }
+    }
+
+    /// What happened, for kinds where the peak alone doesn't say:
+    /// "30M → 36M at block 1,234", "95s (7.9× expected)" with an expected block time, the
+    /// first mismatched account and why its proof failed, which endpoint is behind, the
+    /// newest large transfer's parties,
+    /// "2.4s σ, worst 9.0s off a 12.1s mean over 23 intervals", or "6 blocks in a row near
+    /// the 8.39 MB limit, largest 8.10 MB".
+    fn detail(self, peak: f64, metrics: &SignetMetrics) -> Option<String> {
+        match self {
+            AlertKind::BlockDelay => metrics
+                .blocks_expected(peak)
+                .map(|blocks| format!("{} ({:.1}× expected)", self.fmt_value(peak), blocks)),
+            AlertKind::BlockJitter => metrics.block_jitter.map(|jitter| {
+                format!(
+                    "{}, worst {:.1}s off a {:.1}s mean over {} intervals",
+                    self.fmt_value(peak),
+                    jitter.max_deviation,
+                    jitter.mean,
+                    jitter.intervals
+                )
+            }),
+            AlertKind::BlockSize => metrics.block_size_pressure.map(|pressure| {
+                format!(
+                    "{} blocks in a row near the {} limit, largest {}",
+                    pressure.blocks,
+                    units::format_bytes(pressure.limit),
+                    self.fmt_value(peak)
+                )
+            }),
+            AlertKind::GasLimitChanged => metrics.last_gas_limit_change.map(|change| {
+                format!(
+                    "{} → {} at block {}",
+                    fmt_gas_millions(change.from),
+                    fmt_gas_millions(change.to),
+                    units::format_count(change.block)
+                )
+            }),
+            AlertKind::LargeTransfer => metrics.last_large_transfer.map(|transfer| {
+                format!(
+                    "{} from {} to {} at block {}",
+                    self.fmt_value(wei_to_eth(transfer.value)),
+                    short_hash(&format!("{:#x}", transfer.from)),
+                    transfer.to.map_or("a new contract".to_string(), |to| {
+                        short_hash(&format!("{:#x}", to))
+                    }),
+                    units::format_count(transfer.block)
+                )
+            }),
+            AlertKind::StateProofMismatch => {
+                metrics
+                    .proofs
+                    .iter()
+                    .find_map(|check| match &check.outcome {
+                        ProofOutcome::Mismatch(reason) => Some(format!(
+                            "{:#} at block {}: {}",
+                            check.address,
+                            units::format_count(check.block),
+                            reason
+                        )),
+                        _ => None,
+                    })
+            }
+            AlertKind::EndpointDivergence => {
+                let comparison = metrics.comparison.as_ref()?;
+                Some(match (&comparison.mismatch, comparison.lag()) {
+                    (Some(mismatch), _) => format!(
+                        "different blocks at {}: {} vs {}",
+                        units::format_count(mismatch.number),
+                        short_hash(&mismatch.ours),
+                        short_hash(&mismatch.theirs)
+                    ),
+                    (None, Some(lag)) if lag < 0 => {
+                        format!("{} blocks behind {}", -lag, comparison.rpc_url)
+                    }
+                    (None, lag) => {
+                        format!("{} {} blocks behind", comparison.rpc_url, lag.unwrap_or(0))
+                    }
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// The current value if this condition holds for `metrics`, `None` if it is clear.
+    /// Higher is worse for every kind, so the peak is the maximum.
+    fn measure(self, metrics: &SignetMetrics, now: u64) -> Option<f64> {
+        let reachable = matches!(
+            metrics.connection_status,
+            ConnectionStatus::Connected | ConnectionStatus::Stale
+        );
+        match self {
+            AlertKind::ConnectionLost => matches!(
+                metrics.connection_status,
+                ConnectionStatus::Error(_) | ConnectionStatus::Disconnected
+            )
+            .then_some(0.0),
+            AlertKind::BlockDelay => {
+                let delay = now.saturating_sub(metrics.latest_block_timestamp?);
+                (reachable && delay > metrics.block_delay.warning).then_some(delay as f64)
+            }
+            AlertKind::BlockJitter => {
+                let stddev = metrics.block_jitter?.stddev;
+                (reachable && stddev >= metrics.jitter_limit?).then_some(stddev)
+            }
+            // Only computed with a limit configured
+            AlertKind::BlockSize => metrics
+                .block_size_pressure
+                .filter(|pressure| reachable && pressure.blocks >= BLOCK_SIZE_ALERT_BLOCKS)
+                .map(|pressure| pressure.largest as f64),
+            AlertKind::HighBaseFee => {
+                let gwei = units::to_gwei(metrics.base_fee_per_gas?);
+                (reachable && gwei >= metrics.gas_thresholds.high_gwei).then_some(gwei)
+            }
+            AlertKind::FeeSpike => {
+                let multiplier = metrics.fee_spike_multiplier()?;
+                (reachable && multiplier >= metrics.gas_thresholds.spike_multiplier)
+                    .then_some(multiplier)
+            }
+            // Active for a while after the reorg, since it is an event rather than a state
+            AlertKind::Reorg => metrics
+                .last_reorg
+                .filter(|reorg| now.saturating_sub(reorg.at) <= REORG_ALERT_SECS)
+                .map(|reorg| reorg.depth as f64),
+            // Rollups only: no block for several intervals while the host keeps going
+            AlertKind::SequencerStalled => metrics
+                .sequencer_lag
+                .filter(|lag| reachable && lag.is_stalled())
+                .map(|lag| lag.since_block_secs as f64),
+            // Like a reorg, an event: active for a while after the changing block
+            AlertKind::GasLimitChanged => metrics
+                .last_gas_limit_change
+                .filter(|change| now.saturating_sub(change.at) <= GAS_LIMIT_ALERT_SECS)
+                .map(|change| change.to as f64),
+            // An event too: active for a while after the transferring block
+            AlertKind::LargeTransfer => metrics
+                .last_large_transfer
+                .filter(|transfer| now.saturating_sub(transfer.at) <= LARGE_TRANSFER_ALERT_SECS)
+                .map(|transfer| wei_to_eth(transfer.value)),
+            // Holds until a later block's proofs check out
+            AlertKind::StateProofMismatch => {
+                let mismatched = metrics
+                    .proofs
+                    .iter()
+                    .filter(|check| check.is_mismatch())
+                    .count();
+                (mismatched > 0).then_some(mismatched as f64)
+            }
+            AlertKind::EndpointDivergence => metrics
+                .comparison
+                .as_ref()
+                .filter(|comparison| reachable && comparison.diverged())
+                .map(|comparison| comparison.lag().unwrap_or(0).unsigned_abs() as f64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub chain: String,
+    pub kind: AlertKind,
+    pub started: u64, // unix seconds
+    pub ended: Option<u64>,
+    pub peak: f64,
+    pub severity: Severity,     // the highest reached
+    pub detail: Option<String>, // see AlertKind::detail
+    pub acknowledged: bool,
+}
+
+impl Alert {
+    pub fn is_active(&self) -> bool {
+        self.ended.is_none()
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.severity == Severity::Critical
+    }
+
+    /// The detail if the kind has one, else the formatted peak.
+    pub fn summary(&self) -> String {
+        self.detail
+            .clone()
+            .unwrap_or_else(|| self.kind.fmt_value(self.peak))
+    }
+}
+
+/// Gas in millions, whole where possible: `30M`, `36.05M`.
+fn fmt_gas_millions(gas: u64) -> String {
+    if gas.is_multiple_of(1_000_000) {
+        format!("{}M", gas / 1_000_000)
+    } else {
+        format!("{:.2}M", gas as f64 / 1e6)
+    }
+}
+
+fn wei_to_eth(wei: U256) -> f64 {
+    wei.saturating_to::<u128>() as f64 / units::WEI_PER_ETH as f64
+}
+
+/// The first four bytes of a hex hash: `0x1a2b3c4d…`.
+fn short_hash(hash: &str) -> String {
+    match hash.get(..10) {
+        Some(prefix) if hash.len() > 10 => format!("{}…", prefix),
+        _ => hash.to_string(),
+    }
+}
+
+/// An alert kind kept out of the banner, bell, and `--on-alert-exec` for now. Its alerts
+/// are still recorded in the history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Silence {
+    Muted,
+    Snoozed { until: u64 }, // unix seconds
+}
+
+/// One evaluation of an active alert condition.
+struct Reading {
+    value: f64,
+    severity: Severity,
+}
+
+/// Every alert fired this session, newest first, capped at [`ALERT_HISTORY`].
+///
+/// Acknowledging only hides an active alert from the banner; it keeps tracking its peak
+/// and end time. Silencing a kind does the same for all its alerts until unmuted or the
+/// snooze runs out, and also keeps them from notifying.
+#[derive(Debug, Clone, Default)]
+pub struct AlertHistory {
+    alerts: VecDeque<Alert>,
+    fired: usize, // including alerts since dropped from the history
+    silences: HashMap<AlertKind, Silence>,
+}
+
+impl AlertHistory {
+    /// Evaluate every alert condition for `chain` and return the alerts that just fired,
+    /// or just escalated to critical.
+    pub fn observe(&mut self, chain: &str, metrics: &SignetMetrics, now: u64) -> Vec<Alert> {
+        self.expire_snoozes(now);
+        let mut fired: Vec<Alert> = AlertKind::ALL
+            .into_iter()
+            .filter_map(|kind| {
+                let reading = kind.measure(metrics, now).map(|value| Reading {
+                    value,
+                    severity: kind.severity(value, metrics),
+                });
+                self.update(chain, kind, reading, metrics, now)
+            })
+            .collect();
+        fired.retain(|alert| !self.is_silenced(alert.kind));
+        fired
+    }
+
+    fn update(
+        &mut self,
+        chain: &str,
+        kind: AlertKind,
+        reading: Option<Reading>,
+        metrics: &SignetMetrics,
+        now: u64,
+    ) -> Option<Alert> {
+        let active = self
+            .alerts
+            .iter_mut()
+            .find(|alert| alert.is_active() && alert.kind == kind && alert.chain == chain);
+
+        match (active, reading) {
+            (Some(alert), Some(reading)) => {
+                alert.peak = alert.peak.max(reading.value);
+                // A second change while the first is still showing replaces it
+                alert.detail = kind.detail(alert.peak, metrics);
+                if reading.severity > alert.severity {
+                    // Escalating notifies again, and shows again if acknowledged
+                    alert.severity = reading.severity;
+                    alert.acknowledged = false;
+                    return Some(alert.clone());
+                }
+                None
+            }
+            (Some(alert), None) => {
+                alert.ended = Some(now);
+                None
+            }
+            (None, Some(reading)) => {
+                let alert = Alert {
+                    chain: chain.to_string(),
+                    kind,
+                    started: now,
+                    ended: None,
+                    peak: reading.value,
+                    severity: reading.severity,
+                    detail: kind.detail(reading.value, metrics),
+                    acknowledged: false,
+                };
+                self.alerts.push_front(alert.clone());
+                self.truncate(ALERT_HISTORY);
+                self.fired += 1;
+                Some(alert)
+            }
+            (None, None) => None,
+        }
+    }
+
+    pub fn fired_count(&self) -> usize {
+        self.fired
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Alert> {
+        self.alerts.iter()
+    }
+
+    /// Active alerts nobody has acknowledged or silenced; these drive the banner.
+    pub fn unacknowledged(&self) -> impl Iterator<Item = &Alert> {
+        self.alerts.iter().filter(|alert| {
+            alert.is_active() && !alert.acknowledged && !self.is_silenced(alert.kind)
+        })
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.alerts.iter().filter(|alert| alert.is_active()).count()
+    }
+
+    /// Forget the oldest ended alerts until at most `len` remain; `fired_count` still counts
+    /// them. Active alerts are always kept, or they would fire again on the next cycle.
+    pub fn truncate(&mut self, len: usize) {
+        let mut excess = self.alerts.len().saturating_sub(len);
+        let mut index = self.alerts.len();
+        while excess > 0 && index > 0 {
+            index -= 1;
+            if self.alerts[index].ended.is_some() {
+                self.alerts.remove(index);
+                excess -= 1;
+            }
+        }
+    }
+
+    pub fn acknowledge_all(&mut self) {
+        for alert in self.alerts.iter_mut().filter(|alert| alert.is_active()) {
+            alert.acknowledged = true;
+        }
+    }
+
+    pub fn silence(&mut self, kind: AlertKind, silence: Silence) {
+        self.silences.insert(kind, silence);
+    }
+
+    pub fn unsilence(&mut self, kind: AlertKind) {
+        self.silences.remove(&kind);
+    }
+
+    pub fn silence_of(&self, kind: AlertKind) -> Option<Silence> {
+        self.silences.get(&kind).copied()
+    }
+
+    pub fn is_silenced(&self, kind: AlertKind) -> bool {
+        self.silences.contains_key(&kind)
+    }
+
+    /// Silenced kinds in [`AlertKind::ALL`] order.
+    pub fn silences(&self) -> impl Iterator<Item = (AlertKind, Silence)> + '_ {
+        AlertKind::ALL
+            .into_iter()
+            .filter_map(|kind| Some((kind, self.silence_of(kind)?)))
+    }
+
+    /// Lift snoozes that ran out by `now`. A kind's alerts still active then show in the
+    /// banner again; they don't notify again, since they never stopped.
+    fn expire_snoozes(&mut self, now: u64) {
+        let expired: Vec<AlertKind> = self
+            .silences
+            .iter()
+            .filter(|(_, silence)| matches!(silence, Silence::Snoozed { until } if *until <= now))
+            .map(|(kind, _)| *kind)
+            .collect();
+        for kind in expired {
+            self.silences.remove(&kind);
+            for alert in self.alerts.iter_mut().filter(|alert| alert.kind == kind) {
+                alert.acknowledged = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AlertHistory, AlertKind, Severity, Silence};
+    use crate::block_size::SizePressure;
+    use crate::compare::EndpointComparison;
+    use crate::config::{
+        BLOCK_SIZE_ALERT_BLOCKS, GAS_LIMIT_ALERT_SECS, LARGE_TRANSFER_ALERT_SECS, REORG_ALERT_SECS,
+    };
+    use crate::data::{
+        BlockJitter, Config, ConnectionStatus, GasLimitChange, Reorg, SignetMetrics,
+    };
+    use crate::proofs::{ProofCheck, ProofOutcome};
+    use crate::sequencer::SequencerLag;
+    use crate::thresholds::DelayThresholds;
+    use crate::transfers::LargeTransfer;
+    use alloy::primitives::{Address, B256, U256};
+    use std::time::Duration;
+
+    fn metrics() -> SignetMetrics {
+        let mut metrics = SignetMetrics::new(Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            block_delay: DelayThresholds {
+                warning: 30,
+                critical: 60,
+            },
+            expected_block_time: None,
+            stale_after: Duration::from_secs(15),
+            gas_alert_gwei: None,
+            gas_spike_multiplier: None,
+            max_block_history: 1,
+            txpool_max_rows: 1,
+            txpool_fetch_list: false,
+            txpool_watch_signet_host_contracts: false,
+            txpool_stream: None,
+            fetch_receipts: false,
+            http: Default::default(),
+        });
+        metrics.connection_status = ConnectionStatus::Connected;
+        metrics.latest_block_timestamp = Some(1_000);
+        metrics
+    }
+
+    #[test]
+    fn block_delay_alert_escalates_and_tracks_peak_and_end() {
+        let mut history = AlertHistory::default();
+        let mut m = metrics();
+
+        assert!(history.observe("host", &m, 1_030).is_empty());
+        let fired = history.observe("host", &m, 1_040);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].kind, AlertKind::BlockDelay);
+        assert_eq!(fired[0].severity, Severity::Warning);
+        history.acknowledge_all();
+
+        // Past the critical threshold the same alert notifies again and reappears
+        let fired = history.observe("host", &m, 1_070);
+        assert_eq!(fired.len(), 1);
+        assert!(fired[0].is_critical());
+        assert_eq!(history.unacknowledged().count(), 1);
+        assert!(history.observe("host", &m, 1_090).is_empty());
+
+        m.latest_block_timestamp = Some(1_095);
+        history.observe("host", &m, 1_100);
+
+        let alert = history.iter().next().unwrap();
+        assert_eq!(alert.peak, 90.0);
+        assert_eq!((alert.started, alert.ended), (1_040, Some(1_100)));
+        assert_eq!(history.active_count(), 0);
+    }
+
+    #[test]
+    fn truncating_drops_the_oldest_ended_alerts_and_keeps_active_ones() {
+        let mut history = AlertHistory::default();
+        let delayed = metrics();
+        let mut fresh = metrics();
+        fresh.latest_block_timestamp = Some(1_040);
+
+        assert_eq!(history.observe("old", &delayed, 1_040).len(), 1);
+        for chain in ["a", "b", "c"] {
+            assert_eq!(history.observe(chain, &delayed, 1_040).len(), 1);
+            history.observe(chain, &fresh, 1_041);
+        }
+
+        history.truncate(2);
+        let chains: Vec<&str> = history.iter().map(|alert| alert.chain.as_str()).collect();
+        assert_eq!(chains, ["c", "old"]);
+        // The still-active alert isn't forgotten, so it doesn't fire again
+        assert!(history.observe("old", &delayed, 1_050).is_empty());
+        assert_eq!(history.fired_count(), 4);
+    }
+
+    #[test]
+    fn block_delay_bands_start_just_past_each_threshold() {
+        let m = metrics();
+        let severity_at = |delay: u64| {
+            let mut history = AlertHistory::default();
+            history.observe("host", &m, 1_000 + delay);
+            history.iter().next().map(|alert| alert.severity)
+        };
+        // Warning is 30s and critical 60s; each level is exclusive of its threshold
+        assert_eq!(severity_at(30), None);
+        assert_eq!(severity_at(31), Some(Severity::Warning));
+        assert_eq!(severity_at(60), Some(Severity::Warning));
+        assert_eq!(severity_at(61), Some(Severity::Critical));
+
+        // Crossing into critical escalates the same alert rather than starting another
+        let mut history = AlertHistory::default();
+        history.observe("host", &m, 1_031);
+        let fired = history.observe("host", &m, 1_061);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].severity, Severity::Critical);
+        assert_eq!(fired[0].started, 1_031);
+        assert_eq!(history.iter().count(), 1);
+    }
+
+    #[test]
+    fn silenced_kinds_stay_out_of_notifications_and_the_banner() {
+        let mut history = AlertHistory::default();
+        let m = metrics();
+        history.silence(AlertKind::BlockDelay, Silence::Muted);
+
+        // Still recorded, but neither fired nor shown
+        assert!(history.observe("host", &m, 1_070).is_empty());
+        assert_eq!(history.active_count(), 1);
+        assert_eq!(history.unacknowledged().count(), 0);
+
+        history.silence(AlertKind::BlockDelay, Silence::Snoozed { until: 1_200 });
+        history.acknowledge_all();
+        history.observe("host", &m, 1_100);
+        assert_eq!(history.unacknowledged().count(), 0);
+        // Once the snooze runs out the still-active alert is back in the banner
+        history.observe("host", &m, 1_200);
+        assert_eq!(history.silence_of(AlertKind::BlockDelay), None);
+        assert_eq!(history.unacknowledged().count(), 1);
+    }
+
+    #[test]
+    fn block_delay_is_shown_relative_to_the_expected_block_time() {
+        let mut history = AlertHistory::default();
+        let mut m = metrics();
+        m.expected_block_time = Some(12.0);
+
+        let fired = history.observe("host", &m, 1_040);
+        assert_eq!(fired[0].summary(), "40s (3.3× expected)");
+        history.observe("host", &m, 1_095);
+        assert_eq!(
+            history.iter().next().unwrap().summary(),
+            "95s (7.9× expected)"
+        );
+    }
+
+    #[test]
+    fn block_jitter_alerts_only_with_a_limit() {
+        let mut history = AlertHistory::default();
+        let mut m = metrics();
+        m.block_jitter = Some(BlockJitter {
+            mean: 12.1,
+            stddev: 2.4,
+            max_deviation: 9.0,
+            intervals: 23,
+        });
+        assert!(history.observe("host", &m, 1_001).is_empty());
+
+        m.jitter_limit = Some(2.0);
+        let fired = history.observe("host", &m, 1_002);
+        assert_eq!(fired[0].severity, Severity::Warning);
+        assert_eq!(
+            fired[0].summary(),
+            "2.4s σ, worst 9.0s off a 12.1s mean over 23 intervals"
+        );
+        m.jitter_limit = Some(3.0);
+        history.observe("host", &m, 1_003);
+        assert_eq!(history.active_count(), 0);
+    }
+
+    #[test]
+    fn block_size_alert_needs_a_sustained_run() {
+        let mut history = AlertHistory::default();
+        let mut m = metrics();
+        m.block_size_pressure = Some(SizePressure {
+            blocks: BLOCK_SIZE_ALERT_BLOCKS - 1,
+            largest: 8_100_000,
+            limit: 8_388_608,
+        });
+        assert!(history.observe("rollup", &m, 1_001).is_empty());
+
+        m.block_size_pressure = Some(SizePressure {
+            blocks: 6,
+            largest: 8_100_000,
+            limit: 8_388_608,
+        });
+        let fired = history.observe("rollup", &m, 1_002);
+        assert_eq!(fired[0].kind, AlertKind::BlockSize);
+        assert_eq!(
+            fired[0].summary(),
+            "6 blocks in a row near the 8.39 MB limit, largest 8.10 MB"
+        );
+        m.block_size_pressure = None;
+        history.observe("rollup", &m, 1_003);
+        assert_eq!(history.active_count(), 0);
+    }
+
+    #[test]
+    fn state_proof_mismatch_holds_until_proofs_check_out() {
+        let mut history = AlertHistory::default();
+        let mut m = metrics();
+        let check = |outcome| ProofCheck {
+            address: Address::with_last_byte(0xaa),
+            block: 1_234,
+            outcome,
+        };
+        m.proofs = vec![check(ProofOutcome::Failed("timeout".to_string()))];
+        assert!(history.observe("host", &m, 1_001).is_empty());
+
+        m.proofs = vec![check(ProofOutcome::Mismatch(
+            "account proof: bad".to_string(),
+        ))];
+        let fired = history.observe("host", &m, 1_002);
+        assert!(fired[0].is_critical());
+        assert_eq!(
+            fired[0].summary(),
+            "0x0000…00AA at block 1,234: account proof: bad"
+        );
+        m.proofs = vec![check(ProofOutcome::Verified)];
+        history.observe("host", &m, 1_003);
+        assert_eq!(history.active_count(), 0);
+    }
+
+    #[test]
+    fn endpoint_divergence_escalates_on_a_hash_mismatch() {
+        let mut history = AlertHistory::default();
+        let mut m = metrics();
+        let mut comparison = EndpointComparison::new("http://backup".to_string(), 3);
+        (comparison.ours, comparison.head) = (Some(100), Some(95));
+        m.comparison = Some(comparison.clone());
+        let fired = history.observe("host", &m, 1_001);
+        assert_eq!(fired[0].severity, Severity::Warning);
+        assert_eq!(fired[0].summary(), "http://backup 5 blocks behind");
+
+        comparison.head = Some(100);
+        comparison.observe_hashes(98, "0x1111111111aa", "0x2222222222bb");
+        m.comparison = Some(comparison);
+        let fired = history.observe("host", &m, 1_002);
+        assert!(fired[0].is_critical());
+        assert_eq!(
+            fired[0].summary(),
+            "different blocks at 98: 0x11111111… vs 0x22222222…"
+        );
+    }
+
+    #[test]
+    fn acknowledging_hides_but_keeps_tracking() {
+        let mut history = AlertHistory::default();
+        let mut m = metrics();
+        m.connection_status = ConnectionStatus::Error("refused".to_string());
+
+        history.observe("rollup", &m, 10);
+        assert_eq!(history.unacknowledged().count(), 1);
+        history.acknowledge_all();
+        assert_eq!(history.unacknowledged().count(), 0);
+        assert_eq!(history.active_count(), 1);
+
+        // Still-active alerts stay acknowledged; a new chain's alert needs acknowledging
+        assert!(history.observe("rollup", &m, 20).is_empty());
+        assert_eq!(history.observe("host", &m, 20).len(), 1);
+        assert_eq!(history.unacknowledged().count(), 1);
+    }
+
+    #[test]
+    fn reorg_alert_is_critical_and_expires() {
+        let mut history = AlertHistory::default();
+        let mut m = metrics();
+        m.latest_block_timestamp = Some(1_000);
+        m.last_reorg = Some(Reorg {
+            depth: 2,
+            head: 42,
+            at: 1_000,
+        });
+
+        let fired = history.observe("host", &m, 1_010);
+        assert_eq!(fired.len(), 1);
+        assert!(fired[0].is_critical());
+        assert_eq!(fired[0].kind.fmt_value(fired[0].peak), "2 blocks deep");
+
+        m.latest_block_timestamp = Some(1_000 + REORG_ALERT_SECS);
+        history.observe("host", &m, 1_000 + REORG_ALERT_SECS + 1);
+        assert_eq!(history.active_count(), 0);
+    }
+
+    #[test]
+    fn gas_limit_change_alert_describes_the_change() {
+        let mut history = AlertHistory::default();
+        let mut m = metrics();
+        m.last_gas_limit_change = Some(GasLimitChange {
+            block: 1_234,
+            from: 30_000_000,
+            to: 36_000_000,
+            at: 1_000,
+        });
+
+        let fired = history.observe("host", &m, 1_010);
+        assert_eq!(fired.len(), 1);
+        assert!(!fired[0].is_critical());
+        assert_eq!(fired[0].summary(), "30M → 36M at block 1,234");
+        assert_eq!(fired[0].kind.fmt_value(30_120_000.0), "30.12M");
+
+        m.latest_block_timestamp = Some(1_000 + GAS_LIMIT_ALERT_SECS);
+        history.observe("host", &m, 1_000 + GAS_LIMIT_ALERT_SECS + 1);
+        assert_eq!(history.active_count(), 0);
+    }
+
+    #[test]
+    fn large_transfer_alert_names_the_parties() {
+        let mut history = AlertHistory::default();
+        let mut m = metrics();
+        m.last_large_transfer = Some(LargeTransfer {
+            block: 1_234,
+            at: 1_000,
+            hash: B256::ZERO,
+            from: Address::repeat_byte(0xaa),
+            to: None,
+            value: U256::from(1_500u64) * U256::from(10u64.pow(15)),
+        });
+
+        let fired = history.observe("rollup", &m, 1_010);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].kind, AlertKind::LargeTransfer);
+        assert_eq!(
+            fired[0].summary(),
+            "1.50 ETH from 0xaaaaaaaa… to a new contract at block 1,234"
+        );
+
+        m.latest_block_timestamp = Some(1_000 + LARGE_TRANSFER_ALERT_SECS);
+        history.observe("rollup", &m, 1_000 + LARGE_TRANSFER_ALERT_SECS + 1);
+        assert_eq!(history.active_count(), 0);
+    }
+
+    #[test]
+    fn sequencer_stall_is_its_own_critical_alert() {
+        let mut history = AlertHistory::default();
+        let mut m = metrics();
+        m.sequencer_lag = Some(SequencerLag {
+            since_block_secs: 25,
+            expected_interval_secs: 8.0,
+            host_blocks_since: 3,
+        });
+
+        let fired = history.observe("rollup", &m, 1_025);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].kind, AlertKind::SequencerStalled);
+        assert!(fired[0].is_critical());
+
+        m.sequencer_lag = None;
+        history.observe("rollup", &m, 1_028);
+        assert_eq!(history.active_count(), 0);
+    }
+}
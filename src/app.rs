@@ -0,0 +1,1145 @@
+//! The watch session as an update loop: `run_watch` turns terminal events and its redraw
+//! timer into [`Message`]s, [`App::update`] applies each one to the dashboard and networks,
+//! and [`App::draw`] renders the result. Only the loop touches the terminal.
+use crate::address_book::{self, AddressBookFile};
+use crate::alerts::{AlertHistory, AlertKind, Silence};
+use crate::chains::ChainRegistry;
+use crate::chart::ChartView;
+use crate::check::{self, CheckStatus};
+use crate::cli::WatchArgs;
+use crate::config::{self, IDLE_REDRAW_MILLIS};
+use crate::data::SignetMetrics;
+use crate::email::EmailAlerts;
+use crate::error::{FutureSightError, Result};
+use crate::explorer;
+use crate::http::HttpOptions;
+use crate::influx::InfluxSink;
+use crate::keymap::{self, Action, Category};
+use crate::network::{ChainRole, Network, Refreshed};
+use crate::notify::{AlertHook, Bell};
+use crate::otlp::{Observation, OtlpExporter};
+use crate::paging::{self, Pager};
+use crate::record::{self, Recorder};
+use crate::retention::Retention;
+use crate::session;
+use crate::settings::{self, Settings, SettingsFile};
+use crate::state::{self, DashboardState};
+use crate::statsd::StatsdSink;
+use crate::stream::StreamServer;
+use crate::ui::{
+    self, BlockQuery, Dashboard, EndpointPicker, InputOutcome, PanelLayout, ProfilePicker, Prompt,
+    PromptPurpose, Tab, TextInput,
+};
+use crate::watchlist::{self, Watchlist, WatchlistFile};
+use crate::{build_networks, builder_labels, endpoint_options, reload_thresholds};
+use alloy::primitives::Address;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use futures::future::join_all;
+use ratatui::Frame;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Everything that can change the session's state.
+#[derive(Debug)]
+pub enum Message {
+    /// One pass of the loop: start due collections, take in finished ones, export and alert
+    /// on them, and reload watched files that changed.
+    Tick,
+    Key(KeyEvent),
+    Paste(String),
+    /// The terminal was resized or handed back after a suspend: draw everything again.
+    Redraw,
+}
+
+/// What the loop should do after an update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    /// Ctrl-Z: hand the terminal back to the shell until resumed.
+    Suspend,
+    Quit,
+}
+
+pub struct App {
+    cli: WatchArgs,
+    /// Kept whole so the profile picker can rebuild the networks from another profile.
+    config: Settings,
+    settings_file: Option<SettingsFile>,
+    endpoints: Vec<String>,
+    address_book_file: Option<AddressBookFile>,
+    watchlist_file: Option<WatchlistFile>,
+    watchlist: Watchlist,
+    builder_overrides: HashMap<Address, String>,
+    http: HttpOptions,
+    networks: Vec<Network>,
+    selected: usize,
+    dashboard: Dashboard,
+    alert_history: AlertHistory,
+    retention: Retention,
+    recorder: Option<Recorder>,
+    otlp: Option<OtlpExporter>,
+    statsd: Option<StatsdSink>,
+    influx: Option<InfluxSink>,
+    stream: Option<StreamServer>,
+    email: Option<EmailAlerts>,
+    pagers: Vec<Pager>,
+    bell: Option<Bell>,
+    alert_hook: Option<AlertHook>,
+    state_path: Option<PathBuf>,
+    session_started: Instant,
+    exit: Option<(String, CheckStatus)>, // set when --exit-on-disconnect stops the session
+    /// Something shown changed since the last frame.
+    dirty: bool,
+    last_frame: Option<Instant>,
+}
+
+impl App {
+    /// Load the config file, address book, and watchlist, build the networks, and open
+    /// every sink `cli` asks for.
+    pub async fn new(cli: WatchArgs) -> Result<Self> {
+        if cli.txpool_max_rows == 0 {
+            return Err(FutureSightError::Config(
+                "--txpool-max-rows must be at least 1".to_string(),
+            ));
+        }
+        if cli.max_block_history == 0 {
+            return Err(FutureSightError::Config(
+                "--max-block-history must be at least 1".to_string(),
+            ));
+        }
+
+        let config = Settings::load(cli.config.as_deref())?;
+        let settings_file = cli
+            .config
+            .clone()
+            .or_else(settings::default_config_path)
+            .map(SettingsFile::new);
+        let settings = match &cli.profile {
+            Some(name) => config.with_profile(name)?,
+            None => config.clone(),
+        };
+        let endpoints = endpoint_options(&cli, &settings);
+        let panels = settings
+            .panels
+            .as_deref()
+            .map(PanelLayout::from_config)
+            .unwrap_or_default();
+        // An explicit --address-book must exist; the default one is optional
+        if let Some(path) = &cli.address_book
+            && !path.is_file()
+        {
+            return Err(FutureSightError::Config(format!(
+                "address book '{}' not found",
+                path.display()
+            )));
+        }
+        let mut address_book_file = cli
+            .address_book
+            .clone()
+            .or_else(address_book::default_address_book_path)
+            .map(AddressBookFile::new);
+        let address_book = address_book_file
+            .as_mut()
+            .and_then(AddressBookFile::poll)
+            .transpose()?
+            .unwrap_or_default();
+        // Likewise for the watchlist
+        if let Some(path) = &cli.watchlist
+            && !path.is_file()
+        {
+            return Err(FutureSightError::Config(format!(
+                "watchlist '{}' not found",
+                path.display()
+            )));
+        }
+        let mut watchlist_file = cli
+            .watchlist
+            .clone()
+            .or_else(watchlist::default_watchlist_path)
+            .map(WatchlistFile::new);
+        let watchlist = watchlist_file
+            .as_mut()
+            .and_then(WatchlistFile::poll)
+            .transpose()?
+            .unwrap_or_default();
+        let builder_overrides = settings.builders.clone();
+        let chains = ChainRegistry::new(settings.chains.clone());
+        let http = cli.http.options()?;
+        let email = settings
+            .alerts
+            .email
+            .clone()
+            .map(|email| EmailAlerts::new(email, &http))
+            .transpose()?;
+        let pagers = paging::pagers(&settings.alerts, &http)?;
+        let mut networks = build_networks(&cli, settings, &address_book, &http)?;
+        for network in &mut networks {
+            network.set_watchlist(&watchlist);
+        }
+        let recorder = cli.record.as_deref().map(Recorder::open).transpose()?;
+        let otlp = cli
+            .otlp_endpoint
+            .as_deref()
+            .map(|endpoint| OtlpExporter::new(endpoint, &http))
+            .transpose()?;
+        let statsd = cli.statsd.as_deref().map(StatsdSink::new).transpose()?;
+        let influx = cli.influx.as_deref().map(InfluxSink::open).transpose()?;
+        let stream = match cli.stream_listen {
+            Some(addr) => Some(StreamServer::bind(addr).await?),
+            None => None,
+        };
+
+        let state_path = if cli.no_state {
+            None
+        } else {
+            cli.state_file.clone().or_else(state::default_state_path)
+        };
+        let saved = state_path
+            .as_deref()
+            .map(DashboardState::load)
+            .unwrap_or_default();
+        if let Some(active) = saved.host_contract_filter_active {
+            for network in &mut networks {
+                network.set_host_contract_filter(active);
+            }
+        }
+        let selected = saved
+            .selected_network
+            .and_then(|name| networks.iter().position(|network| network.name == name))
+            .unwrap_or(0);
+
+        let mut dashboard = Dashboard::new(cli.refresh_interval);
+        dashboard.tab = saved.tab.unwrap_or(dashboard.tab);
+        dashboard.chart = ChartView::restore(
+            saved.chart_zoom.unwrap_or(dashboard.chart.zoom()),
+            saved.chart_pan.unwrap_or(dashboard.chart.pan()),
+        );
+        dashboard.alert_kind = saved
+            .alert_kind
+            .filter(|row| *row < AlertKind::ALL.len())
+            .unwrap_or(0);
+        dashboard.paused = saved.paused.unwrap_or(false);
+        dashboard.panels = panels;
+        dashboard.profile = cli.profile.clone();
+        dashboard.address_book = address_book;
+        dashboard.chains = chains;
+        let bell =
+            (cli.bell || cli.bell_command.is_some()).then(|| Bell::new(cli.bell_command.clone()));
+        let alert_hook = cli
+            .on_alert_exec
+            .clone()
+            .map(|command| AlertHook::new(command, cli.on_alert_kinds.clone(), cli.on_alert_after));
+
+        Ok(Self {
+            retention: Retention::new(cli.max_memory_mb),
+            cli,
+            config,
+            settings_file,
+            endpoints,
+            address_book_file,
+            watchlist_file,
+            watchlist,
+            builder_overrides,
+            http,
+            networks,
+            selected,
+            dashboard,
+            alert_history: AlertHistory::default(),
+            recorder,
+            otlp,
+            statsd,
+            influx,
+            stream,
+            email,
+            pagers,
+            bell,
+            alert_hook,
+            state_path,
+            session_started: Instant::now(),
+            exit: None,
+            dirty: true,
+            last_frame: None,
+        })
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        self.dashboard
+            .render(frame, &self.networks, self.selected, &self.alert_history);
+        self.dirty = false;
+        self.last_frame = Some(Instant::now());
+    }
+
+    /// Whether to draw at `now`. A change is drawn once `--max-fps` allows another frame;
+    /// without one the dashboard is redrawn every [`IDLE_REDRAW_MILLIS`] so ages keep
+    /// counting. A running collection counts as a change, so its spinner turns.
+    pub fn frame_due(&self, now: Instant) -> bool {
+        self.until_frame(now).is_zero()
+    }
+
+    /// How long until [`App::frame_due`] turns true, for the loop to wait on input meanwhile.
+    pub fn until_frame(&self, now: Instant) -> Duration {
+        let Some(last) = self.last_frame else {
+            return Duration::ZERO;
+        };
+        let changing = self.dirty || self.networks.iter().any(Network::is_collecting);
+        let interval = if changing {
+            Duration::from_secs(1) / self.cli.max_fps as u32
+        } else {
+            Duration::from_millis(IDLE_REDRAW_MILLIS)
+        };
+        (last + interval).saturating_duration_since(now)
+    }
+
+    /// `--backfill`: fetch up to `count` past blocks per chain before the first refresh.
+    pub async fn backfill(&mut self, count: usize) {
+        let added: usize = join_all(
+            self.networks
+                .iter_mut()
+                .map(|network| network.backfill(count)),
+        )
+        .await
+        .into_iter()
+        .map(|(host, rollup)| host + rollup)
+        .sum();
+        self.set_notice(format!("backfilled {} blocks", added));
+    }
+
+    /// Collect every chain once, waiting for all of them.
+    pub async fn collect_all(&mut self) {
+        join_all(self.networks.iter_mut().map(Network::collect_all)).await;
+    }
+
+    /// Show a footer notice, e.g. progress the loop reports before the first update.
+    pub fn set_notice(&mut self, notice: String) {
+        self.dashboard.notice = Some(notice);
+        self.dirty = true;
+    }
+
+    pub fn update(&mut self, message: Message) -> Result<Flow> {
+        match message {
+            Message::Tick => self.tick(),
+            Message::Key(key) => {
+                self.dirty = true;
+                Ok(self.handle_key(key))
+            }
+            Message::Paste(text) => {
+                if let Some(prompt) = &mut self.dashboard.prompt {
+                    prompt.input.paste(&text);
+                    self.dirty = true;
+                }
+                Ok(Flow::Continue)
+            }
+            Message::Redraw => {
+                self.dirty = true;
+                Ok(Flow::Continue)
+            }
+        }
+    }
+
+    /// Collection runs in background tasks, so keys and redraws never wait on a poll.
+    /// While paused, only file reloads run; finished refreshes wait for the resume.
+    fn tick(&mut self) -> Result<Flow> {
+        if self.dashboard.paused {
+            self.reload_files();
+            return Ok(Flow::Continue);
+        }
+        let refresh_every = Duration::from_secs(self.cli.refresh_interval);
+        for network in &mut self.networks {
+            self.dirty |= network.start_due(refresh_every);
+        }
+        let refreshed: Vec<Refreshed> = self
+            .networks
+            .iter_mut()
+            .map(Network::take_finished)
+            .collect();
+        let multi = self.networks.len() > 1;
+        let chain_label = |network: &Network, role: ChainRole| network.chain_label(role, multi);
+
+        // Chains collected this pass, for the recorder and exporter
+        let collected: Vec<(String, &SignetMetrics)> = self
+            .networks
+            .iter()
+            .zip(&refreshed)
+            .flat_map(|(network, refreshed)| {
+                let host = refreshed.host.then(|| {
+                    (
+                        chain_label(network, ChainRole::Host),
+                        network.host_metrics(),
+                    )
+                });
+                let rollup = network
+                    .rollup_metrics()
+                    .filter(|_| refreshed.rollup)
+                    .map(|rollup| (chain_label(network, ChainRole::Rollup), rollup));
+                host.into_iter().chain(rollup)
+            })
+            .collect();
+        if let Some(recorder) = &mut self.recorder {
+            for (chain, metrics) in &collected {
+                recorder.record(chain, metrics)?;
+            }
+        }
+        let observations: Vec<Observation> = collected
+            .iter()
+            .map(|(chain, metrics)| Observation::new(chain.clone(), metrics))
+            .collect();
+        if let Some(otlp) = &self.otlp {
+            otlp.export(&observations);
+        }
+        if let Some(statsd) = &self.statsd {
+            statsd.gauges(&observations);
+        }
+        if let Some(influx) = &mut self.influx {
+            influx.write(&observations)?;
+        }
+        if let Some(stream) = &self.stream {
+            let ts = record::unix_now();
+            for (chain, metrics) in &collected {
+                stream.publish_sample(chain, metrics, ts);
+            }
+        }
+
+        let now = record::unix_now();
+        let mut fired = Vec::new();
+        for network in &mut self.networks {
+            network.check_staleness();
+            network.check_sequencer(now);
+            self.dirty |= network.drain_txpool_streams();
+            fired.extend(self.alert_history.observe(
+                &chain_label(network, ChainRole::Host),
+                network.host_metrics(),
+                now,
+            ));
+            if let Some(rollup) = network.rollup_metrics() {
+                fired.extend(self.alert_history.observe(
+                    &chain_label(network, ChainRole::Rollup),
+                    rollup,
+                    now,
+                ));
+            }
+        }
+        if !fired.is_empty() {
+            self.dirty = true;
+        }
+        if refreshed
+            .iter()
+            .any(|refreshed| refreshed.host || refreshed.rollup)
+        {
+            self.dirty = true;
+            self.dashboard.memory = Some(
+                self.retention
+                    .enforce(&mut self.networks, &mut self.alert_history),
+            );
+        }
+        if let Some(bell) = &self.bell {
+            bell.notify(&fired);
+        }
+        if let Some(stream) = &self.stream {
+            fired.iter().for_each(|alert| stream.publish_alert(alert));
+        }
+        if let Some(statsd) = &self.statsd {
+            statsd.events(&fired);
+        }
+        if let Some(error) = self
+            .email
+            .as_mut()
+            .and_then(|email| email.notify(&fired, now))
+        {
+            self.set_notice(format!("alert email failed: {}", error));
+        }
+        let pager_errors: Vec<String> = self
+            .pagers
+            .iter_mut()
+            .filter_map(|pager| pager.poll(&self.alert_history))
+            .collect();
+        for error in pager_errors {
+            self.set_notice(error);
+        }
+        if let Some(hook) = &mut self.alert_hook {
+            hook.poll(&self.alert_history, now);
+        }
+        if self.cli.exit_on_disconnect
+            && let Some(failure) = first_failure(&self.networks, multi, now)
+        {
+            self.exit = Some(failure);
+            return Ok(Flow::Quit);
+        }
+
+        self.reload_files();
+        Ok(Flow::Continue)
+    }
+
+    /// Apply address book, watchlist, and config file edits made since the last check.
+    fn reload_files(&mut self) {
+        match self
+            .address_book_file
+            .as_mut()
+            .and_then(AddressBookFile::poll)
+        {
+            Some(Ok(book)) => {
+                let labels = builder_labels(&book, &self.builder_overrides);
+                for network in &mut self.networks {
+                    network.set_builder_labels(&labels);
+                }
+                self.dashboard.address_book = book;
+                self.set_notice("address book reloaded".to_string());
+            }
+            Some(Err(e)) => self.set_notice(e.to_string()),
+            None => {}
+        }
+        match self.watchlist_file.as_mut().and_then(WatchlistFile::poll) {
+            Some(Ok(reloaded)) => {
+                self.watchlist = reloaded;
+                for network in &mut self.networks {
+                    network.set_watchlist(&self.watchlist);
+                }
+                self.set_notice("watchlist reloaded".to_string());
+            }
+            Some(Err(e)) => self.set_notice(e.to_string()),
+            None => {}
+        }
+        match self.settings_file.as_mut().and_then(SettingsFile::poll) {
+            Some(Ok(reloaded)) => {
+                let applied = match &self.dashboard.profile {
+                    Some(name) => reloaded.with_profile(name),
+                    None => Ok(reloaded.clone()),
+                }
+                .and_then(|settings| reload_thresholds(&self.cli, &settings, &mut self.networks));
+                match applied {
+                    Ok(changes) => {
+                        self.config = reloaded;
+                        self.set_notice(if changes.is_empty() {
+                            "config reloaded, no threshold changed".to_string()
+                        } else {
+                            format!("thresholds updated: {}", changes.join("; "))
+                        });
+                    }
+                    Err(e) => self.set_notice(format!("config not reloaded: {}", e)),
+                }
+            }
+            Some(Err(e)) => self.set_notice(format!("config not reloaded: {}", e)),
+            None => {}
+        }
+    }
+
+    /// Route a key to whichever modal is open, else to the current tab and the dashboard.
+    fn handle_key(&mut self, key: KeyEvent) -> Flow {
+        #[cfg(unix)]
+        if key.code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Flow::Suspend;
+        }
+        let dashboard = &mut self.dashboard;
+        let network = &mut self.networks[self.selected];
+        if dashboard.prompt.is_some() {
+            handle_prompt_key(dashboard, network, &mut self.alert_history, key);
+            return Flow::Continue;
+        }
+        if dashboard.layout_editor.is_some() {
+            handle_layout_key(dashboard, key.code);
+            return Flow::Continue;
+        }
+        if dashboard.endpoint_picker.is_some() {
+            handle_picker_key(dashboard, network, key.code);
+            return Flow::Continue;
+        }
+        if dashboard.profile_picker.is_some() {
+            if let Some(name) = handle_profile_picker_key(dashboard, key.code) {
+                self.switch_profile(name);
+            }
+            return Flow::Continue;
+        }
+        if dashboard.show_help {
+            // Any key closes the help overlay
+            dashboard.show_help = false;
+            return Flow::Continue;
+        }
+        if dashboard.tab == Tab::Alerts
+            && let Some(action) = keymap::action_for(Category::AlertsTab, key.code)
+        {
+            handle_alerts_tab_action(dashboard, &mut self.alert_history, action);
+            return Flow::Continue;
+        }
+        match keymap::dashboard_action(key.code) {
+            Some(Action::Quit) => dashboard.quit(),
+            Some(Action::ToggleHostFilter) => network.toggle_host_contract_filter(),
+            Some(Action::OpenEndpointPicker) => {
+                dashboard.notice = None;
+                dashboard.endpoint_picker = Some(EndpointPicker::new(
+                    self.endpoints.clone(),
+                    ChainRole::Host,
+                    &network.host_metrics().rpc_url,
+                ));
+            }
+            Some(Action::OpenProfilePicker) => {
+                let names = self.config.profile_names();
+                if names.is_empty() {
+                    dashboard.notice = Some("the config file defines no profiles".to_string());
+                } else {
+                    dashboard.notice = None;
+                    dashboard.profile_picker =
+                        Some(ProfilePicker::new(names, dashboard.profile.as_deref()));
+                }
+            }
+            Some(Action::NextNetwork) => {
+                self.selected = (self.selected + 1) % self.networks.len();
+                dashboard.selected_block = None;
+            }
+            Some(Action::PrevNetwork) => {
+                self.selected = (self.selected + self.networks.len() - 1) % self.networks.len();
+                dashboard.selected_block = None;
+            }
+            Some(Action::AcknowledgeAlerts) => self.alert_history.acknowledge_all(),
+            Some(Action::TogglePause) => dashboard.paused = !dashboard.paused,
+            Some(Action::NextTab) => dashboard.tab = dashboard.tab.next(),
+            Some(Action::ToggleHelp) => dashboard.show_help = true,
+            Some(Action::EditLayout) => dashboard.layout_editor = Some(0),
+            Some(Action::SearchBlocks) => {
+                let current = dashboard
+                    .block_search
+                    .as_ref()
+                    .map(|query| query.raw().to_string())
+                    .unwrap_or_default();
+                let input = TextInput::new(format!("Search blocks · {}", ui::SEARCH_SYNTAX))
+                    .with_value(&current)
+                    .with_validator(|value| BlockQuery::parse(value).map(|_| ()));
+                dashboard.prompt = Some(Prompt {
+                    input,
+                    purpose: PromptPurpose::BlockSearch,
+                });
+            }
+            Some(Action::NextMatch) => {
+                dashboard.jump_to_match(network, 1);
+            }
+            Some(Action::PrevMatch) => {
+                dashboard.jump_to_match(network, -1);
+            }
+            Some(Action::SelectOlder) => dashboard.move_block_selection(network, 1),
+            Some(Action::SelectNewer) => dashboard.move_block_selection(network, -1),
+            Some(Action::SwitchTape) => dashboard.switch_selected_tape(network),
+            Some(Action::OpenExplorer) => {
+                dashboard.notice = Some(match open_selected_block(dashboard, network) {
+                    Ok(url) => format!("opened {}", url),
+                    Err(notice) => notice,
+                });
+            }
+            Some(Action::ZoomIn) => dashboard.chart.zoom_in(),
+            Some(Action::ZoomOut) => dashboard.chart.zoom_out(),
+            Some(Action::PanBack) => dashboard.chart.pan_back(),
+            Some(Action::PanForward) => dashboard.chart.pan_forward(),
+            Some(Action::ClearSearch) => {
+                dashboard.block_search = None;
+                dashboard.selected_block = None;
+            }
+            _ => {}
+        }
+
+        if dashboard.should_quit {
+            Flow::Quit
+        } else {
+            Flow::Continue
+        }
+    }
+
+    /// Rebuild every network from profile `name` of the config file.
+    fn switch_profile(&mut self, name: String) {
+        let switched = self.config.with_profile(&name).and_then(|settings| {
+            let built = build_networks(
+                &self.cli,
+                settings.clone(),
+                &self.dashboard.address_book,
+                &self.http,
+            )?;
+            Ok((settings, built))
+        });
+        let dashboard = &mut self.dashboard;
+        match switched {
+            Ok((settings, built)) => {
+                self.networks = built;
+                for network in &mut self.networks {
+                    network.set_watchlist(&self.watchlist);
+                }
+                self.selected = 0;
+                self.endpoints = endpoint_options(&self.cli, &settings);
+                self.builder_overrides = settings.builders.clone();
+                dashboard.chains = ChainRegistry::new(settings.chains.clone());
+                dashboard.panels = settings
+                    .panels
+                    .as_deref()
+                    .map(PanelLayout::from_config)
+                    .unwrap_or_default();
+                dashboard.selected_block = None;
+                dashboard.notice = Some(format!("switched to profile {}", name));
+                dashboard.profile = Some(name);
+            }
+            Err(e) => dashboard.notice = Some(e.to_string()),
+        }
+    }
+
+    /// Save the dashboard state and print the session summary; call once the terminal is
+    /// restored so the summary stays in the shell's scrollback. Returns the exit status.
+    pub fn finish(self) -> Result<CheckStatus> {
+        let multi = self.networks.len() > 1;
+        if let Some(path) = &self.state_path {
+            let network = &self.networks[self.selected];
+            let state = DashboardState {
+                host_contract_filter_active: Some(
+                    network.host_metrics().host_contract_filter_active,
+                ),
+                selected_network: multi.then(|| network.name.clone()),
+                tab: Some(self.dashboard.tab),
+                chart_zoom: Some(self.dashboard.chart.zoom()),
+                chart_pan: Some(self.dashboard.chart.pan()),
+                alert_kind: Some(self.dashboard.alert_kind),
+                paused: Some(self.dashboard.paused),
+            };
+            state.save(path)?;
+        }
+
+        let chains: Vec<(String, &SignetMetrics)> = self
+            .networks
+            .iter()
+            .flat_map(|network| {
+                let host = (
+                    network.chain_label(ChainRole::Host, multi),
+                    network.host_metrics(),
+                );
+                let rollup = network
+                    .rollup_metrics()
+                    .map(|rollup| (network.chain_label(ChainRole::Rollup, multi), rollup));
+                std::iter::once(host).chain(rollup)
+            })
+            .collect();
+        print!(
+            "{}",
+            session::render_summary(
+                self.session_started.elapsed(),
+                &chains,
+                self.alert_history.fired_count()
+            )
+        );
+        if let Some((chain, status)) = &self.exit {
+            eprintln!("exiting: {} {}", chain, status.label().to_lowercase());
+        }
+
+        Ok(self.exit.map_or(CheckStatus::Ok, |(_, status)| status))
+    }
+}
+
+/// Keys while the endpoint picker is open; the picker swallows everything else.
+fn handle_picker_key(dashboard: &mut Dashboard, network: &mut Network, code: KeyCode) {
+    let Some(picker) = dashboard.endpoint_picker.as_mut() else {
+        return;
+    };
+    let Some(action) = keymap::action_for(Category::EndpointPicker, code) else {
+        return;
+    };
+    match action {
+        Action::PickerUp => picker.move_by(-1),
+        Action::PickerDown => picker.move_by(1),
+        Action::PickerToggleTarget => {
+            let target = match picker.target {
+                ChainRole::Host if network.has_rollup() => ChainRole::Rollup,
+                _ => ChainRole::Host,
+            };
+            let current = match target {
+                ChainRole::Host => Some(network.host_metrics()),
+                ChainRole::Rollup => network.rollup_metrics(),
+            };
+            if let Some(current) = current {
+                *picker = EndpointPicker::new(
+                    std::mem::take(&mut picker.options),
+                    target,
+                    &current.rpc_url,
+                );
+            }
+        }
+        Action::PickerCustomUrl => {
+            let input = TextInput::new(format!("Custom {} RPC URL", picker.target.label()))
+                .with_value(picker.choice().unwrap_or_default())
+                .with_validator(validate_rpc_url);
+            dashboard.prompt = Some(Prompt {
+                input,
+                purpose: PromptPurpose::Endpoint(picker.target),
+            });
+            dashboard.endpoint_picker = None;
+        }
+        Action::PickerSwitch => {
+            let target = picker.target;
+            let choice = picker.choice().map(str::to_string);
+            dashboard.endpoint_picker = None;
+            if let Some(url) = choice {
+                switch_endpoint(dashboard, network, target, url);
+            }
+        }
+        Action::PickerCancel => dashboard.endpoint_picker = None,
+        _ => {}
+    }
+}
+
+/// Keys while the profile picker is open. Returns the profile to switch to.
+fn handle_profile_picker_key(dashboard: &mut Dashboard, code: KeyCode) -> Option<String> {
+    let picker = dashboard.profile_picker.as_mut()?;
+    match keymap::action_for(Category::ProfilePicker, code)? {
+        Action::ProfileUp => picker.move_by(-1),
+        Action::ProfileDown => picker.move_by(1),
+        Action::ProfileSwitch => {
+            let choice = picker.choice().map(str::to_string);
+            dashboard.profile_picker = None;
+            return choice;
+        }
+        Action::ProfileCancel => dashboard.profile_picker = None,
+        _ => {}
+    }
+    None
+}
+
+/// Keys while the panel layout editor is open.
+fn handle_layout_key(dashboard: &mut Dashboard, code: KeyCode) {
+    let Some(selected) = dashboard.layout_editor else {
+        return;
+    };
+    let last = dashboard.panels.entries().len().saturating_sub(1);
+    dashboard.layout_editor = match keymap::action_for(Category::LayoutEditor, code) {
+        Some(Action::LayoutUp) => Some(selected.saturating_sub(1)),
+        Some(Action::LayoutDown) => Some((selected + 1).min(last)),
+        Some(Action::LayoutToggle) => {
+            dashboard.panels.toggle(selected);
+            Some(selected)
+        }
+        Some(Action::LayoutMoveUp) => Some(dashboard.panels.move_entry(selected, -1)),
+        Some(Action::LayoutMoveDown) => Some(dashboard.panels.move_entry(selected, 1)),
+        Some(Action::LayoutClose) => None,
+        _ => Some(selected),
+    };
+}
+
+/// Keys while a text prompt is open.
+fn handle_prompt_key(
+    dashboard: &mut Dashboard,
+    network: &mut Network,
+    alerts: &mut AlertHistory,
+    key: KeyEvent,
+) {
+    let Some(prompt) = dashboard.prompt.as_mut() else {
+        return;
+    };
+    match prompt.input.handle_key(key) {
+        InputOutcome::Pending => {}
+        InputOutcome::Cancelled => dashboard.prompt = None,
+        InputOutcome::Submitted(value) => {
+            let Some(Prompt { purpose, .. }) = dashboard.prompt.take() else {
+                return;
+            };
+            match purpose {
+                PromptPurpose::Endpoint(target) => {
+                    switch_endpoint(dashboard, network, target, value)
+                }
+                PromptPurpose::BlockSearch => {
+                    dashboard.block_search = BlockQuery::parse(&value).ok();
+                    dashboard.selected_block = None;
+                    if !dashboard.jump_to_match(network, 1) {
+                        dashboard.notice = Some(format!("no blocks match /{}", value));
+                    }
+                }
+                PromptPurpose::Snooze(kind) => {
+                    if let Ok(minutes) = snooze_minutes(&value) {
+                        let until = record::unix_now() + minutes * 60;
+                        alerts.silence(kind, Silence::Snoozed { until });
+                        dashboard.notice =
+                            Some(format!("{} snoozed for {}m", kind.label(), minutes));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn snooze_minutes(value: &str) -> std::result::Result<u64, String> {
+    match value.trim().parse::<u64>() {
+        Ok(minutes) if minutes > 0 => Ok(minutes),
+        _ => Err("enter a whole number of minutes".to_string()),
+    }
+}
+
+/// Mute, unmute, or snooze alert types from the Alerts tab.
+fn handle_alerts_tab_action(dashboard: &mut Dashboard, alerts: &mut AlertHistory, action: Action) {
+    let kind = dashboard.selected_alert_kind();
+    match action {
+        Action::AlertKindUp => dashboard.move_alert_kind(-1),
+        Action::AlertKindDown => dashboard.move_alert_kind(1),
+        Action::ToggleMute if alerts.is_silenced(kind) => {
+            alerts.unsilence(kind);
+            dashboard.notice = Some(format!("{} unmuted", kind.label()));
+        }
+        Action::ToggleMute => {
+            alerts.silence(kind, Silence::Muted);
+            dashboard.notice = Some(format!("{} muted", kind.label()));
+        }
+        Action::Snooze => {
+            let input = TextInput::new(format!("Snooze {} for how many minutes", kind.label()))
+                .with_value(&config::SNOOZE_DEFAULT_MINUTES.to_string())
+                .with_validator(|value| snooze_minutes(value).map(|_| ()));
+            dashboard.prompt = Some(Prompt {
+                input,
+                purpose: PromptPurpose::Snooze(kind),
+            });
+        }
+        _ => {}
+    }
+}
+
+fn switch_endpoint(
+    dashboard: &mut Dashboard,
+    network: &mut Network,
+    target: ChainRole,
+    url: String,
+) {
+    dashboard.notice = Some(match network.switch_endpoint(target, url.clone()) {
+        Ok(()) => format!("{} → {}", target.label(), url),
+        Err(e) => format!("{} endpoint unchanged: {}", target.label(), e),
+    });
+}
+
+/// Open the selected block in its chain's explorer and return the URL, or a footer notice.
+fn open_selected_block(
+    dashboard: &Dashboard,
+    network: &Network,
+) -> std::result::Result<String, String> {
+    let (role, number) = dashboard
+        .selected_block
+        .ok_or("no block selected: use j/k or / to select one")?;
+    let block = network
+        .metrics(role)
+        .and_then(|metrics| {
+            metrics
+                .block_history
+                .iter()
+                .find(|block| block.number == number)
+        })
+        .ok_or_else(|| format!("block #{} is no longer in the history", number))?;
+    let template = network.explorer(role).ok_or_else(|| {
+        format!(
+            "no {} explorer configured (--{}-explorer-url)",
+            role.label(),
+            role.label()
+        )
+    })?;
+    let url = explorer::block_url(template, block);
+    explorer::open_in_browser(&url).map_err(|e| format!("cannot launch browser: {}", e))?;
+    Ok(url)
+}
+
+fn validate_rpc_url(value: &str) -> std::result::Result<(), String> {
+    let url = url::Url::parse(value).map_err(|e| format!("invalid URL: {}", e))?;
+    match url.scheme() {
+        "http" | "https" => Ok(()),
+        scheme => Err(format!(
+            "unsupported scheme '{}': use http or https",
+            scheme
+        )),
+    }
+}
+
+/// The first chain that `--exit-on-disconnect` should stop on, with its label.
+fn first_failure(
+    networks: &[Network],
+    multi: bool,
+    now: u64,
+) -> Option<(String, check::CheckStatus)> {
+    networks.iter().find_map(|network| {
+        let host = check::watch_failure(network.host_metrics(), now)
+            .map(|status| (network.chain_label(ChainRole::Host, multi), status));
+        host.or_else(|| {
+            check::watch_failure(network.rollup_metrics()?, now)
+                .map(|status| (network.chain_label(ChainRole::Rollup, multi), status))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{App, Flow, Message};
+    use crate::cli::{Cli, Command};
+    use crate::config::IDLE_REDRAW_MILLIS;
+    use clap::Parser;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{Duration, Instant},
+    };
+
+    const CONFIG: &str = r#"
+        [[networks]]
+        name = "local"
+        host_rpc_url = "http://127.0.0.1:1"
+
+        [profiles.devnet]
+        panels = ["blocks"]
+
+        [[profiles.devnet.networks]]
+        name = "devnet-a"
+        host_rpc_url = "http://127.0.0.1:2"
+
+        [[profiles.devnet.networks]]
+        name = "devnet-b"
+        host_rpc_url = "http://127.0.0.1:3"
+    "#;
+
+    /// An app over a temporary config file that never reads state, an address book, or a
+    /// watchlist from $HOME, and never polls until ticked.
+    async fn app(name: &str, extra: &[&str]) -> (App, PathBuf) {
+        let dir =
+            std::env::temp_dir().join(format!("futuresight-app-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (file, contents) in [
+            ("config.toml", CONFIG),
+            ("addresses.toml", ""),
+            ("watchlist.toml", ""),
+        ] {
+            fs::write(dir.join(file), contents).unwrap();
+        }
+        let path = |file: &str| dir.join(file).display().to_string();
+        let cli = Cli::try_parse_from(
+            [
+                "futuresight".to_string(),
+                "--no-state".to_string(),
+                "--config".to_string(),
+                path("config.toml"),
+                "--address-book".to_string(),
+                path("addresses.toml"),
+                "--watchlist".to_string(),
+                path("watchlist.toml"),
+            ]
+            .into_iter()
+            .chain(extra.iter().map(|arg| arg.to_string())),
+        )
+        .unwrap();
+        let Command::Watch(args) = cli.into_command() else {
+            panic!("expected watch");
+        };
+        (App::new(args).await.unwrap(), dir)
+    }
+
+    fn key(code: KeyCode) -> Message {
+        Message::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[tokio::test]
+    async fn quit_closes_overlays_first() {
+        let (mut app, dir) = app("quit", &[]).await;
+        assert_eq!(app.update(key(KeyCode::Char('?'))).unwrap(), Flow::Continue);
+        assert!(app.dashboard.show_help);
+        // The first key only closes the help overlay
+        assert_eq!(app.update(key(KeyCode::Char('q'))).unwrap(), Flow::Continue);
+        assert!(!app.dashboard.show_help);
+        assert_eq!(app.update(key(KeyCode::Esc)).unwrap(), Flow::Quit);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn profile_picker_switches_networks() {
+        let (mut app, dir) = app("profile", &[]).await;
+        assert_eq!(app.networks.len(), 1);
+        app.update(key(KeyCode::Char('p'))).unwrap();
+        assert!(app.dashboard.profile_picker.is_some());
+        app.update(key(KeyCode::Enter)).unwrap();
+
+        assert!(app.dashboard.profile_picker.is_none());
+        assert_eq!(app.dashboard.profile.as_deref(), Some("devnet"));
+        let names: Vec<_> = app.networks.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, ["devnet-a", "devnet-b"]);
+        assert_eq!(
+            app.dashboard.notice.as_deref(),
+            Some("switched to profile devnet")
+        );
+
+        // An unknown profile leaves the networks alone and says why
+        app.switch_profile("missing".to_string());
+        assert_eq!(app.networks.len(), 2);
+        assert!(app.dashboard.notice.as_deref().unwrap().contains("missing"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn notices_mark_the_dashboard_for_redraw() {
+        let (mut app, dir) = app("notice", &[]).await;
+        app.dirty = false;
+        app.set_notice("backfilling 10 blocks…".to_string());
+        assert!(app.dirty);
+        assert_eq!(
+            app.dashboard.notice.as_deref(),
+            Some("backfilling 10 blocks…")
+        );
+
+        // A failed action reports in the footer instead of erroring
+        app.dirty = false;
+        assert_eq!(app.update(key(KeyCode::Char('o'))).unwrap(), Flow::Continue);
+        assert!(app.dirty);
+        assert!(
+            app.dashboard
+                .notice
+                .as_deref()
+                .unwrap()
+                .starts_with("no block selected")
+        );
+        // Opening a picker clears it
+        app.update(key(KeyCode::Char('p'))).unwrap();
+        assert_eq!(app.dashboard.notice, None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn changes_draw_at_max_fps_and_idle_screens_at_the_idle_rate() {
+        let (mut app, dir) = app("frames", &["--max-fps", "4"]).await;
+        let start = Instant::now();
+        // Nothing drawn yet
+        assert!(app.frame_due(start));
+
+        app.last_frame = Some(start);
+        app.dirty = true;
+        assert!(!app.frame_due(start));
+        assert_eq!(app.until_frame(start), Duration::from_millis(250));
+        assert_eq!(
+            app.until_frame(start + Duration::from_millis(100)),
+            Duration::from_millis(150)
+        );
+        assert!(app.frame_due(start + Duration::from_millis(250)));
+
+        // Nothing changed and nothing collecting: only the idle redraw is due
+        app.dirty = false;
+        assert!(!app.frame_due(start + Duration::from_millis(250)));
+        let idle = Duration::from_millis(IDLE_REDRAW_MILLIS);
+        assert_eq!(app.until_frame(start), idle);
+        assert!(app.frame_due(start + idle));
+
+        // Input marks the screen changed again
+        app.update(Message::Redraw).unwrap();
+        assert!(app.frame_due(start + Duration::from_millis(250)));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_collection_finished_while_paused_stops_max_fps_redraws() {
+        let (mut app, dir) = app("paused", &["--max-fps", "4"]).await;
+        app.tick().unwrap();
+        assert!(app.networks[0].is_collecting());
+        app.dashboard.paused = true;
+
+        // The unreachable endpoint fails fast; the paused tick leaves the result untaken
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while app.networks[0].is_collecting() && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            app.tick().unwrap();
+        }
+        assert!(!app.networks[0].is_collecting());
+
+        let start = Instant::now();
+        app.last_frame = Some(start);
+        app.dirty = false;
+        let idle = Duration::from_millis(IDLE_REDRAW_MILLIS);
+        assert_eq!(app.until_frame(start), idle);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
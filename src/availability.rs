@@ -0,0 +1,358 @@
+//! Session uptime/downtime accounting and the outage log, derived from connection-status
+//! transitions
+use crate::config::OUTAGE_LOG;
+use serde::{Deserialize, Deserializer};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Number of hourly availability buckets retained.
+const HOURLY_BUCKETS: usize = 24;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HourBucket {
+    pub hour: u64, // hours since session start
+    pub up: Duration,
+    pub total: Duration,
+}
+
+impl HourBucket {
+    pub fn availability_pct(&self) -> Option<f64> {
+        (!self.total.is_zero()).then(|| self.up.as_secs_f64() * 100.0 / self.total.as_secs_f64())
+    }
+}
+
+/// One stretch without a healthy connection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outage {
+    pub start: Instant,
+    pub end: Option<Instant>, // None while ongoing
+    pub cause: String,        // the status that started it
+}
+
+impl Outage {
+    pub fn duration_at(&self, now: Instant) -> Duration {
+        self.end
+            .unwrap_or(now)
+            .saturating_duration_since(self.start)
+    }
+}
+
+/// An availability objective from `config.toml`, e.g. `slo = { target = 99.5, window = "24h" }`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Slo {
+    #[serde(deserialize_with = "target_pct")]
+    pub target: f64, // percent of the window connected
+    #[serde(deserialize_with = "window")]
+    pub window: Duration,
+}
+
+fn target_pct<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    let target = f64::deserialize(deserializer)?;
+    if target > 0.0 && target < 100.0 {
+        Ok(target)
+    } else {
+        Err(serde::de::Error::custom(format!(
+            "SLO target {} must be a percentage between 0 and 100",
+            target
+        )))
+    }
+}
+
+fn window<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    let raw = String::deserialize(deserializer)?;
+    match humantime::parse_duration(&raw) {
+        Ok(window) if !window.is_zero() => Ok(window),
+        Ok(_) => Err(serde::de::Error::custom(
+            "SLO window must be longer than zero",
+        )),
+        Err(e) => Err(serde::de::Error::custom(format!(
+            "SLO window '{}': {}",
+            raw, e
+        ))),
+    }
+}
+
+impl Slo {
+    /// Downtime the objective allows over a full window.
+    pub fn allowed_downtime(&self) -> Duration {
+        self.window.mul_f64((100.0 - self.target) / 100.0)
+    }
+}
+
+/// How much of an [`Slo`]'s downtime allowance the rolling window has used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorBudget {
+    pub allowed: Duration,
+    pub spent: Duration,
+    /// Until the rest is spent, if downtime keeps the pace it has had over the window so
+    /// far; `None` without downtime.
+    pub exhausted_in: Option<Duration>,
+}
+
+impl ErrorBudget {
+    pub fn remaining(&self) -> Duration {
+        self.allowed.saturating_sub(self.spent)
+    }
+
+    pub fn remaining_pct(&self) -> f64 {
+        if self.allowed.is_zero() {
+            return 0.0;
+        }
+        self.remaining().as_secs_f64() * 100.0 / self.allowed.as_secs_f64()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AvailabilityStats {
+    session_start: Instant,
+    last_observed: Option<(Instant, bool)>,
+    outage_start: Option<Instant>,
+    pub uptime: Duration,
+    pub downtime: Duration,
+    pub outages: u32,
+    pub longest_outage: Duration,
+    pub hourly: VecDeque<HourBucket>,
+    pub log: VecDeque<Outage>, // newest first, at most OUTAGE_LOG
+}
+
+impl AvailabilityStats {
+    pub fn new(session_start: Instant) -> Self {
+        Self {
+            session_start,
+            last_observed: None,
+            outage_start: None,
+            uptime: Duration::ZERO,
+            downtime: Duration::ZERO,
+            outages: 0,
+            longest_outage: Duration::ZERO,
+            hourly: VecDeque::with_capacity(HOURLY_BUCKETS),
+            log: VecDeque::new(),
+        }
+    }
+
+    /// Record the connection state at `now`, attributing the time since the previous
+    /// observation to the previous state. `outage_cause` describes an unhealthy state and
+    /// is `None` while connected; it is logged when an outage starts.
+    pub fn observe(&mut self, outage_cause: Option<&str>, now: Instant) {
+        let up = outage_cause.is_none();
+        if let Some((last_at, last_up)) = self.last_observed {
+            let elapsed = now.saturating_duration_since(last_at);
+            if last_up {
+                self.uptime += elapsed;
+            } else {
+                self.downtime += elapsed;
+            }
+            self.add_to_hour(now, elapsed, last_up);
+
+            match (last_up, up) {
+                (true, false) => self.start_outage(outage_cause, now),
+                (false, true) => {
+                    if let Some(start) = self.outage_start.take() {
+                        self.longest_outage = self
+                            .longest_outage
+                            .max(now.saturating_duration_since(start));
+                    }
+                    if let Some(outage) = self.log.front_mut() {
+                        outage.end = Some(now);
+                    }
+                }
+                _ => {}
+            }
+        } else if !up {
+            // Down from the first observation: count it as an outage once it recovers.
+            self.start_outage(outage_cause, now);
+        }
+        self.last_observed = Some((now, up));
+    }
+
+    fn start_outage(&mut self, cause: Option<&str>, now: Instant) {
+        self.outages += 1;
+        self.outage_start = Some(now);
+        self.log.push_front(Outage {
+            start: now,
+            end: None,
+            cause: cause.unwrap_or_default().to_string(),
+        });
+        self.log.truncate(OUTAGE_LOG);
+    }
+
+    /// Session availability, or `None` before any time has been attributed.
+    pub fn session_pct(&self) -> Option<f64> {
+        let total = self.uptime + self.downtime;
+        (!total.is_zero()).then(|| self.uptime.as_secs_f64() * 100.0 / total.as_secs_f64())
+    }
+
+    /// Longest outage so far, including one that is still ongoing at `now`.
+    pub fn longest_outage_at(&self, now: Instant) -> Duration {
+        let ongoing = self
+            .outage_start
+            .map(|start| now.saturating_duration_since(start))
+            .unwrap_or_default();
+        self.longest_outage.max(ongoing)
+    }
+
+    /// `slo`'s budget over the window ending at `now`, from the outage log. Time before the
+    /// session started counts as up, and outages trimmed from the log are forgotten.
+    pub fn error_budget(&self, slo: &Slo, now: Instant) -> ErrorBudget {
+        let window_start = now
+            .checked_sub(slo.window)
+            .map_or(self.session_start, |start| start.max(self.session_start));
+        let spent: Duration = self
+            .log
+            .iter()
+            .map(|outage| {
+                let start = outage.start.max(window_start);
+                outage.end.unwrap_or(now).saturating_duration_since(start)
+            })
+            .sum();
+        let observed = now.saturating_duration_since(window_start);
+        let allowed = slo.allowed_downtime();
+        let exhausted_in = (!spent.is_zero()).then(|| {
+            allowed
+                .saturating_sub(spent)
+                .mul_f64(observed.as_secs_f64() / spent.as_secs_f64())
+        });
+        ErrorBudget {
+            allowed,
+            spent,
+            exhausted_in,
+        }
+    }
+
+    pub fn current_hour(&self) -> Option<&HourBucket> {
+        self.hourly.back()
+    }
+
+    fn add_to_hour(&mut self, now: Instant, elapsed: Duration, up: bool) {
+        let hour = now.saturating_duration_since(self.session_start).as_secs() / 3_600;
+        if self.hourly.back().map(|bucket| bucket.hour) != Some(hour) {
+            self.hourly.push_back(HourBucket {
+                hour,
+                ..HourBucket::default()
+            });
+            while self.hourly.len() > HOURLY_BUCKETS {
+                self.hourly.pop_front();
+            }
+        }
+        if let Some(bucket) = self.hourly.back_mut() {
+            bucket.total += elapsed;
+            if up {
+                bucket.up += elapsed;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AvailabilityStats, Slo};
+    use crate::config::OUTAGE_LOG;
+    use std::time::{Duration, Instant};
+
+    fn parse_slo(target: f64, window: &str) -> Result<Slo, toml::de::Error> {
+        toml::from_str(&format!("target = {}\nwindow = \"{}\"", target, window))
+    }
+
+    #[test]
+    fn tracks_uptime_outages_and_longest_outage() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut stats = AvailabilityStats::new(start);
+
+        stats.observe(None, at(0));
+        stats.observe(None, at(90));
+        stats.observe(Some("timeout"), at(100));
+        stats.observe(Some("refused"), at(110));
+        stats.observe(None, at(130));
+        stats.observe(Some("stale"), at(170));
+        stats.observe(None, at(175));
+        stats.observe(None, at(200));
+
+        assert_eq!(stats.uptime, Duration::from_secs(165));
+        assert_eq!(stats.downtime, Duration::from_secs(35));
+        assert_eq!(stats.outages, 2);
+        assert_eq!(stats.longest_outage, Duration::from_secs(30));
+        assert_eq!(stats.session_pct(), Some(82.5));
+        assert_eq!(stats.current_hour().unwrap().availability_pct(), Some(82.5));
+
+        // Newest first, each with the status that started it
+        let log: Vec<_> = stats
+            .log
+            .iter()
+            .map(|outage| (outage.cause.as_str(), outage.duration_at(at(200)).as_secs()))
+            .collect();
+        assert_eq!(log, vec![("stale", 5), ("timeout", 30)]);
+    }
+
+    #[test]
+    fn outage_log_records_start_and_end_and_keeps_the_newest() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut stats = AvailabilityStats::new(start);
+
+        // Down from the first observation still logs an outage, ended on recovery
+        stats.observe(Some("refused"), at(0));
+        assert_eq!(stats.log[0].end, None);
+        stats.observe(None, at(10));
+        assert_eq!(
+            (stats.log[0].start, stats.log[0].end),
+            (at(0), Some(at(10)))
+        );
+
+        // 10s down every 20s, well past the log's capacity
+        let total = OUTAGE_LOG as u64 + 2;
+        for outage in 1..total {
+            stats.observe(Some("timeout"), at(outage * 20));
+            stats.observe(None, at(outage * 20 + 10));
+        }
+        assert_eq!(stats.outages as u64, total);
+        assert_eq!(stats.log.len(), OUTAGE_LOG);
+        let newest = &stats.log[0];
+        assert_eq!(newest.start, at((total - 1) * 20));
+        assert_eq!(newest.duration_at(at(10_000)), Duration::from_secs(10));
+        // The two oldest outages were dropped
+        assert_eq!(stats.log[OUTAGE_LOG - 1].start, at(40));
+    }
+
+    #[test]
+    fn error_budget_counts_downtime_inside_the_window() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut stats = AvailabilityStats::new(start);
+        stats.observe(None, at(0));
+        stats.observe(Some("timeout"), at(100));
+        stats.observe(None, at(150));
+        stats.observe(Some("refused"), at(1_000));
+
+        // 99% of 2,000s allows 20s; the first outage is half out of the window
+        let slo = parse_slo(99.0, "2000s").unwrap();
+        let budget = stats.error_budget(&slo, at(2_130));
+        assert_eq!(budget.allowed, Duration::from_secs(20));
+        assert_eq!(budget.spent, Duration::from_secs(20 + 1_130));
+        assert_eq!(budget.remaining(), Duration::ZERO);
+        assert_eq!(budget.exhausted_in, Some(Duration::ZERO));
+
+        // 90% of a day allows 2.4h; 50s in the first 1,000s leaves 8,590s at 20× that pace
+        let slo = parse_slo(90.0, "1day").unwrap();
+        let budget = stats.error_budget(&slo, at(1_000));
+        assert_eq!(budget.spent, Duration::from_secs(50));
+        assert_eq!(budget.exhausted_in, Some(Duration::from_secs(171_800)));
+        assert!(parse_slo(100.0, "24h").is_err());
+        assert!(parse_slo(99.0, "0s").is_err());
+    }
+
+    #[test]
+    fn ongoing_outage_counts_toward_longest() {
+        let start = Instant::now();
+        let mut stats = AvailabilityStats::new(start);
+        stats.observe(None, start);
+        stats.observe(Some("timeout"), start + Duration::from_secs(10));
+        assert_eq!(
+            stats.longest_outage_at(start + Duration::from_secs(100)),
+            Duration::from_secs(90)
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Compressed HTTP responses and the bytes each chain's clients move. reqwest is built
+//! without its decompression features, so bodies arrive exactly as sent on the wire and
+//! are inflated here, which is also what lets the wire size be measured
+use crate::config::MAX_DECODED_BODY_BYTES;
+use miniz_oxide::inflate::{self, DecompressError};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// `Accept-Encoding` sent with every RPC and tx-pool request.
+pub const ACCEPT_ENCODING: &str = "gzip, deflate";
+
+/// Running byte counts for one HTTP client, shared with its in-flight requests.
+#[derive(Debug, Default)]
+pub struct ByteCounter {
+    requests: AtomicU64,
+    sent: AtomicU64,
+    received: AtomicU64,
+    decoded: AtomicU64,
+}
+
+impl ByteCounter {
+    pub fn record(&self, sent: usize, received: usize, decoded: usize) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.sent.fetch_add(sent as u64, Ordering::Relaxed);
+        self.received.fetch_add(received as u64, Ordering::Relaxed);
+        self.decoded.fetch_add(decoded as u64, Ordering::Relaxed);
+    }
+
+    pub fn totals(&self) -> Traffic {
+        Traffic {
+            requests: self.requests.load(Ordering::Relaxed),
+            sent: self.sent.load(Ordering::Relaxed),
+            received: self.received.load(Ordering::Relaxed),
+            decoded: self.decoded.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Bytes moved by a chain's clients: request bodies sent, response bodies as received on
+/// the wire, and the same responses decompressed. Headers aren't counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Traffic {
+    pub requests: u64,
+    pub sent: u64,
+    pub received: u64,
+    pub decoded: u64,
+}
+
+impl std::ops::Add for Traffic {
+    type Output = Traffic;
+
+    fn add(self, other: Traffic) -> Traffic {
+        Traffic {
+            requests: self.requests + other.requests,
+            sent: self.sent + other.sent,
+            received: self.received + other.received,
+            decoded: self.decoded + other.decoded,
+        }
+    }
+}
+
+impl Traffic {
+    /// What was moved since `earlier`, a previous total of the same counters.
+    pub fn since(self, earlier: Traffic) -> Traffic {
+        Traffic {
+            requests: self.requests.saturating_sub(earlier.requests),
+            sent: self.sent.saturating_sub(earlier.sent),
+            received: self.received.saturating_sub(earlier.received),
+            decoded: self.decoded.saturating_sub(earlier.decoded),
+        }
+    }
+
+    /// Share of response bytes compression kept off the wire, 0.0–1.0.
+    pub fn compression_savings(&self) -> Option<f64> {
+        (self.decoded > 0).then(|| 1.0 - self.received as f64 / self.decoded as f64)
+    }
+}
+
+/// Inflate a response body according to its `Content-Encoding`.
+pub fn decode(encoding: Option<&str>, body: &[u8]) -> Result<Vec<u8>, String> {
+    let encoding = encoding.map(|value| value.trim().to_ascii_lowercase());
+    match encoding.as_deref() {
+        None | Some("" | "identity") => Ok(body.to_vec()),
+        Some("gzip" | "x-gzip") => gunzip(body),
+        // HTTP's deflate is zlib-wrapped, but some servers send a bare deflate stream
+        Some("deflate") => inflate::decompress_to_vec_zlib_with_limit(body, MAX_DECODED_BODY_BYTES)
+            .or_else(|_| inflate::decompress_to_vec_with_limit(body, MAX_DECODED_BODY_BYTES))
+            .map_err(|e| inflate_error("deflate", e)),
+        Some(other) => Err(format!("unsupported Content-Encoding '{}'", other)),
+    }
+}
+
+/// A gzip member: a header of 10+ bytes, a deflate stream, then a CRC and size trailer.
+fn gunzip(body: &[u8]) -> Result<Vec<u8>, String> {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+    let truncated = || "truncated gzip header".to_string();
+    if body.len() < 18 || body[..3] != [0x1f, 0x8b, 8] {
+        return Err("not a gzip body".to_string());
+    }
+    let flags = body[3];
+    let mut at = 10;
+    if flags & FEXTRA != 0 {
+        let len = body.get(at..at + 2).ok_or_else(truncated)?;
+        at += 2 + u16::from_le_bytes([len[0], len[1]]) as usize;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            let rest = body.get(at..).ok_or_else(truncated)?;
+            at += rest.iter().position(|&b| b == 0).ok_or_else(truncated)? + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        at += 2;
+    }
+    let stream = body.get(at..).ok_or_else(truncated)?;
+    inflate::decompress_to_vec_with_limit(stream, MAX_DECODED_BODY_BYTES)
+        .map_err(|e| inflate_error("gzip", e))
+}
+
+fn inflate_error(encoding: &str, e: DecompressError) -> String {
+    format!("bad {} body: {}", encoding, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ByteCounter, Traffic, decode};
+    use miniz_oxide::deflate::{compress_to_vec, compress_to_vec_zlib};
+
+    #[test]
+    fn inflates_each_content_encoding() {
+        let json = br#"{"jsonrpc":"2.0","id":1,"result":"0x1234"}"#.repeat(20);
+        assert_eq!(decode(None, &json).unwrap(), json);
+
+        // Header with a file name, then the stream and an (unchecked) trailer
+        let mut gzip = vec![0x1f, 0x8b, 8, 0x08, 0, 0, 0, 0, 0, 0xff];
+        gzip.extend_from_slice(b"body.json\0");
+        gzip.extend(compress_to_vec(&json, 6));
+        gzip.extend([0; 8]);
+        assert_eq!(decode(Some("gzip"), &gzip).unwrap(), json);
+
+        assert_eq!(
+            decode(Some("Deflate"), &compress_to_vec_zlib(&json, 6)).unwrap(),
+            json
+        );
+        assert_eq!(
+            decode(Some("deflate"), &compress_to_vec(&json, 6)).unwrap(),
+            json
+        );
+        assert!(decode(Some("gzip"), &json).is_err());
+        assert!(decode(Some("br"), &json).is_err());
+    }
+
+    #[test]
+    fn traffic_adds_up_per_cycle() {
+        let counter = ByteCounter::default();
+        counter.record(100, 300, 1_200);
+        let first = counter.totals();
+        counter.record(100, 200, 800);
+        let cycle = counter.totals().since(first);
+        assert_eq!(
+            cycle,
+            Traffic {
+                requests: 1,
+                sent: 100,
+                received: 200,
+                decoded: 800,
+            }
+        );
+        assert_eq!(counter.totals().compression_savings(), Some(0.75));
+        assert_eq!(Traffic::default().compression_savings(), None);
+        assert_eq!((first + cycle).received, 500);
+    }
+}
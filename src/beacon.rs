@@ -0,0 +1,175 @@
+//! Blob sidecars from a consensus client's beacon API (`--beacon-url`). Execution nodes
+//! don't serve blobs, so the host's blocks are matched to beacon slots by timestamp
+use crate::bandwidth::{ByteCounter, Traffic};
+use crate::config::{BEACON_SECONDS_PER_SLOT, BEACON_TIMEOUT_SECS};
+use crate::error::{FutureSightError, Result};
+use crate::http::{self, HttpOptions};
+use alloy::eips::eip4844::{BYTES_PER_BLOB, Blob, BlobTransactionSidecarItem, Bytes48};
+use alloy::primitives::B256;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+pub struct BeaconClient {
+    url: String,
+    http: reqwest::Client,
+    traffic: ByteCounter,
+    genesis_time: Option<u64>, // fetched once
+}
+
+impl BeaconClient {
+    pub fn new(url: String, options: &HttpOptions) -> Result<Self> {
+        let http = options
+            .client_builder()?
+            .timeout(Duration::from_secs(BEACON_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| FutureSightError::Config(e.to_string()))?;
+        Ok(Self {
+            url: url.trim_end_matches('/').to_string(),
+            http,
+            traffic: ByteCounter::default(),
+            genesis_time: None,
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn traffic(&self) -> Traffic {
+        self.traffic.totals()
+    }
+
+    /// The `data` of a beacon API response.
+    async fn get(&self, path: &str) -> std::result::Result<Value, String> {
+        let url = format!("{}{}", self.url, path);
+        let resp = http::fetch(self.http.get(&url), 0, &self.traffic).await?;
+        if !resp.status.is_success() {
+            return Err(format!("{} HTTP {}", path, resp.status));
+        }
+        let mut body: Value = serde_json::from_slice(&resp.body).map_err(|e| e.to_string())?;
+        Ok(body["data"].take())
+    }
+
+    /// Sidecars of the block proposed at `timestamp`, in blob index order. Empty when the
+    /// slot has no blobs.
+    pub async fn blob_sidecars(
+        &mut self,
+        timestamp: u64,
+    ) -> std::result::Result<Vec<BlobTransactionSidecarItem>, String> {
+        let genesis_time = match self.genesis_time {
+            Some(time) => time,
+            None => {
+                let genesis = self.get("/eth/v1/beacon/genesis").await?;
+                let time = genesis["genesis_time"]
+                    .as_str()
+                    .and_then(|time| time.parse().ok())
+                    .ok_or("genesis: no genesis_time")?;
+                *self.genesis_time.insert(time)
+            }
+        };
+        let slot = slot_at(genesis_time, timestamp)
+            .ok_or_else(|| format!("block at {} predates beacon genesis", timestamp))?;
+        let sidecars = self
+            .get(&format!("/eth/v1/beacon/blob_sidecars/{}", slot))
+            .await?;
+        parse_sidecars(&sidecars)
+    }
+}
+
+fn slot_at(genesis_time: u64, timestamp: u64) -> Option<u64> {
+    Some(timestamp.checked_sub(genesis_time)? / BEACON_SECONDS_PER_SLOT)
+}
+
+/// Sidecars from a `blob_sidecars` response's `data`, which spells numbers as decimal
+/// strings and bytes as hex.
+fn parse_sidecars(data: &Value) -> std::result::Result<Vec<BlobTransactionSidecarItem>, String> {
+    let mut items = data
+        .as_array()
+        .ok_or("blob_sidecars: expected an array")?
+        .iter()
+        .map(|sidecar| {
+            let field = |key: &str| {
+                sidecar[key]
+                    .as_str()
+                    .ok_or_else(|| format!("blob_sidecars: no {}", key))
+            };
+            let bytes = |key: &str| {
+                hex::decode(field(key)?.trim_start_matches("0x"))
+                    .map_err(|e| format!("blob_sidecars: bad {}: {}", key, e))
+            };
+            let bytes48 = |key: &str| {
+                Bytes48::try_from(bytes(key)?.as_slice())
+                    .map_err(|_| format!("blob_sidecars: {} isn't 48 bytes", key))
+            };
+            let blob = bytes("blob")?;
+            if blob.len() != BYTES_PER_BLOB {
+                return Err(format!("blob_sidecars: blob of {} bytes", blob.len()));
+            }
+            Ok(BlobTransactionSidecarItem {
+                index: field("index")?
+                    .parse()
+                    .map_err(|_| "blob_sidecars: bad index".to_string())?,
+                blob: Box::new(Blob::from_slice(&blob)),
+                kzg_commitment: bytes48("kzg_commitment")?,
+                kzg_proof: bytes48("kzg_proof")?,
+            })
+        })
+        .collect::<std::result::Result<Vec<_>, String>>()?;
+    items.sort_by_key(|item| item.index);
+    Ok(items)
+}
+
+/// The hash a blob transaction commits to: `0x01 ++ sha256(commitment)[1..]`.
+pub fn versioned_hash(item: &BlobTransactionSidecarItem) -> B256 {
+    let mut hash: [u8; 32] = Sha256::digest(item.kzg_commitment.as_slice()).into();
+    hash[0] = 0x01;
+    B256::from(hash)
+}
+
+/// Bytes up to the blob's last non-zero byte: roughly how much of the 128 KiB the rollup
+/// or other sender actually used, since blobs are zero-padded.
+pub fn used_bytes(item: &BlobTransactionSidecarItem) -> usize {
+    item.blob
+        .iter()
+        .rposition(|&byte| byte != 0)
+        .map_or(0, |last| last + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_sidecars, slot_at, used_bytes, versioned_hash};
+    use serde_json::json;
+
+    #[test]
+    fn parses_sidecars_and_hashes_commitments() {
+        let mut blob = vec![0u8; 131_072];
+        blob[..3].copy_from_slice(&[0, 0xab, 0xcd]);
+        let sidecar = |index: &str| {
+            json!({
+                "index": index,
+                "blob": format!("0x{}", hex::encode(&blob)),
+                "kzg_commitment": format!("0xc0{}", "00".repeat(47)),
+                "kzg_proof": format!("0x{}", "00".repeat(48)),
+            })
+        };
+        let items = parse_sidecars(&json!([sidecar("1"), sidecar("0")])).unwrap();
+        assert_eq!(
+            items.iter().map(|item| item.index).collect::<Vec<_>>(),
+            [0, 1]
+        );
+        assert_eq!(used_bytes(&items[0]), 3);
+        // sha256 of the point at infinity, with the version byte swapped in
+        assert_eq!(
+            versioned_hash(&items[0]).to_string(),
+            "0x010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"
+        );
+
+        let mut short = sidecar("0");
+        short["blob"] = json!("0x00");
+        assert!(parse_sidecars(&json!([short])).is_err());
+        assert!(parse_sidecars(&json!({})).is_err());
+        assert_eq!(slot_at(1_606_824_023, 1_606_824_023 + 25), Some(2));
+        assert_eq!(slot_at(100, 99), None);
+    }
+}
@@ -0,0 +1,114 @@
+//! Block size in bytes, for rollups whose costs follow data rather than gas: calldata
+//! counted from the newest block's full body, and runs of blocks close to the size limit
+use crate::config::BLOCK_SIZE_NEAR_LIMIT;
+use crate::data::BlockInfo;
+use serde_json::Value;
+use std::collections::VecDeque;
+
+/// Newest blocks at or past [`BLOCK_SIZE_NEAR_LIMIT`] of the configured limit, in a row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizePressure {
+    pub blocks: usize,
+    pub largest: u64, // bytes
+    pub limit: u64,
+}
+
+/// Calldata bytes across a block fetched with full transaction objects. `None` if it has
+/// no `transactions` array or lists only hashes.
+pub fn calldata_bytes(block: &Value) -> Option<u64> {
+    let transactions = block.get("transactions")?.as_array()?;
+    if transactions.iter().any(Value::is_string) {
+        return None;
+    }
+    Some(
+        transactions
+            .iter()
+            .filter_map(|tx| tx.get("input").and_then(Value::as_str))
+            .map(|input| input.trim_start_matches("0x").len() as u64 / 2)
+            .sum(),
+    )
+}
+
+/// The run of near-limit blocks at the head of `history`, newest first, stopping at the
+/// first block under the mark, without a reported size, or after a gap in numbers. `None`
+/// if the newest block isn't near the limit.
+pub fn near_limit_run(history: &VecDeque<BlockInfo>, limit: u64) -> Option<SizePressure> {
+    let mark = (limit as f64 * BLOCK_SIZE_NEAR_LIMIT) as u64;
+    let mut pressure = SizePressure {
+        blocks: 0,
+        largest: 0,
+        limit,
+    };
+    let mut expected = history.front()?.number;
+    for block in history {
+        match block.size {
+            Some(size) if block.number == expected && size >= mark => {
+                pressure.blocks += 1;
+                pressure.largest = pressure.largest.max(size);
+                expected = expected.saturating_sub(1);
+            }
+            _ => break,
+        }
+    }
+    (pressure.blocks > 0).then_some(pressure)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calldata_bytes, near_limit_run};
+    use crate::data::BlockInfo;
+    use alloy::primitives::{Address, B256};
+    use serde_json::json;
+    use std::collections::VecDeque;
+
+    #[test]
+    fn sums_calldata_of_full_transactions() {
+        let block = json!({"transactions": [
+            {"input": "0x"}, {"input": "0xa9059cbb00"}, {"input": "0x0102"}, {}
+        ]});
+        assert_eq!(calldata_bytes(&block), Some(7));
+        assert_eq!(calldata_bytes(&json!({"transactions": ["0xabc"]})), None);
+        assert_eq!(calldata_bytes(&json!({})), None);
+    }
+
+    #[test]
+    fn counts_the_run_of_near_limit_blocks_at_the_head() {
+        let block = |number: u64, size: Option<u64>| BlockInfo {
+            number,
+            hash: String::new(),
+            parent_hash: String::new(),
+            state_root: B256::ZERO,
+            timestamp: 0,
+            tx_count: 0,
+            gas_used: 0,
+            gas_limit: 0,
+            blobs: vec![],
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
+            base_fee_per_gas: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            withdrawals_count: None,
+            size,
+            calldata_bytes: None,
+            receipts: None,
+        };
+        // 90% of 1,000 is the mark; block 7 is missing, so 6 can't extend the run
+        let history: VecDeque<BlockInfo> = [
+            block(10, Some(950)),
+            block(9, Some(900)),
+            block(8, Some(990)),
+            block(6, Some(1_000)),
+        ]
+        .into();
+        let run = near_limit_run(&history, 1_000).unwrap();
+        assert_eq!((run.blocks, run.largest, run.limit), (3, 990, 1_000));
+
+        let history: VecDeque<BlockInfo> = [block(10, Some(899)), block(9, Some(950))].into();
+        assert_eq!(near_limit_run(&history, 1_000), None);
+        let history: VecDeque<BlockInfo> = [block(10, None)].into();
+        assert_eq!(near_limit_run(&history, 1_000), None);
+    }
+}
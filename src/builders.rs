@@ -0,0 +1,133 @@
+//! Block builder attribution from fee recipients and extra-data
+use crate::address_book::short_address;
+use crate::data::BlockInfo;
+use alloy::primitives::{Address, address};
+use std::collections::HashMap;
+
+/// Fee recipients of well-known mainnet block builders.
+const KNOWN_BUILDERS: [(Address, &str); 6] = [
+    (
+        address!("95222290DD7278Aa3Ddd389Cc1E1d165CC4BAfe5"),
+        "beaverbuild",
+    ),
+    (
+        address!("4838B106FCe9647Bdf1E7877BF73cE8B0BAD5f97"),
+        "titan",
+    ),
+    (
+        address!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326"),
+        "rsync",
+    ),
+    (
+        address!("DAFEA492D9c6733ae3d56b7Ed1ADB60692c98Bc5"),
+        "flashbots",
+    ),
+    (
+        address!("199D5ED7F45F4eE35960cF22EAde2076e95B253F"),
+        "bloxroute",
+    ),
+    (address!("388C818CA8B9251b393131C08a736A67ccB19297"), "lido"),
+];
+
+/// Fee-recipient → label table, seeded with [`KNOWN_BUILDERS`] and extended from config.
+#[derive(Debug, Clone)]
+pub struct BuilderLabels {
+    by_recipient: HashMap<Address, String>,
+}
+
+impl Default for BuilderLabels {
+    fn default() -> Self {
+        Self {
+            by_recipient: KNOWN_BUILDERS
+                .iter()
+                .map(|(address, label)| (*address, label.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl BuilderLabels {
+    /// Built-in labels plus `extra`, which wins on conflicts.
+    pub fn with_overrides(extra: impl IntoIterator<Item = (Address, String)>) -> Self {
+        let mut labels = Self::default();
+        labels.by_recipient.extend(extra);
+        labels
+    }
+
+    /// Label a block by its fee recipient, then by readable extra-data, then by short address.
+    pub fn attribute(&self, fee_recipient: Address, extra_data: &str) -> String {
+        if let Some(label) = self.by_recipient.get(&fee_recipient) {
+            return label.clone();
+        }
+        if !extra_data.is_empty() {
+            return extra_data.to_string();
+        }
+        short_address(fee_recipient)
+    }
+}
+
+/// Readable text from header extra-data: printable runs joined by spaces, so RLP-encoded
+/// client versions read as e.g. "geth go1.21.5 linux". Empty if nothing readable remains.
+pub fn printable_extra_data(raw: &[u8]) -> String {
+    let runs: Vec<&str> = raw
+        .split(|byte| !(byte.is_ascii_graphic() || *byte == b' '))
+        .filter(|run| run.len() >= 2)
+        .filter_map(|run| std::str::from_utf8(run).ok())
+        .map(str::trim)
+        .filter(|run| !run.is_empty())
+        .collect();
+    runs.join(" ")
+}
+
+/// Blocks per builder label, most frequent first (ties keep first-seen order).
+pub fn distribution<'a>(history: impl IntoIterator<Item = &'a BlockInfo>) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for block in history {
+        match counts.iter_mut().find(|(label, _)| *label == block.builder) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((block.builder.clone(), 1)),
+        }
+    }
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BuilderLabels, printable_extra_data};
+    use alloy::primitives::{Address, address};
+
+    #[test]
+    fn attribution_prefers_labels_then_extra_data() {
+        let custom = address!("00000000000000000000000000000000000000aa");
+        let labels = BuilderLabels::with_overrides([(custom, "local".to_string())]);
+
+        assert_eq!(
+            labels.attribute(
+                address!("95222290DD7278Aa3Ddd389Cc1E1d165CC4BAfe5"),
+                "beaverbuild.org"
+            ),
+            "beaverbuild"
+        );
+        assert_eq!(labels.attribute(custom, ""), "local");
+        assert_eq!(
+            labels.attribute(Address::repeat_byte(0x11), "Titan (titanbuilder.xyz)"),
+            "Titan (titanbuilder.xyz)"
+        );
+        assert_eq!(
+            labels.attribute(Address::repeat_byte(0x11), ""),
+            "0x1111…1111"
+        );
+    }
+
+    #[test]
+    fn extra_data_keeps_printable_runs() {
+        assert_eq!(printable_extra_data(b"beaverbuild.org"), "beaverbuild.org");
+        assert_eq!(
+            printable_extra_data(&[0xd8, 0x83, 0x01, 0x0e, 0x05, 0x84, b'g', b'e', b't', b'h']),
+            "geth"
+        );
+        assert_eq!(printable_extra_data(&[0x01, 0x9f, 0x41, 0x00]), "");
+        assert_eq!(printable_extra_data(&[]), "");
+    }
+}
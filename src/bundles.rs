@@ -0,0 +1,140 @@
+//! Bundle simulation outcomes reported by the tx-pool webservice, when it reports them
+use crate::config::BUNDLE_SIM_HISTORY;
+use serde_json::Value;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleSimStatus {
+    Ok,
+    Reverted,
+    Pending,
+}
+
+impl BundleSimStatus {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.to_ascii_lowercase().as_str() {
+            "ok" | "success" | "succeeded" | "simulated" | "valid" => Some(Self::Ok),
+            "reverted" | "revert" | "failed" | "failure" | "error" | "rejected" | "invalid" => {
+                Some(Self::Reverted)
+            }
+            "pending" | "queued" | "unsimulated" | "simulating" => Some(Self::Pending),
+            _ => None,
+        }
+    }
+}
+
+/// Simulation status per bundle in a `/bundles` response. Bundles without a recognizable
+/// status are left out, so a service without simulation results yields nothing.
+pub fn parse_bundle_statuses(json: &Value) -> Vec<(String, BundleSimStatus)> {
+    let bundles = json
+        .as_array()
+        .or_else(|| json.get("bundles").and_then(Value::as_array));
+    bundles
+        .into_iter()
+        .flatten()
+        .filter_map(|bundle| {
+            let id = bundle.get("id")?.as_str()?.to_string();
+            Some((id, bundle_status(bundle)?))
+        })
+        .collect()
+}
+
+fn bundle_status(bundle: &Value) -> Option<BundleSimStatus> {
+    let simulation = bundle.get("simulation").unwrap_or(&Value::Null);
+    if let Some(success) = simulation.get("success").and_then(Value::as_bool) {
+        return Some(if success {
+            BundleSimStatus::Ok
+        } else {
+            BundleSimStatus::Reverted
+        });
+    }
+    [
+        Some(simulation),
+        simulation.get("status"),
+        bundle.get("simulationStatus"),
+        bundle.get("simStatus"),
+        bundle.get("status"),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|status| BundleSimStatus::parse(status.as_str()?))
+}
+
+/// The last [`BUNDLE_SIM_HISTORY`] bundles seen, newest first, with their latest status.
+#[derive(Debug, Clone, Default)]
+pub struct BundleSims {
+    bundles: VecDeque<(String, BundleSimStatus)>,
+}
+
+impl BundleSims {
+    /// Update bundles already seen and add new ones.
+    pub fn observe(&mut self, statuses: &[(String, BundleSimStatus)]) {
+        for (id, status) in statuses {
+            match self.bundles.iter_mut().find(|(seen, _)| seen == id) {
+                Some((_, seen_status)) => *seen_status = *status,
+                None => self.bundles.push_front((id.clone(), *status)),
+            }
+        }
+        self.bundles.truncate(BUNDLE_SIM_HISTORY);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bundles.is_empty()
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.bundles.iter().map(|(id, _)| id.as_str())
+    }
+
+    /// Forget all but the newest `len` bundles.
+    pub fn truncate(&mut self, len: usize) {
+        self.bundles.truncate(len);
+    }
+
+    pub fn statuses(&self) -> impl Iterator<Item = BundleSimStatus> + '_ {
+        self.bundles.iter().map(|(_, status)| *status)
+    }
+
+    pub fn count(&self, status: BundleSimStatus) -> usize {
+        self.statuses().filter(|seen| *seen == status).count()
+    }
+
+    /// Reverted share of the bundles whose simulation finished, in percent.
+    pub fn failure_pct(&self) -> Option<f64> {
+        let reverted = self.count(BundleSimStatus::Reverted);
+        let finished = reverted + self.count(BundleSimStatus::Ok);
+        (finished > 0).then(|| reverted as f64 * 100.0 / finished as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BundleSimStatus, BundleSims, parse_bundle_statuses};
+
+    #[test]
+    fn tracks_simulation_outcomes_across_polls() {
+        let first = parse_bundle_statuses(&serde_json::json!({"bundles": [
+            {"id": "a", "simulation": {"status": "pending"}},
+            {"id": "b", "simulation": {"success": false}},
+            {"id": "c", "bundle": {}},
+        ]}));
+        assert_eq!(
+            first,
+            vec![
+                ("a".to_string(), BundleSimStatus::Pending),
+                ("b".to_string(), BundleSimStatus::Reverted),
+            ]
+        );
+
+        let mut sims = BundleSims::default();
+        sims.observe(&first);
+        assert_eq!(sims.failure_pct(), Some(100.0));
+
+        sims.observe(&parse_bundle_statuses(&serde_json::json!([
+            {"id": "a", "status": "SIMULATED"},
+        ])));
+        assert_eq!(sims.count(BundleSimStatus::Pending), 0);
+        assert_eq!(sims.failure_pct(), Some(50.0));
+        assert!(parse_bundle_statuses(&serde_json::json!({"bundles": []})).is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+//! Which optional RPC methods an endpoint implements
+use crate::error::Result;
+use crate::rpc_stats::RpcMethod;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Support {
+    #[default]
+    Unknown, // not probed yet, or only failed for other reasons
+    Supported,
+    Unsupported,
+}
+
+/// Support for each of [`RpcMethod::OPTIONAL`]. Required methods are always assumed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Capabilities {
+    support: [Support; RpcMethod::ALL.len()],
+    pub probed: bool,
+}
+
+impl Capabilities {
+    pub fn get(&self, method: RpcMethod) -> Support {
+        if method.is_optional() {
+            self.support[method as usize]
+        } else {
+            Support::Supported
+        }
+    }
+
+    /// False only once the endpoint has said it doesn't implement `method`.
+    pub fn supports(&self, method: RpcMethod) -> bool {
+        self.get(method) != Support::Unsupported
+    }
+
+    /// Learn from a call's outcome. Errors other than "method not found" say nothing
+    /// about support, so they leave it unchanged.
+    pub fn observe<T>(&mut self, method: RpcMethod, result: &Result<T>) {
+        if !method.is_optional() {
+            return;
+        }
+        match result {
+            Ok(_) => self.support[method as usize] = Support::Supported,
+            Err(e) if e.is_unsupported() => self.support[method as usize] = Support::Unsupported,
+            Err(_) => {}
+        }
+    }
+
+    pub fn unsupported(&self) -> impl Iterator<Item = RpcMethod> + '_ {
+        RpcMethod::OPTIONAL
+            .into_iter()
+            .filter(|method| self.get(*method) == Support::Unsupported)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Capabilities, Support};
+    use crate::error::FutureSightError;
+    use crate::rpc_stats::RpcMethod;
+
+    #[test]
+    fn only_method_not_found_disables_a_method() {
+        let mut capabilities = Capabilities::default();
+        capabilities.observe(
+            RpcMethod::FeeHistory,
+            &Err::<(), _>(FutureSightError::rpc("HTTP 502")),
+        );
+        assert_eq!(capabilities.get(RpcMethod::FeeHistory), Support::Unknown);
+        assert!(capabilities.supports(RpcMethod::FeeHistory));
+
+        capabilities.observe(
+            RpcMethod::TxPoolStatus,
+            &Err::<(), _>(FutureSightError::rpc("Method not found")),
+        );
+        capabilities.observe(RpcMethod::Syncing, &Ok(false));
+        capabilities.observe(
+            RpcMethod::ChainId,
+            &Err::<(), _>(FutureSightError::rpc("Method not found")),
+        );
+        assert!(!capabilities.supports(RpcMethod::TxPoolStatus));
+        assert_eq!(capabilities.get(RpcMethod::Syncing), Support::Supported);
+        assert!(capabilities.supports(RpcMethod::ChainId));
+        assert_eq!(
+            capabilities.unsupported().collect::<Vec<_>>(),
+            [RpcMethod::TxPoolStatus]
+        );
+    }
+}
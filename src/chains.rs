@@ -0,0 +1,94 @@
+//! Network names by chain ID: a built-in registry, overridden by config `[chains]`
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+
+/// Names shown when the config doesn't name a chain ID.
+const KNOWN_CHAINS: &[(u64, &str)] = &[
+    (1, "Ethereum Mainnet"),
+    (10, "OP Mainnet"),
+    (519, "Signet Mainnet"),
+    (1337, "Geth Dev"),
+    (8453, "Base"),
+    (14174, "Signet Pecorino"),
+    (17000, "Holesky"),
+    (31337, "Anvil"),
+    (42161, "Arbitrum One"),
+    (88888, "Signet Parmigiana"),
+    (560048, "Hoodi"),
+    (3151908, "Signet Host Testnet"),
+    (11155111, "Sepolia"),
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChainRegistry {
+    overrides: BTreeMap<u64, String>,
+}
+
+impl ChainRegistry {
+    pub fn new(overrides: BTreeMap<u64, String>) -> Self {
+        Self { overrides }
+    }
+
+    pub fn name(&self, chain_id: u64) -> Option<&str> {
+        self.overrides
+            .get(&chain_id)
+            .map(String::as_str)
+            .or_else(|| {
+                KNOWN_CHAINS
+                    .iter()
+                    .find(|(id, _)| *id == chain_id)
+                    .map(|(_, name)| *name)
+            })
+    }
+
+    /// "Signet Pecorino (14174)", or "unknown network (…)" for an ID nobody named.
+    pub fn label(&self, chain_id: u64) -> String {
+        format!(
+            "{} ({})",
+            self.name(chain_id).unwrap_or("unknown network"),
+            chain_id
+        )
+    }
+}
+
+/// A `[chains]` table: TOML keys are strings, so each is parsed as a decimal chain ID.
+pub fn by_chain_id<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<u64, String>, D::Error> {
+    BTreeMap::<String, String>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(id, name)| match id.trim().parse() {
+            Ok(id) => Ok((id, name)),
+            Err(_) => Err(serde::de::Error::custom(format!(
+                "chain ID '{}' must be a decimal number",
+                id
+            ))),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChainRegistry, KNOWN_CHAINS};
+    use crate::settings::Settings;
+
+    #[test]
+    fn config_names_override_the_registry() {
+        let settings = Settings::parse(
+            r#"
+            [chains]
+            14174 = "pecorino devnet"
+            7 = "scratch"
+            "#,
+        )
+        .unwrap();
+        let registry = ChainRegistry::new(settings.chains);
+        assert_eq!(registry.label(14174), "pecorino devnet (14174)");
+        assert_eq!(registry.label(7), "scratch (7)");
+        assert_eq!(registry.label(1), "Ethereum Mainnet (1)");
+        assert_eq!(registry.name(424242), None);
+        assert_eq!(registry.label(424242), "unknown network (424242)");
+        assert!(KNOWN_CHAINS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(Settings::parse("[chains]\nmainnet = \"x\"\n").is_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! Zoom and pan over the time-series store for the gas deck's charts. Wider windows read
+//! the 1-minute and 15-minute aggregates, so a week fits in a panel's width
+use crate::config::TIMESERIES_QUARTER_POINTS;
+use crate::timeseries::{Resolution, Series};
+
+/// Window widths `+` and `-` step through, in seconds.
+pub const ZOOM_LEVELS: [u64; 6] = [300, 900, 3_600, 21_600, 86_400, 604_800];
+
+/// Which stretch of time the charts show: a zoom level, and how far the window's end
+/// has been panned back from now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChartView {
+    zoom: usize, // index into ZOOM_LEVELS
+    pan: u64,    // seconds
+}
+
+impl Default for ChartView {
+    fn default() -> Self {
+        Self { zoom: 1, pan: 0 }
+    }
+}
+
+impl ChartView {
+    /// A saved zoom level and pan, clamped to what the charts can show.
+    pub fn restore(zoom: usize, pan: u64) -> Self {
+        let zoom = zoom.min(ZOOM_LEVELS.len() - 1);
+        Self {
+            zoom,
+            pan: pan.min(max_pan(ZOOM_LEVELS[zoom])),
+        }
+    }
+
+    /// Index into the zoom levels, for saving.
+    pub fn zoom(&self) -> usize {
+        self.zoom
+    }
+
+    pub fn pan(&self) -> u64 {
+        self.pan
+    }
+
+    /// Seconds the window covers.
+    pub fn span(&self) -> u64 {
+        ZOOM_LEVELS[self.zoom]
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.zoom = self.zoom.saturating_sub(1);
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = (self.zoom + 1).min(ZOOM_LEVELS.len() - 1);
+        self.pan = self.pan.min(max_pan(self.span()));
+    }
+
+    /// Move the window half its width into the past, no further than the oldest
+    /// aggregates reach.
+    pub fn pan_back(&mut self) {
+        self.pan = (self.pan + self.span() / 2).min(max_pan(self.span()));
+    }
+
+    pub fn pan_forward(&mut self) {
+        self.pan = self.pan.saturating_sub(self.span() / 2);
+    }
+
+    /// The window as unix seconds, `since..until`.
+    pub fn bounds(&self, now: u64) -> (u64, u64) {
+        let until = now.saturating_sub(self.pan);
+        (until.saturating_sub(self.span()), until)
+    }
+
+    /// "last 1h", or "1h to 30m ago" once panned.
+    pub fn label(&self) -> String {
+        if self.pan == 0 {
+            format!("last {}", fmt_span(self.span()))
+        } else {
+            format!("{} to {} ago", fmt_span(self.span()), fmt_span(self.pan))
+        }
+    }
+
+    /// Oldest, middle, and newest x-axis labels, e.g. "1h ago", "30m ago", "now".
+    pub fn axis_labels(&self) -> [String; 3] {
+        let ago = |secs| match secs {
+            0 => "now".to_string(),
+            secs => format!("{} ago", fmt_span(secs)),
+        };
+        [
+            ago(self.pan + self.span()),
+            ago(self.pan + self.span() / 2),
+            ago(self.pan),
+        ]
+    }
+
+    /// `series` over the window in `columns` equal buckets, oldest first: each bucket's
+    /// mean, or `None` where nothing was recorded.
+    pub fn columns(&self, series: &Series, now: u64, columns: usize) -> Vec<Option<f64>> {
+        let (since, until) = self.bounds(now);
+        if columns == 0 {
+            return Vec::new();
+        }
+        let mut buckets = vec![(0.0, 0u32); columns];
+        let width = (until - since) as f64 / columns as f64;
+        for point in series.range(since, until) {
+            let column = ((point.at.max(since) - since) as f64 / width) as usize;
+            let (sum, count) = &mut buckets[column.min(columns - 1)];
+            *sum += point.mean() * point.count as f64;
+            *count += point.count;
+        }
+        buckets
+            .into_iter()
+            .map(|(sum, count)| (count > 0).then(|| sum / count as f64))
+            .collect()
+    }
+}
+
+/// Farthest back the window's end may go: the window then starts at the oldest 15-minute
+/// aggregate kept.
+fn max_pan(span: u64) -> u64 {
+    (TIMESERIES_QUARTER_POINTS as u64 * Resolution::Quarter.width()).saturating_sub(span)
+}
+
+/// `90s`, `5m`, `6h`, `7d`, or mixed like `2h30m` when a pan isn't a whole unit.
+fn fmt_span(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3_600 if secs.is_multiple_of(60) => format!("{}m", secs / 60),
+        60..3_600 => format!("{}m{}s", secs / 60, secs % 60),
+        3_600..86_400 if secs.is_multiple_of(3_600) => format!("{}h", secs / 3_600),
+        3_600..86_400 => format!("{}h{}m", secs / 3_600, secs % 3_600 / 60),
+        _ if secs.is_multiple_of(86_400) => format!("{}d", secs / 86_400),
+        _ => format!("{}d{}h", secs / 86_400, secs % 86_400 / 3_600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChartView;
+    use crate::timeseries::Series;
+
+    #[test]
+    fn zoom_and_pan_move_the_window() {
+        let mut view = ChartView::default();
+        assert_eq!(view.label(), "last 15m");
+        view.zoom_out();
+        view.pan_back();
+        assert_eq!(view.label(), "1h to 30m ago");
+        assert_eq!(view.bounds(10_000), (4_600, 8_200));
+        assert_eq!(view.axis_labels(), ["1h30m ago", "1h ago", "30m ago"]);
+        view.zoom_in();
+        view.pan_forward();
+        assert_eq!(view.label(), "15m to 22m30s ago");
+        for _ in 0..10 {
+            view.zoom_in();
+            view.pan_forward();
+        }
+        assert_eq!(view.label(), "last 5m");
+        for _ in 0..10 {
+            view.zoom_out();
+        }
+        // A week back is as far as the 15-minute aggregates reach
+        view.pan_back();
+        assert_eq!(view.label(), "last 7d");
+    }
+
+    #[test]
+    fn restored_views_are_clamped() {
+        let view = ChartView::restore(2, 1_800);
+        assert_eq!(view.label(), "1h to 30m ago");
+        let view = ChartView::restore(99, u64::MAX);
+        assert_eq!((view.label(), view.pan()), ("last 7d".to_string(), 0));
+    }
+
+    #[test]
+    fn buckets_average_the_samples_in_each_column() {
+        let mut series = Series::default();
+        let start = 1_700_000_100;
+        for i in 0..60u64 {
+            series.record(start + i * 5, if i < 30 { 1.0 } else { 3.0 });
+        }
+        let view = ChartView::default();
+        let now = start + 300;
+        // 15 minutes in 3 columns: two empty, then the five recorded minutes
+        assert_eq!(view.columns(&series, now, 3), [None, None, Some(2.0)]);
+        // The newest 5 minutes as 2 columns of 2.5 minutes each
+        let mut zoomed = view;
+        zoomed.zoom_in();
+        assert_eq!(zoomed.columns(&series, now, 2), [Some(1.0), Some(3.0)]);
+        assert!(view.columns(&series, now, 0).is_empty());
+    }
+}
@@ -0,0 +1,215 @@
+//! One-shot health check for scripts, cron, and service watchdogs, and the exit codes
+//! `check` and `watch --exit-on-disconnect` share
+use crate::data::{ConnectionStatus, SignetMetrics};
+
+/// Final state of a chain. `exit_code` follows the Nagios plugin conventions;
+/// `detailed_exit_code` gives each failure its own code so systemd `Restart=on-failure`
+/// and shell scripts can tell them apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Disconnected,
+    Stale,
+    Halted,
+}
+
+impl CheckStatus {
+    /// 0 (OK), 1 (WARNING), or 2 (CRITICAL) for every failure.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            CheckStatus::Ok => 0,
+            CheckStatus::Warning => 1,
+            CheckStatus::Disconnected | CheckStatus::Stale | CheckStatus::Halted => 2,
+        }
+    }
+
+    /// 2 (disconnected), 3 (stale), or 4 (halted) for failures. Nagios reads 3 as UNKNOWN.
+    pub fn detailed_exit_code(self) -> i32 {
+        match self {
+            CheckStatus::Ok => 0,
+            CheckStatus::Warning => 1,
+            CheckStatus::Disconnected => 2,
+            CheckStatus::Stale => 3,
+            CheckStatus::Halted => 4,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "OK",
+            CheckStatus::Warning => "WARNING",
+            CheckStatus::Disconnected => "DISCONNECTED",
+            CheckStatus::Stale => "STALE",
+            CheckStatus::Halted => "HALTED",
+        }
+    }
+}
+
+/// The state that ends a `watch --exit-on-disconnect` session, `None` while healthy.
+///
+/// A chain is halted when it is reachable but its newest block is older than its critical
+/// block delay. Chains that haven't finished a collection yet are not judged.
+pub fn watch_failure(metrics: &SignetMetrics, now: u64) -> Option<CheckStatus> {
+    match metrics.connection_status {
+        ConnectionStatus::Error(_) => Some(CheckStatus::Disconnected),
+        ConnectionStatus::Stale => Some(CheckStatus::Stale),
+        ConnectionStatus::Connected => metrics
+            .latest_block_timestamp
+            .filter(|ts| now.saturating_sub(*ts) > metrics.block_delay.critical)
+            .map(|_| CheckStatus::Halted),
+        ConnectionStatus::Disconnected | ConnectionStatus::RateLimited(_) => None,
+    }
+}
+
+/// Classify a single collection cycle and produce a one-line summary.
+///
+/// Connection failures are `Disconnected` and a block older than `max_block_age` seconds is
+/// `Halted`; rate limiting, or a block timestamp that could not be read, is a warning.
+pub fn evaluate(metrics: &SignetMetrics, max_block_age: u64, now: u64) -> (CheckStatus, String) {
+    let chain = metrics
+        .chain_id
+        .map(|id| format!("chain {}", id))
+        .unwrap_or_else(|| "chain ?".to_string());
+
+    match &metrics.connection_status {
+        ConnectionStatus::Connected => {}
+        ConnectionStatus::RateLimited(e) => {
+            return (
+                CheckStatus::Warning,
+                format!("{} {}: rate limited: {}", metrics.rpc_url, chain, e),
+            );
+        }
+        ConnectionStatus::Error(e) => {
+            return (
+                CheckStatus::Disconnected,
+                format!("{} {}: {}", metrics.rpc_url, chain, e),
+            );
+        }
+        ConnectionStatus::Stale => {
+            return (
+                CheckStatus::Stale,
+                format!("{} {}: stale", metrics.rpc_url, chain),
+            );
+        }
+        status => {
+            return (
+                CheckStatus::Disconnected,
+                format!("{} {}: {:?}", metrics.rpc_url, chain, status),
+            );
+        }
+    }
+
+    let height = metrics
+        .chain_height()
+        .map(|n| format!("#{}", n))
+        .unwrap_or_else(|| "#?".to_string());
+
+    match metrics.latest_block_timestamp {
+        Some(ts) => {
+            let age = now.saturating_sub(ts);
+            let status = if age > max_block_age {
+                CheckStatus::Halted
+            } else {
+                CheckStatus::Ok
+            };
+            (
+                status,
+                format!(
+                    "{} {} block {} age {}s (max {}s)",
+                    metrics.rpc_url, chain, height, age, max_block_age
+                ),
+            )
+        }
+        None => (
+            CheckStatus::Warning,
+            format!(
+                "{} {} block {} age unknown (block header unavailable)",
+                metrics.rpc_url, chain, height
+            ),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CheckStatus, evaluate, watch_failure};
+    use crate::data::{Config, ConnectionStatus, SignetMetrics};
+    use crate::thresholds::DelayThresholds;
+    use std::time::Duration;
+
+    fn metrics() -> SignetMetrics {
+        let mut metrics = SignetMetrics::new(Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            block_delay: DelayThresholds {
+                warning: 30,
+                critical: 60,
+            },
+            expected_block_time: None,
+            stale_after: Duration::from_secs(15),
+            gas_alert_gwei: None,
+            gas_spike_multiplier: None,
+            max_block_history: 1,
+            txpool_max_rows: 1,
+            txpool_fetch_list: false,
+            txpool_watch_signet_host_contracts: false,
+            txpool_stream: None,
+            fetch_receipts: false,
+            http: Default::default(),
+        });
+        metrics.connection_status = ConnectionStatus::Connected;
+        metrics.chain_id = Some(1);
+        metrics.block_number = Some(10);
+        metrics
+    }
+
+    #[test]
+    fn fresh_block_is_ok_and_old_block_is_halted() {
+        let mut m = metrics();
+        m.latest_block_timestamp = Some(1_000);
+        assert_eq!(evaluate(&m, 60, 1_030).0, CheckStatus::Ok);
+        assert_eq!(evaluate(&m, 60, 1_061).0, CheckStatus::Halted);
+
+        m.latest_block_timestamp = None;
+        assert_eq!(evaluate(&m, 60, 1_030).0, CheckStatus::Warning);
+    }
+
+    #[test]
+    fn connection_errors_are_disconnected() {
+        let mut m = metrics();
+        m.connection_status = ConnectionStatus::Error("Chain ID: refused".to_string());
+        let (status, summary) = evaluate(&m, 60, 0);
+        assert_eq!(status, CheckStatus::Disconnected);
+        assert!(summary.contains("refused"));
+    }
+
+    #[test]
+    fn exit_codes_stay_within_nagios_unless_detailed() {
+        let failures = [
+            CheckStatus::Disconnected,
+            CheckStatus::Stale,
+            CheckStatus::Halted,
+        ];
+        assert_eq!(CheckStatus::Ok.exit_code(), 0);
+        assert_eq!(CheckStatus::Warning.exit_code(), 1);
+        assert!(failures.iter().all(|status| status.exit_code() == 2));
+        assert_eq!(failures.map(CheckStatus::detailed_exit_code), [2, 3, 4],);
+        assert_eq!(CheckStatus::Warning.detailed_exit_code(), 1);
+    }
+
+    #[test]
+    fn watch_exits_on_failures_only_after_a_collection() {
+        let mut m = metrics();
+        m.latest_block_timestamp = Some(1_000);
+        assert_eq!(watch_failure(&m, 1_060), None);
+        assert_eq!(watch_failure(&m, 1_061), Some(CheckStatus::Halted));
+
+        m.connection_status = ConnectionStatus::Stale;
+        assert_eq!(watch_failure(&m, 1_000), Some(CheckStatus::Stale));
+        m.connection_status = ConnectionStatus::Error("timeout".to_string());
+        assert_eq!(watch_failure(&m, 1_000), Some(CheckStatus::Disconnected));
+        // Not yet collected
+        m.connection_status = ConnectionStatus::Disconnected;
+        assert_eq!(watch_failure(&m, 2_000), None);
+    }
+}
@@ -0,0 +1,611 @@
+//! Command-line interface definition
+use crate::alerts::AlertKind;
+use crate::http::HttpOptions;
+use crate::probes::SlotWatch;
+use crate::record::ExportFormat;
+use crate::report::ReportFormat;
+use alloy::primitives::Address;
+use clap::{Args, CommandFactory, Parser, Subcommand, value_parser};
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
+
+#[derive(Parser, Debug)]
+#[command(
+    name = env!("CARGO_PKG_NAME"),
+    version = env!("CARGO_PKG_VERSION"),
+    about = "FutureSight is a terminal dashboard for Ethereum RPC and tx-pool telemetry.",
+    next_line_help = true,
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    /// Dashboard options, accepted without a subcommand as shorthand for `watch`.
+    #[command(flatten)]
+    pub watch: WatchArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    /// Resolve the subcommand to run, defaulting to `watch`.
+    pub fn into_command(self) -> Command {
+        self.command.unwrap_or(Command::Watch(self.watch))
+    }
+}
+
+// Parsed once at startup, so the size of the `watch` variant doesn't matter
+#[allow(clippy::large_enum_variant)]
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run the interactive dashboard (default).
+    Watch(WatchArgs),
+    /// Run one collection cycle and exit 0 (ok), 1 (warning), or 2 (critical), like a
+    /// Nagios plugin.
+    Check(CheckArgs),
+    /// Summarize a metrics recording over a time range.
+    Report(ReportArgs),
+    /// Export recorded samples over a time range as CSV or JSON lines.
+    Export(ExportArgs),
+    /// Print a shell completion script covering every subcommand and flag.
+    Completions(CompletionsArgs),
+    /// Show another instance's --stream-listen feed, read-only.
+    Mirror(MirrorArgs),
+    /// Play a metrics recording back over a time range, faster than real time.
+    Replay(ReplayArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct WatchArgs {
+    /// Host (L1) JSON-RPC endpoint.
+    #[arg(
+        long = "host-rpc-url",
+        env = "HOST_RPC_URL",
+        default_value = "https://host-rpc.parmigiana.signet.sh"
+    )]
+    pub host_rpc_url: String,
+
+    /// Rollup (L2) JSON-RPC endpoint.
+    #[arg(
+        long = "rollup-rpc-url",
+        env = "ROLLUP_RPC_URL",
+        default_value = "https://rpc.parmigiana.signet.sh"
+    )]
+    pub rollup_rpc_url: String,
+
+    /// Second host endpoint whose head block number and hash are cross-checked each cycle.
+    #[arg(long = "compare-rpc", env = "COMPARE_RPC_URL")]
+    pub compare_rpc_url: Option<String>,
+
+    /// Second rollup endpoint to cross-check, like --compare-rpc.
+    #[arg(long = "rollup-compare-rpc", env = "ROLLUP_COMPARE_RPC_URL")]
+    pub rollup_compare_rpc_url: Option<String>,
+
+    /// Beacon API of the host's consensus client, e.g. http://localhost:5052, to fetch
+    /// blob sidecars for the Blobs tab.
+    #[arg(long = "beacon-url", env = "BEACON_URL")]
+    pub beacon_url: Option<String>,
+
+    /// External gas oracle URL whose safe/standard/fast suggestions are shown next to the
+    /// host's fee-history tiers. Repeatable; see the README for the JSON shapes read.
+    #[arg(
+        long = "gas-oracle",
+        env = "FUTURESIGHT_GAS_ORACLE",
+        value_delimiter = ','
+    )]
+    pub gas_oracles: Vec<String>,
+
+    /// Blocks the --compare-rpc endpoints may be apart before an endpoint divergence alert.
+    /// Different hashes at the same height always alert.
+    #[arg(
+        long = "compare-max-lag",
+        env = "COMPARE_MAX_LAG",
+        default_value_t = crate::config::DEFAULT_COMPARE_MAX_LAG
+    )]
+    pub compare_max_lag: u64,
+
+    #[command(flatten)]
+    pub http: HttpArgs,
+
+    /// Host block explorer URL template for `o`, with `{number}` and `{hash}` placeholders.
+    /// Defaults to Etherscan for mainnet, Holesky, and Sepolia.
+    #[arg(long = "host-explorer-url", env = "HOST_EXPLORER_URL")]
+    pub host_explorer_url: Option<String>,
+
+    /// Rollup block explorer URL template, e.g. https://explorer.pecorino.signet.sh/block/{number}.
+    #[arg(long = "rollup-explorer-url", env = "ROLLUP_EXPLORER_URL")]
+    pub rollup_explorer_url: Option<String>,
+
+    /// Seconds without a block before the block delay alert turns critical (red, rings
+    /// --bell). Defaults to 5 expected block times, or 60 without --expected-block-time.
+    #[arg(
+        long = "block-delay-secs",
+        env = "BLOCK_DELAY_SECS",
+        value_parser = value_parser!(u64).range(1..)
+    )]
+    pub block_delay_secs: Option<u64>,
+
+    /// Seconds without a block before a warning (yellow) block delay alert is shown.
+    /// Defaults to half of --block-delay-secs.
+    #[arg(
+        long = "block-delay-warn-secs",
+        env = "BLOCK_DELAY_WARN_SECS",
+        value_parser = value_parser!(u64).range(1..)
+    )]
+    pub block_delay_warn_secs: Option<u64>,
+
+    /// Seconds the chains are expected to take per block, e.g. 12 for mainnet. Block delay
+    /// thresholds scale to it and delays show as multiples of it.
+    #[arg(long = "expected-block-time", env = "EXPECTED_BLOCK_TIME", value_parser = positive_f64)]
+    pub expected_block_time: Option<f64>,
+
+    /// Expected block time for the rollup, when it differs from --expected-block-time.
+    #[arg(
+        long = "rollup-expected-block-time",
+        env = "ROLLUP_EXPECTED_BLOCK_TIME",
+        value_parser = positive_f64
+    )]
+    pub rollup_expected_block_time: Option<f64>,
+
+    /// Standard deviation of block intervals, in seconds, that raises a block jitter alert:
+    /// a sequencer hiccuping while the average block time still looks fine. Off by default.
+    #[arg(long = "max-block-jitter", env = "MAX_BLOCK_JITTER", value_parser = positive_f64)]
+    pub max_block_jitter: Option<f64>,
+
+    /// Block jitter limit for the rollup, when it differs from --max-block-jitter.
+    #[arg(
+        long = "rollup-max-block-jitter",
+        env = "ROLLUP_MAX_BLOCK_JITTER",
+        value_parser = positive_f64
+    )]
+    pub rollup_max_block_jitter: Option<f64>,
+
+    /// The host's block size limit in bytes (8388608 on Ethereum since EIP-7934). Blocks
+    /// within 10% of it for several blocks in a row raise a block size alert. Off by default.
+    #[arg(
+        long = "block-size-limit",
+        env = "BLOCK_SIZE_LIMIT",
+        value_parser = value_parser!(u64).range(1..)
+    )]
+    pub block_size_limit: Option<u64>,
+
+    /// The rollup's block size limit in bytes.
+    #[arg(
+        long = "rollup-block-size-limit",
+        env = "ROLLUP_BLOCK_SIZE_LIMIT",
+        value_parser = value_parser!(u64).range(1..)
+    )]
+    pub rollup_block_size_limit: Option<u64>,
+
+    /// ETH moved by a single transaction that raises a large transfer alert, on either chain.
+    /// Scans the full body of every new block. Off by default.
+    #[arg(
+        long = "large-transfer-eth",
+        env = "LARGE_TRANSFER_ETH",
+        value_name = "LARGE_TRANSFER_ETH",
+        value_parser = crate::units::parse_eth
+    )]
+    pub large_transfer_wei: Option<u128>,
+
+    /// Metrics refresh interval in seconds.
+    #[arg(
+        long,
+        short,
+        env = "REFRESH_INTERVAL",
+        default_value_t = crate::config::DEFAULT_REFRESH_INTERVAL,
+        value_parser = value_parser!(u64).range(1..)
+    )]
+    pub refresh_interval: u64,
+
+    /// Most redraws per second. The dashboard redraws only when something changed, and
+    /// once a second otherwise so ages keep counting.
+    #[arg(
+        long = "max-fps",
+        env = "MAX_FPS",
+        default_value_t = crate::config::DEFAULT_MAX_FPS,
+        value_parser = value_parser!(u64).range(1..=60)
+    )]
+    pub max_fps: u64,
+
+    /// Host base fee (gwei) that raises a high base fee alert. Defaults to 5x the median
+    /// base fee seen in the first 5 minutes (100 gwei until then).
+    #[arg(long = "host-gas-alert-gwei", env = "HOST_GAS_ALERT_GWEI", value_parser = positive_f64)]
+    pub host_gas_alert_gwei: Option<f64>,
+
+    /// Rollup base fee (gwei) that raises a high base fee alert. Auto-scaled like the host's.
+    #[arg(long = "rollup-gas-alert-gwei", env = "ROLLUP_GAS_ALERT_GWEI", value_parser = positive_f64)]
+    pub rollup_gas_alert_gwei: Option<f64>,
+
+    /// Base fee to moving-average ratio that raises a fee spike alert. Defaults to 1.25x the
+    /// largest ratio seen in the first 5 minutes, and never below 2.
+    #[arg(long = "gas-spike-multiplier", env = "GAS_SPIKE_MULTIPLIER", value_parser = positive_f64)]
+    pub gas_spike_multiplier: Option<f64>,
+
+    /// Seconds without a successful refresh before a chain is shown as stale.
+    /// Defaults to 7 refresh intervals.
+    #[arg(
+        long = "stale-after",
+        env = "STALE_AFTER_SECS",
+        value_parser = value_parser!(u64).range(1..)
+    )]
+    pub stale_after: Option<u64>,
+
+    /// Base URL for the network tx-pool service.
+    #[arg(long, env = "TXPOOL_URL")]
+    pub txpool_url: Option<String>,
+
+    /// Maximum tx-pool rows rendered per panel.
+    #[arg(
+        long = "txpool-max-rows",
+        env = "TXPOOL_MAX_ROWS",
+        default_value_t = crate::config::DEFAULT_TXPOOL_MAX_ROWS
+    )]
+    pub txpool_max_rows: usize,
+
+    /// Server-sent event stream of the tx-pool service (a path under --txpool-url, or a full
+    /// URL). Pushed transactions, bundles, and orders show up between polls.
+    #[arg(long = "txpool-stream", env = "TXPOOL_STREAM")]
+    pub txpool_stream: Option<String>,
+
+    /// Disable fetching and displaying tx-pool transactions.
+    #[arg(long = "no-txpool-list", default_value_t = false)]
+    pub txpool_disable_list: bool,
+
+    /// Fetch the newest block's receipts each cycle (eth_getBlockReceipts) to count
+    /// reverts and contract deployments.
+    #[arg(long, env = "FUTURESIGHT_RECEIPTS", default_value_t = false)]
+    pub receipts: bool,
+
+    /// Trace the newest block each cycle with geth's callTracer (debug_traceBlockByNumber)
+    /// to count internal calls and reverts and find the heaviest call targets. Needs the
+    /// debug namespace, which public endpoints rarely expose.
+    #[arg(
+        long = "enable-trace",
+        env = "FUTURESIGHT_ENABLE_TRACE",
+        default_value_t = false
+    )]
+    pub enable_trace: bool,
+
+    /// Host storage slot to read every cycle, as `<address>:<slot>[:<type>]`, e.g. an EIP-1967
+    /// implementation slot with `:address`. Repeatable; changes are highlighted.
+    #[arg(
+        long = "watch-slot",
+        env = "FUTURESIGHT_WATCH_SLOTS",
+        value_parser = SlotWatch::parse,
+        value_delimiter = ','
+    )]
+    pub watch_slots: Vec<SlotWatch>,
+
+    /// Rollup storage slot to read every cycle, like --watch-slot.
+    #[arg(
+        long = "rollup-watch-slot",
+        env = "FUTURESIGHT_ROLLUP_WATCH_SLOTS",
+        value_parser = SlotWatch::parse,
+        value_delimiter = ','
+    )]
+    pub rollup_watch_slots: Vec<SlotWatch>,
+
+    /// Host account to prove with eth_getProof against each new block's state root, along
+    /// with its --watch-slot slots. Repeatable; a failed proof raises a critical alert.
+    #[arg(
+        long = "verify-account",
+        env = "FUTURESIGHT_VERIFY_ACCOUNTS",
+        value_delimiter = ','
+    )]
+    pub verify_accounts: Vec<Address>,
+
+    /// Rollup account to prove, like --verify-account.
+    #[arg(
+        long = "rollup-verify-account",
+        env = "FUTURESIGHT_ROLLUP_VERIFY_ACCOUNTS",
+        value_delimiter = ','
+    )]
+    pub rollup_verify_accounts: Vec<Address>,
+
+    /// TOML file of `"0x…" = "label"` pairs naming contracts in the Gas Users tab.
+    #[arg(long = "address-book", env = "FUTURESIGHT_ADDRESS_BOOK")]
+    pub address_book: Option<PathBuf>,
+
+    /// TOML file of addresses, transaction hashes, and log filters to check on every new
+    /// block, re-read when it changes.
+    #[arg(long = "watchlist", env = "FUTURESIGHT_WATCHLIST")]
+    pub watchlist: Option<PathBuf>,
+
+    /// Number of recent blocks retained in memory.
+    #[arg(
+        long = "max-block-history",
+        env = "MAX_BLOCK_HISTORY",
+        default_value_t = crate::config::DEFAULT_MAX_BLOCK_HISTORY
+    )]
+    pub max_block_history: usize,
+
+    /// Approximate memory budget in MB for everything kept over the session: block history,
+    /// time series, alerts, and event logs. Past it, the oldest entries are dropped.
+    #[arg(
+        long = "max-memory-mb",
+        env = "FUTURESIGHT_MAX_MEMORY_MB",
+        value_parser = value_parser!(u64).range(1..)
+    )]
+    pub max_memory_mb: Option<u64>,
+
+    /// Fetch the newest N blocks per chain at startup, in rate-limited batches, so block
+    /// stats are populated immediately. Raises --max-block-history to at least N.
+    #[arg(long, env = "FUTURESIGHT_BACKFILL", default_value_t = 0)]
+    pub backfill: usize,
+
+    /// File used to persist dashboard state across restarts.
+    /// Defaults to $XDG_STATE_HOME/futuresight/state.json.
+    #[arg(long = "state-file", env = "FUTURESIGHT_STATE_FILE")]
+    pub state_file: Option<PathBuf>,
+
+    /// Do not restore or save dashboard state.
+    #[arg(long = "no-state", default_value_t = false)]
+    pub no_state: bool,
+
+    /// Append a JSON-lines metrics sample per chain each refresh, for later `report` runs.
+    #[arg(long, env = "FUTURESIGHT_RECORD")]
+    pub record: Option<PathBuf>,
+
+    /// Ring the terminal bell when a critical alert (connection lost, block delay) first fires.
+    #[arg(long, env = "FUTURESIGHT_BELL", default_value_t = false)]
+    pub bell: bool,
+
+    /// Shell command run instead of the terminal bell, e.g. "paplay alert.oga". Implies --bell.
+    #[arg(long = "bell-command", env = "FUTURESIGHT_BELL_COMMAND")]
+    pub bell_command: Option<String>,
+
+    /// Shell command run when an alert of an --on-alert-kinds kind has been active for
+    /// --on-alert-after, e.g. "docker restart reth". Gets FUTURESIGHT_ALERT_* env vars.
+    #[arg(long = "on-alert-exec", env = "FUTURESIGHT_ON_ALERT_EXEC")]
+    pub on_alert_exec: Option<String>,
+
+    /// Comma-separated alert kinds that trigger --on-alert-exec. Defaults to the critical
+    /// ones (connection-lost, block-delay, reorg, sequencer-stalled, state-proof-mismatch).
+    #[arg(
+        long = "on-alert-kinds",
+        env = "FUTURESIGHT_ON_ALERT_KINDS",
+        value_enum,
+        value_delimiter = ','
+    )]
+    pub on_alert_kinds: Vec<AlertKind>,
+
+    /// How long an alert must stay active before --on-alert-exec runs, e.g. 2m.
+    #[arg(
+        long = "on-alert-after",
+        env = "FUTURESIGHT_ON_ALERT_AFTER",
+        default_value = "0s",
+        value_parser = humantime::parse_duration
+    )]
+    pub on_alert_after: Duration,
+
+    /// Quit when a chain loses its connection, goes stale, or stops producing blocks for
+    /// --block-delay-secs, exiting 2, 3, or 4 respectively (0 on a normal quit).
+    #[arg(
+        long = "exit-on-disconnect",
+        env = "FUTURESIGHT_EXIT_ON_DISCONNECT",
+        default_value_t = false
+    )]
+    pub exit_on_disconnect: bool,
+
+    /// OTLP/HTTP collector base URL, e.g. http://localhost:4318. Exports a span and gauges
+    /// per chain collection as JSON to /v1/traces and /v1/metrics.
+    #[arg(long = "otlp-endpoint", env = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    pub otlp_endpoint: Option<String>,
+
+    /// StatsD/DogStatsD agent as host:port, e.g. localhost:8125. Sends the OTLP gauges per
+    /// chain collection tagged by chain, and each fired alert as an event.
+    #[arg(long = "statsd", env = "FUTURESIGHT_STATSD")]
+    pub statsd: Option<String>,
+
+    /// Write InfluxDB line protocol per chain collection: a file path to append to, or
+    /// udp://host:port for a Telegraf socket listener.
+    #[arg(long = "influx", env = "FUTURESIGHT_INFLUX")]
+    pub influx: Option<String>,
+
+    /// Serve server-sent events on this address, e.g. 127.0.0.1:9470: GET /events streams
+    /// each chain's snapshot as JSON when it's collected, plus alerts as they fire.
+    #[arg(long = "stream-listen", env = "FUTURESIGHT_STREAM_LISTEN")]
+    pub stream_listen: Option<SocketAddr>,
+
+    /// TOML config file. Networks listed there replace the RPC URL flags.
+    /// Defaults to $XDG_CONFIG_HOME/futuresight/config.toml when present.
+    #[arg(long, env = "FUTURESIGHT_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Config file profile (`[profiles.<name>]`) to start with; `p` switches at runtime.
+    #[arg(long, env = "FUTURESIGHT_PROFILE")]
+    pub profile: Option<String>,
+}
+
+impl WatchArgs {
+    /// `--stale-after`, or a multiple of the refresh interval.
+    pub fn stale_after(&self) -> Duration {
+        Duration::from_secs(
+            self.stale_after
+                .unwrap_or(self.refresh_interval * crate::config::STALE_AFTER_REFRESHES),
+        )
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct CheckArgs {
+    /// JSON-RPC endpoint to probe.
+    #[arg(long = "rpc-url", env = "RPC_URL")]
+    pub rpc_url: String,
+
+    /// Seconds since the latest block before the check reports a warning.
+    #[arg(
+        long = "max-block-age",
+        default_value_t = crate::config::BLOCK_DELAY_DEFAULT,
+        value_parser = value_parser!(u64).range(1..)
+    )]
+    pub max_block_age: u64,
+
+    /// Exit 2 (disconnected), 3 (stale), or 4 (halted) instead of 2 for every critical
+    /// state, like `watch --exit-on-disconnect`. Nagios treats 3 and above as UNKNOWN.
+    #[arg(long = "detailed-exit-codes")]
+    pub detailed_exit_codes: bool,
+
+    #[command(flatten)]
+    pub http: HttpArgs,
+}
+
+/// Proxy, TLS, and authentication options for the HTTP clients.
+#[derive(Args, Debug)]
+pub struct HttpArgs {
+    /// HTTP(S) proxy for the RPC and tx-pool clients; hosts in NO_PROXY bypass it. Without
+    /// it, HTTPS_PROXY and HTTP_PROXY apply as usual.
+    #[arg(long = "proxy", env = "FUTURESIGHT_PROXY", value_parser = crate::http::parse_proxy)]
+    pub proxy: Option<String>,
+
+    /// PEM bundle of extra CA certificates to trust, for endpoints behind a private CA.
+    #[arg(long = "ca-cert", env = "FUTURESIGHT_CA_CERT")]
+    pub ca_cert: Option<PathBuf>,
+
+    /// Accept any TLS certificate, e.g. a self-signed one. Only for endpoints you trust.
+    #[arg(long = "insecure")]
+    pub insecure: bool,
+
+    /// Engine API JWT secret file (the node's jwt.hex) for authenticated RPC ports like
+    /// localhost:8551. Signs every RPC request; tx-pool and OTLP requests never carry it.
+    #[arg(long = "jwt-secret", env = "FUTURESIGHT_JWT_SECRET")]
+    pub jwt_secret: Option<PathBuf>,
+}
+
+impl HttpArgs {
+    pub fn options(&self) -> crate::error::Result<HttpOptions> {
+        HttpOptions::new(
+            self.proxy.clone(),
+            self.ca_cert.as_deref(),
+            self.insecure,
+            self.jwt_secret.as_deref(),
+        )
+    }
+}
+
+/// A recording file and the time range to read from it.
+#[derive(Args, Debug)]
+pub struct RangeArgs {
+    /// Recording written by `watch --record`.
+    #[arg(long, env = "FUTURESIGHT_RECORD")]
+    pub record: PathBuf,
+
+    /// Start of the range: now, unix seconds, RFC 3339, or a duration ago such as 24h.
+    #[arg(long, default_value = "24h")]
+    pub from: String,
+
+    /// End of the range, in the same formats as --from.
+    #[arg(long, default_value = "now")]
+    pub to: String,
+}
+
+#[derive(Args, Debug)]
+pub struct ReportArgs {
+    #[command(flatten)]
+    pub range: RangeArgs,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+    pub format: ReportFormat,
+}
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    #[command(flatten)]
+    pub range: RangeArgs,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+    pub format: ExportFormat,
+}
+
+#[derive(Args, Debug)]
+pub struct ReplayArgs {
+    #[command(flatten)]
+    pub range: RangeArgs,
+
+    /// Recorded seconds played per real second.
+    #[arg(long, default_value_t = crate::config::DEFAULT_REPLAY_SPEED, value_parser = positive_f64)]
+    pub speed: f64,
+}
+
+#[derive(Args, Debug)]
+pub struct CompletionsArgs {
+    /// Shell to generate for, e.g. `futuresight completions zsh > _futuresight`.
+    #[arg(value_enum)]
+    pub shell: clap_complete::Shell,
+}
+
+#[derive(Args, Debug)]
+pub struct MirrorArgs {
+    /// The remote's --stream-listen address, e.g. http://server:9470; a bare host gets the
+    /// /events path.
+    pub url: String,
+
+    #[command(flatten)]
+    pub http: HttpArgs,
+}
+
+/// Write the completion script for `shell` to `out`.
+pub fn write_completions(shell: clap_complete::Shell, out: &mut dyn std::io::Write) {
+    clap_complete::generate(shell, &mut Cli::command(), env!("CARGO_PKG_NAME"), out);
+}
+
+fn positive_f64(raw: &str) -> Result<f64, String> {
+    match raw.parse::<f64>() {
+        Ok(value) if value.is_finite() && value > 0.0 => Ok(value),
+        _ => Err(format!("'{}' is not a positive number", raw)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cli, Command, write_completions};
+    use clap::{CommandFactory, Parser};
+
+    #[test]
+    fn cli_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn bare_flags_default_to_watch() {
+        let cli = Cli::try_parse_from(["futuresight", "--refresh-interval", "7"]).unwrap();
+        match cli.into_command() {
+            Command::Watch(args) => assert_eq!(args.refresh_interval, 7),
+            other => panic!("expected watch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn report_subcommand_parses_range() {
+        let cli = Cli::try_parse_from([
+            "futuresight",
+            "report",
+            "--record",
+            "x.jsonl",
+            "--from",
+            "1h",
+        ])
+        .unwrap();
+        match cli.into_command() {
+            Command::Report(args) => {
+                assert_eq!(args.range.from, "1h");
+                assert_eq!(args.range.to, "now");
+            }
+            other => panic!("expected report, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn completions_cover_subcommands_and_flags() {
+        let cli = Cli::try_parse_from(["futuresight", "completions", "bash"]).unwrap();
+        let Command::Completions(args) = cli.into_command() else {
+            panic!("expected completions");
+        };
+        let mut script = Vec::new();
+        write_completions(args.shell, &mut script);
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains("--host-rpc-url"));
+        assert!(script.contains("report"));
+        assert!(Cli::try_parse_from(["futuresight", "completions", "tcsh"]).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! Cross-checks of a chain's head against a second endpoint (`--compare-rpc`), to catch a
+//! lagging provider or one following a different fork
+use crate::config::COMPARE_DEPTH;
+
+/// The latest cross-check against the comparison endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointComparison {
+    pub rpc_url: String,
+    pub max_lag: u64, // blocks apart before the endpoints count as diverged
+    pub head: Option<u64>,
+    pub ours: Option<u64>,
+    pub checked: Option<u64>, // latest height whose hashes matched
+    pub mismatch: Option<HashMismatch>,
+    pub error: Option<String>, // the comparison endpoint's latest failure
+}
+
+/// Different blocks at the same height: one of the endpoints is on another fork.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HashMismatch {
+    pub number: u64,
+    pub ours: String,
+    pub theirs: String,
+}
+
+impl EndpointComparison {
+    pub fn new(rpc_url: String, max_lag: u64) -> Self {
+        Self {
+            rpc_url,
+            max_lag,
+            head: None,
+            ours: None,
+            checked: None,
+            mismatch: None,
+            error: None,
+        }
+    }
+
+    /// Blocks the comparison endpoint is behind ours; negative when it is ahead.
+    pub fn lag(&self) -> Option<i64> {
+        Some(self.ours? as i64 - self.head? as i64)
+    }
+
+    pub fn is_lagging(&self) -> bool {
+        self.lag()
+            .is_some_and(|lag| lag.unsigned_abs() > self.max_lag)
+    }
+
+    pub fn diverged(&self) -> bool {
+        self.mismatch.is_some() || self.is_lagging()
+    }
+
+    /// Record the hashes both endpoints report at `number`.
+    pub fn observe_hashes(&mut self, number: u64, ours: &str, theirs: &str) {
+        if ours.eq_ignore_ascii_case(theirs) {
+            self.checked = Some(number);
+            self.mismatch = None;
+        } else {
+            self.mismatch = Some(HashMismatch {
+                number,
+                ours: ours.to_string(),
+                theirs: theirs.to_string(),
+            });
+        }
+    }
+}
+
+/// The height compared by hash: a few blocks below the lower head, so a reorg of the tip
+/// that one endpoint has seen and the other hasn't yet doesn't count as a split.
+pub fn compared_height(ours: u64, theirs: u64) -> Option<u64> {
+    ours.min(theirs).checked_sub(COMPARE_DEPTH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EndpointComparison, compared_height};
+
+    #[test]
+    fn lag_and_hash_disagreements_count_as_divergence() {
+        assert_eq!(compared_height(100, 97), Some(95));
+        assert_eq!(compared_height(1, 5), None);
+
+        let mut comparison = EndpointComparison::new("http://backup:8545".to_string(), 3);
+        (comparison.ours, comparison.head) = (Some(100), Some(97));
+        assert_eq!(comparison.lag(), Some(3));
+        assert!(!comparison.diverged());
+        comparison.head = Some(104);
+        assert_eq!(comparison.lag(), Some(-4));
+        assert!(comparison.is_lagging());
+
+        comparison.head = Some(100);
+        comparison.observe_hashes(98, "0xaa", "0xbb");
+        assert!(comparison.diverged());
+        assert_eq!(comparison.mismatch.as_ref().unwrap().number, 98);
+        comparison.observe_hashes(99, "0xcc", "0xcc");
+        assert_eq!(
+            (comparison.checked, comparison.diverged()),
+            (Some(99), false)
+        );
+    }
+}
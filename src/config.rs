@@ -3,20 +3,142 @@ use std::time::Duration;
 
 /// How often metric data is refreshed
 pub const DEFAULT_REFRESH_INTERVAL: u64 = 2;
-/// How long before metric data is considered stale
-pub const STALE_AFTER: Duration = Duration::from_secs(15);
+/// Refresh intervals without a successful cycle before metric data is considered stale,
+/// unless `--stale-after` is given
+pub const STALE_AFTER_REFRESHES: u64 = 7;
+
+/// Most dashboard redraws per second while its state keeps changing (`--max-fps`).
+pub const DEFAULT_MAX_FPS: u64 = 10;
+
+/// An unchanged dashboard is still redrawn this often, so ages and countdowns keep ticking.
+pub const IDLE_REDRAW_MILLIS: u64 = 1_000;
 /// Number of blocks of history to keep in memory
 pub const DEFAULT_MAX_BLOCK_HISTORY: usize = 24;
 /// Number of tx-pool transactions to keep in memory/display
 pub const DEFAULT_TXPOOL_MAX_ROWS: usize = 12;
+/// Blocks replaced by a reorg that stay visible (struck through) in the block tape
+pub const ORPHANED_BLOCK_HISTORY: usize = 16;
+/// How long a reorg keeps the reorg alert active
+pub const REORG_ALERT_SECS: u64 = 300;
+/// How long a gas limit change keeps its alert active, from the changing block's timestamp
+pub const GAS_LIMIT_ALERT_SECS: u64 = 300;
+/// How long a large transfer keeps its alert active, from its block's timestamp
+pub const LARGE_TRANSFER_ALERT_SECS: u64 = 300;
+/// Most new blocks one cycle fetches full bodies of, so large transfers and deployments in
+/// blocks that arrived between polls aren't missed
+pub const BODY_SCAN_BLOCKS: u64 = 16;
+/// Blocks a --compare-rpc endpoint may be apart from the primary before alerting,
+/// unless `--compare-max-lag` is given
+pub const DEFAULT_COMPARE_MAX_LAG: u64 = 3;
+/// Blocks below the lower of the two heads whose hashes are compared with --compare-rpc
+pub const COMPARE_DEPTH: u64 = 2;
+/// Seconds each layer of a connection diagnosis (DNS, TCP, TLS) may take before it counts as failed
+pub const DIAGNOSE_TIMEOUT_SECS: u64 = 5;
+/// Minimum seconds between connection diagnoses while the RPC keeps failing
+pub const DIAGNOSE_INTERVAL_SECS: u64 = 30;
+/// Largest decompressed HTTP response accepted, so a hostile endpoint can't inflate a tiny
+/// gzip body into gigabytes
+pub const MAX_DECODED_BODY_BYTES: usize = 256 * 1024 * 1024;
+/// Contracts per block kept from receipts for the top gas users leaderboard
+pub const GAS_USERS_PER_BLOCK: usize = 20;
+/// Rows shown in each chain's top gas users leaderboard
+pub const GAS_USERS_LEADERBOARD_ROWS: usize = 15;
+/// Newest blocks classified for the account-abstraction activity line
+pub const AA_WINDOW_BLOCKS: usize = 32;
+/// Heaviest internal call targets kept from the newest block's trace.
+pub const TRACE_TARGETS: usize = 3;
+/// Order fills kept per chain for the Flow Radar
+pub const ORDER_FILLS_KEPT: usize = 50;
+/// Recent bundles whose simulation outcome feeds the bundle failure rate
+pub const BUNDLE_SIM_HISTORY: usize = 100;
 /// Maximum number of missing blocks to backfill per update cycle
 pub const MAX_BACKFILL_PER_CYCLE: u64 = 12;
-/// How long to wait before considering the chain halted
+/// Blocks requested concurrently per `--backfill` batch
+pub const BACKFILL_BATCH_SIZE: usize = 10;
+/// Pause between `--backfill` batches, to stay under endpoint rate limits
+pub const BACKFILL_BATCH_DELAY: Duration = Duration::from_millis(250);
+/// Upper bound on the refresh interval multiplier applied while rate limited
+pub const MAX_POLL_BACKOFF: u32 = 16;
+/// Seconds without a block before the chain is considered halted (a critical alert), when
+/// no expected block time is set
 pub const BLOCK_DELAY_DEFAULT: u64 = 60;
+/// Expected block times without a block before the chain is considered halted
+pub const BLOCK_DELAY_EXPECTED_BLOCKS: f64 = 5.0;
+/// Rollup block intervals without a block before the sequencer counts as stalled
+pub const SEQUENCER_STALL_INTERVALS: f64 = 3.0;
+/// Host blocks that must land meanwhile, so a host outage isn't blamed on the sequencer
+pub const SEQUENCER_STALL_HOST_BLOCKS: u64 = 2;
+/// Fewest consecutive block intervals block jitter is computed over.
+pub const JITTER_MIN_INTERVALS: usize = 4;
+/// Share of `--block-size-limit` a block must reach to count as near the limit
+pub const BLOCK_SIZE_NEAR_LIMIT: f64 = 0.9;
+/// Near-limit blocks in a row that raise a block size alert
+pub const BLOCK_SIZE_ALERT_BLOCKS: usize = 5;
+/// Most blocks one watchlist check scans for event matches, after the collector fell behind
+pub const WATCHLIST_LOG_BLOCKS: u64 = 64;
+/// Most full bodies one watchlist check scans for watched addresses' transactions
+pub const WATCHLIST_BODY_BLOCKS: u64 = 16;
+/// Transactions and balance or nonce changes of watched addresses kept per chain for the
+/// Watchlist tab's activity feed
+pub const WATCHLIST_ACTIVITY_ROWS: usize = 100;
+/// Contract deployments kept per chain for the Deployments tab
+pub const DEPLOYMENT_FEED_ROWS: usize = 50;
+/// Number of recent collection latency samples the latency chart and percentiles cover
+pub const LATENCY_HISTORY: usize = 120;
+/// Samples kept per metric at full resolution: an hour at a 5s refresh interval
+pub const TIMESERIES_RAW_POINTS: usize = 720;
+/// 1-minute aggregates kept per metric: a day
+pub const TIMESERIES_MINUTE_POINTS: usize = 1_440;
+/// 15-minute aggregates kept per metric: a week
+pub const TIMESERIES_QUARTER_POINTS: usize = 672;
+/// Seconds of base fee samples behind the gas panel's moving average and volatility
+pub const GAS_MA_WINDOW_SECS: u64 = 900;
+/// Threshold guide lines in the Gas tab's chart are drawn up to this multiple of the
+/// window's highest base fee; higher ones would flatten the plot
+pub const CHART_GUIDE_HEADROOM: f64 = 4.0;
+/// Rows of the Flow Radar's stacked tx-pool history chart, title and axes included
+pub const POOL_CHART_HEIGHT: u16 = 8;
+/// Share of --max-memory-mb that trimming brings usage back down to, so a store doesn't
+/// get trimmed again on the very next refresh
+pub const RETENTION_TARGET: f64 = 0.9;
+/// Entries each store keeps however far --max-memory-mb trims it
+pub const RETENTION_MIN_ENTRIES: usize = 8;
+/// Number of fired alerts kept in the alert history
+pub const ALERT_HISTORY: usize = 200;
+/// Minutes the Alerts tab's snooze prompt starts with
+pub const SNOOZE_DEFAULT_MINUTES: u64 = 30;
+/// Connection outages kept per chain for the Diagnostics tab's outage log
+pub const OUTAGE_LOG: usize = 50;
+/// Decimal places shown for custom probe values scaled by their `decimals`
+pub const PROBE_PRECISION: usize = 4;
+/// How long a watched storage slot stays highlighted after its value changes
+pub const SLOT_CHANGE_HIGHLIGHT_SECS: u64 = 300;
+/// Time allowed for a whole SMTP conversation when mailing alerts
+pub const SMTP_TIMEOUT_SECS: u64 = 30;
+/// Idle time after which a --stream-listen client is sent a keep-alive comment
+pub const STREAM_KEEPALIVE_SECS: u64 = 15;
+/// Largest --statsd datagram, under a typical 1500-byte MTU after IP and UDP headers
+pub const STATSD_MAX_PACKET: usize = 1432;
 /// HTTP timeout for RPC requests
 pub const RPC_TIMEOUT_SECS: u64 = 6;
 /// HTTP timeout for tx-pool requests
 pub const TXPOOL_TIMEOUT_SECS: u64 = 4;
+/// Timeout for beacon API requests; a slot's sidecars can run to a few megabytes of hex.
+pub const BEACON_TIMEOUT_SECS: u64 = 10;
+/// Beacon chain slot length, the same on every Ethereum network.
+pub const BEACON_SECONDS_PER_SLOT: u64 = 12;
+/// Timeout for --gas-oracle requests
+pub const GAS_ORACLE_TIMEOUT_SECS: u64 = 5;
+/// Minimum time between --gas-oracle queries, to stay inside free API rate limits
+pub const GAS_ORACLE_INTERVAL: Duration = Duration::from_secs(30);
+/// Gap between an oracle's tier and ours, in percent, flagged as a skewed fee view
+pub const GAS_ORACLE_DISAGREEMENT_PCT: f64 = 30.0;
+/// Pause before reconnecting a dropped tx-pool event stream
+pub const TXPOOL_STREAM_RETRY: Duration = Duration::from_secs(5);
+/// Remote alerts `futuresight mirror` keeps on screen, newest first
+pub const MIRROR_ALERT_ROWS: usize = 50;
+/// Recorded seconds `futuresight replay` plays per real second (`--speed`)
+pub const DEFAULT_REPLAY_SPEED: f64 = 60.0;
 
 // ========================= GAS CONFIG =========================
 /// Number of blocks to request in eth_feeHistory per poll
@@ -25,8 +147,18 @@ pub const FEE_HISTORY_BLOCKS: u64 = 24;
 pub const FEE_HISTORY_PERCENTILES: [f64; 5] = [10.0, 25.0, 50.0, 75.0, 90.0];
 /// Max fee headroom factor applied to priority fee when computing suggested maxFee
 pub const SUGGESTION_RAMP_FACTOR: f64 = 2.0;
-/// High gas price/base fee warning threshold (in Gwei)
+/// Number of blocks ahead to project the base fee assuming sustained utilization
+pub const BASE_FEE_PROJECTION_BLOCKS: usize = 3;
+/// Number of blocks ahead for the utilization-trend base fee forecast
+pub const BASE_FEE_FORECAST_BLOCKS: usize = 10;
+/// High gas price/base fee warning threshold (in Gwei) until a baseline has been observed
 pub const GAS_ALERT_HIGH_GWEI: f64 = 100.0;
-/// Spike multiplier threshold for base fee vs MA
+/// Spike multiplier threshold for base fee vs MA; also the floor for auto-scaled multipliers
 pub const GAS_SPIKE_MULTIPLIER: f64 = 2.0;
+/// How long base fees are observed before gas alert thresholds are auto-scaled
+pub const GAS_BASELINE_WINDOW: Duration = Duration::from_secs(5 * 60);
+/// Auto-scaled high base fee threshold, as a multiple of the baseline median base fee
+pub const GAS_BASELINE_HIGH_FACTOR: f64 = 5.0;
+/// Auto-scaled spike multiplier, as a multiple of the largest spike seen in the baseline
+pub const GAS_BASELINE_SPIKE_HEADROOM: f64 = 1.25;
 // Utilization moving average window is aligned with FEE_HISTORY_BLOCKS in code paths
@@ -1,18 +1,58 @@
+use crate::aa::{AaActivity, AaBlock};
+use crate::availability::{AvailabilityStats, Slo};
+use crate::bandwidth::{ByteCounter, Traffic};
+use crate::beacon::BeaconClient;
+use crate::block_size::{self, SizePressure};
+use crate::builders::{self, BuilderLabels, printable_extra_data};
+use crate::bundles::{self, BundleSimStatus, BundleSims};
+use crate::capabilities::Capabilities;
+use crate::compare::{self, EndpointComparison};
 use crate::config::{
-    FEE_HISTORY_BLOCKS, FEE_HISTORY_PERCENTILES, MAX_BACKFILL_PER_CYCLE, RPC_TIMEOUT_SECS,
-    STALE_AFTER, SUGGESTION_RAMP_FACTOR, TXPOOL_TIMEOUT_SECS,
+    BACKFILL_BATCH_DELAY, BACKFILL_BATCH_SIZE, BASE_FEE_FORECAST_BLOCKS,
+    BASE_FEE_PROJECTION_BLOCKS, BODY_SCAN_BLOCKS, DIAGNOSE_INTERVAL_SECS, FEE_HISTORY_BLOCKS,
+    FEE_HISTORY_PERCENTILES, GAS_ORACLE_INTERVAL, GAS_USERS_PER_BLOCK, JITTER_MIN_INTERVALS,
+    LATENCY_HISTORY, MAX_BACKFILL_PER_CYCLE, MAX_POLL_BACKOFF, RPC_TIMEOUT_SECS,
+    SUGGESTION_RAMP_FACTOR, TXPOOL_TIMEOUT_SECS, WATCHLIST_BODY_BLOCKS, WATCHLIST_LOG_BLOCKS,
 };
+use crate::deployments::{self, Deployment, DeploymentFeed};
+use crate::diagnose::{self, Diagnosis};
+use crate::error::{FutureSightError, Result};
+use crate::forks::Fork;
+use crate::gas_oracle::{GasOracle, OracleReading};
+use crate::history::BlockHistory;
+use crate::http::{self, HttpOptions, MeteredHttp};
+use crate::jwt::JwtSecret;
+use crate::orders::{self, FILLED_TOPIC, OrderFill, OrderFlow};
+use crate::probes::{Probe, ProbeReading, SlotReading, SlotWatch};
+use crate::proofs::{self, ProofCheck, ProofOutcome};
+use crate::record::unix_now;
+use crate::retention::Limits;
+use crate::rpc_stats::{RpcMethod, RpcStats};
+use crate::sequencer::SequencerLag;
+use crate::session::SessionStats;
+use crate::thresholds::{ChainThresholds, DelayThresholds, GasBaseline, GasThresholds};
+use crate::timeseries::{Metric, Resolution, TimeSeries};
+use crate::trace::{self, BlockTrace};
+use crate::transfers::{self, LargeTransfer};
+use crate::tx_types::{self, TxType, TxTypeMix};
+use crate::txpool_stream::{StreamKind, StreamMessage, StreamStatus, TxPoolStream};
+use crate::units;
+use crate::watchlist::{self, Activity, EventFilter, Inclusion, Watchlist, WatchlistReadings};
 use alloy::consensus::{Transaction as _, Typed2718 as _};
 use alloy::eips::eip4844::BlobTransactionSidecarItem;
-use alloy::primitives::{Address, B256, U256};
+use alloy::primitives::{Address, B256, Bytes, U256};
+use alloy::rpc::client::RpcClient;
+use alloy::rpc::types::EIP1186AccountProofResponse;
+use alloy::transports::utils::guess_local_url;
 use alloy_consensus::transaction::SignerRecoverable as _;
 use alloy_provider::{Provider as ProviderTrait, RootProvider as AlloyProvider};
-use eyre::Result;
+use futures::future::join_all;
 use serde::{Deserialize, Serialize};
 use signet_constants::SignetSystemConstants;
 use signet_tx_cache::client::TxCache;
 use std::{
     collections::{HashSet, VecDeque},
+    sync::Arc,
     time::{Duration, Instant},
 };
 use tokio::time::timeout;
@@ -50,30 +90,78 @@ pub struct FeeHistoryMetrics {
 #[derive(Debug, Clone)]
 pub struct SignetMetrics {
     pub block_number: Option<u64>,
+    pub start_block: Option<u64>, // first block number reported this session
     pub gas_price: Option<u128>,
     pub chain_id: Option<u64>,
     pub last_updated: Instant,
     pub last_successful: Option<Instant>,
+    pub collecting_since: Option<Instant>, // while a collection cycle is in flight
     pub rpc_url: String,
     pub connection_status: ConnectionStatus,
-    pub block_history: VecDeque<BlockInfo>,
+    pub block_history: BlockHistory,
     pub max_block_history: usize,
     pub latest_block_timestamp: Option<u64>, // unix seconds
-    pub block_delay_threshold: u64,          // seconds
+    pub block_delay: DelayThresholds,
+    pub expected_block_time: Option<f64>, // seconds per block, if configured
+    pub block_jitter: Option<BlockJitter>,
+    pub jitter_limit: Option<f64>, // stddev in seconds that raises an alert, if configured
+    pub block_size_limit: Option<u64>, // bytes, with --block-size-limit
+    pub slo: Option<Slo>,          // from config.toml
+    pub forks: Vec<Fork>,          // from config.toml, for every chain id
+    pub block_size_pressure: Option<SizePressure>,
+    pub stale_after: Duration, // since the last successful cycle
     pub txpool: Option<TxPoolMetrics>,
+    pub txpool_stream: Option<StreamStatus>,
     pub host_contract_filter_active: bool,
+    pub availability: AvailabilityStats,
+    pub timeseries: TimeSeries, // per-cycle samples behind the charts and moving averages
+    pub poll_backoff: u32,      // refresh interval multiplier, 1 = normal
+    pub rpc_stats: RpcStats,
+    pub reorg_count: u32,
+    pub last_reorg: Option<Reorg>,
+    pub last_gas_limit_change: Option<GasLimitChange>,
+    pub large_transfer_wei: Option<u128>, // --large-transfer-eth
+    pub last_large_transfer: Option<LargeTransfer>,
+    pub capabilities: Capabilities,
+    pub node_sync: Option<NodeSync>,
+    pub node_info: Option<NodeInfo>, // once per endpoint, after its first healthy cycle
+    pub node_txpool: Option<(u64, u64)>, // txpool_status (pending, queued)
+    pub pending_block: Option<PendingBlock>, // only while ahead of the newest block
+    pub sequencer_lag: Option<SequencerLag>, // rollups only, see Network::check_sequencer
+    pub order_flow: OrderFlow,       // Signet chains only
+    pub bundle_sims: BundleSims,     // empty unless the tx-pool reports simulations
+    pub session: SessionStats,       // every block fetched, for the summary on quit
+    pub probes: Vec<ProbeReading>,   // custom eth_call probes, in config order
+    pub slots: Vec<SlotReading>,     // watched storage slots, in flag order
+    pub proofs: Vec<ProofCheck>,     // --verify-account checks of the newest block
+    pub block_trace: Option<BlockTrace>, // --enable-trace summary of the newest block traced
+    pub tx_types: Option<TxTypeMix>, // transactions by type in the newest block counted
+    pub aa: AaActivity,              // 7702 and 4337 use in the recent blocks counted
+    pub deployments: DeploymentFeed, // contract creations in the blocks counted
+    pub beacon: Option<BeaconStatus>, // with --beacon-url, host chains only
+    pub gas_oracles: Vec<OracleReading>, // with --gas-oracle, host chains only
+    pub watchlist: WatchlistReadings, // entries of watchlist.toml, in file order
+    pub comparison: Option<EndpointComparison>, // with --compare-rpc
+    pub diagnosis: Option<Diagnosis>, // which layer fails while the RPC errors
+    pub traffic: Traffic,            // bytes moved by this chain's clients this session
+    pub cycle_traffic: Traffic,      // ... in the latest collection cycle
 
     // Gas tracking (EIP-1559)
-    pub base_fee_per_gas: Option<u128>,           // wei
-    pub next_base_fee_per_gas: Option<u128>,      // wei
+    pub base_fee_per_gas: Option<u128>,      // wei
+    pub next_base_fee_per_gas: Option<u128>, // wei
+    pub projected_base_fees: Vec<u128>,      // wei, next N blocks at current utilization
+    pub target_deviation: Option<TargetDeviation>,
+    pub base_fee_forecast: Option<BaseFeeForecast>,
+    pub builder_distribution: Vec<(String, usize)>, // blocks per builder label over history
+    pub burn: BurnStats,
     pub max_priority_fee_suggested: Option<u128>, // wei
     pub suggested_fees: Option<SuggestedFees>,
     pub fee_history: Option<FeeHistoryMetrics>,
     pub gas_utilization_ma_n: Option<f64>, // percent 0..=100
     pub gas_volatility_5m: Option<f64>,    // relative pct vs MA
+    pub gas_thresholds: GasThresholds,
 
     // EIP-4844 (optional)
-    #[allow(dead_code)]
     pub blob_base_fee: Option<u128>,
     #[allow(dead_code)]
     pub blob_base_fee_next: Option<u128>,
@@ -81,113 +169,589 @@ pub struct SignetMetrics {
     pub blob_gas_utilization_ma_n: Option<f64>,
 }
 
+/// Blocks replaced in the history by a different chain, as seen in one collection cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reorg {
+    pub depth: usize, // blocks orphaned
+    pub head: u64,    // newest canonical block afterwards
+    pub at: u64,      // unix seconds
+}
+
+/// How unevenly blocks arrived over the history window, from the intervals between
+/// consecutive blocks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockJitter {
+    pub mean: f64,          // seconds
+    pub stddev: f64,        // seconds
+    pub max_deviation: f64, // seconds, the interval furthest from the mean
+    pub intervals: usize,
+}
+
+/// Where blob sidecars come from, and how fetching them went.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeaconStatus {
+    pub url: String,
+    pub blocks: usize,         // blocks whose sidecars were fetched this session
+    pub error: Option<String>, // from the latest attempt
+}
+
+/// A block whose gas limit differs from its parent's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasLimitChange {
+    pub block: u64,
+    pub from: u64,
+    pub to: u64,
+    pub at: u64, // block timestamp
+}
+
+/// ETH destroyed by the EIP-1559 base fee.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BurnStats {
+    pub window_wei: u128,               // over the retained block history
+    pub rate_wei_per_hour: Option<f64>, // window burn over the window's time span
+    pub session_wei: u128,              // every block fetched this session, including backfill
+}
+
+/// Where recent blocks sit relative to the EIP-1559 gas target (half the gas limit).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TargetDeviation {
+    pub avg_pct: f64,  // mean (gas_used - target) / target over the streak, percent
+    pub blocks: usize, // consecutive newest blocks on the same side of the target
+    pub direction: FeeDirection,
+}
+
+/// Base fee extrapolated from the fee-history utilization trend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BaseFeeForecast {
+    pub blocks: usize,
+    pub expected: u128, // wei
+    pub low: u128,      // wei, trend minus one residual standard deviation
+    pub high: u128,     // wei, trend plus one residual standard deviation
+    pub confidence: ForecastConfidence,
+}
+
+/// How well a straight line explains recent utilization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForecastConfidence {
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeDirection {
+    Rising,
+    Falling,
+    Flat,
+}
+
+/// What the collector reads from a block fetched with full transaction bodies.
+#[derive(Debug, Clone)]
+pub struct FullBlock {
+    pub mix: TxTypeMix,
+    pub calldata: u64, // bytes
+    pub aa: AaBlock,
+    pub large_transfer: Option<LargeTransfer>, // with --large-transfer-eth
+    pub deployments: Vec<Deployment>,
+}
+
+/// The node's own `eth_syncing` report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeSync {
+    Synced,
+    Syncing { current: u64, highest: u64 },
+}
+
+/// What answers the endpoint, so look-alike devnets can be told apart. Either side is
+/// `None` when its call failed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeInfo {
+    pub client: Option<String>, // web3_clientVersion
+    pub genesis_hash: Option<B256>,
+}
+
+impl NodeInfo {
+    /// The client's name and version without the platform and toolchain suffixes, e.g.
+    /// `Geth/v1.14.11-stable-f3c696fa` from `Geth/v1.14.11-stable-f3c696fa/linux-amd64/go1.23.2`.
+    pub fn client_name(&self) -> Option<String> {
+        let client = self.client.as_deref()?;
+        Some(client.split('/').take(2).collect::<Vec<_>>().join("/"))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConnectionStatus {
     Connected,
     Stale,
     Disconnected,
+    RateLimited(String),
     Error(String),
 }
 
+impl ConnectionStatus {
+    /// `RateLimited` for throttling, so polling backs off, else `Error`.
+    fn from_error(context: &str, error: &FutureSightError) -> Self {
+        let message = format!("{}: {}", context, error);
+        if error.is_rate_limited() {
+            ConnectionStatus::RateLimited(message)
+        } else {
+            ConnectionStatus::Error(message)
+        }
+    }
+}
+
+/// Blob gas consumed by each EIP-4844 blob.
+const GAS_PER_BLOB: u64 = 131_072;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockInfo {
     pub number: u64,
     pub hash: String,
     pub parent_hash: String,
+    #[serde(default)]
+    pub state_root: B256,
     pub timestamp: u64,
     pub tx_count: usize,
     pub gas_used: u64,
     pub gas_limit: u64,
     pub blobs: Vec<BlobTransactionSidecarItem>,
+    pub fee_recipient: Address,
+    pub extra_data: String,            // printable runs only
+    pub builder: String,               // attributed label, see builders.rs
+    pub tip_revenue_wei: Option<u128>, // estimated proposer tips, see estimate_tip_revenue
 
     // header-derived gas fields
     pub base_fee_per_gas: Option<u128>, // 1559
     pub blob_gas_used: Option<u64>,     // 4844 (if available)
     pub excess_blob_gas: Option<u64>,   // 4844 (if available)
+    #[serde(default)]
+    pub withdrawals_count: Option<usize>, // 4895, Shanghai onwards
+    #[serde(default)]
+    pub size: Option<u64>, // RLP-encoded bytes, as the node reports it
+    #[serde(default)]
+    pub calldata_bytes: Option<u64>, // newest blocks only, from the full body
+    #[serde(default)]
+    pub receipts: Option<ReceiptSummary>, // newest block only, with --receipts
+}
+
+/// The block the node is currently building, from `eth_getBlockByNumber("pending")`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingBlock {
+    pub number: u64,
+    pub tx_count: usize,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub base_fee_per_gas: Option<u128>,
+}
+
+/// Outcome counts from a block's transaction receipts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptSummary {
+    pub succeeded: usize,
+    pub reverted: usize,
+    pub contract_creations: usize,
+    pub avg_effective_gas_price: Option<u128>, // wei
+    #[serde(default)]
+    pub gas_users: Vec<GasUser>, // heaviest first, at most GAS_USERS_PER_BLOCK
+    #[serde(default)]
+    pub gas_by_type: Vec<(TxType, u64)>, // gas used per transaction type, in TxType order
+}
+
+/// Gas consumed by transactions sent to one address (or deploying it).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GasUser {
+    pub address: Address,
+    pub gas_used: u64,
+    pub txs: usize,
+}
+
+impl ReceiptSummary {
+    pub fn total(&self) -> usize {
+        self.succeeded + self.reverted
+    }
+
+    pub fn revert_pct(&self) -> Option<f64> {
+        (self.total() > 0).then(|| self.reverted as f64 * 100.0 / self.total() as f64)
+    }
+}
+
+impl BlockInfo {
+    /// Blobs carried by this block, from its blob gas; `None` before Cancun.
+    pub fn blob_count(&self) -> Option<u64> {
+        self.blob_gas_used.map(|gas| gas / GAS_PER_BLOB)
+    }
+
+    /// Wei burned by this block's base fee; `None` for pre-London blocks.
+    pub fn burned_wei(&self) -> Option<u128> {
+        self.base_fee_per_gas
+            .map(|fee| fee.saturating_mul(self.gas_used as u128))
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Config {
     pub rpc_url: String,
-    pub block_delay_threshold: u64,
+    pub block_delay: DelayThresholds,
+    pub expected_block_time: Option<f64>, // seconds
+    pub stale_after: Duration,
+    pub gas_alert_gwei: Option<f64>, // None = scale to the observed baseline
+    pub gas_spike_multiplier: Option<f64>, // None = scale to the observed baseline
     pub max_block_history: usize,
     pub txpool_max_rows: usize,
     pub txpool_fetch_list: bool,
     pub txpool_watch_signet_host_contracts: bool,
+    pub txpool_stream: Option<String>, // SSE path under the tx-pool URL, or a full URL
+    pub fetch_receipts: bool,
+    pub http: HttpOptions, // proxy and TLS options for the RPC and tx-pool clients
 }
 
 impl SignetMetrics {
     pub fn new(config: Config) -> Self {
         Self {
             block_number: None,
+            start_block: None,
             gas_price: None,
             chain_id: None,
             last_updated: Instant::now(),
             last_successful: None,
+            collecting_since: None,
             rpc_url: config.rpc_url,
             connection_status: ConnectionStatus::Disconnected,
-            block_history: VecDeque::with_capacity(config.max_block_history),
+            block_history: BlockHistory::new(config.max_block_history),
             max_block_history: config.max_block_history,
             latest_block_timestamp: None,
-            block_delay_threshold: config.block_delay_threshold,
+            block_delay: config.block_delay,
+            expected_block_time: config.expected_block_time,
+            block_jitter: None,
+            jitter_limit: None,
+            block_size_limit: None,
+            slo: None,
+            forks: Vec::new(),
+            block_size_pressure: None,
+            stale_after: config.stale_after,
             txpool: None,
+            txpool_stream: None,
             host_contract_filter_active: config.txpool_watch_signet_host_contracts,
+            availability: AvailabilityStats::new(Instant::now()),
+            timeseries: TimeSeries::default(),
+            poll_backoff: 1,
+            rpc_stats: RpcStats::default(),
+            reorg_count: 0,
+            last_reorg: None,
+            last_gas_limit_change: None,
+            large_transfer_wei: None,
+            last_large_transfer: None,
+            capabilities: Capabilities::default(),
+            node_sync: None,
+            node_info: None,
+            node_txpool: None,
+            pending_block: None,
+            sequencer_lag: None,
+            order_flow: OrderFlow::default(),
+            bundle_sims: BundleSims::default(),
+            session: SessionStats::default(),
+            probes: Vec::new(),
+            slots: Vec::new(),
+            proofs: Vec::new(),
+            block_trace: None,
+            tx_types: None,
+            aa: AaActivity::default(),
+            deployments: DeploymentFeed::default(),
+            beacon: None,
+            gas_oracles: Vec::new(),
+            watchlist: WatchlistReadings::default(),
+            comparison: None,
+            diagnosis: None,
+            traffic: Traffic::default(),
+            cycle_traffic: Traffic::default(),
 
             // init gas fields
             base_fee_per_gas: None,
             next_base_fee_per_gas: None,
+            projected_base_fees: Vec::new(),
+            target_deviation: None,
+            base_fee_forecast: None,
+            builder_distribution: Vec::new(),
+            burn: BurnStats::default(),
             max_priority_fee_suggested: None,
             suggested_fees: None,
             fee_history: None,
             gas_utilization_ma_n: None,
             gas_volatility_5m: None,
+            gas_thresholds: GasBaseline::new(config.gas_alert_gwei, config.gas_spike_multiplier)
+                .thresholds(),
             blob_base_fee: None,
             blob_base_fee_next: None,
             blob_gas_utilization_ma_n: None,
         }
     }
 
+    /// Collection latency at percentile `pct` (0..=100) over the retained samples.
+    pub fn latency_percentile(&self, pct: f64) -> Option<Duration> {
+        let mut sorted: Vec<Duration> = self.recent_latency(LATENCY_HISTORY).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_unstable();
+        let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted.get(rank.clamp(1, sorted.len()) - 1).copied()
+    }
+
+    /// The base fee's moving average over `window` seconds, in wei, and its volatility.
+    pub fn base_fee_ma(&self, window: u64) -> Option<(f64, Option<f64>)> {
+        let series = self.timeseries.get(Metric::BaseFee)?;
+        let now = unix_now();
+        Some((
+            series.mean_over(window, now)?,
+            series.volatility(window, now),
+        ))
+    }
+
+    /// Current base fee over its fee-history moving average, e.g. 2.0 for double.
+    pub fn fee_spike_multiplier(&self) -> Option<f64> {
+        self.gas_volatility_5m.map(|value| 1.0 + value.max(-1.0))
+    }
+
+    /// Count a call's outcome and learn whether the endpoint implements the method.
+    fn record_rpc<T>(&mut self, method: RpcMethod, result: &Result<T>) {
+        self.rpc_stats.record(method, result);
+        self.capabilities.observe(method, result);
+    }
+
+    /// [`Self::record_rpc`] for a call skipped (`None`) when the method is unsupported.
+    fn record_optional_rpc<T>(&mut self, method: RpcMethod, result: &Option<Result<T>>) {
+        if let Some(result) = result {
+            self.record_rpc(method, result);
+        }
+    }
+
+    fn record_latency(&mut self, elapsed: Duration) {
+        self.timeseries
+            .record(Metric::Latency, unix_now(), elapsed.as_micros() as f64);
+    }
+
+    /// The newest `count` collection latencies, oldest first.
+    pub fn recent_latency(&self, count: usize) -> impl Iterator<Item = Duration> + '_ {
+        let samples = self
+            .timeseries
+            .get(Metric::Latency)
+            .map(|series| series.points(Resolution::Raw));
+        let skip = samples.map_or(0, |points| points.len().saturating_sub(count));
+        samples
+            .into_iter()
+            .flatten()
+            .skip(skip)
+            .map(|point| Duration::from_micros(point.last as u64))
+    }
+
+    pub fn last_latency(&self) -> Option<Duration> {
+        self.recent_latency(1).next()
+    }
+
+    /// Mean estimated tip revenue over history blocks that have an estimate.
+    pub fn avg_tip_revenue(&self) -> Option<(u128, usize)> {
+        let estimates: Vec<u128> = self
+            .block_history
+            .iter()
+            .filter_map(|block| block.tip_revenue_wei)
+            .collect();
+        (!estimates.is_empty()).then(|| {
+            let total = estimates
+                .iter()
+                .fold(0u128, |acc, v| acc.saturating_add(*v));
+            (total / estimates.len() as u128, estimates.len())
+        })
+    }
+
+    /// Mean of a per-block count over history blocks that report it, with how many did.
+    pub fn avg_per_block(&self, count: impl Fn(&BlockInfo) -> Option<u64>) -> Option<(f64, usize)> {
+        let counts: Vec<u64> = self.block_history.iter().filter_map(count).collect();
+        (!counts.is_empty()).then(|| {
+            (
+                counts.iter().sum::<u64>() as f64 / counts.len() as f64,
+                counts.len(),
+            )
+        })
+    }
+
+    /// Receipt counts summed over history blocks that have them (the gas price and gas
+    /// users are left unset), and how many blocks contributed.
+    pub fn receipt_totals(&self) -> Option<(ReceiptSummary, usize)> {
+        let summaries: Vec<&ReceiptSummary> = self
+            .block_history
+            .iter()
+            .filter_map(|block| block.receipts.as_ref())
+            .collect();
+        let totals = summaries
+            .iter()
+            .fold(ReceiptSummary::default(), |acc, s| ReceiptSummary {
+                succeeded: acc.succeeded + s.succeeded,
+                reverted: acc.reverted + s.reverted,
+                contract_creations: acc.contract_creations + s.contract_creations,
+                avg_effective_gas_price: None,
+                gas_users: Vec::new(),
+                gas_by_type: Vec::new(),
+            });
+        (!summaries.is_empty()).then_some((totals, summaries.len()))
+    }
+
+    /// Addresses ranked by gas used across history blocks with receipts, each with its
+    /// share of those blocks' total gas.
+    pub fn top_gas_users(&self, limit: usize) -> Vec<(GasUser, f64)> {
+        let mut total_gas: u64 = 0;
+        let mut users: Vec<GasUser> = Vec::new();
+        for block in self.block_history.iter() {
+            let Some(receipts) = &block.receipts else {
+                continue;
+            };
+            total_gas += block.gas_used;
+            for user in &receipts.gas_users {
+                match users.iter_mut().find(|u| u.address == user.address) {
+                    Some(existing) => {
+                        existing.gas_used += user.gas_used;
+                        existing.txs += user.txs;
+                    }
+                    None => users.push(user.clone()),
+                }
+            }
+        }
+        users.sort_by_key(|user| std::cmp::Reverse(user.gas_used));
+        users
+            .into_iter()
+            .take(limit)
+            .map(|user| {
+                let share = if total_gas > 0 {
+                    user.gas_used as f64 * 100.0 / total_gas as f64
+                } else {
+                    0.0
+                };
+                (user, share)
+            })
+            .collect()
+    }
+
     pub fn chain_height(&self) -> Option<u64> {
         self.block_number
             .into_iter()
             .chain(self.block_history.front().map(|block| block.number))
             .max()
     }
+
+    /// Drop the oldest entries past `limits`, for `--max-memory-mb`. The block history
+    /// stays at its new size, so backfill doesn't fetch the trimmed blocks again.
+    pub fn retain(&mut self, limits: &Limits) {
+        self.max_block_history = self.max_block_history.min(limits.blocks.max(1));
+        self.block_history.set_capacity(self.max_block_history);
+        self.timeseries.truncate(limits.series);
+        self.order_flow.fills.truncate(limits.fills);
+        self.bundle_sims.truncate(limits.bundles);
+        self.availability.log.truncate(limits.outages);
+    }
+
+    /// The thresholds these metrics are judged by; gas thresholds follow from the collector's
+    /// baseline instead.
+    pub fn set_thresholds(&mut self, thresholds: &ChainThresholds) {
+        self.block_delay = thresholds.block_delay;
+        self.expected_block_time = thresholds.expected_block_time;
+        self.stale_after = thresholds.stale_after;
+        self.large_transfer_wei = thresholds.large_transfer_wei;
+    }
+
+    /// Mark the chain stale once its last successful cycle is older than `stale_after`.
+    pub fn check_staleness(&mut self) {
+        if matches!(
+            self.connection_status,
+            ConnectionStatus::Connected | ConnectionStatus::Stale
+        ) && let Some(last_ok) = self.last_successful
+            && last_ok.elapsed() > self.stale_after
+        {
+            if self.connection_status == ConnectionStatus::Connected {
+                self.availability.observe(Some("stale"), Instant::now());
+            }
+            self.connection_status = ConnectionStatus::Stale;
+        }
+    }
+
+    /// How far the chain has advanced since the first block number this session.
+    pub fn blocks_since_start(&self) -> Option<u64> {
+        Some(self.chain_height()?.saturating_sub(self.start_block?))
+    }
+
+    /// The configured forks of this chain, once its chain id is known.
+    pub fn chain_forks(&self) -> impl Iterator<Item = &Fork> {
+        self.forks
+            .iter()
+            .filter(move |fork| Some(fork.chain_id) == self.chain_id)
+    }
+
+    /// Seconds per block: the observed mean, else the configured expectation.
+    pub fn block_time(&self) -> Option<f64> {
+        self.block_jitter
+            .map(|jitter| jitter.mean)
+            .filter(|mean| *mean > 0.0)
+            .or(self.expected_block_time)
+    }
+
+    /// `secs` as a multiple of the expected block time, if one is configured.
+    pub fn blocks_expected(&self, secs: f64) -> Option<f64> {
+        self.expected_block_time.map(|expected| secs / expected)
+    }
 }
 
 pub struct SignetRpcClient {
     provider: AlloyProvider,
     rpc_url: String,
     http: reqwest::Client,
+    jwt: Option<JwtSecret>,
+    traffic: Arc<ByteCounter>, // shared by the provider's transport and `request`
 }
 
 impl SignetRpcClient {
-    pub fn new(rpc_url: String) -> Result<Self> {
-        let url = Url::parse(&rpc_url)?;
-        let provider = AlloyProvider::new_http(url);
-        let http = reqwest::Client::builder()
+    pub fn new(rpc_url: String, options: &HttpOptions) -> Result<Self> {
+        let url = Url::parse(&rpc_url).map_err(|e| {
+            FutureSightError::Config(format!("invalid RPC url '{}': {}", rpc_url, e))
+        })?;
+        let traffic = Arc::new(ByteCounter::default());
+        let is_local = guess_local_url(url.as_str());
+        let transport = MeteredHttp::new(
+            options.alloy_client()?,
+            url,
+            traffic.clone(),
+            options.jwt.clone(),
+        );
+        let provider = AlloyProvider::new(RpcClient::new(transport, is_local));
+        let http = options
+            .client_builder()?
             .timeout(Duration::from_secs(RPC_TIMEOUT_SECS))
             .user_agent(format!(
                 "{}/{}",
                 env!("CARGO_PKG_NAME"),
                 env!("CARGO_PKG_VERSION")
             ))
-            .build()?;
+            .build()
+            .map_err(|e| FutureSightError::Config(e.to_string()))?;
 
         Ok(Self {
             provider,
             rpc_url,
             http,
+            jwt: options.jwt.clone(),
+            traffic,
         })
     }
 
+    pub fn traffic(&self) -> Traffic {
+        self.traffic.totals()
+    }
+
     pub async fn get_block_number(&self) -> Result<u64> {
         let block_number = timeout(
             Duration::from_secs(RPC_TIMEOUT_SECS),
             self.provider.get_block_number(),
         )
         .await
-        .map_err(|_| eyre::eyre!("eth_blockNumber timed out after {}s", RPC_TIMEOUT_SECS))??;
+        .map_err(|_| FutureSightError::rpc_timeout("eth_blockNumber"))?
+        .map_err(FutureSightError::rpc)?;
         Ok(block_number)
     }
 
@@ -197,7 +761,8 @@ impl SignetRpcClient {
             self.provider.get_gas_price(),
         )
         .await
-        .map_err(|_| eyre::eyre!("eth_gasPrice timed out after {}s", RPC_TIMEOUT_SECS))??;
+        .map_err(|_| FutureSightError::rpc_timeout("eth_gasPrice"))?
+        .map_err(FutureSightError::rpc)?;
         Ok(gas)
     }
 
@@ -207,7 +772,8 @@ impl SignetRpcClient {
             self.provider.get_chain_id(),
         )
         .await
-        .map_err(|_| eyre::eyre!("eth_chainId timed out after {}s", RPC_TIMEOUT_SECS))??;
+        .map_err(|_| FutureSightError::rpc_timeout("eth_chainId"))?
+        .map_err(FutureSightError::rpc)?;
         Ok(id)
     }
 
@@ -217,12 +783,8 @@ impl SignetRpcClient {
             self.provider.get_max_priority_fee_per_gas(),
         )
         .await
-        .map_err(|_| {
-            eyre::eyre!(
-                "eth_maxPriorityFeePerGas timed out after {}s",
-                RPC_TIMEOUT_SECS
-            )
-        })??;
+        .map_err(|_| FutureSightError::rpc_timeout("eth_maxPriorityFeePerGas"))?
+        .map_err(FutureSightError::rpc)?;
         Ok(priority_fee)
     }
 
@@ -234,157 +796,1399 @@ impl SignetRpcClient {
                 .get_block_by_number(alloy::eips::BlockNumberOrTag::Number(number)),
         )
         .await
-        .map_err(|_| {
-            eyre::eyre!(
-                "eth_getBlockByNumber({}) timed out after {}s",
-                number,
-                RPC_TIMEOUT_SECS
-            )
-        })??
-        .ok_or_else(|| eyre::eyre!("block not found"))?;
+        .map_err(|_| FutureSightError::rpc_timeout(&format!("eth_getBlockByNumber({})", number)))?
+        .map_err(FutureSightError::rpc)?
+        .ok_or_else(|| FutureSightError::rpc("block not found"))?;
 
         // Best-effort header-derived gas fields (may be None on pre-1559/4844)
         let base_fee_per_gas = block.header.base_fee_per_gas.map(|v| v as u128);
-        let blob_gas_used = None;
-        let excess_blob_gas = None;
+        let blob_gas_used = block.header.blob_gas_used;
+        let excess_blob_gas = block.header.excess_blob_gas;
+        let withdrawals_count = block.withdrawals.as_ref().map(|w| w.len());
 
         Ok(BlockInfo {
             number: block.number(),
             hash: block.hash().to_string(),
             parent_hash: block.header.parent_hash.to_string(),
+            state_root: block.header.state_root,
             timestamp: block.header.timestamp,
             tx_count: block.transactions.len(),
             gas_used: block.header.gas_used,
             gas_limit: block.header.gas_limit,
             blobs: vec![],
+            fee_recipient: block.header.beneficiary,
+            extra_data: printable_extra_data(&block.header.extra_data),
+            builder: String::new(),
+            tip_revenue_wei: None,
+
+            base_fee_per_gas,
+            blob_gas_used,
+            excess_blob_gas,
+            withdrawals_count,
+            size: block.header.size.map(|size| size.saturating_to()),
+            calldata_bytes: None,
+            receipts: None,
+        })
+    }
+
+    /// Raw JSON-RPC call for methods the alloy provider doesn't cover; returns `result`.
+    async fn request(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params
+        });
+        let body = serde_json::to_vec(&body).map_err(FutureSightError::rpc)?;
+        let sent = body.len();
+        let mut request = self
+            .http
+            .post(&self.rpc_url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        if let Some(jwt) = &self.jwt {
+            request = request.header(reqwest::header::AUTHORIZATION, jwt.bearer());
+        }
+        let resp = http::fetch(request, sent, &self.traffic)
+            .await
+            .map_err(FutureSightError::rpc)?;
+        if !resp.status.is_success() {
+            return Err(FutureSightError::rpc(format!(
+                "{} HTTP {}",
+                method, resp.status
+            )));
+        }
+        let mut v: serde_json::Value =
+            serde_json::from_slice(&resp.body).map_err(FutureSightError::rpc)?;
+        if let Some(err) = v.get("error") {
+            return Err(FutureSightError::rpc(format!("{} error: {}", method, err)));
+        }
+        Ok(v["result"].take())
+    }
+
+    pub async fn get_fee_history(
+        &self,
+        block_count: u64,
+        newest: &str,               // "latest" or hex
+        reward_percentiles: &[f64], // 0..=100
+    ) -> Result<EthFeeHistoryResult> {
+        let params = serde_json::json!([to_hex_qty(block_count), newest, reward_percentiles]);
+        let result = self.request("eth_feeHistory", params).await?;
+        serde_json::from_value(result).map_err(FutureSightError::rpc)
+    }
+
+    pub async fn get_blob_base_fee(&self) -> Result<u128> {
+        let result = self
+            .request("eth_blobBaseFee", serde_json::json!([]))
+            .await?;
+        result.as_str().and_then(hex_to_u128).ok_or_else(|| {
+            FutureSightError::rpc(format!("eth_blobBaseFee: unexpected result {}", result))
+        })
+    }
+
+    pub async fn get_syncing(&self) -> Result<NodeSync> {
+        let result = self.request("eth_syncing", serde_json::json!([])).await?;
+        let block = |key: &str| {
+            result
+                .get(key)
+                .and_then(|v| v.as_str())
+                .and_then(hex_to_u64)
+        };
+        match (&result, block("currentBlock"), block("highestBlock")) {
+            (serde_json::Value::Bool(false), _, _) => Ok(NodeSync::Synced),
+            (_, Some(current), Some(highest)) => Ok(NodeSync::Syncing { current, highest }),
+            _ => Err(FutureSightError::rpc(format!(
+                "eth_syncing: unexpected result {}",
+                result
+            ))),
+        }
+    }
+
+    pub async fn get_client_version(&self) -> Result<String> {
+        let result = self
+            .request("web3_clientVersion", serde_json::json!([]))
+            .await?;
+        result.as_str().map(str::to_string).ok_or_else(|| {
+            FutureSightError::rpc(format!("web3_clientVersion: unexpected result {}", result))
+        })
+    }
+
+    /// The hash of block 0.
+    pub async fn get_genesis_hash(&self) -> Result<B256> {
+        let result = self
+            .request(
+                "eth_getBlockByNumber",
+                serde_json::json!([to_hex_qty(0), false]),
+            )
+            .await?;
+        result
+            .get("hash")
+            .and_then(|hash| hash.as_str())
+            .and_then(|hash| hash.parse().ok())
+            .ok_or_else(|| FutureSightError::rpc("eth_getBlockByNumber(0): unexpected result"))
+    }
+
+    /// Summary of every receipt in block `number`.
+    pub async fn get_block_receipts(&self, number: u64) -> Result<ReceiptSummary> {
+        let result = self
+            .request(
+                "eth_getBlockReceipts",
+                serde_json::json!([to_hex_qty(number)]),
+            )
+            .await?;
+        result
+            .as_array()
+            .map(|receipts| summarize_receipts(receipts))
+            .ok_or_else(|| {
+                FutureSightError::rpc(format!(
+                    "eth_getBlockReceipts: unexpected result {}",
+                    result
+                ))
+            })
+    }
+
+    /// Call frames of every transaction in block `number`, from geth's `callTracer`.
+    pub async fn trace_block(&self, number: u64) -> Result<BlockTrace> {
+        let result = self
+            .request(
+                "debug_traceBlockByNumber",
+                serde_json::json!([to_hex_qty(number), { "tracer": "callTracer" }]),
+            )
+            .await?;
+        trace::summarize(number, &result).ok_or_else(|| {
+            FutureSightError::rpc(format!(
+                "debug_traceBlockByNumber: unexpected result {}",
+                result
+            ))
+        })
+    }
+
+    /// Return data of a read-only call to `to` against the latest block.
+    pub async fn call(&self, to: Address, data: &Bytes) -> Result<Bytes> {
+        let result = self
+            .request(
+                "eth_call",
+                serde_json::json!([{ "to": to, "data": data }, "latest"]),
+            )
+            .await?;
+        serde_json::from_value(result)
+            .map_err(|e| FutureSightError::rpc(format!("eth_call: unexpected result: {}", e)))
+    }
+
+    /// The word at storage `slot` of `address` in the latest block.
+    pub async fn get_storage_at(&self, address: Address, slot: B256) -> Result<B256> {
+        let result = self
+            .request(
+                "eth_getStorageAt",
+                serde_json::json!([address, slot, "latest"]),
+            )
+            .await?;
+        // Nodes may drop leading zeros, so read it as a quantity
+        serde_json::from_value::<U256>(result)
+            .map(B256::from)
+            .map_err(|e| {
+                FutureSightError::rpc(format!("eth_getStorageAt: unexpected result: {}", e))
+            })
+    }
+
+    /// Hash of block `number`, without its transactions.
+    pub async fn get_block_hash(&self, number: u64) -> Result<String> {
+        let result = self
+            .request(
+                "eth_getBlockByNumber",
+                serde_json::json!([to_hex_qty(number), false]),
+            )
+            .await?;
+        result
+            .get("hash")
+            .and_then(|hash| hash.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                FutureSightError::rpc(format!("eth_getBlockByNumber: no block {}", number))
+            })
+    }
+
+    /// Merkle proof of `address` and its storage `slots` in the block with `block_hash`.
+    pub async fn get_proof(
+        &self,
+        address: Address,
+        slots: &[B256],
+        block_hash: &str,
+    ) -> Result<EIP1186AccountProofResponse> {
+        let result = self
+            .request(
+                "eth_getProof",
+                serde_json::json!([address, slots, { "blockHash": block_hash }]),
+            )
+            .await?;
+        serde_json::from_value(result).map_err(FutureSightError::rpc)
+    }
+
+    /// Balance and nonce of `address` at block `number`.
+    pub async fn get_account(&self, address: Address, number: u64) -> (Result<U256>, Result<u64>) {
+        let block = to_hex_qty(number);
+        let (balance, nonce) = tokio::join!(
+            self.request("eth_getBalance", serde_json::json!([address, block])),
+            self.request(
+                "eth_getTransactionCount",
+                serde_json::json!([address, block])
+            ),
+        );
+        (
+            balance.and_then(|balance| {
+                serde_json::from_value(balance).map_err(|e| {
+                    FutureSightError::rpc(format!("eth_getBalance: unexpected result: {}", e))
+                })
+            }),
+            nonce.and_then(|nonce| {
+                nonce.as_str().and_then(hex_to_u64).ok_or_else(|| {
+                    FutureSightError::rpc(format!(
+                        "eth_getTransactionCount: unexpected result {}",
+                        nonce
+                    ))
+                })
+            }),
+        )
+    }
+
+    /// Where transaction `hash` was included, or `None` while the node has no receipt.
+    pub async fn get_inclusion(&self, hash: B256) -> Result<Option<Inclusion>> {
+        let result = self
+            .request("eth_getTransactionReceipt", serde_json::json!([hash]))
+            .await?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        watchlist::parse_receipt(&result).map(Some).ok_or_else(|| {
+            FutureSightError::rpc(format!(
+                "eth_getTransactionReceipt: unexpected result {}",
+                result
+            ))
+        })
+    }
+
+    /// Logs matching `filter` in blocks `from..=to`: how many, and the newest block with one.
+    pub async fn count_logs(
+        &self,
+        filter: &EventFilter,
+        from: u64,
+        to: u64,
+    ) -> Result<(u64, Option<u64>)> {
+        let mut params = serde_json::json!({
+            "fromBlock": to_hex_qty(from),
+            "toBlock": to_hex_qty(to),
+            "topics": filter.topics(),
+        });
+        if let Some(address) = filter.address {
+            params["address"] = serde_json::json!(address);
+        }
+        let result = self
+            .request("eth_getLogs", serde_json::json!([params]))
+            .await?;
+        let logs = result.as_array().ok_or_else(|| {
+            FutureSightError::rpc(format!("eth_getLogs: unexpected result {}", result))
+        })?;
+        let newest = logs
+            .iter()
+            .filter_map(|log| log.get("blockNumber")?.as_str().and_then(hex_to_u64))
+            .max();
+        Ok((logs.len() as u64, newest))
+    }
+
+    /// `Filled` events emitted by `orders` in blocks `from..=to`.
+    pub async fn get_order_fills(
+        &self,
+        orders: Address,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<OrderFill>> {
+        let result = self
+            .request(
+                "eth_getLogs",
+                serde_json::json!([{
+                    "address": orders,
+                    "fromBlock": to_hex_qty(from),
+                    "toBlock": to_hex_qty(to),
+                    "topics": [FILLED_TOPIC],
+                }]),
+            )
+            .await?;
+        result
+            .as_array()
+            .map(|logs| orders::parse_fills(logs))
+            .ok_or_else(|| {
+                FutureSightError::rpc(format!("eth_getLogs: unexpected result {}", result))
+            })
+    }
+
+    /// The pending block, or `None` when the node returns none.
+    pub async fn get_pending_block(&self) -> Result<Option<PendingBlock>> {
+        let result = self
+            .request(
+                "eth_getBlockByNumber",
+                serde_json::json!(["pending", false]),
+            )
+            .await?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        parse_pending_block(&result).map(Some).ok_or_else(|| {
+            FutureSightError::rpc("eth_getBlockByNumber(pending): unexpected result")
+        })
+    }
+
+    /// Block `number` with full transaction objects, as returned.
+    pub async fn get_full_block_raw(&self, number: u64) -> Result<serde_json::Value> {
+        self.request(
+            "eth_getBlockByNumber",
+            serde_json::json!([to_hex_qty(number), true]),
+        )
+        .await
+    }
+
+    /// Transactions per type, calldata bytes, and account-abstraction activity in block
+    /// `number`, fetched with full transaction bodies, and its largest transfer of at least
+    /// `min_transfer` wei when given.
+    pub async fn get_full_block(
+        &self,
+        number: u64,
+        min_transfer: Option<U256>,
+    ) -> Result<FullBlock> {
+        let result = self.get_full_block_raw(number).await?;
+        let (mix, calldata, aa) = TxTypeMix::of_block(&result)
+            .zip(block_size::calldata_bytes(&result))
+            .zip(AaBlock::of_block(&result))
+            .map(|((mix, calldata), aa)| (mix, calldata, aa))
+            .ok_or_else(|| {
+                FutureSightError::rpc("eth_getBlockByNumber(full): unexpected result")
+            })?;
+        Ok(FullBlock {
+            mix,
+            calldata,
+            aa,
+            large_transfer: min_transfer.and_then(|min| transfers::largest_transfer(&result, min)),
+            deployments: deployments::deployments(&result),
+        })
+    }
+
+    /// Pending and queued transaction counts from the node's own pool.
+    pub async fn get_txpool_status(&self) -> Result<(u64, u64)> {
+        let result = self.request("txpool_status", serde_json::json!([])).await?;
+        let count = |key: &str| {
+            result
+                .get(key)
+                .and_then(|v| v.as_str())
+                .and_then(hex_to_u64)
+        };
+        match (count("pending"), count("queued")) {
+            (Some(pending), Some(queued)) => Ok((pending, queued)),
+            _ => Err(FutureSightError::rpc(format!(
+                "txpool_status: unexpected result {}",
+                result
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthFeeHistoryResult {
+    pub oldest_block: String,
+    #[serde(default)]
+    pub base_fee_per_gas: Vec<String>, // hex; length blockCount+1
+    #[serde(default)]
+    pub gas_used_ratio: Vec<f64>, // 0..=1 length blockCount
+    #[serde(default)]
+    pub reward: Vec<Vec<String>>, // [blockCount][percentiles]
+}
+
+/// Read the fields the pending block panel shows; `None` if any required one is missing.
+fn parse_pending_block(block: &serde_json::Value) -> Option<PendingBlock> {
+    let quantity = |key: &str| block.get(key).and_then(|v| v.as_str()).and_then(hex_to_u64);
+    Some(PendingBlock {
+        number: quantity("number")?,
+        tx_count: block.get("transactions")?.as_array()?.len(),
+        gas_used: quantity("gasUsed")?,
+        gas_limit: quantity("gasLimit")?,
+        base_fee_per_gas: block
+            .get("baseFeePerGas")
+            .and_then(|v| v.as_str())
+            .and_then(hex_to_u128),
+    })
+}
+
+/// Count successes, reverts, and deployments, average the effective gas price, rank the
+/// receiving contracts by gas used, and total gas used per transaction type.
+fn summarize_receipts(receipts: &[serde_json::Value]) -> ReceiptSummary {
+    let mut summary = ReceiptSummary::default();
+    let mut price_total: u128 = 0;
+    let mut priced = 0u128;
+    let mut gas_users: Vec<GasUser> = Vec::new();
+    for receipt in receipts {
+        let field = |key: &str| receipt.get(key).and_then(|v| v.as_str());
+        if field("status") == Some("0x0") {
+            summary.reverted += 1;
+        } else {
+            summary.succeeded += 1;
+        }
+        let created = field("contractAddress");
+        if created.is_some() {
+            summary.contract_creations += 1;
+        }
+        if let Some(price) = field("effectiveGasPrice").and_then(hex_to_u128) {
+            price_total = price_total.saturating_add(price);
+            priced += 1;
+        }
+        let recipient = field("to")
+            .or(created)
+            .and_then(|address| address.parse::<Address>().ok());
+        if let (Some(address), Some(gas_used)) = (recipient, field("gasUsed").and_then(hex_to_u64))
+        {
+            match gas_users.iter_mut().find(|user| user.address == address) {
+                Some(user) => {
+                    user.gas_used += gas_used;
+                    user.txs += 1;
+                }
+                None => gas_users.push(GasUser {
+                    address,
+                    gas_used,
+                    txs: 1,
+                }),
+            }
+        }
+    }
+    summary.avg_effective_gas_price = (priced > 0).then(|| price_total / priced);
+    summary.gas_by_type = tx_types::tally(receipts.iter().filter_map(|receipt| {
+        let gas_used = receipt.get("gasUsed")?.as_str().and_then(hex_to_u64)?;
+        Some((TxType::of(receipt)?, gas_used))
+    }));
+    gas_users.sort_by_key(|user| std::cmp::Reverse(user.gas_used));
+    gas_users.truncate(GAS_USERS_PER_BLOCK);
+    summary.gas_users = gas_users;
+    summary
+}
+
+/// Await `call` only when the endpoint hasn't reported the method unsupported.
+async fn optional_call<T>(
+    supported: bool,
+    call: impl std::future::Future<Output = Result<T>>,
+) -> Option<Result<T>> {
+    if supported { Some(call.await) } else { None }
+}
+
+/// Gas limit over gas target under EIP-1559.
+pub const ELASTICITY_MULTIPLIER: u64 = 2;
+const BASE_FEE_MAX_CHANGE_DENOMINATOR: u128 = 8;
+
+/// EIP-1559 base fee for the block following one with the given base fee and gas usage.
+fn next_base_fee(base_fee: u128, gas_used: u64, gas_limit: u64) -> u128 {
+    let gas_target = (gas_limit / ELASTICITY_MULTIPLIER) as u128;
+    if gas_target == 0 {
+        return base_fee;
+    }
+
+    let gas_used = gas_used as u128;
+    match gas_used.cmp(&gas_target) {
+        std::cmp::Ordering::Equal => base_fee,
+        std::cmp::Ordering::Greater => {
+            let delta =
+                base_fee * (gas_used - gas_target) / gas_target / BASE_FEE_MAX_CHANGE_DENOMINATOR;
+            base_fee.saturating_add(delta.max(1))
+        }
+        std::cmp::Ordering::Less => {
+            let delta =
+                base_fee * (gas_target - gas_used) / gas_target / BASE_FEE_MAX_CHANGE_DENOMINATOR;
+            base_fee.saturating_sub(delta)
+        }
+    }
+}
+
+/// Summarize the run of newest blocks that are all above, all below, or all at the gas target.
+fn target_deviation(history: &VecDeque<BlockInfo>) -> Option<TargetDeviation> {
+    let side = |block: &BlockInfo| {
+        let target = block.gas_limit / ELASTICITY_MULTIPLIER;
+        (target > 0).then(|| block.gas_used.cmp(&target))
+    };
+    let newest = side(history.front()?)?;
+
+    let streak: Vec<f64> = history
+        .iter()
+        .take_while(|block| side(block) == Some(newest))
+        .map(|block| {
+            let target = (block.gas_limit / ELASTICITY_MULTIPLIER) as f64;
+            (block.gas_used as f64 - target) * 100.0 / target
+        })
+        .collect();
+
+    Some(TargetDeviation {
+        avg_pct: streak.iter().sum::<f64>() / streak.len() as f64,
+        blocks: streak.len(),
+        direction: match newest {
+            std::cmp::Ordering::Greater => FeeDirection::Rising,
+            std::cmp::Ordering::Less => FeeDirection::Falling,
+            std::cmp::Ordering::Equal => FeeDirection::Flat,
+        },
+    })
+}
+
+/// Fewest fee-history blocks worth fitting a trend to.
+const FORECAST_MIN_SAMPLES: usize = 6;
+
+/// Extrapolate the base fee `blocks` ahead of the latest block.
+///
+/// `pending_base_fee` is the already-known fee of the next block; each later block's
+/// utilization comes from a least-squares line through `gas_used_ratios` (0..=1, oldest
+/// first). The low/high band shifts that line by one residual standard deviation, and the
+/// same spread grades confidence.
+fn forecast_base_fee(
+    pending_base_fee: u128,
+    gas_used_ratios: &[f64],
+    blocks: usize,
+) -> Option<BaseFeeForecast> {
+    let n = gas_used_ratios.len();
+    if n < FORECAST_MIN_SAMPLES || blocks == 0 {
+        return None;
+    }
+
+    let mean_x = (n - 1) as f64 / 2.0;
+    let mean_y = gas_used_ratios.iter().sum::<f64>() / n as f64;
+    let (mut covariance, mut variance) = (0.0, 0.0);
+    for (x, y) in gas_used_ratios.iter().enumerate() {
+        covariance += (x as f64 - mean_x) * (y - mean_y);
+        variance += (x as f64 - mean_x).powi(2);
+    }
+    let slope = covariance / variance;
+    let intercept = mean_y - slope * mean_x;
+    let residual_sd = (gas_used_ratios
+        .iter()
+        .enumerate()
+        .map(|(x, y)| (y - (intercept + slope * x as f64)).powi(2))
+        .sum::<f64>()
+        / n as f64)
+        .sqrt();
+
+    // Ratios are applied to a nominal gas limit; only used/limit matters to the fee update
+    const NOMINAL_GAS_LIMIT: u64 = 1_000_000;
+    let extrapolate = |shift: f64| {
+        (0..blocks - 1).fold(pending_base_fee, |fee, step| {
+            let utilization = (intercept + slope * (n + step) as f64 + shift).clamp(0.0, 1.0);
+            let gas_used = (utilization * NOMINAL_GAS_LIMIT as f64) as u64;
+            next_base_fee(fee, gas_used, NOMINAL_GAS_LIMIT)
+        })
+    };
+
+    Some(BaseFeeForecast {
+        blocks,
+        expected: extrapolate(0.0),
+        low: extrapolate(-residual_sd),
+        high: extrapolate(residual_sd),
+        confidence: if residual_sd < 0.1 {
+            ForecastConfidence::High
+        } else if residual_sd < 0.25 {
+            ForecastConfidence::Medium
+        } else {
+            ForecastConfidence::Low
+        },
+    })
+}
+
+/// Project base fees `blocks` ahead, assuming every block repeats the given block's utilization.
+fn project_base_fees(block: &BlockInfo, blocks: usize) -> Vec<u128> {
+    let Some(mut base_fee) = block.base_fee_per_gas else {
+        return Vec::new();
+    };
+
+    (0..blocks)
+        .map(|_| {
+            base_fee = next_base_fee(base_fee, block.gas_used, block.gas_limit);
+            base_fee
+        })
+        .collect()
+}
+
+fn to_hex_qty(n: u64) -> String {
+    format!("0x{:x}", n)
+}
+pub fn hex_to_u64(s: &str) -> Option<u64> {
+    let t = s.trim_start_matches("0x");
+    u64::from_str_radix(t, 16).ok()
+}
+fn hex_to_u128(s: &str) -> Option<u128> {
+    let t = s.trim_start_matches("0x");
+    u128::from_str_radix(t, 16).ok()
+}
+
+pub struct MetricsCollector {
+    config: Config,
+    client: SignetRpcClient,
+    metrics: SignetMetrics,
+    tx_client: Option<TxPoolClient>,
+    txpool_watch_signet_host_contracts: bool,
+    builder_labels: BuilderLabels,
+    gas_baseline: GasBaseline,
+    probes: Vec<Probe>,
+    slots: Vec<SlotWatch>,
+    verified_accounts: Vec<Address>,
+    compare: Option<(SignetRpcClient, u64)>, // --compare-rpc client and max lag
+    trace_blocks: bool,                      // --enable-trace
+    beacon: Option<BeaconClient>,            // --beacon-url, for blob sidecars
+    gas_oracles: Vec<GasOracle>,             // --gas-oracle, queried every GAS_ORACLE_INTERVAL
+    gas_oracles_checked: Option<Instant>,
+    blobs_checked: Option<u64>, // newest block whose sidecars were requested
+    watchlist: Watchlist,
+    watchlist_checked: Option<u64>, // newest block the watchlist was checked at
+    jitter_limit: Option<f64>,      // --max-jitter, reapplied on endpoint switches
+    block_size_limit: Option<u64>,  // --block-size-limit, reapplied on endpoint switches
+    slo: Option<Slo>,               // from config.toml, reapplied on endpoint switches
+    forks: Vec<Fork>,               // from config.toml, reapplied on endpoint switches
+}
+
+impl MetricsCollector {
+    pub fn new(config: Config) -> Result<Self> {
+        let client = SignetRpcClient::new(config.rpc_url.clone(), &config.http)?;
+        let metrics = SignetMetrics::new(config.clone());
+        Ok(Self {
+            txpool_watch_signet_host_contracts: config.txpool_watch_signet_host_contracts,
+            gas_baseline: GasBaseline::new(config.gas_alert_gwei, config.gas_spike_multiplier),
+            config,
+            client,
+            metrics,
+            tx_client: None,
+            builder_labels: BuilderLabels::default(),
+            probes: Vec::new(),
+            slots: Vec::new(),
+            verified_accounts: Vec::new(),
+            compare: None,
+            trace_blocks: false,
+            beacon: None,
+            gas_oracles: Vec::new(),
+            gas_oracles_checked: None,
+            blobs_checked: None,
+            watchlist: Watchlist::default(),
+            watchlist_checked: None,
+            jitter_limit: None,
+            block_size_limit: None,
+            slo: None,
+            forks: Vec::new(),
+        })
+    }
+
+    /// Point the collector at a different RPC endpoint, discarding all per-chain state.
+    ///
+    /// Everything configured through the `set_*` methods is kept: the tx-pool client, host
+    /// contract filter, thresholds, probes and storage slots, comparison endpoint, beacon
+    /// API, gas oracles, watchlist, jitter and block size limits, SLO, and forks. Watched
+    /// contracts are re-derived once the new endpoint reports its chain ID.
+    pub fn switch_endpoint(&mut self, client: SignetRpcClient, rpc_url: String) {
+        self.client = client;
+        self.config.rpc_url = rpc_url;
+        let large_transfer_wei = self.metrics.large_transfer_wei;
+        self.metrics = SignetMetrics::new(self.config.clone());
+        self.metrics.large_transfer_wei = large_transfer_wei;
+        self.gas_baseline =
+            GasBaseline::new(self.config.gas_alert_gwei, self.config.gas_spike_multiplier);
+        self.metrics.host_contract_filter_active = self.txpool_watch_signet_host_contracts;
+        self.metrics.watchlist = self.watchlist.readings(&WatchlistReadings::default());
+        self.watchlist_checked = None;
+        self.metrics.gas_oracles = self.gas_oracles.iter().map(GasOracle::reading).collect();
+        self.gas_oracles_checked = None;
+        self.metrics.slots = self.slots.iter().cloned().map(SlotReading::new).collect();
+        self.metrics.comparison = self
+            .compare
+            .as_ref()
+            .map(|(client, max_lag)| EndpointComparison::new(client.rpc_url.clone(), *max_lag));
+        self.metrics.beacon = self.beacon.as_ref().map(beacon_status);
+        self.blobs_checked = None;
+        self.metrics.jitter_limit = self.jitter_limit;
+        self.metrics.block_size_limit = self.block_size_limit;
+        self.metrics.slo = self.slo;
+        self.metrics.forks = self.forks.clone();
+        if let Some(client) = &mut self.tx_client {
+            client.set_filter_contracts(std::iter::empty::<Address>());
+        }
+    }
+
+    /// Construct a collector with an optional tx-pool-webservice base URL.
+    pub fn new_with_txpool(config: Config, txpool_url: Option<String>) -> Result<Self> {
+        let max_rows = config.txpool_max_rows;
+        let fetch_list = config.txpool_fetch_list;
+        let stream = config.txpool_stream.clone();
+        let options = config.http.clone();
+        let mut collector = Self::new(config)?;
+        if let Some(url) = txpool_url {
+            let mut client = TxPoolClient::new(url, max_rows, fetch_list, options)?;
+            if let Some(path) = stream {
+                client.subscribe(&path)?;
+            }
+            collector.tx_client = Some(client);
+        }
+        Ok(collector)
+    }
+
+    /// Fill the block history with the newest `count` blocks ahead of the first cycle.
+    ///
+    /// Blocks are fetched in concurrent batches with a pause in between. The backfill stops
+    /// at the first failed block so the history stays contiguous; regular cycles fill in the
+    /// rest. Returns the number of blocks added.
+    pub async fn backfill(&mut self, count: usize) -> Result<usize> {
+        let latest = self.client.get_block_number().await;
+        self.metrics.record_rpc(RpcMethod::BlockNumber, &latest);
+        let latest = latest?;
+        let count = count.min(self.metrics.max_block_history);
+        let mut added = 0;
+        for (i, batch) in backfill_batches(latest, count, BACKFILL_BATCH_SIZE)
+            .into_iter()
+            .enumerate()
+        {
+            if i > 0 {
+                tokio::time::sleep(BACKFILL_BATCH_DELAY).await;
+            }
+            let results = join_all(
+                batch
+                    .iter()
+                    .map(|num| self.client.get_block_by_number(*num)),
+            )
+            .await;
+            for result in results {
+                self.metrics.record_rpc(RpcMethod::GetBlock, &result);
+                let Ok(block) = result else {
+                    self.refresh_block_stats();
+                    return Ok(added);
+                };
+                self.admit_block(block);
+                added += 1;
+            }
+        }
+        self.refresh_block_stats();
+        Ok(added)
+    }
+
+    /// Attribute a freshly fetched block, count it and its burn toward the session totals,
+    /// and add it to the history. Returns the number of blocks it orphaned.
+    fn admit_block(&mut self, mut block: BlockInfo) -> usize {
+        block.builder = self
+            .builder_labels
+            .attribute(block.fee_recipient, &block.extra_data);
+        self.metrics.burn.session_wei = self
+            .metrics
+            .burn
+            .session_wei
+            .saturating_add(block.burned_wei().unwrap_or(0));
+        self.metrics.session.observe(&block);
+        if let Some(change) = gas_limit_change(&self.metrics.block_history, &block)
+            && self
+                .metrics
+                .last_gas_limit_change
+                .is_none_or(|last| change.block >= last.block)
+        {
+            self.metrics.last_gas_limit_change = Some(change);
+        }
+        if self
+            .metrics
+            .latest_block_timestamp
+            .is_none_or(|current| block.timestamp > current)
+        {
+            self.metrics.latest_block_timestamp = Some(block.timestamp);
+            // A new head: its average interval since the previous one, across any skipped
+            if let Some(previous) = self.metrics.block_history.front()
+                && block.number > previous.number
+            {
+                let interval = block.timestamp.saturating_sub(previous.timestamp) as f64
+                    / (block.number - previous.number) as f64;
+                self.metrics
+                    .timeseries
+                    .record(Metric::BlockTime, block.timestamp, interval);
+            }
+            if let Some(size) = block.size {
+                self.metrics
+                    .timeseries
+                    .record(Metric::BlockSize, block.timestamp, size as f64);
+            }
+        }
+        self.metrics.block_history.insert(block)
+    }
+
+    /// Fetch and admit `numbers` in order, skipping failures. Returns blocks orphaned.
+    async fn fetch_blocks(&mut self, numbers: Vec<u64>) -> usize {
+        let mut orphaned = 0;
+        for num in numbers {
+            let result = self.client.get_block_by_number(num).await;
+            self.metrics.record_rpc(RpcMethod::GetBlock, &result);
+            if let Ok(block) = result {
+                orphaned += self.admit_block(block);
+            }
+        }
+        orphaned
+    }
+
+    /// Call each optional method once so the first cycle already skips unsupported ones.
+    /// Probe calls only feed [`Capabilities`], not the per-method call counts.
+    async fn probe_capabilities(&mut self) {
+        let client = &self.client;
+        let (fee_history, priority_fee, blob_base_fee, syncing, txpool_status, pending) = tokio::join!(
+            client.get_fee_history(1, "latest", &[]),
+            client.get_max_priority_fee_per_gas(),
+            client.get_blob_base_fee(),
+            client.get_syncing(),
+            client.get_txpool_status(),
+            client.get_pending_block(),
+        );
+        let capabilities = &mut self.metrics.capabilities;
+        capabilities.observe(RpcMethod::FeeHistory, &fee_history);
+        capabilities.observe(RpcMethod::MaxPriorityFee, &priority_fee);
+        capabilities.observe(RpcMethod::BlobBaseFee, &blob_base_fee);
+        capabilities.observe(RpcMethod::Syncing, &syncing);
+        capabilities.observe(RpcMethod::TxPoolStatus, &txpool_status);
+        capabilities.observe(RpcMethod::PendingBlock, &pending);
+        capabilities.probed = true;
+    }
+
+    /// Ask the endpoint, once, which client it runs and which genesis it follows.
+    async fn fetch_node_info(&mut self) {
+        let (client, genesis_hash) = tokio::join!(
+            self.client.get_client_version(),
+            self.client.get_genesis_hash()
+        );
+        self.metrics.record_rpc(RpcMethod::ClientVersion, &client);
+        self.metrics.record_rpc(RpcMethod::GetBlock, &genesis_hash);
+        self.metrics.node_info = Some(NodeInfo {
+            client: client.ok(),
+            genesis_hash: genesis_hash.ok(),
+        });
+    }
+
+    /// Attach a receipt summary to the newest block, once, when `--receipts` is set.
+    async fn fetch_newest_receipts(&mut self) {
+        if !self.config.fetch_receipts
+            || !self.metrics.capabilities.supports(RpcMethod::BlockReceipts)
+        {
+            return;
+        }
+        let Some(newest) = self
+            .metrics
+            .block_history
+            .front()
+            .filter(|block| block.receipts.is_none())
+            .map(|block| block.number)
+        else {
+            return;
+        };
+        let result = self.client.get_block_receipts(newest).await;
+        self.metrics.record_rpc(RpcMethod::BlockReceipts, &result);
+        if let Ok(summary) = result
+            && let Some(block) = self
+                .metrics
+                .block_history
+                .iter_mut()
+                .find(|block| block.number == newest)
+        {
+            block.receipts = Some(summary);
+        }
+    }
+
+    /// Attach blob sidecars to the newest block, once, when it carries blobs and a beacon
+    /// API is configured.
+    async fn fetch_newest_blobs(&mut self) {
+        let Some(beacon) = &mut self.beacon else {
+            return;
+        };
+        let Some((number, timestamp)) = self
+            .metrics
+            .block_history
+            .front()
+            .filter(|block| block.blob_count().unwrap_or(0) > 0 && block.blobs.is_empty())
+            .map(|block| (block.number, block.timestamp))
+        else {
+            return;
+        };
+        if self.blobs_checked == Some(number) {
+            return;
+        }
+        self.blobs_checked = Some(number);
+        let result = beacon.blob_sidecars(timestamp).await;
+        self.attach_sidecars(number, result);
+    }
+
+    /// Store block `number`'s sidecars, or the beacon API's error, on the metrics.
+    fn attach_sidecars(
+        &mut self,
+        number: u64,
+        result: std::result::Result<Vec<BlobTransactionSidecarItem>, String>,
+    ) {
+        let Some(status) = &mut self.metrics.beacon else {
+            return;
+        };
+        match result {
+            Ok(sidecars) => {
+                status.error = None;
+                status.blocks += 1;
+                if let Some(block) = self
+                    .metrics
+                    .block_history
+                    .iter_mut()
+                    .find(|block| block.number == number)
+                {
+                    block.blobs = sidecars;
+                }
+            }
+            Err(e) => status.error = Some(e),
+        }
+    }
+
+    /// Count the newest block's transactions by type, its calldata, and its account-abstraction
+    /// activity, once per head. Every block since the last one counted (at most
+    /// `BODY_SCAN_BLOCKS`) is fetched, so none goes unscanned for large transfers and contract
+    /// deployments; new deployments then get their receipts for the gas they used.
+    async fn fetch_newest_body(&mut self) {
+        let Some(newest) = self.metrics.block_history.front().map(|block| block.number) else {
+            return;
+        };
+        let counted = self.metrics.tx_types.as_ref().map(|mix| mix.number);
+        if counted == Some(newest) {
+            return;
+        }
+        let oldest = match counted {
+            Some(counted) if counted < newest => {
+                (counted + 1).max(newest.saturating_sub(BODY_SCAN_BLOCKS - 1))
+            }
+            _ => newest,
+        };
+        let min_transfer = self.metrics.large_transfer_wei.map(U256::from);
+        let client = &self.client;
+        let bodies =
+            join_all((oldest..=newest).map(|number| client.get_full_block(number, min_transfer)))
+                .await;
+        let mut deployed = Vec::new();
+        for (number, result) in (oldest..=newest).zip(bodies) {
+            self.metrics.record_rpc(RpcMethod::GetBlock, &result);
+            let Ok(body) = result else {
+                continue;
+            };
+            if number == newest {
+                self.metrics.tx_types = Some(body.mix);
+            }
+            self.metrics.aa.record(body.aa);
+            if let Some(transfer) = body.large_transfer {
+                self.metrics.last_large_transfer = Some(transfer);
+            }
+            for deployment in body.deployments {
+                if self.metrics.deployments.record(deployment) {
+                    deployed.push(deployment.hash);
+                }
+            }
+            if let Some(block) = self
+                .metrics
+                .block_history
+                .iter_mut()
+                .find(|block| block.number == number)
+            {
+                block.calldata_bytes = Some(body.calldata);
+                self.metrics.timeseries.record(
+                    Metric::Calldata,
+                    block.timestamp,
+                    body.calldata as f64,
+                );
+            }
+        }
+        let receipts = join_all(deployed.iter().map(|hash| client.get_inclusion(*hash))).await;
+        for (hash, result) in deployed.into_iter().zip(receipts) {
+            self.metrics.record_rpc(RpcMethod::TxReceipt, &result);
+            if let Ok(Some(inclusion)) = result {
+                self.metrics.deployments.set_receipt(hash, &inclusion);
+            }
+        }
+    }
+
+    /// Trace the newest block, once, when `--enable-trace` is set.
+    async fn trace_newest_block(&mut self) {
+        if !self.trace_blocks || !self.metrics.capabilities.supports(RpcMethod::TraceBlock) {
+            return;
+        }
+        let Some(newest) = self.metrics.block_history.front().map(|block| block.number) else {
+            return;
+        };
+        if self
+            .metrics
+            .block_trace
+            .as_ref()
+            .is_some_and(|trace| trace.number == newest)
+        {
+            return;
+        }
+        let result = self.client.trace_block(newest).await;
+        self.metrics.record_rpc(RpcMethod::TraceBlock, &result);
+        if let Ok(trace) = result {
+            self.metrics.block_trace = Some(trace);
+        }
+    }
 
-            base_fee_per_gas,
-            blob_gas_used,
-            excess_blob_gas,
-        })
+    /// Scan blocks added since the last scan for fills of the chain's Signet orders contract.
+    async fn scan_order_fills(&mut self) {
+        let (Some(orders), Some(newest), Some(oldest)) = (
+            self.metrics.chain_id.and_then(orders_contract_for_chain_id),
+            self.metrics.block_history.front().map(|block| block.number),
+            self.metrics.block_history.back().map(|block| block.number),
+        ) else {
+            return;
+        };
+        if !self.metrics.capabilities.supports(RpcMethod::GetLogs) {
+            return;
+        }
+        let from = self
+            .metrics
+            .order_flow
+            .scanned_to
+            .map_or(oldest, |scanned| scanned + 1);
+        if from > newest {
+            return;
+        }
+        let result = self.client.get_order_fills(orders, from, newest).await;
+        self.metrics.record_rpc(RpcMethod::GetLogs, &result);
+        if let Ok(fills) = result {
+            self.metrics.order_flow.record(fills, newest);
+        }
     }
 
-    pub async fn get_fee_history(
-        &self,
-        block_count: u64,
-        newest: &str,               // "latest" or hex
-        reward_percentiles: &[f64], // 0..=100
-    ) -> Result<EthFeeHistoryResult> {
-        let params = serde_json::json!([to_hex_qty(block_count), newest, reward_percentiles]);
-        let body = serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "eth_feeHistory",
-            "params": params
-        });
-        let resp = self.http.post(&self.rpc_url).json(&body).send().await?;
-        if !resp.status().is_success() {
-            return Err(eyre::eyre!(format!(
-                "eth_feeHistory HTTP {}",
-                resp.status()
-            )));
+    /// Make every configured `eth_call` probe and read every watched slot.
+    async fn run_probes(&mut self) {
+        let client = &self.client;
+        let (results, words) = tokio::join!(
+            join_all(
+                self.probes
+                    .iter()
+                    .map(|probe| client.call(probe.to, &probe.data)),
+            ),
+            join_all(
+                self.slots
+                    .iter()
+                    .map(|watch| client.get_storage_at(watch.address, watch.slot)),
+            )
+        );
+        let mut readings = Vec::with_capacity(results.len());
+        for (probe, result) in self.probes.iter().zip(results) {
+            self.metrics.record_rpc(RpcMethod::Call, &result);
+            readings.push(ProbeReading {
+                label: probe.label.clone(),
+                value: result
+                    .map_err(|e| e.to_string())
+                    .and_then(|output| probe.decode(&output)),
+            });
         }
-        let v: serde_json::Value = resp.json().await?;
-        if let Some(err) = v.get("error") {
-            return Err(eyre::eyre!(format!("eth_feeHistory error: {}", err)));
+        self.metrics.probes = readings;
+
+        if self.metrics.slots.len() != self.slots.len() {
+            self.metrics.slots = self.slots.iter().cloned().map(SlotReading::new).collect();
+        }
+        let now = Instant::now();
+        for (reading, result) in self.metrics.slots.iter_mut().zip(words) {
+            self.metrics.rpc_stats.record(RpcMethod::StorageAt, &result);
+            reading.observe(result.map_err(|e| e.to_string()), now);
         }
-        let res: EthFeeHistoryResult = serde_json::from_value(v["result"].clone())?;
-        Ok(res)
     }
-}
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct EthFeeHistoryResult {
-    pub oldest_block: String,
-    #[serde(default)]
-    pub base_fee_per_gas: Vec<String>, // hex; length blockCount+1
-    #[serde(default)]
-    pub gas_used_ratio: Vec<f64>, // 0..=1 length blockCount
-    #[serde(default)]
-    pub reward: Vec<Vec<String>>, // [blockCount][percentiles]
-}
+    /// Prove every `--verify-account` account, with its watched slots, against the state
+    /// root of the newest block. Skipped once the endpoint reports `eth_getProof` missing.
+    async fn verify_accounts(&mut self) {
+        let Some(newest) = self.metrics.block_history.front() else {
+            return;
+        };
+        if self.verified_accounts.is_empty()
+            || !self.metrics.capabilities.supports(RpcMethod::GetProof)
+        {
+            return;
+        }
+        let (number, state_root) = (newest.number, newest.state_root);
+        let client = &self.client;
+        let slots = &self.slots;
+        let results = join_all(self.verified_accounts.iter().map(|address| async move {
+            let keys: Vec<B256> = slots
+                .iter()
+                .filter(|watch| watch.address == *address)
+                .map(|watch| watch.slot)
+                .collect();
+            client.get_proof(*address, &keys, &newest.hash).await
+        }))
+        .await;
+        let mut checks = Vec::with_capacity(results.len());
+        for (address, result) in self.verified_accounts.iter().zip(results) {
+            self.metrics.record_rpc(RpcMethod::GetProof, &result);
+            let outcome = match result {
+                Ok(response) => match proofs::verify(state_root, &response) {
+                    Ok(()) => ProofOutcome::Verified,
+                    Err(e) => ProofOutcome::Mismatch(e),
+                },
+                Err(e) => ProofOutcome::Failed(e.to_string()),
+            };
+            checks.push(ProofCheck {
+                address: *address,
+                block: number,
+                outcome,
+            });
+        }
+        self.metrics.proofs = checks;
+    }
 
-fn to_hex_qty(n: u64) -> String {
-    format!("0x{:x}", n)
-}
-fn hex_to_u64(s: &str) -> Option<u64> {
-    let t = s.trim_start_matches("0x");
-    u64::from_str_radix(t, 16).ok()
-}
-fn hex_to_u128(s: &str) -> Option<u128> {
-    let t = s.trim_start_matches("0x");
-    u128::from_str_radix(t, 16).ok()
-}
+    /// Read every watchlist entry at the newest block, once per head: account balances
+    /// and nonces (logging each change to the activity feed, after the transactions the
+    /// addresses sent or received in the blocks since the last check), receipts of
+    /// transactions not yet included, and log matches in the blocks since the last check
+    /// (at most `WATCHLIST_LOG_BLOCKS` of them).
+    async fn check_watchlist(&mut self) {
+        let Some(newest) = self.metrics.block_history.front().map(|block| block.number) else {
+            return;
+        };
+        if self.watchlist.is_empty() || self.watchlist_checked == Some(newest) {
+            return;
+        }
+        let from = self
+            .watchlist_checked
+            .filter(|checked| *checked < newest)
+            .map_or(newest, |checked| checked + 1)
+            .max(newest.saturating_sub(WATCHLIST_LOG_BLOCKS - 1));
+        self.watchlist_checked = Some(newest);
 
-pub struct MetricsCollector {
-    client: SignetRpcClient,
-    metrics: SignetMetrics,
-    tx_client: Option<TxPoolClient>,
-    txpool_watch_signet_host_contracts: bool,
-}
+        let client = &self.client;
+        let readings = &mut self.metrics.watchlist;
+        let accounts = join_all(
+            readings
+                .accounts
+                .iter()
+                .map(|reading| client.get_account(reading.address, newest)),
+        );
+        let pending: Vec<usize> = (0..readings.transactions.len())
+            .filter(|i| readings.transactions[*i].inclusion.is_none())
+            .collect();
+        let receipts = join_all(
+            pending
+                .iter()
+                .map(|i| client.get_inclusion(readings.transactions[*i].hash)),
+        );
+        let supports_logs = self.metrics.capabilities.supports(RpcMethod::GetLogs);
+        let logs = join_all(
+            readings
+                .events
+                .iter()
+                .filter(|_| supports_logs)
+                .map(|reading| client.count_logs(&reading.filter, from, newest)),
+        );
+        // Full bodies of the same blocks, for transactions the watched addresses sent or got
+        let addresses: Vec<Address> = readings.accounts.iter().map(|r| r.address).collect();
+        let bodies = join_all(
+            (from.max(newest.saturating_sub(WATCHLIST_BODY_BLOCKS - 1))..=newest)
+                .filter(|_| !addresses.is_empty())
+                .map(|number| client.get_full_block_raw(number)),
+        );
+        let (accounts, receipts, logs, bodies) = tokio::join!(accounts, receipts, logs, bodies);
 
-impl MetricsCollector {
-    pub fn new(config: Config) -> Result<Self> {
-        let client = SignetRpcClient::new(config.rpc_url.clone())?;
-        let metrics = SignetMetrics::new(config.clone());
-        Ok(Self {
-            client,
-            metrics,
-            tx_client: None,
-            txpool_watch_signet_host_contracts: config.txpool_watch_signet_host_contracts,
-        })
+        let stats = &mut self.metrics.rpc_stats;
+        for body in bodies {
+            stats.record(RpcMethod::GetBlock, &body);
+            let found = body
+                .ok()
+                .and_then(|body| watchlist::address_txs(&body, &addresses));
+            for tx in found.into_iter().flatten() {
+                readings.log(Activity::Tx(tx));
+            }
+        }
+        let mut changes = Vec::new();
+        for (reading, (balance, nonce)) in readings.accounts.iter_mut().zip(accounts) {
+            stats.record(RpcMethod::GetBalance, &balance);
+            stats.record(RpcMethod::GetNonce, &nonce);
+            reading.error = None;
+            match balance.and_then(|balance| Ok((balance, nonce?))) {
+                Ok((balance, nonce)) => {
+                    changes.extend(reading.update(balance, nonce, newest, unix_now()));
+                }
+                Err(e) => reading.error = Some(e.to_string()),
+            }
+        }
+        for change in changes {
+            readings.log(Activity::Change(change));
+        }
+        for (i, result) in pending.into_iter().zip(receipts) {
+            stats.record(RpcMethod::TxReceipt, &result);
+            let reading = &mut readings.transactions[i];
+            match result {
+                Ok(inclusion) => {
+                    reading.inclusion = inclusion;
+                    reading.error = None;
+                }
+                Err(e) => reading.error = Some(e.to_string()),
+            }
+        }
+        for (reading, result) in readings.events.iter_mut().zip(logs) {
+            stats.record(RpcMethod::GetLogs, &result);
+            match result {
+                Ok((matches, last_block)) => {
+                    reading.matches += matches;
+                    reading.last_block = last_block.or(reading.last_block);
+                    reading.error = None;
+                }
+                Err(e) => reading.error = Some(e.to_string()),
+            }
+        }
     }
 
-    /// Construct a collector with an optional tx-pool-webservice base URL.
-    pub fn new_with_txpool(config: Config, txpool_url: Option<String>) -> Result<Self> {
-        let max_rows = config.txpool_max_rows;
-        let fetch_list = config.txpool_fetch_list;
-        let mut collector = Self::new(config)?;
-        if let Some(url) = txpool_url {
-            collector.tx_client = Some(TxPoolClient::new(url, max_rows, fetch_list)?);
+    /// Check our head and a recent block hash against the --compare-rpc endpoint.
+    async fn compare_endpoints(&mut self) {
+        let Some((client, max_lag)) = &self.compare else {
+            return;
+        };
+        let head = client.get_block_number().await;
+        let ours = self.metrics.block_number;
+        let comparison = self
+            .metrics
+            .comparison
+            .get_or_insert_with(|| EndpointComparison::new(client.rpc_url.clone(), *max_lag));
+        comparison.ours = ours;
+        let head = match head {
+            Ok(head) => head,
+            Err(e) => {
+                comparison.error = Some(e.to_string());
+                return;
+            }
+        };
+        comparison.head = Some(head);
+        comparison.error = None;
+        let Some(number) = ours.and_then(|ours| compare::compared_height(ours, head)) else {
+            return;
+        };
+        let Some(our_hash) = self
+            .metrics
+            .block_history
+            .iter()
+            .find(|block| block.number == number)
+            .map(|block| block.hash.clone())
+        else {
+            return;
+        };
+        let theirs = client.get_block_hash(number).await;
+        let comparison = self.metrics.comparison.as_mut().expect("set above");
+        match theirs {
+            Ok(theirs) => comparison.observe_hashes(number, &our_hash, &theirs),
+            Err(e) => comparison.error = Some(e.to_string()),
         }
-        Ok(collector)
+    }
+
+    /// Recompute the stats derived from the whole block history.
+    fn refresh_block_stats(&mut self) {
+        let history = &self.metrics.block_history;
+        self.metrics.builder_distribution = builders::distribution(history.iter());
+        self.metrics.block_jitter = block_jitter(history);
+        self.metrics.block_size_pressure = self
+            .metrics
+            .block_size_limit
+            .and_then(|limit| block_size::near_limit_run(history, limit));
+        self.metrics.burn = BurnStats {
+            session_wei: self.metrics.burn.session_wei,
+            ..burn_stats(history)
+        };
     }
 
     pub async fn collect_metrics(&mut self) -> &SignetMetrics {
-        let mut status = match self.client.get_chain_id().await {
+        let started = Instant::now();
+        let chain_id_result = self.client.get_chain_id().await;
+        self.metrics
+            .record_rpc(RpcMethod::ChainId, &chain_id_result);
+        let mut status = match chain_id_result {
             Ok(chain_id) => {
                 self.metrics.chain_id = Some(chain_id);
                 self.update_txpool_watch_contracts(chain_id);
                 ConnectionStatus::Connected
             }
-            Err(e) => ConnectionStatus::Error(format!("Chain ID: {}", e)),
+            Err(e) => ConnectionStatus::from_error("Chain ID", &e),
         };
 
         if !matches!(status, ConnectionStatus::Connected) {
             self.clear_fee_metrics();
-            self.metrics.connection_status = status;
-            self.metrics.last_updated = Instant::now();
-            self.collect_txpool_metrics().await;
+            self.finish_cycle(status, started, false).await;
             return &self.metrics;
         }
 
-        let (block_number_result, gas_price_result, fee_history_result, priority_fee_result) = tokio::join!(
+        if !self.metrics.capabilities.probed {
+            self.probe_capabilities().await;
+        }
+        if self.metrics.node_info.is_none() {
+            self.fetch_node_info().await;
+        }
+
+        let supports = |method| self.metrics.capabilities.supports(method);
+        let (
+            block_number_result,
+            gas_price_result,
+            fee_history_result,
+            priority_fee_result,
+            blob_base_fee_result,
+            syncing_result,
+            txpool_status_result,
+            pending_block_result,
+        ) = tokio::join!(
             self.client.get_block_number(),
             self.client.get_gas_price(),
-            self.client
-                .get_fee_history(FEE_HISTORY_BLOCKS, "latest", &FEE_HISTORY_PERCENTILES),
-            self.client.get_max_priority_fee_per_gas(),
+            optional_call(
+                supports(RpcMethod::FeeHistory),
+                self.client
+                    .get_fee_history(FEE_HISTORY_BLOCKS, "latest", &FEE_HISTORY_PERCENTILES)
+            ),
+            optional_call(
+                supports(RpcMethod::MaxPriorityFee),
+                self.client.get_max_priority_fee_per_gas()
+            ),
+            optional_call(
+                supports(RpcMethod::BlobBaseFee),
+                self.client.get_blob_base_fee()
+            ),
+            optional_call(supports(RpcMethod::Syncing), self.client.get_syncing()),
+            optional_call(
+                supports(RpcMethod::TxPoolStatus),
+                self.client.get_txpool_status()
+            ),
+            optional_call(
+                supports(RpcMethod::PendingBlock),
+                self.client.get_pending_block()
+            ),
         );
+        let metrics = &mut self.metrics;
+        metrics.record_rpc(RpcMethod::BlockNumber, &block_number_result);
+        metrics.record_rpc(RpcMethod::GasPrice, &gas_price_result);
+        metrics.record_optional_rpc(RpcMethod::FeeHistory, &fee_history_result);
+        metrics.record_optional_rpc(RpcMethod::MaxPriorityFee, &priority_fee_result);
+        metrics.record_optional_rpc(RpcMethod::BlobBaseFee, &blob_base_fee_result);
+        metrics.record_optional_rpc(RpcMethod::Syncing, &syncing_result);
+        metrics.record_optional_rpc(RpcMethod::TxPoolStatus, &txpool_status_result);
+        metrics.record_optional_rpc(RpcMethod::PendingBlock, &pending_block_result);
+        // Optional methods degrade silently, but throttling on them still slows polling down
+        let throttled = is_rate_limited(&fee_history_result)
+            || is_rate_limited(&priority_fee_result)
+            || is_rate_limited(&blob_base_fee_result)
+            || is_rate_limited(&syncing_result)
+            || is_rate_limited(&txpool_status_result)
+            || is_rate_limited(&pending_block_result);
+        metrics.blob_base_fee = blob_base_fee_result.and_then(Result::ok);
+        metrics.node_sync = syncing_result.and_then(Result::ok);
+        metrics.node_txpool = txpool_status_result.and_then(Result::ok);
 
         match block_number_result {
-            Ok(block_number) => self.metrics.block_number = Some(block_number),
+            Ok(block_number) => {
+                self.metrics.block_number = Some(block_number);
+                self.metrics.start_block.get_or_insert(block_number);
+            }
             Err(e) => {
-                status = ConnectionStatus::Error(format!("Block number: {}", e));
+                status = ConnectionStatus::from_error("Block number", &e);
             }
         }
 
         if matches!(status, ConnectionStatus::Connected) {
             match gas_price_result {
-                Ok(gas_price) => self.metrics.gas_price = Some(gas_price),
+                Ok(gas_price) => {
+                    self.metrics.gas_price = Some(gas_price);
+                    self.metrics.session.gas_price.observe(gas_price);
+                    self.metrics
+                        .timeseries
+                        .record(Metric::GasPrice, unix_now(), gas_price as f64);
+                }
                 Err(e) => {
-                    status = ConnectionStatus::Error(format!("Gas price: {}", e));
+                    status = ConnectionStatus::from_error("Gas price", &e);
                 }
             }
         }
@@ -392,51 +2196,65 @@ impl MetricsCollector {
         if matches!(status, ConnectionStatus::Connected)
             && let Some(latest_num) = self.metrics.block_number
         {
-            match block_fetch_plan(
+            let mut orphaned = match block_fetch_plan(
                 latest_num,
                 self.metrics.block_history.front().map(|block| block.number),
                 self.metrics.block_history.back().map(|block| block.number),
                 self.metrics.block_history.len(),
                 self.metrics.max_block_history,
             ) {
-                BlockFetchPlan::Newer(fetch_range) => {
-                    for num in fetch_range {
-                        if let Ok(block) = self.client.get_block_by_number(num).await {
-                            let ts = block.timestamp;
-                            if self
-                                .metrics
-                                .latest_block_timestamp
-                                .map(|cur| ts > cur)
-                                .unwrap_or(true)
-                            {
-                                self.metrics.latest_block_timestamp = Some(ts);
-                            }
-                            self.metrics.block_history.push_front(block);
-                            while self.metrics.block_history.len() > self.metrics.max_block_history
-                            {
-                                self.metrics.block_history.pop_back();
-                            }
-                        }
-                    }
-                }
-                BlockFetchPlan::Older(fetch_range) => {
-                    for num in fetch_range {
-                        if let Ok(block) = self.client.get_block_by_number(num).await {
-                            self.metrics.block_history.push_back(block);
-                            while self.metrics.block_history.len() > self.metrics.max_block_history
-                            {
-                                self.metrics.block_history.pop_back();
-                            }
-                        }
-                    }
+                BlockFetchPlan::Newer(numbers) | BlockFetchPlan::Older(numbers) => {
+                    self.fetch_blocks(numbers).await
                 }
-                BlockFetchPlan::None => {}
+                BlockFetchPlan::None => 0,
+            };
+            // Heights left empty by a reorg (or a jump past MAX_BACKFILL_PER_CYCLE)
+            let holes = self
+                .metrics
+                .block_history
+                .missing(MAX_BACKFILL_PER_CYCLE as usize);
+            orphaned += self.fetch_blocks(holes).await;
+            self.fetch_newest_receipts().await;
+            self.fetch_newest_body().await;
+            self.fetch_newest_blobs().await;
+            self.trace_newest_block().await;
+            // Nodes without a real pending block echo the latest one back
+            let newest = self.metrics.block_history.front().map(|block| block.number);
+            self.metrics.pending_block = pending_block_result
+                .and_then(Result::ok)
+                .flatten()
+                .filter(|pending| Some(pending.number) > newest);
+            if orphaned > 0 {
+                let head = self.metrics.block_history.front().map_or(0, |b| b.number);
+                self.metrics
+                    .order_flow
+                    .rewind(head.saturating_sub(orphaned as u64));
+                self.metrics.reorg_count += 1;
+                self.metrics.last_reorg = Some(Reorg {
+                    depth: orphaned,
+                    head,
+                    at: unix_now(),
+                });
             }
+            self.scan_order_fills().await;
+            self.refresh_block_stats();
+            self.run_probes().await;
+            self.verify_accounts().await;
+            self.check_watchlist().await;
+            self.compare_endpoints().await;
         }
 
         if matches!(status, ConnectionStatus::Connected) {
+            let mut median_tip = None;
+            let projected_base_fees = self
+                .metrics
+                .block_history
+                .front()
+                .map(|block| project_base_fees(block, BASE_FEE_PROJECTION_BLOCKS))
+                .unwrap_or_default();
+
             match fee_history_result {
-                Ok(h) => {
+                Some(Ok(h)) => {
                     let mut base_fees: Vec<u128> = Vec::with_capacity(h.base_fee_per_gas.len());
                     for hf in &h.base_fee_per_gas {
                         if let Some(v) = hex_to_u128(hf) {
@@ -453,7 +2271,17 @@ impl MetricsCollector {
                         (base_fees.last().copied(), None)
                     };
                     self.metrics.base_fee_per_gas = current_base_fee;
-                    self.metrics.next_base_fee_per_gas = next_base_fee;
+                    if let Some(fee) = current_base_fee {
+                        self.metrics
+                            .timeseries
+                            .record(Metric::BaseFee, unix_now(), fee as f64);
+                    }
+                    self.metrics.next_base_fee_per_gas =
+                        projected_base_fees.first().copied().or(next_base_fee);
+
+                    self.metrics.base_fee_forecast = next_base_fee.and_then(|fee| {
+                        forecast_base_fee(fee, &h.gas_used_ratio, BASE_FEE_FORECAST_BLOCKS)
+                    });
 
                     let gas_used_ratios: Vec<f64> =
                         h.gas_used_ratio.iter().map(|r| r * 100.0).collect();
@@ -477,6 +2305,14 @@ impl MetricsCollector {
                         None
                     };
 
+                    if let Some(fee) = current_base_fee {
+                        self.metrics.gas_thresholds = self.gas_baseline.observe(
+                            units::to_gwei(fee),
+                            self.metrics.fee_spike_multiplier(),
+                            Instant::now(),
+                        );
+                    }
+
                     let block_count = h.gas_used_ratio.len() as u64;
                     let mut reward_perc: Vec<(u8, Vec<u128>)> = Vec::new();
                     for (pi, pct) in FEE_HISTORY_PERCENTILES.iter().enumerate() {
@@ -509,6 +2345,7 @@ impl MetricsCollector {
                         .or_else(|| pick_priority(10.0))
                         .or_else(|| pick_priority(50.0));
                     let prio_std = pick_priority(50.0).or(prio_safe);
+                    median_tip = prio_std;
                     let prio_fast = pick_priority(75.0)
                         .or_else(|| pick_priority(90.0))
                         .or(prio_std);
@@ -531,30 +2368,139 @@ impl MetricsCollector {
                         fast: mk(prio_fast),
                     });
 
-                    self.metrics.fee_history = Some(FeeHistoryMetrics {
+                    let fee_history = FeeHistoryMetrics {
                         oldest_block: hex_to_u64(&h.oldest_block).unwrap_or(0),
                         block_count,
                         base_fees,
                         gas_used_ratios,
                         reward_percentiles: reward_perc,
-                    });
+                    };
+                    for block in self.metrics.block_history.iter_mut() {
+                        if let Some(tips) = estimate_tip_revenue(block, &fee_history) {
+                            block.tip_revenue_wei = Some(tips);
+                        }
+                    }
+                    self.metrics.fee_history = Some(fee_history);
+                }
+                _ => {
+                    self.clear_fee_metrics();
+                    self.metrics.next_base_fee_per_gas = projected_base_fees.first().copied();
                 }
-                Err(_) => self.clear_fee_metrics(),
             }
+            self.metrics.projected_base_fees = projected_base_fees;
+            self.metrics.target_deviation = target_deviation(&self.metrics.block_history);
+
+            self.metrics.max_priority_fee_suggested =
+                suggested_priority_fee(priority_fee_result.as_ref(), median_tip);
+        }
+
+        self.query_gas_oracles().await;
+        self.finish_cycle(status, started, throttled).await;
+        &self.metrics
+    }
 
-            match priority_fee_result {
-                Ok(p) => self.metrics.max_priority_fee_suggested = Some(p),
-                Err(_) => self.metrics.max_priority_fee_suggested = None,
+    /// Ask every --gas-oracle for its tiers, at most once per `GAS_ORACLE_INTERVAL`.
+    async fn query_gas_oracles(&mut self) {
+        if self.gas_oracles.is_empty()
+            || self
+                .gas_oracles_checked
+                .is_some_and(|at| at.elapsed() < GAS_ORACLE_INTERVAL)
+        {
+            return;
+        }
+        self.gas_oracles_checked = Some(Instant::now());
+        let quotes = join_all(self.gas_oracles.iter().map(GasOracle::quote)).await;
+        for (reading, quote) in self.metrics.gas_oracles.iter_mut().zip(quotes) {
+            match quote {
+                Ok(quote) => {
+                    reading.quote = Some(quote);
+                    reading.error = None;
+                }
+                Err(e) => reading.error = Some(e),
             }
         }
+    }
+
+    /// Publish the cycle's status, update availability/latency/backoff, and refresh the tx-pool.
+    async fn finish_cycle(&mut self, status: ConnectionStatus, started: Instant, throttled: bool) {
+        let throttled = throttled || matches!(status, ConnectionStatus::RateLimited(_));
+        self.metrics.poll_backoff = if throttled {
+            (self.metrics.poll_backoff * 2).min(MAX_POLL_BACKOFF)
+        } else {
+            (self.metrics.poll_backoff / 2).max(1)
+        };
 
         self.metrics.connection_status = status;
         self.metrics.last_updated = Instant::now();
-        if matches!(self.metrics.connection_status, ConnectionStatus::Connected) {
+        self.metrics.collecting_since = None;
+        let outage_cause = match &self.metrics.connection_status {
+            ConnectionStatus::Connected => None,
+            ConnectionStatus::Stale => Some("stale".to_string()),
+            ConnectionStatus::Disconnected => Some("disconnected".to_string()),
+            ConnectionStatus::RateLimited(e) => Some(format!("rate limited: {}", e)),
+            ConnectionStatus::Error(e) => Some(e.clone()),
+        };
+        if outage_cause.is_none() {
             self.metrics.last_successful = Some(self.metrics.last_updated);
         }
+        self.diagnose_connection().await;
+        self.metrics
+            .availability
+            .observe(outage_cause.as_deref(), self.metrics.last_updated);
+        self.metrics.record_latency(started.elapsed());
         self.collect_txpool_metrics().await;
-        &self.metrics
+        self.count_traffic();
+    }
+
+    fn count_traffic(&mut self) {
+        let total = [
+            Some(self.client.traffic()),
+            self.tx_client.as_ref().map(TxPoolClient::traffic),
+            self.compare.as_ref().map(|(client, _)| client.traffic()),
+            self.beacon.as_ref().map(BeaconClient::traffic),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.gas_oracles.iter().map(GasOracle::traffic))
+        .fold(Traffic::default(), |sum, traffic| sum + traffic);
+        self.metrics.cycle_traffic = total.since(self.metrics.traffic);
+        self.metrics.traffic = total;
+    }
+
+    /// While the RPC errors, find which layer fails: DNS, TCP connect, or TLS. Reruns at most
+    /// every `DIAGNOSE_INTERVAL_SECS`; cleared once the endpoint answers again.
+    async fn diagnose_connection(&mut self) {
+        match self.metrics.connection_status {
+            ConnectionStatus::Error(_) => {}
+            ConnectionStatus::Connected => {
+                self.metrics.diagnosis = None;
+                return;
+            }
+            _ => return,
+        }
+        let interval = Duration::from_secs(DIAGNOSE_INTERVAL_SECS);
+        if self
+            .metrics
+            .diagnosis
+            .as_ref()
+            .is_some_and(|diagnosis| diagnosis.at.elapsed() < interval)
+        {
+            return;
+        }
+        let http = &self.config.http;
+        // Behind a proxy the RPC host is out of reach anyway; check the hop we can see
+        let target = http.proxy.as_ref().unwrap_or(&self.config.rpc_url);
+        self.metrics.diagnosis = Some(diagnose::diagnose(target, http).await);
+    }
+
+    /// Flag a collection as in flight, so the snapshot shown meanwhile says so.
+    pub fn mark_collecting(&mut self) {
+        self.metrics.collecting_since = Some(Instant::now());
+    }
+
+    /// Refresh interval for this collector after applying any rate-limit backoff.
+    pub fn poll_interval(&self, base: Duration) -> Duration {
+        base * self.metrics.poll_backoff
     }
 
     pub fn get_metrics(&self) -> &SignetMetrics {
@@ -562,19 +2508,24 @@ impl MetricsCollector {
     }
 
     pub fn check_staleness(&mut self) {
-        if matches!(
-            self.metrics.connection_status,
-            ConnectionStatus::Connected | ConnectionStatus::Stale
-        ) && let Some(last_ok) = self.metrics.last_successful
-            && last_ok.elapsed() > STALE_AFTER
-        {
-            self.metrics.connection_status = ConnectionStatus::Stale;
-        }
+        self.metrics.check_staleness();
+    }
+
+    pub fn retain(&mut self, limits: &Limits) {
+        self.metrics.retain(limits);
+    }
+
+    pub fn set_sequencer_lag(&mut self, lag: Option<SequencerLag>) {
+        self.metrics.sequencer_lag = lag;
     }
 
     fn clear_fee_metrics(&mut self) {
+        self.metrics.pending_block = None;
         self.metrics.base_fee_per_gas = None;
         self.metrics.next_base_fee_per_gas = None;
+        self.metrics.projected_base_fees.clear();
+        self.metrics.target_deviation = None;
+        self.metrics.base_fee_forecast = None;
         self.metrics.max_priority_fee_suggested = None;
         self.metrics.suggested_fees = None;
         self.metrics.fee_history = None;
@@ -592,9 +2543,113 @@ impl MetricsCollector {
         }
     }
 
-    pub fn toggle_host_contract_filter(&mut self) {
-        self.txpool_watch_signet_host_contracts = !self.txpool_watch_signet_host_contracts;
-        self.metrics.host_contract_filter_active = self.txpool_watch_signet_host_contracts;
+    pub fn set_probes(&mut self, probes: Vec<Probe>, slots: Vec<SlotWatch>) {
+        self.probes = probes;
+        self.metrics.slots = slots.iter().cloned().map(SlotReading::new).collect();
+        self.slots = slots;
+    }
+
+    /// Cross-check this chain against a second endpoint from now on.
+    pub fn set_compare_rpc(&mut self, rpc_url: String, max_lag: u64) -> Result<()> {
+        self.compare = Some((
+            SignetRpcClient::new(rpc_url.clone(), &self.config.http)?,
+            max_lag,
+        ));
+        self.metrics.comparison = Some(EndpointComparison::new(rpc_url, max_lag));
+        Ok(())
+    }
+
+    pub fn set_verified_accounts(&mut self, accounts: Vec<Address>) {
+        self.verified_accounts = accounts;
+    }
+
+    /// Fetch blob sidecars for new blocks from a beacon API from now on.
+    pub fn set_beacon_url(&mut self, url: String) -> Result<()> {
+        let beacon = BeaconClient::new(url, &self.config.http)?;
+        self.metrics.beacon = Some(beacon_status(&beacon));
+        self.beacon = Some(beacon);
+        Ok(())
+    }
+
+    /// Query these external gas oracles alongside the fee-history tiers from now on.
+    pub fn set_gas_oracles(&mut self, urls: Vec<String>) -> Result<()> {
+        self.gas_oracles = urls
+            .into_iter()
+            .map(|url| GasOracle::new(url, &self.config.http))
+            .collect::<Result<_>>()?;
+        self.metrics.gas_oracles = self.gas_oracles.iter().map(GasOracle::reading).collect();
+        self.gas_oracles_checked = None;
+        Ok(())
+    }
+
+    pub fn set_trace_blocks(&mut self, enabled: bool) {
+        self.trace_blocks = enabled;
+    }
+
+    /// Check `watchlist` from the next cycle on, keeping readings of entries it still has.
+    pub fn set_watchlist(&mut self, watchlist: Watchlist) {
+        self.metrics.watchlist = watchlist.readings(&self.metrics.watchlist);
+        self.watchlist = watchlist;
+        self.watchlist_checked = None;
+    }
+
+    pub fn set_jitter_limit(&mut self, limit: Option<f64>) {
+        self.jitter_limit = limit;
+        self.metrics.jitter_limit = limit;
+    }
+
+    pub fn set_block_size_limit(&mut self, limit: Option<u64>) {
+        self.block_size_limit = limit;
+        self.metrics.block_size_limit = limit;
+    }
+
+    pub fn thresholds(&self) -> ChainThresholds {
+        ChainThresholds {
+            block_delay: self.config.block_delay,
+            expected_block_time: self.config.expected_block_time,
+            stale_after: self.config.stale_after,
+            gas_alert_gwei: self.config.gas_alert_gwei,
+            gas_spike_multiplier: self.config.gas_spike_multiplier,
+            large_transfer_wei: self.metrics.large_transfer_wei,
+        }
+    }
+
+    /// Apply new thresholds, e.g. from a reloaded config file, keeping everything collected
+    /// so far, including an observed gas baseline.
+    pub fn set_thresholds(&mut self, thresholds: ChainThresholds) {
+        self.config.block_delay = thresholds.block_delay;
+        self.config.expected_block_time = thresholds.expected_block_time;
+        self.config.stale_after = thresholds.stale_after;
+        self.config.gas_alert_gwei = thresholds.gas_alert_gwei;
+        self.config.gas_spike_multiplier = thresholds.gas_spike_multiplier;
+        self.gas_baseline
+            .reconfigure(thresholds.gas_alert_gwei, thresholds.gas_spike_multiplier);
+        self.metrics.set_thresholds(&thresholds);
+        self.metrics.gas_thresholds = self.gas_baseline.thresholds();
+    }
+
+    pub fn set_slo(&mut self, slo: Option<Slo>) {
+        self.slo = slo;
+        self.metrics.slo = slo;
+    }
+
+    pub fn set_forks(&mut self, forks: Vec<Fork>) {
+        self.metrics.forks = forks.clone();
+        self.forks = forks;
+    }
+
+    /// Use `labels` from now on and re-attribute the blocks already in history.
+    pub fn set_builder_labels(&mut self, labels: BuilderLabels) {
+        for block in self.metrics.block_history.iter_mut() {
+            block.builder = labels.attribute(block.fee_recipient, &block.extra_data);
+        }
+        self.builder_labels = labels;
+        self.refresh_block_stats();
+    }
+
+    pub fn set_host_contract_filter(&mut self, active: bool) {
+        self.txpool_watch_signet_host_contracts = active;
+        self.metrics.host_contract_filter_active = active;
 
         if let Some(client) = &mut self.tx_client {
             if self.txpool_watch_signet_host_contracts {
@@ -607,10 +2662,65 @@ impl MetricsCollector {
         }
     }
 
+    /// Apply tx-pool stream messages received since the last call, so pushed
+    /// transactions, bundles, and orders appear before the next poll. `false` if none came.
+    pub fn drain_txpool_stream(&mut self) -> bool {
+        let Some(client) = &mut self.tx_client else {
+            return false;
+        };
+        let Some(stream) = &mut client.stream else {
+            return false;
+        };
+        let messages = stream.drain();
+        let received = !messages.is_empty();
+        let metrics = &mut self.metrics;
+        let status = metrics
+            .txpool_stream
+            .get_or_insert_with(StreamStatus::default);
+        for message in messages {
+            match message {
+                StreamMessage::Connected => {
+                    status.connected = true;
+                    status.error = None;
+                }
+                StreamMessage::Disconnected(reason) => {
+                    status.connected = false;
+                    status.error = Some(reason);
+                }
+                StreamMessage::Event(kind, payload) => {
+                    status.events += 1;
+                    status.last_event = Some(Instant::now());
+                    if kind == StreamKind::Bundle {
+                        metrics.bundle_sims.observe(&bundles::parse_bundle_statuses(
+                            &serde_json::json!([payload]),
+                        ));
+                    }
+                    if let Some(txpool) = &mut metrics.txpool {
+                        client.apply_stream_event(txpool, kind, payload);
+                    }
+                }
+            }
+        }
+        received
+    }
+
     async fn collect_txpool_metrics(&mut self) {
         if let Some(client) = &self.tx_client {
             match client.fetch_metrics().await {
-                Ok(txm) => self.metrics.txpool = Some(txm),
+                Ok(txm) => {
+                    self.metrics.bundle_sims.observe(&txm.bundle_statuses);
+                    let now = unix_now();
+                    for (metric, cache) in [
+                        (Metric::PoolTransactions, &txm.transactions_cache),
+                        (Metric::PoolBundles, &txm.bundles_cache),
+                        (Metric::PoolOrders, &txm.signed_orders_cache),
+                    ] {
+                        if let Some(count) = cache.count() {
+                            self.metrics.timeseries.record(metric, now, count as f64);
+                        }
+                    }
+                    self.metrics.txpool = Some(txm);
+                }
                 Err(e) => {
                     self.metrics.txpool = Some(TxPoolMetrics::with_error(
                         client.base_url.clone(),
@@ -668,15 +2778,43 @@ impl TxPoolTx {
     }
 }
 
+/// Items a tx-pool listing returned and, when the service reports it, how many it holds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheCount {
+    pub visible: Option<u64>, // items in the response
+    pub total: Option<u64>,   // from a total field or header
+    pub more: bool,           // a next-page cursor came back
+}
+
+impl CacheCount {
+    /// The reported total, else the items seen.
+    pub fn count(&self) -> Option<u64> {
+        self.total.or(self.visible)
+    }
+
+    pub fn is_known(&self) -> bool {
+        self.count().is_some()
+    }
+
+    /// Count an item pushed by the event stream.
+    fn bump(&mut self) {
+        self.visible = Some(self.visible.unwrap_or(0) + 1);
+        self.total = self.total.map(|total| total + 1);
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TxPoolMetrics {
     pub healthy: bool,
     pub last_updated: Instant,
     pub error: Option<String>,
     pub base_url: String,
-    pub transactions_cache: Option<u64>,
-    pub bundles_cache: Option<u64>,
-    pub signed_orders_cache: Option<u64>,
+    pub transactions_cache: CacheCount,
+    pub bundles_cache: CacheCount,
+    pub bundle_statuses: Vec<(String, BundleSimStatus)>, // bundle id, simulation outcome
+    pub signed_orders_cache: CacheCount,
+    pub orders_pending: Option<u64>, // before their deadline
+    pub orders_expired: Option<u64>, // past their deadline, not yet evicted
     pub transactions: VecDeque<TxPoolTx>,
     pub has_more: bool,
 }
@@ -688,9 +2826,12 @@ impl TxPoolMetrics {
             last_updated: Instant::now(),
             error: None,
             base_url,
-            transactions_cache: None,
-            bundles_cache: None,
-            signed_orders_cache: None,
+            transactions_cache: CacheCount::default(),
+            bundles_cache: CacheCount::default(),
+            bundle_statuses: Vec::new(),
+            signed_orders_cache: CacheCount::default(),
+            orders_pending: None,
+            orders_expired: None,
             transactions: VecDeque::new(),
             has_more: false,
         }
@@ -702,9 +2843,12 @@ impl TxPoolMetrics {
             last_updated: Instant::now(),
             error: Some(err),
             base_url,
-            transactions_cache: None,
-            bundles_cache: None,
-            signed_orders_cache: None,
+            transactions_cache: CacheCount::default(),
+            bundles_cache: CacheCount::default(),
+            bundle_statuses: Vec::new(),
+            signed_orders_cache: CacheCount::default(),
+            orders_pending: None,
+            orders_expired: None,
             transactions: VecDeque::new(),
             has_more: false,
         }
@@ -718,20 +2862,32 @@ pub struct TxPoolClient {
     max_rows: usize,
     fetch_list: bool,
     filter_contracts: Option<HashSet<Address>>, // restrict to specific contract calls when set
+    stream: Option<TxPoolStream>,
+    options: HttpOptions, // for the stream client, built on subscribe
+    traffic: ByteCounter,
 }
 
 impl TxPoolClient {
-    pub fn new(base_url: String, max_rows: usize, fetch_list: bool) -> Result<Self> {
-        let tx_cache = TxCache::new_from_string(&base_url)
-            .map_err(|e| eyre::eyre!("invalid tx-pool url '{}': {}", base_url, e))?;
-        let http = reqwest::Client::builder()
+    pub fn new(
+        base_url: String,
+        max_rows: usize,
+        fetch_list: bool,
+        options: HttpOptions,
+    ) -> Result<Self> {
+        let url = Url::parse(&base_url).map_err(|e| {
+            FutureSightError::Config(format!("invalid tx-pool url '{}': {}", base_url, e))
+        })?;
+        let tx_cache = TxCache::new_with_client(url, options.alloy_client()?);
+        let http = options
+            .client_builder()?
             .timeout(Duration::from_secs(TXPOOL_TIMEOUT_SECS))
             .user_agent(format!(
                 "{}/{}",
                 env!("CARGO_PKG_NAME"),
                 env!("CARGO_PKG_VERSION")
             ))
-            .build()?;
+            .build()
+            .map_err(|e| FutureSightError::Config(e.to_string()))?;
 
         Ok(Self {
             base_url,
@@ -740,9 +2896,67 @@ impl TxPoolClient {
             max_rows: max_rows.max(1),
             fetch_list,
             filter_contracts: None,
+            stream: None,
+            options,
+            traffic: ByteCounter::default(),
         })
     }
 
+    /// Bytes moved by polling; orders go through the tx cache and the event stream isn't
+    /// metered.
+    pub fn traffic(&self) -> Traffic {
+        self.traffic.totals()
+    }
+
+    /// Subscribe to the service's event stream at `path` (relative to the base URL, or a
+    /// full URL); polling carries on to reconcile counts.
+    pub fn subscribe(&mut self, path: &str) -> Result<()> {
+        let url = if path.starts_with("http://") || path.starts_with("https://") {
+            path.to_string()
+        } else {
+            self.join_url(path)
+        };
+        self.stream = Some(TxPoolStream::spawn(url, &self.options)?);
+        Ok(())
+    }
+
+    /// Count a pushed item and list pushed transactions that pass the contract filter.
+    fn apply_stream_event(
+        &self,
+        txpool: &mut TxPoolMetrics,
+        kind: StreamKind,
+        payload: serde_json::Value,
+    ) {
+        match kind {
+            StreamKind::Transaction => {
+                txpool.transactions_cache.bump();
+                let Some(tx) = serde_json::from_value(payload)
+                    .ok()
+                    .and_then(|envelope| TxPoolTx::from_envelope(&envelope))
+                else {
+                    return;
+                };
+                let watched = match (&self.filter_contracts, &tx.to) {
+                    (None, _) => true,
+                    (Some(filter), Some(to)) => filter.contains(to),
+                    (Some(_), None) => false,
+                };
+                if self.fetch_list
+                    && watched
+                    && !txpool.transactions.iter().any(|seen| seen.hash == tx.hash)
+                {
+                    txpool.transactions.push_front(tx);
+                    txpool.transactions.truncate(self.max_rows);
+                }
+            }
+            StreamKind::Bundle => txpool.bundles_cache.bump(),
+            StreamKind::Order => {
+                txpool.signed_orders_cache.bump();
+                txpool.orders_pending = Some(txpool.orders_pending.unwrap_or(0) + 1);
+            }
+        }
+    }
+
     pub fn set_filter_contracts<I>(&mut self, filter_contracts: I)
     where
         I: IntoIterator<Item = Address>,
@@ -773,7 +2987,7 @@ impl TxPoolClient {
             .tx_cache
             .get_transactions(None)
             .await
-            .map_err(|e| eyre::eyre!("{}", e))?;
+            .map_err(|e| FutureSightError::TxPool(e.to_string()))?;
         let has_more = resp.has_more();
         let inner = resp.into_inner();
         let mut out: Vec<TxPoolTx> = inner
@@ -797,33 +3011,39 @@ impl TxPoolClient {
         Ok((out, has_more))
     }
 
-    async fn fetch_count_from(&self, path: &str) -> Result<Option<u64>> {
+    async fn fetch_count_from(&self, path: &str) -> Result<CacheCount> {
+        Ok(self
+            .fetch_listing(path)
+            .await?
+            .map(|(_, count)| count)
+            .unwrap_or_default())
+    }
+
+    /// The listing's JSON and item count; `None` when the service answers with an error
+    /// status or a non-JSON body.
+    async fn fetch_listing(&self, path: &str) -> Result<Option<(serde_json::Value, CacheCount)>> {
         let url = self.join_url(path);
-        let resp = self.http.get(&url).send().await?;
+        let resp = http::fetch(self.http.get(&url), 0, &self.traffic)
+            .await
+            .map_err(FutureSightError::TxPool)?;
 
-        if !resp.status().is_success() {
+        if !resp.status.is_success() {
             return Ok(None);
         }
-        let body = resp.text().await?;
-        let json: serde_json::Value = match serde_json::from_str(&body) {
-            Ok(v) => v,
-            Err(_) => return Ok(None),
-        };
-        Ok(count_items(&json))
+        let total = header_total(&resp.headers);
+        Ok(serde_json::from_slice(&resp.body).ok().map(|json| {
+            let count = count_items(&json, total);
+            (json, count)
+        }))
     }
 
     pub async fn fetch_metrics(&self) -> Result<TxPoolMetrics> {
         let mut out = TxPoolMetrics::new(self.base_url.clone());
         out.last_updated = Instant::now();
 
-        let (
-            transactions_count_result,
-            bundles_count_result,
-            orders_result,
-            transaction_list_result,
-        ) = tokio::join!(
+        let (transactions_count_result, bundles_result, orders_result, transaction_list_result) = tokio::join!(
             self.fetch_count_from("/transactions"),
-            self.fetch_count_from("/bundles"),
+            self.fetch_listing("/bundles"),
             self.tx_cache.get_orders(None),
             self.fetch_transactions(),
         );
@@ -835,13 +3055,32 @@ impl TxPoolClient {
             Err(e) => errors.push(format!("transactions: {}", e)),
         }
 
-        match bundles_count_result {
-            Ok(value) => out.bundles_cache = value,
+        match bundles_result {
+            Ok(Some((json, count))) => {
+                out.bundles_cache = count;
+                out.bundle_statuses = bundles::parse_bundle_statuses(&json);
+            }
+            Ok(None) => {}
             Err(e) => errors.push(format!("bundles: {}", e)),
         }
 
         match orders_result {
-            Ok(resp) => out.signed_orders_cache = Some(resp.into_inner().orders.len() as u64),
+            Ok(resp) => {
+                let more = resp.has_more();
+                let orders = resp.into_inner().orders;
+                let now = unix_now();
+                let pending = orders
+                    .iter()
+                    .filter(|order| order.validate(now).is_ok())
+                    .count() as u64;
+                out.signed_orders_cache = CacheCount {
+                    visible: Some(orders.len() as u64),
+                    total: None,
+                    more,
+                };
+                out.orders_pending = Some(pending);
+                out.orders_expired = Some(orders.len() as u64 - pending);
+            }
             Err(e) => errors.push(format!("orders: {}", e)),
         }
 
@@ -856,9 +3095,9 @@ impl TxPoolClient {
         if errors.is_empty() {
             out.healthy = true;
         } else {
-            out.healthy = out.transactions_cache.is_some()
-                || out.bundles_cache.is_some()
-                || out.signed_orders_cache.is_some()
+            out.healthy = out.transactions_cache.is_known()
+                || out.bundles_cache.is_known()
+                || out.signed_orders_cache.is_known()
                 || !out.transactions.is_empty();
             out.error = Some(errors.join(", "));
         }
@@ -867,34 +3106,54 @@ impl TxPoolClient {
     }
 }
 
-fn count_items(v: &serde_json::Value) -> Option<u64> {
-    // Accept common container shapes
-    if let Some(arr) = v.as_array() {
-        return Some(arr.len() as u64);
-    }
-    if let Some(obj) = v.as_object() {
-        // Look for "items", "data", or pluralized keys
-        for key in [
+/// Items in a listing response (a bare array, or an array under a common key) and the total
+/// the service reports in the body (`total`, `count`, `meta.total`, ...) or, failing that,
+/// in a pagination header.
+fn count_items(v: &serde_json::Value, header_total: Option<u64>) -> CacheCount {
+    let array = v.as_array().or_else(|| {
+        [
             "items",
             "data",
             "transactions",
             "bundles",
+            "orders",
             "signedOrders",
             "signed_orders",
             "results",
-        ] {
-            if let Some(arr) = obj.get(key).and_then(|x| x.as_array()) {
-                return Some(arr.len() as u64);
-            }
-        }
-
-        for key in ["count", "total", "totalCount", "total_count"] {
-            if let Some(value) = obj.get(key).and_then(|x| x.as_u64()) {
-                return Some(value);
-            }
-        }
+        ]
+        .iter()
+        .find_map(|key| v.get(key)?.as_array())
+    });
+    let total_in = |obj: &serde_json::Value| {
+        ["total", "count", "totalCount", "total_count"]
+            .iter()
+            .find_map(|key| obj.get(key)?.as_u64())
+    };
+    let body_total = total_in(v).or_else(|| {
+        ["meta", "pagination", "page"]
+            .iter()
+            .find_map(|key| total_in(v.get(key)?))
+    });
+    let more = ["nextCursor", "next_cursor", "next"]
+        .iter()
+        .any(|key| v.get(key).is_some_and(|next| !next.is_null()));
+    CacheCount {
+        visible: array.map(|items| items.len() as u64),
+        total: body_total.or(header_total),
+        more,
     }
-    None
+}
+
+/// `X-Total-Count`-style pagination header.
+fn header_total(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+    ["x-total-count", "x-total", "total-count"]
+        .iter()
+        .find_map(|name| headers.get(*name)?.to_str().ok()?.trim().parse().ok())
+}
+
+/// Whether an optional call was throttled, so polling should back off.
+fn is_rate_limited<T>(result: &Option<Result<T>>) -> bool {
+    matches!(result, Some(Err(e)) if e.is_rate_limited())
 }
 
 fn host_watch_contracts_for_chain_id(chain_id: u64) -> Vec<Address> {
@@ -910,16 +3169,152 @@ fn host_watch_contracts_for_chain_id(chain_id: u64) -> Vec<Address> {
         .unwrap_or_default()
 }
 
-fn signet_constants_for_host_chain_id(chain_id: u64) -> Option<SignetSystemConstants> {
-    for constants in [
+/// The host or rollup orders contract, whichever side of a known Signet network `chain_id` is.
+fn orders_contract_for_chain_id(chain_id: u64) -> Option<Address> {
+    [
         SignetSystemConstants::parmigiana(),
         SignetSystemConstants::mainnet(),
-    ] {
+    ]
+    .into_iter()
+    .find_map(|constants| {
         if constants.host_chain_id() == chain_id {
-            return Some(constants);
+            Some(constants.host_orders())
+        } else if constants.ru_chain_id() == chain_id {
+            Some(constants.ru_orders())
+        } else {
+            None
+        }
+    })
+}
+
+/// A fresh status for `beacon`, before any sidecars are fetched.
+fn beacon_status(beacon: &BeaconClient) -> BeaconStatus {
+    BeaconStatus {
+        url: beacon.url().to_string(),
+        blocks: 0,
+        error: None,
+    }
+}
+
+fn signet_constants_for_host_chain_id(chain_id: u64) -> Option<SignetSystemConstants> {
+    [
+        SignetSystemConstants::parmigiana(),
+        SignetSystemConstants::mainnet(),
+    ]
+    .into_iter()
+    .find(|constants| constants.host_chain_id() == chain_id)
+}
+
+/// The node's `eth_maxPriorityFeePerGas` answer, or the fee-history median tip when the
+/// endpoint has no answer. A zero tip from either source is a real reading and is kept.
+fn suggested_priority_fee(
+    node: Option<&Result<u128>>,
+    fee_history_median: Option<u128>,
+) -> Option<u128> {
+    match node {
+        Some(Ok(tip)) => Some(*tip),
+        _ => fee_history_median,
+    }
+}
+
+/// Proposer tip revenue for `block`, approximated as the fee-history median reward times
+/// gas used. `None` when the block falls outside the fee-history window.
+fn estimate_tip_revenue(block: &BlockInfo, history: &FeeHistoryMetrics) -> Option<u128> {
+    let index = block.number.checked_sub(history.oldest_block)? as usize;
+    let (_, median) = history
+        .reward_percentiles
+        .iter()
+        .find(|(pct, _)| *pct == 50)?;
+    let tip = median.get(index)?;
+    Some(tip.saturating_mul(block.gas_used as u128))
+}
+
+/// Burn over the block history (newest first) and the hourly rate it implies.
+/// The oldest block only anchors the time span; its burn happened before it.
+fn burn_stats(history: &VecDeque<BlockInfo>) -> BurnStats {
+    let window_wei = history
+        .iter()
+        .filter_map(BlockInfo::burned_wei)
+        .fold(0u128, u128::saturating_add);
+    let rate_wei_per_hour = match (history.front(), history.back()) {
+        (Some(newest), Some(oldest)) if newest.timestamp > oldest.timestamp => {
+            let burned = window_wei - oldest.burned_wei().unwrap_or(0);
+            let span = (newest.timestamp - oldest.timestamp) as f64;
+            Some(burned as f64 * 3600.0 / span)
         }
+        _ => None,
+    };
+    BurnStats {
+        window_wei,
+        rate_wei_per_hour,
+        session_wei: 0,
+    }
+}
+
+/// Jitter of the intervals between adjacent blocks in `history` (newest first), skipping
+/// gaps left by unfetched blocks. `None` below [`JITTER_MIN_INTERVALS`] intervals.
+fn block_jitter(history: &VecDeque<BlockInfo>) -> Option<BlockJitter> {
+    let intervals: Vec<f64> = history
+        .iter()
+        .zip(history.iter().skip(1))
+        .filter(|(newer, older)| newer.number == older.number + 1)
+        .map(|(newer, older)| newer.timestamp.saturating_sub(older.timestamp) as f64)
+        .collect();
+    if intervals.len() < JITTER_MIN_INTERVALS {
+        return None;
     }
-    None
+    let mean = intervals.iter().sum::<f64>() / intervals.len() as f64;
+    let variance = intervals
+        .iter()
+        .map(|interval| (interval - mean).powi(2))
+        .sum::<f64>()
+        / intervals.len() as f64;
+    Some(BlockJitter {
+        mean,
+        stddev: variance.sqrt(),
+        max_deviation: intervals
+            .iter()
+            .map(|interval| (interval - mean).abs())
+            .fold(0.0, f64::max),
+        intervals: intervals.len(),
+    })
+}
+
+/// The gas limit change between `block` and its parent or child in `history`, if any.
+/// Backfill admits blocks oldest-last, so the child may already be there.
+fn gas_limit_change(history: &VecDeque<BlockInfo>, block: &BlockInfo) -> Option<GasLimitChange> {
+    let (parent, child) = history.iter().find_map(|other| {
+        if other.hash == block.parent_hash && other.number + 1 == block.number {
+            Some((other, block))
+        } else if other.parent_hash == block.hash && other.number == block.number + 1 {
+            Some((block, other))
+        } else {
+            None
+        }
+    })?;
+    (parent.gas_limit > 0 && child.gas_limit > 0 && parent.gas_limit != child.gas_limit).then_some(
+        GasLimitChange {
+            block: child.number,
+            from: parent.gas_limit,
+            to: child.gas_limit,
+            at: child.timestamp,
+        },
+    )
+}
+
+/// Block numbers for a startup backfill of `count` blocks ending at `latest`, newest first,
+/// split into batches of `batch_size`.
+fn backfill_batches(latest: u64, count: usize, batch_size: usize) -> Vec<Vec<u64>> {
+    let oldest = latest.saturating_sub((count as u64).saturating_sub(1));
+    let numbers: Vec<u64> = if count == 0 {
+        Vec::new()
+    } else {
+        (oldest..=latest).rev().collect()
+    };
+    numbers
+        .chunks(batch_size.max(1))
+        .map(<[u64]>::to_vec)
+        .collect()
 }
 
 enum BlockFetchPlan {
@@ -975,18 +3370,88 @@ fn block_fetch_plan(
 #[cfg(test)]
 mod tests {
     use super::{
-        BlockFetchPlan, BlockInfo, Config, SignetMetrics, block_fetch_plan, count_items,
-        host_watch_contracts_for_chain_id,
+        BlockFetchPlan, BlockInfo, Config, FeeDirection, FeeHistoryMetrics, ForecastConfidence,
+        GasUser, NodeInfo, PendingBlock, ReceiptSummary, SignetMetrics, backfill_batches,
+        block_fetch_plan, block_jitter, burn_stats, count_items, estimate_tip_revenue,
+        forecast_base_fee, gas_limit_change, host_watch_contracts_for_chain_id, next_base_fee,
+        parse_pending_block, project_base_fees, suggested_priority_fee, summarize_receipts,
+        target_deviation,
     };
+    use super::{GAS_PER_BLOB, MetricsCollector, SignetRpcClient};
+    use crate::availability::Slo;
+    use crate::forks::{Activation, Fork};
+    use crate::probes::SlotWatch;
+    use crate::thresholds::{ChainThresholds, DelayThresholds};
+    use crate::tx_types::TxType;
+    use crate::watchlist::Watchlist;
+    use alloy::eips::eip4844::{BlobTransactionSidecarItem, Bytes48};
+    use alloy::primitives::{Address, B256};
     use serde_json::json;
     use signet_constants::SignetSystemConstants;
-    use std::collections::VecDeque;
+    use std::{collections::VecDeque, time::Duration};
+
+    fn config() -> Config {
+        Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            block_delay: DelayThresholds {
+                warning: 30,
+                critical: 60,
+            },
+            expected_block_time: None,
+            stale_after: Duration::from_secs(15),
+            gas_alert_gwei: None,
+            gas_spike_multiplier: None,
+            max_block_history: 8,
+            txpool_max_rows: 8,
+            txpool_fetch_list: false,
+            txpool_watch_signet_host_contracts: false,
+            txpool_stream: None,
+            fetch_receipts: false,
+            http: Default::default(),
+        }
+    }
+
+    /// Point `collector` at another local endpoint, as the endpoint picker does.
+    fn switch(collector: &mut MetricsCollector) {
+        let url = "http://localhost:8546".to_string();
+        let client = SignetRpcClient::new(url.clone(), &Default::default()).unwrap();
+        collector.switch_endpoint(client, url);
+    }
 
     #[test]
     fn count_items_supports_common_shapes() {
-        assert_eq!(count_items(&json!([1, 2, 3])), Some(3));
-        assert_eq!(count_items(&json!({"transactions": [1, 2]})), Some(2));
-        assert_eq!(count_items(&json!({"totalCount": 9})), Some(9));
+        assert_eq!(count_items(&json!([1, 2, 3]), None).count(), Some(3));
+        assert_eq!(
+            count_items(&json!({"transactions": [1, 2]}), None).count(),
+            Some(2)
+        );
+        assert_eq!(
+            count_items(&json!({"totalCount": 9}), None).count(),
+            Some(9)
+        );
+
+        // Totals win over the page actually returned
+        let paged = count_items(
+            &json!({"transactions": [1, 2], "meta": {"total": 40}, "nextCursor": {"id": 2}}),
+            None,
+        );
+        assert_eq!(paged.visible, Some(2));
+        assert_eq!(paged.count(), Some(40));
+        assert!(paged.more);
+        assert_eq!(count_items(&json!([1]), Some(7)).count(), Some(7));
+        assert!(!count_items(&json!({"next": null}), None).is_known());
+    }
+
+    #[test]
+    fn priority_fee_falls_back_only_when_missing() {
+        let unsupported = Err(crate::error::FutureSightError::rpc(
+            r#"{"code":-32601,"message":"method not found"}"#,
+        ));
+        assert_eq!(suggested_priority_fee(Some(&Ok(0)), Some(7)), Some(0));
+        assert_eq!(suggested_priority_fee(Some(&Ok(3)), Some(7)), Some(3));
+        assert_eq!(suggested_priority_fee(Some(&unsupported), Some(7)), Some(7));
+        assert_eq!(suggested_priority_fee(None, Some(0)), Some(0));
+        assert_eq!(suggested_priority_fee(None, None), None);
     }
 
     #[test]
@@ -1014,6 +3479,292 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn burn_rate_spans_the_history_window() {
+        let block = |number: u64, timestamp: u64, base_fee: Option<u128>| BlockInfo {
+            number,
+            hash: String::new(),
+            parent_hash: String::new(),
+            state_root: B256::ZERO,
+            timestamp,
+            tx_count: 0,
+            gas_used: 1_000_000,
+            gas_limit: 30_000_000,
+            blobs: vec![],
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
+            base_fee_per_gas: base_fee,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        };
+        // (1 + 2) gwei x 1M gas = 0.003 ETH; the oldest block's burn falls outside the 24s span
+        let history: VecDeque<BlockInfo> = VecDeque::from(vec![
+            block(3, 1_024, Some(1_000_000_000)),
+            block(2, 1_012, None),
+            block(1, 1_000, Some(2_000_000_000)),
+        ]);
+        let stats = burn_stats(&history);
+        assert_eq!(stats.window_wei, 3_000_000_000_000_000);
+        assert_eq!(stats.rate_wei_per_hour, Some(1e15 * 3600.0 / 24.0));
+        assert_eq!(
+            burn_stats(&history.iter().take(1).cloned().collect()).rate_wei_per_hour,
+            None
+        );
+    }
+
+    #[test]
+    fn block_jitter_skips_gaps_in_the_history() {
+        let block = |number: u64, timestamp: u64| BlockInfo {
+            number,
+            hash: String::new(),
+            parent_hash: String::new(),
+            state_root: B256::ZERO,
+            timestamp,
+            tx_count: 0,
+            gas_used: 0,
+            gas_limit: 30_000_000,
+            blobs: vec![],
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
+            base_fee_per_gas: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        };
+        // Intervals of 12, 12, 4 and 20 seconds; 103 was never fetched
+        let history: VecDeque<BlockInfo> = VecDeque::from(vec![
+            block(105, 1_100),
+            block(104, 1_088),
+            block(102, 1_060),
+            block(101, 1_056),
+            block(100, 1_036),
+            block(99, 1_024),
+        ]);
+        let jitter = block_jitter(&history).unwrap();
+        assert_eq!((jitter.mean, jitter.intervals), (12.0, 4));
+        assert_eq!(jitter.stddev, 32f64.sqrt());
+        assert_eq!(jitter.max_deviation, 8.0);
+        assert_eq!(
+            block_jitter(&history.iter().take(4).cloned().collect()),
+            None
+        );
+    }
+
+    #[test]
+    fn gas_limit_change_compares_linked_neighbours() {
+        let block = |number: u64, gas_limit: u64| BlockInfo {
+            number,
+            hash: format!("0x{number}"),
+            parent_hash: format!("0x{}", number - 1),
+            state_root: B256::ZERO,
+            timestamp: 1_000 + number * 12,
+            tx_count: 0,
+            gas_used: 0,
+            gas_limit,
+            blobs: vec![],
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
+            base_fee_per_gas: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        };
+        let history = VecDeque::from(vec![block(10, 30_000_000)]);
+        let change = gas_limit_change(&history, &block(11, 36_000_000)).unwrap();
+        assert_eq!(
+            (change.block, change.from, change.to),
+            (11, 30_000_000, 36_000_000)
+        );
+        assert_eq!(change.at, 1_132);
+        // A backfilled parent reports the change at its child
+        let history = VecDeque::from(vec![block(12, 36_000_000)]);
+        assert_eq!(
+            gas_limit_change(&history, &block(11, 30_000_000)).map(|c| c.block),
+            Some(12)
+        );
+        assert_eq!(gas_limit_change(&history, &block(11, 36_000_000)), None);
+        // Not linked by hash (a gap or another fork) is not a change
+        assert_eq!(gas_limit_change(&history, &block(10, 30_000_000)), None);
+    }
+
+    #[test]
+    fn tip_revenue_uses_the_median_reward_of_the_matching_block() {
+        let history = FeeHistoryMetrics {
+            oldest_block: 100,
+            block_count: 2,
+            base_fees: vec![],
+            gas_used_ratios: vec![],
+            reward_percentiles: vec![(25, vec![1, 1]), (50, vec![2_000_000_000, 3_000_000_000])],
+        };
+        let mut block = BlockInfo {
+            number: 101,
+            hash: String::new(),
+            parent_hash: String::new(),
+            state_root: B256::ZERO,
+            timestamp: 0,
+            tx_count: 0,
+            gas_used: 1_000_000,
+            gas_limit: 30_000_000,
+            blobs: vec![],
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
+            base_fee_per_gas: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        };
+        assert_eq!(
+            estimate_tip_revenue(&block, &history),
+            Some(3_000_000_000_000_000)
+        );
+        block.number = 99;
+        assert_eq!(estimate_tip_revenue(&block, &history), None);
+        block.number = 102;
+        assert_eq!(estimate_tip_revenue(&block, &history), None);
+    }
+
+    #[test]
+    fn backfill_batches_newest_first_and_stops_at_genesis() {
+        assert_eq!(
+            backfill_batches(100, 5, 2),
+            vec![vec![100, 99], vec![98, 97], vec![96]]
+        );
+        assert_eq!(backfill_batches(3, 10, 4), vec![vec![3, 2, 1, 0]]);
+        assert!(backfill_batches(100, 0, 4).is_empty());
+    }
+
+    #[test]
+    fn next_base_fee_follows_eip1559_adjustment() {
+        let base = 1_000_000_000;
+        assert_eq!(next_base_fee(base, 15_000_000, 30_000_000), base);
+        assert_eq!(next_base_fee(base, 30_000_000, 30_000_000), 1_125_000_000);
+        assert_eq!(next_base_fee(base, 0, 30_000_000), 875_000_000);
+        assert_eq!(next_base_fee(7, 15_000_001, 30_000_000), 8);
+    }
+
+    #[test]
+    fn base_fee_projection_compounds_sustained_utilization() {
+        let block = BlockInfo {
+            number: 1,
+            hash: String::new(),
+            parent_hash: String::new(),
+            state_root: B256::ZERO,
+            timestamp: 0,
+            tx_count: 0,
+            gas_used: 30_000_000,
+            gas_limit: 30_000_000,
+            blobs: Vec::new(),
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
+            base_fee_per_gas: Some(1_000_000_000),
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        };
+        assert_eq!(
+            project_base_fees(&block, 3),
+            vec![1_125_000_000, 1_265_625_000, 1_423_828_125]
+        );
+
+        let pre_london = BlockInfo {
+            base_fee_per_gas: None,
+            ..block
+        };
+        assert!(project_base_fees(&pre_london, 3).is_empty());
+    }
+
+    #[test]
+    fn forecast_follows_rising_utilization_trend() {
+        let base = 10_000_000_000;
+        let steady = [0.5; 12];
+        let flat = forecast_base_fee(base, &steady, 10).unwrap();
+        assert_eq!(flat.expected, base);
+        assert_eq!((flat.low, flat.high), (base, base));
+        assert_eq!(flat.confidence, ForecastConfidence::High);
+
+        let rising: Vec<f64> = (0..12).map(|i| 0.4 + i as f64 * 0.05).collect();
+        let forecast = forecast_base_fee(base, &rising, 10).unwrap();
+        assert!(forecast.expected > base);
+        assert!(forecast.low <= forecast.expected && forecast.expected <= forecast.high);
+
+        let noisy = [0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0];
+        assert_eq!(
+            forecast_base_fee(base, &noisy, 10).unwrap().confidence,
+            ForecastConfidence::Low
+        );
+        assert_eq!(forecast_base_fee(base, &steady[..3], 10), None);
+    }
+
+    #[test]
+    fn target_deviation_averages_the_newest_streak() {
+        let block = |number, gas_used| BlockInfo {
+            number,
+            hash: String::new(),
+            parent_hash: String::new(),
+            state_root: B256::ZERO,
+            timestamp: 0,
+            tx_count: 0,
+            gas_used,
+            gas_limit: 30_000_000,
+            blobs: Vec::new(),
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
+            base_fee_per_gas: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        };
+        let history = VecDeque::from(vec![
+            block(4, 21_000_000),
+            block(3, 30_000_000),
+            block(2, 10_000_000),
+            block(1, 30_000_000),
+        ]);
+
+        let deviation = target_deviation(&history).unwrap();
+        assert_eq!(deviation.blocks, 2);
+        assert_eq!(deviation.avg_pct, 70.0);
+        assert_eq!(deviation.direction, FeeDirection::Rising);
+
+        let falling = VecDeque::from(vec![block(2, 0)]);
+        assert_eq!(
+            target_deviation(&falling).unwrap().direction,
+            FeeDirection::Falling
+        );
+        assert_eq!(target_deviation(&VecDeque::new()), None);
+    }
+
     #[test]
     fn known_host_chains_use_signet_system_contract_watch_list() {
         let parmigiana = SignetSystemConstants::parmigiana();
@@ -1041,35 +3792,192 @@ mod tests {
         );
     }
 
+    #[test]
+    fn endpoint_switch_keeps_the_beacon_api() {
+        let mut collector = MetricsCollector::new(config()).unwrap();
+        collector
+            .set_beacon_url("http://localhost:5052".to_string())
+            .unwrap();
+        collector.blobs_checked = Some(7);
+        switch(&mut collector);
+        assert_eq!(collector.blobs_checked, None);
+
+        let block = BlockInfo {
+            number: 8,
+            hash: String::new(),
+            parent_hash: String::new(),
+            state_root: B256::ZERO,
+            timestamp: 1_000,
+            tx_count: 1,
+            gas_used: 21_000,
+            gas_limit: 30_000_000,
+            blobs: vec![],
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
+            base_fee_per_gas: None,
+            blob_gas_used: Some(GAS_PER_BLOB),
+            excess_blob_gas: None,
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        };
+        collector.metrics.block_history.insert(block);
+        let sidecar = BlobTransactionSidecarItem {
+            index: 0,
+            blob: Box::default(),
+            kzg_commitment: Bytes48::ZERO,
+            kzg_proof: Bytes48::ZERO,
+        };
+        collector.attach_sidecars(8, Ok(vec![sidecar]));
+
+        assert_eq!(collector.metrics.block_history[0].blobs.len(), 1);
+        let status = collector.metrics.beacon.as_ref().unwrap();
+        assert_eq!(status.url, "http://localhost:5052");
+        assert_eq!(status.blocks, 1);
+    }
+
+    #[test]
+    fn endpoint_switch_keeps_every_setting() {
+        let mut collector = MetricsCollector::new(config()).unwrap();
+        let slot = SlotWatch::parse("0x0000000000000000000000000000000000000001:0").unwrap();
+        collector.set_probes(Vec::new(), vec![slot.clone()]);
+        collector
+            .set_compare_rpc("http://localhost:8547".to_string(), 3)
+            .unwrap();
+        collector
+            .set_gas_oracles(vec!["http://localhost:9000".to_string()])
+            .unwrap();
+        collector.set_watchlist(Watchlist {
+            addresses: vec![Address::ZERO],
+            transactions: Vec::new(),
+            events: Vec::new(),
+        });
+        collector.set_jitter_limit(Some(2.5));
+        collector.set_block_size_limit(Some(1_000_000));
+        let slo = Slo {
+            target: 99.9,
+            window: Duration::from_secs(3_600),
+        };
+        collector.set_slo(Some(slo));
+        let fork = Fork {
+            name: "pecto".to_string(),
+            chain_id: 1,
+            activation: Activation::Block(100),
+        };
+        collector.set_forks(vec![fork.clone()]);
+        let thresholds = ChainThresholds {
+            large_transfer_wei: Some(10u128.pow(18)),
+            ..collector.thresholds()
+        };
+        collector.set_thresholds(thresholds);
+        collector.set_host_contract_filter(true);
+
+        switch(&mut collector);
+
+        let metrics = &collector.metrics;
+        assert_eq!(metrics.slots.len(), 1);
+        assert_eq!(metrics.slots[0].watch, slot);
+        let comparison = metrics.comparison.as_ref().unwrap();
+        assert_eq!(
+            (comparison.rpc_url.as_str(), comparison.max_lag),
+            ("http://localhost:8547", 3)
+        );
+        assert_eq!(metrics.gas_oracles.len(), 1);
+        assert_eq!(metrics.watchlist.accounts.len(), 1);
+        assert_eq!(metrics.jitter_limit, Some(2.5));
+        assert_eq!(metrics.block_size_limit, Some(1_000_000));
+        assert_eq!(metrics.slo, Some(slo));
+        assert_eq!(metrics.forks, [fork]);
+        assert_eq!(collector.thresholds(), thresholds);
+        assert!(metrics.host_contract_filter_active);
+    }
+
     #[test]
     fn unknown_host_chain_has_no_watched_contracts() {
         assert!(host_watch_contracts_for_chain_id(42).is_empty());
     }
 
+    #[test]
+    fn latency_percentile_uses_nearest_rank() {
+        let mut metrics = SignetMetrics::new(Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            block_delay: DelayThresholds {
+                warning: 30,
+                critical: 60,
+            },
+            expected_block_time: None,
+            stale_after: Duration::from_secs(15),
+            gas_alert_gwei: None,
+            gas_spike_multiplier: None,
+            max_block_history: 8,
+            txpool_max_rows: 8,
+            txpool_fetch_list: false,
+            txpool_watch_signet_host_contracts: false,
+            txpool_stream: None,
+            fetch_receipts: false,
+            http: Default::default(),
+        });
+        assert_eq!(metrics.latency_percentile(95.0), None);
+
+        for ms in 1..=20 {
+            metrics.record_latency(Duration::from_millis(ms * 10));
+        }
+        assert_eq!(
+            metrics.latency_percentile(95.0),
+            Some(Duration::from_millis(190))
+        );
+        assert_eq!(
+            metrics.latency_percentile(50.0),
+            Some(Duration::from_millis(100))
+        );
+    }
+
     #[test]
     fn chain_height_prefers_highest_observed_tip() {
         let mut metrics = SignetMetrics::new(Config {
             rpc_url: "http://localhost:8545".to_string(),
-            block_delay_threshold: 60,
+            block_delay: DelayThresholds {
+                warning: 30,
+                critical: 60,
+            },
+            expected_block_time: None,
+            stale_after: Duration::from_secs(15),
+            gas_alert_gwei: None,
+            gas_spike_multiplier: None,
             max_block_history: 8,
             txpool_max_rows: 8,
             txpool_fetch_list: false,
             txpool_watch_signet_host_contracts: false,
+            txpool_stream: None,
+            fetch_receipts: false,
+            http: Default::default(),
         });
         metrics.block_number = Some(100);
-        metrics.block_history = VecDeque::from(vec![BlockInfo {
+        metrics.block_history.insert(BlockInfo {
             number: 103,
             hash: String::new(),
             parent_hash: String::new(),
+            state_root: B256::ZERO,
             timestamp: 0,
             tx_count: 0,
             gas_used: 0,
             gas_limit: 0,
             blobs: Vec::new(),
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
             base_fee_per_gas: None,
             blob_gas_used: None,
             excess_blob_gas: None,
-        }]);
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        });
 
         assert_eq!(metrics.chain_height(), Some(103));
     }
@@ -1078,27 +3986,185 @@ mod tests {
     fn chain_height_uses_rpc_tip_when_history_is_missing_or_older() {
         let mut metrics = SignetMetrics::new(Config {
             rpc_url: "http://localhost:8545".to_string(),
-            block_delay_threshold: 60,
+            block_delay: DelayThresholds {
+                warning: 30,
+                critical: 60,
+            },
+            expected_block_time: None,
+            stale_after: Duration::from_secs(15),
+            gas_alert_gwei: None,
+            gas_spike_multiplier: None,
             max_block_history: 8,
             txpool_max_rows: 8,
             txpool_fetch_list: false,
             txpool_watch_signet_host_contracts: false,
+            txpool_stream: None,
+            fetch_receipts: false,
+            http: Default::default(),
         });
         metrics.block_number = Some(100);
-        metrics.block_history = VecDeque::from(vec![BlockInfo {
+        metrics.block_history.insert(BlockInfo {
             number: 99,
             hash: String::new(),
             parent_hash: String::new(),
+            state_root: B256::ZERO,
             timestamp: 0,
             tx_count: 0,
             gas_used: 0,
             gas_limit: 0,
             blobs: Vec::new(),
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
             base_fee_per_gas: None,
             blob_gas_used: None,
             excess_blob_gas: None,
-        }]);
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        });
 
         assert_eq!(metrics.chain_height(), Some(100));
+        assert_eq!(metrics.blocks_since_start(), None);
+        metrics.start_block = Some(90);
+        assert_eq!(metrics.blocks_since_start(), Some(10));
+    }
+
+    #[test]
+    fn blob_and_withdrawal_averages_skip_blocks_that_lack_them() {
+        let mut metrics = SignetMetrics::new(Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            block_delay: DelayThresholds {
+                warning: 30,
+                critical: 60,
+            },
+            expected_block_time: None,
+            stale_after: Duration::from_secs(15),
+            gas_alert_gwei: None,
+            gas_spike_multiplier: None,
+            max_block_history: 8,
+            txpool_max_rows: 8,
+            txpool_fetch_list: false,
+            txpool_watch_signet_host_contracts: false,
+            txpool_stream: None,
+            fetch_receipts: false,
+            http: Default::default(),
+        });
+        for (number, blob_gas_used, withdrawals_count) in [
+            (10, None, None),
+            (11, Some(3 * 131_072), Some(16)),
+            (12, Some(0), Some(15)),
+        ] {
+            metrics.block_history.insert(BlockInfo {
+                number,
+                hash: format!("h{}", number),
+                parent_hash: format!("h{}", number - 1),
+                state_root: B256::ZERO,
+                timestamp: 0,
+                tx_count: 0,
+                gas_used: 0,
+                gas_limit: 0,
+                blobs: Vec::new(),
+                fee_recipient: Address::ZERO,
+                extra_data: String::new(),
+                builder: String::new(),
+                tip_revenue_wei: None,
+                base_fee_per_gas: None,
+                blob_gas_used,
+                excess_blob_gas: None,
+                withdrawals_count,
+                size: None,
+                calldata_bytes: None,
+                receipts: None,
+            });
+        }
+
+        assert_eq!(metrics.block_history[1].blob_count(), Some(3));
+        assert_eq!(metrics.avg_per_block(BlockInfo::blob_count), Some((1.5, 2)));
+        assert_eq!(
+            metrics.avg_per_block(|block| block.withdrawals_count.map(|n| n as u64)),
+            Some((15.5, 2))
+        );
+    }
+
+    #[test]
+    fn receipts_are_summarized_by_outcome() {
+        let receipts = json!([
+            {"status": "0x1", "contractAddress": null, "effectiveGasPrice": "0x3b9aca00",
+             "to": "0x00000000000000000000000000000000000000aa", "gasUsed": "0x5208",
+             "type": "0x2"},
+            {"status": "0x0", "contractAddress": null, "effectiveGasPrice": "0x77359400",
+             "to": "0x00000000000000000000000000000000000000aa", "gasUsed": "0x5208",
+             "type": "0x3"},
+            {"status": "0x1", "contractAddress": "0x00000000000000000000000000000000000000bb",
+             "to": null, "gasUsed": "0x30d40", "type": "0x2"},
+        ]);
+        let summary = summarize_receipts(receipts.as_array().unwrap());
+        assert_eq!(
+            summary,
+            ReceiptSummary {
+                succeeded: 2,
+                reverted: 1,
+                contract_creations: 1,
+                avg_effective_gas_price: Some(1_500_000_000),
+                gas_by_type: vec![(TxType::DynamicFee, 221_000), (TxType::Blob, 21_000)],
+                gas_users: vec![
+                    GasUser {
+                        address: Address::with_last_byte(0xbb),
+                        gas_used: 200_000,
+                        txs: 1,
+                    },
+                    GasUser {
+                        address: Address::with_last_byte(0xaa),
+                        gas_used: 42_000,
+                        txs: 2,
+                    },
+                ],
+            }
+        );
+        assert!((summary.revert_pct().unwrap() - 100.0 / 3.0).abs() < 1e-9);
+        assert_eq!(summarize_receipts(&[]).revert_pct(), None);
+    }
+
+    #[test]
+    fn pending_block_fields_are_parsed() {
+        let pending = parse_pending_block(&json!({
+            "number": "0x65",
+            "hash": null,
+            "transactions": ["0x01", "0x02"],
+            "gasUsed": "0x5208",
+            "gasLimit": "0x1c9c380",
+            "baseFeePerGas": "0x3b9aca00",
+        }));
+        assert_eq!(
+            pending,
+            Some(PendingBlock {
+                number: 101,
+                tx_count: 2,
+                gas_used: 21_000,
+                gas_limit: 30_000_000,
+                base_fee_per_gas: Some(1_000_000_000),
+            })
+        );
+        assert_eq!(parse_pending_block(&json!({"number": "0x65"})), None);
+    }
+
+    #[test]
+    fn client_name_drops_platform_suffixes() {
+        let info = |client: &str| NodeInfo {
+            client: Some(client.to_string()),
+            genesis_hash: None,
+        };
+        assert_eq!(
+            info("Geth/v1.14.11-stable-f3c696fa/linux-amd64/go1.23.2").client_name(),
+            Some("Geth/v1.14.11-stable-f3c696fa".to_string())
+        );
+        assert_eq!(
+            info("anvil/v0.2.0").client_name(),
+            Some("anvil/v0.2.0".to_string())
+        );
+        assert_eq!(NodeInfo::default().client_name(), None);
     }
 }
@@ -0,0 +1,145 @@
+//! Contract deployments: top-level creation transactions (`to == null`) found in full block
+//! bodies, with the address they deploy to and the gas their receipts report
+use crate::config::DEPLOYMENT_FEED_ROWS;
+use crate::watchlist::Inclusion;
+use alloy::primitives::{Address, B256};
+use serde_json::Value;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deployment {
+    pub block: u64,
+    pub at: u64, // block timestamp
+    pub hash: B256,
+    pub deployer: Address,
+    pub address: Address,      // CREATE address from the deployer and its nonce
+    pub init_code: usize,      // bytes
+    pub gas_used: Option<u64>, // from the receipt, once fetched
+    pub succeeded: Option<bool>,
+}
+
+impl Deployment {
+    pub fn set_receipt(&mut self, inclusion: &Inclusion) {
+        self.gas_used = Some(inclusion.gas_used);
+        self.succeeded = Some(inclusion.succeeded);
+    }
+}
+
+/// Creation transactions in a block fetched with full transaction objects, in block order.
+/// Contracts created by other contracts (factories, CREATE2) aren't top-level and don't count.
+pub fn deployments(block: &Value) -> Vec<Deployment> {
+    let quantity = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+    };
+    let (Some(number), Some(timestamp), Some(transactions)) = (
+        quantity(block, "number"),
+        quantity(block, "timestamp"),
+        block.get("transactions").and_then(Value::as_array),
+    ) else {
+        return Vec::new();
+    };
+    transactions
+        .iter()
+        .filter(|tx| tx.get("to").is_some_and(Value::is_null))
+        .filter_map(|tx| {
+            let deployer: Address = tx.get("from")?.as_str()?.parse().ok()?;
+            let input = tx.get("input").and_then(Value::as_str).unwrap_or_default();
+            Some(Deployment {
+                block: number,
+                at: timestamp,
+                hash: tx.get("hash")?.as_str()?.parse().ok()?,
+                deployer,
+                address: deployer.create(quantity(tx, "nonce")?),
+                init_code: input.trim_start_matches("0x").len() / 2,
+                gas_used: None,
+                succeeded: None,
+            })
+        })
+        .collect()
+}
+
+/// The newest [`DEPLOYMENT_FEED_ROWS`] deployments seen, newest first.
+#[derive(Debug, Clone, Default)]
+pub struct DeploymentFeed {
+    pub deployments: VecDeque<Deployment>,
+    pub total: usize, // this session, including ones scrolled out of the feed
+}
+
+impl DeploymentFeed {
+    /// Add a deployment unless it's already listed; `false` for a repeat.
+    pub fn record(&mut self, deployment: Deployment) -> bool {
+        if self
+            .deployments
+            .iter()
+            .any(|seen| seen.hash == deployment.hash)
+        {
+            return false;
+        }
+        self.deployments.push_front(deployment);
+        self.deployments.truncate(DEPLOYMENT_FEED_ROWS);
+        self.total += 1;
+        true
+    }
+
+    pub fn set_receipt(&mut self, hash: B256, inclusion: &Inclusion) {
+        if let Some(deployment) = self
+            .deployments
+            .iter_mut()
+            .find(|deployment| deployment.hash == hash)
+        {
+            deployment.set_receipt(inclusion);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeploymentFeed, deployments};
+    use crate::watchlist::Inclusion;
+    use alloy::primitives::{Address, B256, address};
+    use serde_json::json;
+
+    #[test]
+    fn finds_creations_and_their_addresses() {
+        let deployer = address!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+        let block = json!({
+            "number": "0x10",
+            "timestamp": "0x64",
+            "transactions": [
+                {"hash": B256::repeat_byte(1), "from": deployer, "to": null, "nonce": "0x0", "input": "0x6080604052"},
+                {"hash": B256::repeat_byte(2), "from": deployer, "to": Address::ZERO, "nonce": "0x1", "input": "0x"},
+                {"hash": B256::repeat_byte(3), "from": deployer, "to": null, "nonce": "0x2", "input": "0x"}
+            ]
+        });
+        let found = deployments(&block);
+        assert_eq!(found.len(), 2);
+        assert_eq!(
+            found[0].address,
+            address!("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d")
+        );
+        assert_eq!(
+            (found[0].block, found[0].at, found[0].init_code),
+            (16, 100, 5)
+        );
+        assert_eq!(found[1].address, deployer.create(2));
+        assert!(deployments(&json!({"number": "0x1", "transactions": ["0xabc"]})).is_empty());
+
+        let mut feed = DeploymentFeed::default();
+        assert!(feed.record(found[0]));
+        assert!(!feed.record(found[0]));
+        feed.set_receipt(
+            found[0].hash,
+            &Inclusion {
+                block: 16,
+                succeeded: false,
+                gas_used: 53_000,
+            },
+        );
+        assert_eq!(feed.deployments[0].gas_used, Some(53_000));
+        assert_eq!(feed.deployments[0].succeeded, Some(false));
+        assert_eq!(feed.total, 1);
+    }
+}
@@ -0,0 +1,130 @@
+//! Layer-by-layer diagnosis of a failing RPC endpoint (DNS, TCP connect, TLS handshake),
+//! so the Connection panel can say which layer broke instead of showing a raw HTTP error
+use crate::config::DIAGNOSE_TIMEOUT_SECS;
+use crate::http::HttpOptions;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::{TcpStream, lookup_host};
+use tokio::time::timeout;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::ServerName;
+use url::Url;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    Dns,
+    Tcp,
+    Tls,
+}
+
+impl Layer {
+    pub fn label(self) -> &'static str {
+        match self {
+            Layer::Dns => "DNS",
+            Layer::Tcp => "TCP",
+            Layer::Tls => "TLS",
+        }
+    }
+}
+
+/// The layers that worked, with how long each took, and the first one that didn't.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnosis {
+    pub at: Instant,
+    pub passed: Vec<(Layer, Duration)>,
+    pub failed: Option<(Layer, String)>, // None: the failure is above TLS (HTTP or JSON-RPC)
+}
+
+/// Host, port, and whether the endpoint speaks TLS.
+fn target(rpc_url: &str) -> Result<(String, u16, bool), String> {
+    let url = Url::parse(rpc_url).map_err(|e| e.to_string())?;
+    let tls = matches!(url.scheme(), "https" | "wss");
+    let host = url
+        .host_str()
+        .ok_or_else(|| format!("no host in '{}'", rpc_url))?
+        .trim_matches(|c| c == '[' || c == ']')
+        .to_string();
+    let port = url
+        .port_or_known_default()
+        .unwrap_or(if tls { 443 } else { 80 });
+    Ok((host, port, tls))
+}
+
+/// Check `rpc_url` layer by layer, trusting the certificates the HTTP clients trust.
+pub async fn diagnose(rpc_url: &str, options: &HttpOptions) -> Diagnosis {
+    let mut diagnosis = Diagnosis {
+        at: Instant::now(),
+        passed: Vec::new(),
+        failed: None,
+    };
+    if let Err((layer, e)) = run(rpc_url, options, &mut diagnosis.passed).await {
+        diagnosis.failed = Some((layer, e));
+    }
+    diagnosis
+}
+
+async fn run(
+    rpc_url: &str,
+    options: &HttpOptions,
+    passed: &mut Vec<(Layer, Duration)>,
+) -> Result<(), (Layer, String)> {
+    let limit = Duration::from_secs(DIAGNOSE_TIMEOUT_SECS);
+    let timed_out = |layer: Layer| (layer, format!("timed out after {}s", limit.as_secs()));
+    let (host, port, tls) = target(rpc_url).map_err(|e| (Layer::Dns, e))?;
+
+    let started = Instant::now();
+    let addrs: Vec<_> = timeout(limit, lookup_host((host.as_str(), port)))
+        .await
+        .map_err(|_| timed_out(Layer::Dns))?
+        .map_err(|e| (Layer::Dns, e.to_string()))?
+        .collect();
+    let addr = *addrs
+        .first()
+        .ok_or_else(|| (Layer::Dns, format!("{} has no addresses", host)))?;
+    passed.push((Layer::Dns, started.elapsed()));
+
+    let started = Instant::now();
+    let stream = timeout(limit, TcpStream::connect(addr))
+        .await
+        .map_err(|_| timed_out(Layer::Tcp))?
+        .map_err(|e| (Layer::Tcp, format!("{}: {}", addr, e)))?;
+    passed.push((Layer::Tcp, started.elapsed()));
+    if !tls {
+        return Ok(());
+    }
+
+    let started = Instant::now();
+    let server_name =
+        ServerName::try_from(host.as_str()).map_err(|e| (Layer::Tls, e.to_string()))?;
+    timeout(
+        limit,
+        TlsConnector::from(Arc::new(options.tls_config())).connect(server_name, stream),
+    )
+    .await
+    .map_err(|_| timed_out(Layer::Tls))?
+    .map_err(|e| (Layer::Tls, e.to_string()))?;
+    passed.push((Layer::Tls, started.elapsed()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::target;
+
+    #[test]
+    fn targets_default_their_port_by_scheme() {
+        assert_eq!(
+            target("https://rpc.example.org/v1/key").unwrap(),
+            ("rpc.example.org".to_string(), 443, true)
+        );
+        assert_eq!(
+            target("http://localhost:8545").unwrap(),
+            ("localhost".to_string(), 8545, false)
+        );
+        assert_eq!(
+            target("wss://[::1]:9000").unwrap(),
+            ("::1".to_string(), 9000, true)
+        );
+        assert!(target("not a url").is_err());
+    }
+}
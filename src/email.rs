@@ -0,0 +1,441 @@
+//! Alert emails over SMTP (`[alerts.email]` in `config.toml`), batched so a flapping
+//! condition sends one digest per window instead of a message per firing
+use crate::alerts::{Alert, Severity};
+use crate::config::SMTP_TIMEOUT_SECS;
+use crate::error::{FutureSightError, Result};
+use crate::http::HttpOptions;
+use futures::FutureExt;
+use serde::{Deserialize, Deserializer};
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tokio_rustls::TlsConnector;
+use tokio_rustls::rustls::{ClientConfig, ServerName};
+
+/// Environment variable read for the password when `username` is set without one.
+const PASSWORD_ENV: &str = "FUTURESIGHT_SMTP_PASSWORD";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Plain connection upgraded with STARTTLS, usually port 587.
+    #[default]
+    Starttls,
+    /// TLS from the first byte, usually port 465.
+    Tls,
+    /// No encryption, for a relay on localhost.
+    None,
+}
+
+impl SmtpSecurity {
+    fn default_port(self) -> u16 {
+        match self {
+            SmtpSecurity::Starttls => 587,
+            SmtpSecurity::Tls => 465,
+            SmtpSecurity::None => 25,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmailSettings {
+    pub server: String,
+    pub port: Option<u16>, // default by `security`
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub password: Option<String>, // else FUTURESIGHT_SMTP_PASSWORD
+    pub from: String,
+    pub to: Vec<String>,
+    /// Least time between emails; alerts firing meanwhile wait for the next digest.
+    #[serde(default = "default_digest", deserialize_with = "digest")]
+    pub digest: Duration,
+    /// Also mail warnings; by default only critical alerts are sent.
+    #[serde(default)]
+    pub warnings: bool,
+}
+
+fn default_digest() -> Duration {
+    Duration::from_secs(300)
+}
+
+fn digest<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Duration, D::Error> {
+    let raw = String::deserialize(deserializer)?;
+    humantime::parse_duration(&raw)
+        .map_err(|e| serde::de::Error::custom(format!("digest '{}': {}", raw, e)))
+}
+
+/// Alerts waiting for the next email, and when the last one went out.
+#[derive(Debug, Default)]
+struct Batch {
+    pending: Vec<Alert>,
+    last_sent: Option<u64>,
+}
+
+impl Batch {
+    /// The pending alerts, if the digest window since the last email has passed.
+    fn take_due(&mut self, now: u64, window: Duration) -> Option<Vec<Alert>> {
+        let open = self
+            .last_sent
+            .is_none_or(|sent| now >= sent + window.as_secs());
+        if self.pending.is_empty() || !open {
+            return None;
+        }
+        self.last_sent = Some(now);
+        Some(std::mem::take(&mut self.pending))
+    }
+}
+
+pub struct EmailAlerts {
+    settings: EmailSettings,
+    password: Option<String>,
+    tls: Arc<ClientConfig>,
+    batch: Batch,
+    sending: Option<JoinHandle<std::result::Result<(), String>>>,
+}
+
+impl EmailAlerts {
+    pub fn new(settings: EmailSettings, http: &HttpOptions) -> Result<Self> {
+        if settings.to.is_empty() {
+            return Err(FutureSightError::Config(
+                "[alerts.email] needs at least one address in `to`".to_string(),
+            ));
+        }
+        let password = settings
+            .password
+            .clone()
+            .or_else(|| std::env::var(PASSWORD_ENV).ok());
+        if settings.username.is_some() && password.is_none() {
+            return Err(FutureSightError::Config(format!(
+                "[alerts.email] has a username but no password (set `password` or {})",
+                PASSWORD_ENV
+            )));
+        }
+        Ok(Self {
+            settings,
+            password,
+            tls: Arc::new(http.tls_config()),
+            batch: Batch::default(),
+            sending: None,
+        })
+    }
+
+    /// Queue the mailed severities of `fired` and send a digest if one is due. Returns the
+    /// error of a send that failed since the last call; its alerts aren't retried.
+    pub fn notify(&mut self, fired: &[Alert], now: u64) -> Option<String> {
+        self.batch.pending.extend(
+            fired
+                .iter()
+                .filter(|alert| self.settings.warnings || alert.is_critical())
+                .cloned(),
+        );
+        let failed = match self.sending.as_mut().and_then(FutureExt::now_or_never) {
+            Some(result) => {
+                self.sending = None;
+                result
+                    .map_err(|e| e.to_string())
+                    .and_then(|sent| sent)
+                    .err()
+            }
+            None => None,
+        };
+        if self.sending.is_none()
+            && let Some(alerts) = self.batch.take_due(now, self.settings.digest)
+        {
+            let settings = self.settings.clone();
+            let password = self.password.clone();
+            let tls = self.tls.clone();
+            let message = message(&settings, &alerts);
+            self.sending = Some(tokio::spawn(async move {
+                timeout(
+                    Duration::from_secs(SMTP_TIMEOUT_SECS),
+                    send(&settings, password.as_deref(), tls, &message),
+                )
+                .await
+                .map_err(|_| format!("timed out after {}s", SMTP_TIMEOUT_SECS))?
+            }));
+        }
+        failed
+    }
+}
+
+/// "[futuresight] critical: connection lost on host", or for a digest
+/// "[futuresight] 5 alerts (3 critical)".
+fn subject(alerts: &[Alert]) -> String {
+    match alerts {
+        [alert] => format!(
+            "[futuresight] {}: {} on {}",
+            severity_label(alert.severity),
+            alert.kind.label(),
+            alert.chain
+        ),
+        _ => format!(
+            "[futuresight] {} alerts ({} critical)",
+            alerts.len(),
+            alerts.iter().filter(|alert| alert.is_critical()).count()
+        ),
+    }
+}
+
+fn severity_label(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Warning => "warning",
+        Severity::Critical => "critical",
+    }
+}
+
+/// The whole message, headers and body, with CRLF line endings and dot-stuffing applied,
+/// ready to follow `DATA`.
+fn message(settings: &EmailSettings, alerts: &[Alert]) -> String {
+    let mut body = String::new();
+    for alert in alerts {
+        let summary = alert.summary();
+        body.push_str(&format!(
+            "{}  {:<8}  {}  {}{}{}\n",
+            humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_secs(alert.started)),
+            severity_label(alert.severity),
+            alert.chain,
+            alert.kind.label(),
+            if summary.is_empty() { "" } else { ": " },
+            summary
+        ));
+    }
+    let headers = [
+        format!("From: {}", settings.from),
+        format!("To: {}", settings.to.join(", ")),
+        format!("Subject: {}", subject(alerts)),
+        "MIME-Version: 1.0".to_string(),
+        "Content-Type: text/plain; charset=utf-8".to_string(),
+        "Content-Transfer-Encoding: 8bit".to_string(),
+    ];
+    headers
+        .into_iter()
+        .chain([String::new()])
+        .chain(body.lines().map(|line| match line.starts_with('.') {
+            true => format!(".{}", line),
+            false => line.to_string(),
+        }))
+        .map(|line| line + "\r\n")
+        .collect()
+}
+
+async fn send(
+    settings: &EmailSettings,
+    password: Option<&str>,
+    tls: Arc<ClientConfig>,
+    message: &str,
+) -> std::result::Result<(), String> {
+    let port = settings.port.unwrap_or(settings.security.default_port());
+    let stream = TcpStream::connect((settings.server.as_str(), port))
+        .await
+        .map_err(|e| format!("{}:{}: {}", settings.server, port, e))?;
+    let server_name =
+        || ServerName::try_from(settings.server.as_str()).map_err(|e| format!("TLS: {}", e));
+    match settings.security {
+        SmtpSecurity::None => {
+            let mut smtp = Smtp::new(stream);
+            smtp.reply(2).await?;
+            smtp.deliver(settings, password, message).await
+        }
+        SmtpSecurity::Tls => {
+            let stream = TlsConnector::from(tls)
+                .connect(server_name()?, stream)
+                .await
+                .map_err(|e| format!("TLS: {}", e))?;
+            let mut smtp = Smtp::new(stream);
+            smtp.reply(2).await?;
+            smtp.deliver(settings, password, message).await
+        }
+        SmtpSecurity::Starttls => {
+            let mut smtp = Smtp::new(stream);
+            smtp.reply(2).await?;
+            smtp.command("EHLO futuresight", 2).await?;
+            smtp.command("STARTTLS", 2).await?;
+            let stream = TlsConnector::from(tls)
+                .connect(server_name()?, smtp.stream.into_inner())
+                .await
+                .map_err(|e| format!("TLS: {}", e))?;
+            Smtp::new(stream).deliver(settings, password, message).await
+        }
+    }
+}
+
+/// One SMTP conversation over a plain or TLS stream.
+struct Smtp<S> {
+    stream: BufReader<S>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Smtp<S> {
+    fn new(stream: S) -> Self {
+        Self {
+            stream: BufReader::new(stream),
+        }
+    }
+
+    /// Read a possibly multi-line reply and check its code's first digit is `class`.
+    async fn reply(&mut self, class: u8) -> std::result::Result<(), String> {
+        loop {
+            let mut line = String::new();
+            if self
+                .stream
+                .read_line(&mut line)
+                .await
+                .map_err(|e| e.to_string())?
+                == 0
+            {
+                return Err("server closed the connection".to_string());
+            }
+            let line = line.trim_end();
+            if line.as_bytes().first() != Some(&(b'0' + class)) {
+                return Err(format!("server replied '{}'", line));
+            }
+            // "250-..." continues, "250 ..." ends the reply
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn command(&mut self, line: &str, class: u8) -> std::result::Result<(), String> {
+        self.write(&format!("{}\r\n", line)).await?;
+        self.reply(class)
+            .await
+            .map_err(|e| format!("{}: {}", line.split(' ').next().unwrap_or(line), e))
+    }
+
+    async fn write(&mut self, data: &str) -> std::result::Result<(), String> {
+        let stream = self.stream.get_mut();
+        stream
+            .write_all(data.as_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
+        stream.flush().await.map_err(|e| e.to_string())
+    }
+
+    /// EHLO, authenticate if configured, and hand over the message.
+    async fn deliver(
+        &mut self,
+        settings: &EmailSettings,
+        password: Option<&str>,
+        message: &str,
+    ) -> std::result::Result<(), String> {
+        self.command("EHLO futuresight", 2).await?;
+        if let (Some(username), Some(password)) = (&settings.username, password) {
+            let credentials = base64(format!("\0{}\0{}", username, password).as_bytes());
+            self.command(&format!("AUTH PLAIN {}", credentials), 2)
+                .await?;
+        }
+        self.command(&format!("MAIL FROM:<{}>", address(&settings.from)), 2)
+            .await?;
+        for to in &settings.to {
+            self.command(&format!("RCPT TO:<{}>", address(to)), 2)
+                .await?;
+        }
+        self.command("DATA", 3).await?;
+        self.write(message).await?;
+        self.command(".", 2).await?;
+        // The message is accepted; a failed goodbye doesn't matter
+        let _ = self.command("QUIT", 2).await;
+        Ok(())
+    }
+}
+
+/// Padded standard base64, as `AUTH PLAIN` takes its credentials.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char),
+                false => out.push('='),
+            }
+        }
+    }
+    out
+}
+
+/// The bare address of "Name <user@host>" or "user@host".
+fn address(mailbox: &str) -> &str {
+    match (mailbox.find('<'), mailbox.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &mailbox[start + 1..end],
+        _ => mailbox.trim(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Batch, EmailSettings, address, base64, message};
+    use crate::alerts::{Alert, AlertKind, Severity};
+    use std::time::Duration;
+
+    fn alert(kind: AlertKind, started: u64) -> Alert {
+        Alert {
+            chain: "host".to_string(),
+            kind,
+            started,
+            ended: None,
+            peak: 0.0,
+            severity: Severity::Critical,
+            detail: Some("5 blocks behind".to_string()),
+            acknowledged: false,
+        }
+    }
+
+    #[test]
+    fn batches_alerts_into_one_email_per_window() {
+        let window = Duration::from_secs(300);
+        let mut batch = Batch::default();
+        batch.pending.push(alert(AlertKind::ConnectionLost, 1_000));
+        // The first alert goes out at once
+        assert_eq!(batch.take_due(1_000, window).unwrap().len(), 1);
+
+        // Flapping within the window waits for one digest
+        batch.pending.push(alert(AlertKind::ConnectionLost, 1_010));
+        batch.pending.push(alert(AlertKind::ConnectionLost, 1_100));
+        assert_eq!(batch.take_due(1_200, window), None);
+        assert_eq!(batch.take_due(1_300, window).unwrap().len(), 2);
+        assert_eq!(batch.take_due(2_000, window), None);
+    }
+
+    #[test]
+    fn formats_digests_with_crlf_line_endings() {
+        let settings: EmailSettings = toml::from_str(
+            r#"
+            server = "smtp.example.org"
+            from = "FutureSight <alerts@example.org>"
+            to = ["oncall@example.org", "ops@example.org"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(settings.digest, Duration::from_secs(300));
+
+        let alerts = [
+            alert(AlertKind::ConnectionLost, 0),
+            alert(AlertKind::EndpointDivergence, 60),
+        ];
+        let message = message(&settings, &alerts);
+        assert!(message.contains("To: oncall@example.org, ops@example.org\r\n"));
+        assert!(message.contains("Subject: [futuresight] 2 alerts (2 critical)\r\n"));
+        assert!(message.contains(
+            "\r\n1970-01-01T00:01:00Z  critical  host  endpoints diverged: 5 blocks behind\r\n"
+        ));
+        // A blank line between the headers and the body
+        assert!(message.contains("8bit\r\n\r\n1970-01-01T00:00:00Z"));
+        assert_eq!(
+            address("FutureSight <alerts@example.org>"),
+            "alerts@example.org"
+        );
+        assert_eq!(address(" ops@example.org "), "ops@example.org");
+        assert_eq!(base64(b"\0u\0p"), "AHUAcA==");
+        assert_eq!(base64(b"\0user\0pw"), "AHVzZXIAcHc=");
+    }
+}
@@ -0,0 +1,227 @@
+//! Crate-wide error type, so callers can branch on what failed instead of matching messages
+use crate::config::RPC_TIMEOUT_SECS;
+use thiserror::Error;
+
+pub type Result<T, E = FutureSightError> = std::result::Result<T, E>;
+
+#[derive(Debug, Error)]
+pub enum FutureSightError {
+    /// A JSON-RPC call failed or returned something unusable.
+    #[error("{message}")]
+    Rpc { kind: RpcErrorKind, message: String },
+    /// The tx-pool webservice (or its event stream) failed.
+    #[error("{0}")]
+    TxPool(String),
+    /// Drawing to or reading from the terminal failed.
+    #[error("terminal: {0}")]
+    Terminal(std::io::Error),
+    /// Invalid flags, config file, address book, or endpoint URL.
+    #[error("{0}")]
+    Config(String),
+    /// Reading or writing the state file or a recording failed.
+    #[error("{0}")]
+    Storage(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcErrorKind {
+    Timeout,
+    RateLimited,
+    /// The endpoint doesn't implement the method; retrying won't help.
+    Unsupported,
+    /// Anything else: transport failures, node errors, malformed results.
+    Other,
+}
+
+impl RpcErrorKind {
+    fn classify(message: &str) -> Self {
+        if is_rate_limit_error(message) {
+            Self::RateLimited
+        } else if is_unsupported_error(message) {
+            Self::Unsupported
+        } else if message.contains("timed out") {
+            Self::Timeout
+        } else {
+            Self::Other
+        }
+    }
+}
+
+impl FutureSightError {
+    /// An RPC failure, classified once from the node's or transport's message.
+    pub fn rpc(message: impl std::fmt::Display) -> Self {
+        let message = message.to_string();
+        Self::Rpc {
+            kind: RpcErrorKind::classify(&message),
+            message,
+        }
+    }
+
+    pub fn rpc_timeout(method: &str) -> Self {
+        Self::Rpc {
+            kind: RpcErrorKind::Timeout,
+            message: format!("{} timed out after {}s", method, RPC_TIMEOUT_SECS),
+        }
+    }
+
+    pub fn rpc_kind(&self) -> Option<RpcErrorKind> {
+        match self {
+            Self::Rpc { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.rpc_kind() == Some(RpcErrorKind::RateLimited)
+    }
+
+    pub fn is_unsupported(&self) -> bool {
+        self.rpc_kind() == Some(RpcErrorKind::Unsupported)
+    }
+
+    /// Process exit code when this error ends a subcommand. Codes 2–4 belong to the chain
+    /// states in `check::CheckStatus`, so every error exits 1.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Rpc { .. } | Self::TxPool(_) => 1,
+            Self::Terminal(_) | Self::Config(_) | Self::Storage(_) => 1,
+        }
+    }
+}
+
+/// HTTP 429, JSON-RPC error -32005 (Infura/Alchemy "limit exceeded"), or a provider's
+/// rate limit wording.
+fn is_rate_limit_error(message: &str) -> bool {
+    if http_status(message) == Some(429) || rpc_error_code(message) == Some(-32005) {
+        return true;
+    }
+    let message = message.to_ascii_lowercase();
+    [
+        "too many requests",
+        "rate limit",
+        "rate-limit",
+        "ratelimit",
+        "throttl",
+        "compute units per second",
+    ]
+    .iter()
+    .any(|needle| message.contains(needle))
+}
+
+/// JSON-RPC "method not found" (-32601) and the wording clients use for disabled or
+/// unimplemented namespaces.
+fn is_unsupported_error(message: &str) -> bool {
+    if rpc_error_code(message) == Some(-32601) {
+        return true;
+    }
+    let message = message.to_ascii_lowercase();
+    [
+        "method not found",
+        "does not exist",
+        "not supported",
+        "unsupported method",
+        "not available",
+        "not implemented",
+    ]
+    .iter()
+    .any(|needle| message.contains(needle))
+}
+
+/// The status of a failed HTTP response, as `request` ("eth_call HTTP 429 Too Many
+/// Requests") and alloy's transport ("HTTP error 429 with body: …") word it.
+fn http_status(message: &str) -> Option<u16> {
+    ["HTTP error ", "HTTP "]
+        .iter()
+        .find_map(|prefix| number_after(message, prefix))
+        .and_then(|status| u16::try_from(status).ok())
+}
+
+/// The JSON-RPC `error.code`, from a raw error object (`"code":-32005`) or alloy's
+/// "error code -32005: …".
+fn rpc_error_code(message: &str) -> Option<i64> {
+    ["\"code\":", "error code "]
+        .iter()
+        .find_map(|prefix| number_after(message, prefix))
+}
+
+/// The integer right after the first `prefix` in `message` that is followed by one.
+fn number_after(message: &str, prefix: &str) -> Option<i64> {
+    message.match_indices(prefix).find_map(|(at, _)| {
+        let rest = message[at + prefix.len()..].trim_start();
+        let digits = rest.strip_prefix('-').unwrap_or(rest);
+        let end = digits
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(digits.len());
+        rest[..rest.len() - digits.len() + end].parse().ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FutureSightError, RpcErrorKind, is_rate_limit_error, is_unsupported_error};
+
+    #[test]
+    fn rate_limit_errors_are_recognized() {
+        assert!(is_rate_limit_error(
+            "Block number: HTTP error 429 with body: Too Many Requests"
+        ));
+        assert!(is_rate_limit_error(
+            "server returned an error response: error code -32005: daily request count exceeded"
+        ));
+        assert!(is_rate_limit_error(
+            "Your app has exceeded its compute units per second capacity"
+        ));
+        assert!(is_rate_limit_error("eth_call HTTP 429 Too Many Requests"));
+        assert!(is_rate_limit_error(
+            r#"eth_feeHistory error: {"code": -32005,"message":"limit exceeded"}"#
+        ));
+        assert!(!is_rate_limit_error(
+            "Chain ID: error sending request: connection refused"
+        ));
+        // Block numbers, hashes, and URLs that merely contain the digits
+        for message in [
+            "eth_getBlockByNumber: no block 21429000",
+            "eth_getStorageAt: unexpected result: 0x4290000000000000000000000000000000000000",
+            "error sending request for url (https://eth-mainnet.g.alchemy.com/v2/ab429cd-32005x)",
+            "eth_call HTTP 502 Bad Gateway",
+            r#"eth_call error: {"code":-32000,"message":"header not found for 0x429"}"#,
+        ] {
+            assert!(!is_rate_limit_error(message), "{}", message);
+        }
+    }
+
+    #[test]
+    fn unsupported_errors_are_recognized() {
+        for message in [
+            r#"eth_feeHistory error: {"code":-32601,"message":"the method eth_feeHistory does not exist/is not available"}"#,
+            "Method not found",
+            "txpool namespace is not supported",
+        ] {
+            assert!(is_unsupported_error(message), "{}", message);
+        }
+        for message in [
+            "eth_feeHistory HTTP 502 Bad Gateway",
+            "timed out after 6s",
+            "eth_getBlockByNumber: no block 1232601",
+        ] {
+            assert!(!is_unsupported_error(message), "{}", message);
+        }
+    }
+
+    #[test]
+    fn rpc_errors_are_classified_once() {
+        let throttled = FutureSightError::rpc("HTTP error 429");
+        assert!(throttled.is_rate_limited() && !throttled.is_unsupported());
+        let missing = FutureSightError::rpc("Method not found");
+        assert!(missing.is_unsupported());
+        assert_eq!(
+            FutureSightError::rpc_timeout("eth_chainId").rpc_kind(),
+            Some(RpcErrorKind::Timeout)
+        );
+        assert_eq!(
+            FutureSightError::rpc_timeout("eth_chainId").to_string(),
+            "eth_chainId timed out after 6s"
+        );
+        assert_eq!(FutureSightError::Config("bad".into()).rpc_kind(), None);
+    }
+}
@@ -0,0 +1,88 @@
+//! Block explorer links for the selected block
+use crate::data::BlockInfo;
+use std::process::{Command, Stdio};
+
+/// Explorers used when none is configured, by chain ID.
+const KNOWN_EXPLORERS: &[(u64, &str)] = &[
+    (1, "https://etherscan.io/block/{number}"),
+    (17000, "https://holesky.etherscan.io/block/{number}"),
+    (11155111, "https://sepolia.etherscan.io/block/{number}"),
+];
+
+pub fn default_template(chain_id: u64) -> Option<&'static str> {
+    KNOWN_EXPLORERS
+        .iter()
+        .find(|(id, _)| *id == chain_id)
+        .map(|(_, template)| *template)
+}
+
+/// Fill `{number}` and `{hash}` in an explorer URL template.
+pub fn block_url(template: &str, block: &BlockInfo) -> String {
+    template
+        .replace("{number}", &block.number.to_string())
+        .replace("{hash}", &block.hash)
+}
+
+/// Open `url` with the platform's default handler, without waiting for it.
+pub fn open_in_browser(url: &str) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    // The opener may print to the terminal, which would corrupt the dashboard
+    let mut child = command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{block_url, default_template};
+    use crate::data::BlockInfo;
+    use alloy::primitives::{Address, B256};
+
+    #[test]
+    fn template_placeholders_are_filled() {
+        let block = BlockInfo {
+            number: 42,
+            hash: "0xabc".to_string(),
+            parent_hash: String::new(),
+            state_root: B256::ZERO,
+            timestamp: 0,
+            tx_count: 0,
+            gas_used: 0,
+            gas_limit: 0,
+            blobs: vec![],
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
+            base_fee_per_gas: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        };
+        assert_eq!(
+            block_url("https://explorer.test/block/{number}?h={hash}", &block),
+            "https://explorer.test/block/42?h=0xabc"
+        );
+        assert_eq!(
+            block_url(default_template(1).unwrap(), &block),
+            "https://etherscan.io/block/42"
+        );
+        assert_eq!(default_template(88888), None);
+    }
+}
@@ -0,0 +1,171 @@
+//! Upcoming network upgrades from `config.toml` (`[[forks]]`), counted down from the chain's
+//! head using its observed block time
+use serde::Deserialize;
+
+/// A fork activating on chain `chain_id` at a block height or a block timestamp.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "ForkEntry")]
+pub struct Fork {
+    pub name: String,
+    pub chain_id: u64,
+    pub activation: Activation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Activation {
+    Block(u64),
+    Timestamp(u64), // unix seconds, the first block at or after it
+}
+
+/// The raw table: exactly one of `block` and `timestamp`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ForkEntry {
+    name: String,
+    chain_id: u64,
+    block: Option<u64>,
+    timestamp: Option<u64>,
+}
+
+impl TryFrom<ForkEntry> for Fork {
+    type Error = String;
+
+    fn try_from(entry: ForkEntry) -> Result<Self, Self::Error> {
+        let activation = match (entry.block, entry.timestamp) {
+            (Some(block), None) => Activation::Block(block),
+            (None, Some(timestamp)) => Activation::Timestamp(timestamp),
+            _ => {
+                return Err(format!(
+                    "fork '{}' needs exactly one of `block` and `timestamp`",
+                    entry.name
+                ));
+            }
+        };
+        Ok(Self {
+            name: entry.name,
+            chain_id: entry.chain_id,
+            activation,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForkStatus {
+    /// Still ahead of the head; either side is `None` without a block time to convert with.
+    Pending {
+        secs: Option<f64>,
+        blocks: Option<u64>,
+    },
+    Active,
+}
+
+impl Fork {
+    /// Where the fork stands for a head at `height` with `timestamp`, converting between
+    /// blocks and seconds with `block_time` (seconds per block).
+    pub fn status(&self, height: u64, timestamp: u64, block_time: Option<f64>) -> ForkStatus {
+        let block_time = block_time.filter(|secs| *secs > 0.0);
+        match self.activation {
+            Activation::Block(block) if height >= block => ForkStatus::Active,
+            Activation::Timestamp(at) if timestamp >= at => ForkStatus::Active,
+            Activation::Block(block) => {
+                let blocks = block - height;
+                ForkStatus::Pending {
+                    secs: block_time.map(|secs| blocks as f64 * secs),
+                    blocks: Some(blocks),
+                }
+            }
+            Activation::Timestamp(at) => {
+                let secs = (at - timestamp) as f64;
+                ForkStatus::Pending {
+                    secs: Some(secs),
+                    blocks: block_time.map(|block_time| (secs / block_time).ceil() as u64),
+                }
+            }
+        }
+    }
+}
+
+/// A countdown as its two largest units, e.g. `3d 4h`, `5h 12m`, `7m`, `40s`.
+pub fn countdown(secs: f64) -> String {
+    let secs = secs.max(0.0).round() as u64;
+    match secs {
+        86_400.. => format!("{}d {}h", secs / 86_400, secs % 86_400 / 3_600),
+        3_600.. => format!("{}h {}m", secs / 3_600, secs % 3_600 / 60),
+        60.. => format!("{}m", secs / 60),
+        _ => format!("{}s", secs),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Activation, Fork, ForkStatus, countdown};
+    use crate::settings::Settings;
+
+    #[test]
+    fn counts_down_by_block_or_timestamp() {
+        let by_block = Fork {
+            name: "Prague".to_string(),
+            chain_id: 1,
+            activation: Activation::Block(1_000),
+        };
+        assert_eq!(
+            by_block.status(900, 0, Some(12.0)),
+            ForkStatus::Pending {
+                secs: Some(1_200.0),
+                blocks: Some(100)
+            }
+        );
+        assert_eq!(
+            by_block.status(900, 0, None),
+            ForkStatus::Pending {
+                secs: None,
+                blocks: Some(100)
+            }
+        );
+        assert_eq!(by_block.status(1_000, 0, None), ForkStatus::Active);
+
+        let by_time = Fork {
+            activation: Activation::Timestamp(10_000),
+            ..by_block
+        };
+        assert_eq!(
+            by_time.status(0, 9_970, Some(12.0)),
+            ForkStatus::Pending {
+                secs: Some(30.0),
+                blocks: Some(3)
+            }
+        );
+        assert_eq!(by_time.status(0, 10_004, Some(12.0)), ForkStatus::Active);
+
+        assert_eq!(countdown(3.0 * 86_400.0 + 4.0 * 3_600.0 + 59.0), "3d 4h");
+        assert_eq!(countdown(3_660.0), "1h 1m");
+        assert_eq!(countdown(45.0), "45s");
+    }
+
+    #[test]
+    fn parses_fork_tables() {
+        let settings = Settings::parse(
+            r#"
+            [[forks]]
+            name = "Prague"
+            chain_id = 11155111
+            timestamp = 1741159776
+
+            [[forks]]
+            name = "Fusaka"
+            chain_id = 1
+            block = 23000000
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            settings.forks[0].activation,
+            Activation::Timestamp(1741159776)
+        );
+        assert_eq!(settings.forks[1].activation, Activation::Block(23_000_000));
+        let both = "[[forks]]\nname = \"X\"\nchain_id = 1\nblock = 1\ntimestamp = 1\n";
+        let err = Settings::parse(both).unwrap_err().to_string();
+        assert!(err.contains("exactly one of"), "{}", err);
+        assert!(Settings::parse("[[forks]]\nname = \"X\"\nchain_id = 1\n").is_err());
+    }
+}
@@ -0,0 +1,181 @@
+//! External gas oracles (`--gas-oracle`), shown next to the fee-history tiers so a node
+//! whose view of the fee market is skewed stands out
+use crate::bandwidth::{ByteCounter, Traffic};
+use crate::config::GAS_ORACLE_TIMEOUT_SECS;
+use crate::data::SuggestedFees;
+use crate::error::{FutureSightError, Result};
+use crate::http::{self, HttpOptions};
+use crate::units::WEI_PER_GWEI;
+use serde_json::Value;
+use std::time::Duration;
+
+/// An oracle's max fee per gas for each tier, in wei.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OracleQuote {
+    pub safe: Option<u128>,
+    pub standard: Option<u128>,
+    pub fast: Option<u128>,
+}
+
+impl OracleQuote {
+    /// Largest gap between a tier of ours and the oracle's, as a percentage of ours: positive
+    /// when the oracle asks for more. `None` without a tier both sides priced.
+    pub fn disagreement(&self, local: &SuggestedFees) -> Option<f64> {
+        let pairs = [
+            (self.safe, &local.safe),
+            (self.standard, &local.standard),
+            (self.fast, &local.fast),
+        ];
+        pairs
+            .into_iter()
+            .filter_map(|(theirs, ours)| {
+                let ours = ours.max_fee_per_gas;
+                (ours > 0).then_some((theirs? as f64 - ours as f64) / ours as f64 * 100.0)
+            })
+            .max_by(|a, b| a.abs().total_cmp(&b.abs()))
+    }
+}
+
+/// The latest answer from one oracle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OracleReading {
+    pub name: String, // the URL's host
+    pub quote: Option<OracleQuote>,
+    pub error: Option<String>,
+}
+
+pub struct GasOracle {
+    url: String,
+    http: reqwest::Client,
+    traffic: ByteCounter,
+}
+
+impl GasOracle {
+    pub fn new(url: String, options: &HttpOptions) -> Result<Self> {
+        let http = options
+            .client_builder()?
+            .timeout(Duration::from_secs(GAS_ORACLE_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| FutureSightError::Config(e.to_string()))?;
+        url::Url::parse(&url).map_err(|e| {
+            FutureSightError::Config(format!("invalid gas oracle '{}': {}", url, e))
+        })?;
+        Ok(Self {
+            url,
+            http,
+            traffic: ByteCounter::default(),
+        })
+    }
+
+    pub fn traffic(&self) -> Traffic {
+        self.traffic.totals()
+    }
+
+    pub fn reading(&self) -> OracleReading {
+        OracleReading {
+            name: url::Url::parse(&self.url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string))
+                .unwrap_or_else(|| self.url.clone()),
+            quote: None,
+            error: None,
+        }
+    }
+
+    pub async fn quote(&self) -> std::result::Result<OracleQuote, String> {
+        let resp = http::fetch(self.http.get(&self.url), 0, &self.traffic).await?;
+        if !resp.status.is_success() {
+            return Err(format!("HTTP {}", resp.status));
+        }
+        let body: Value = serde_json::from_slice(&resp.body).map_err(|e| e.to_string())?;
+        parse_quote(&body).ok_or_else(|| "unrecognized response".to_string())
+    }
+}
+
+/// Tiers in gwei from any of the shapes oracles commonly answer with:
+/// `{"safe", "standard", "fast"}`, Etherscan's gas tracker
+/// (`result.SafeGasPrice`/`ProposeGasPrice`/`FastGasPrice`), or MetaMask's gas API
+/// (`low`/`medium`/`high` with `suggestedMaxFeePerGas`).
+pub fn parse_quote(body: &Value) -> Option<OracleQuote> {
+    let gwei = |value: &Value| {
+        let gwei = match value {
+            Value::Number(number) => number.as_f64(),
+            Value::String(text) => text.trim().parse().ok(),
+            _ => None,
+        }?;
+        (gwei.is_finite() && gwei >= 0.0).then(|| (gwei * WEI_PER_GWEI as f64).round() as u128)
+    };
+    let tiers = |object: &Value, keys: [&str; 3], field: Option<&str>| {
+        let tier = |key: &str| {
+            let value = object.get(key)?;
+            gwei(field.map_or(Some(value), |field| value.get(field))?)
+        };
+        let quote = OracleQuote {
+            safe: tier(keys[0]),
+            standard: tier(keys[1]),
+            fast: tier(keys[2]),
+        };
+        (quote != OracleQuote::default()).then_some(quote)
+    };
+    tiers(body, ["safe", "standard", "fast"], None)
+        .or_else(|| {
+            tiers(
+                body.get("result")?,
+                ["SafeGasPrice", "ProposeGasPrice", "FastGasPrice"],
+                None,
+            )
+        })
+        .or_else(|| {
+            tiers(
+                body,
+                ["low", "medium", "high"],
+                Some("suggestedMaxFeePerGas"),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OracleQuote, parse_quote};
+    use crate::data::{SuggestedFeeTier, SuggestedFees};
+    use serde_json::json;
+
+    const GWEI: u128 = 1_000_000_000;
+
+    #[test]
+    fn reads_common_oracle_shapes() {
+        let plain = parse_quote(&json!({"safe": 10, "standard": "12.5", "fast": 15})).unwrap();
+        assert_eq!(plain.standard, Some(12_500_000_000));
+        let etherscan = json!({"status": "1", "result": {
+            "SafeGasPrice": "10", "ProposeGasPrice": "12", "FastGasPrice": "15"
+        }});
+        assert_eq!(parse_quote(&etherscan).unwrap().fast, Some(15 * GWEI));
+        let metamask = json!({
+            "low": {"suggestedMaxFeePerGas": "10"},
+            "medium": {"suggestedMaxFeePerGas": "12"},
+            "high": {"suggestedMaxFeePerGas": "15"}
+        });
+        assert_eq!(parse_quote(&metamask).unwrap().safe, Some(10 * GWEI));
+        assert_eq!(parse_quote(&json!({"price": 3})), None);
+    }
+
+    #[test]
+    fn reports_the_widest_tier_gap() {
+        let tier = |gwei: u128| SuggestedFeeTier {
+            max_fee_per_gas: gwei * GWEI,
+            max_priority_fee_per_gas: GWEI,
+        };
+        let ours = SuggestedFees {
+            safe: tier(10),
+            standard: tier(20),
+            fast: tier(40),
+        };
+        let quote = OracleQuote {
+            safe: Some(11 * GWEI),     // +10%
+            standard: Some(10 * GWEI), // -50%
+            fast: None,
+        };
+        assert_eq!(quote.disagreement(&ours), Some(-50.0));
+        assert_eq!(OracleQuote::default().disagreement(&ours), None);
+    }
+}
@@ -0,0 +1,239 @@
+//! Block history that follows the canonical chain by parent hash, keeping blocks
+//! replaced by a reorg as orphans instead of mixing them in with their replacements
+use crate::config::ORPHANED_BLOCK_HISTORY;
+use crate::data::BlockInfo;
+use std::{collections::VecDeque, ops::Deref};
+
+/// Canonical blocks newest first, at most one per height. Dereferences to that list;
+/// changes go through [`BlockHistory::insert`] so the parent links are checked.
+#[derive(Debug, Clone, Default)]
+pub struct BlockHistory {
+    canonical: VecDeque<BlockInfo>,
+    orphaned: VecDeque<BlockInfo>, // most recently orphaned first
+    capacity: usize,
+}
+
+impl Deref for BlockHistory {
+    type Target = VecDeque<BlockInfo>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.canonical
+    }
+}
+
+impl BlockHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            canonical: VecDeque::with_capacity(capacity),
+            orphaned: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Add a freshly fetched block at its height, trimming the oldest past capacity.
+    ///
+    /// A different block already at that height, and adjacent blocks that don't link to
+    /// the new one by parent hash, are moved to the orphaned list: the newest fetch is
+    /// taken as canonical. Returns the number of blocks orphaned.
+    pub fn insert(&mut self, block: BlockInfo) -> usize {
+        let mut orphaned = 0;
+        let mut index = self
+            .canonical
+            .iter()
+            .position(|existing| existing.number <= block.number)
+            .unwrap_or(self.canonical.len());
+
+        if let Some(existing) = self.canonical.get(index)
+            && existing.number == block.number
+        {
+            if existing.hash == block.hash {
+                return 0;
+            }
+            self.orphan_at(index);
+            orphaned += 1;
+        }
+        if index > 0 {
+            let newer = &self.canonical[index - 1];
+            if newer.number == block.number + 1 && newer.parent_hash != block.hash {
+                self.orphan_at(index - 1);
+                index -= 1;
+                orphaned += 1;
+            }
+        }
+        if let Some(older) = self.canonical.get(index)
+            && older.number + 1 == block.number
+            && older.hash != block.parent_hash
+        {
+            self.orphan_at(index);
+            orphaned += 1;
+        }
+
+        self.canonical.insert(index, block);
+        self.canonical.truncate(self.capacity);
+        orphaned
+    }
+
+    fn orphan_at(&mut self, index: usize) {
+        if let Some(block) = self.canonical.remove(index) {
+            self.orphaned.push_front(block);
+            self.orphaned.truncate(ORPHANED_BLOCK_HISTORY);
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut BlockInfo> {
+        self.canonical.iter_mut()
+    }
+
+    pub fn orphaned(&self) -> impl Iterator<Item = &BlockInfo> {
+        self.orphaned.iter()
+    }
+
+    /// Keep at most `capacity` canonical blocks from now on, dropping the oldest.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.canonical.truncate(capacity);
+        self.orphaned.truncate(capacity.min(ORPHANED_BLOCK_HISTORY));
+    }
+
+    /// Heights missing between canonical blocks, newest first, within `capacity` of the
+    /// newest block. Reorgs leave these holes until the replacement blocks are fetched.
+    pub fn missing(&self, limit: usize) -> Vec<u64> {
+        let Some(newest) = self.canonical.front() else {
+            return Vec::new();
+        };
+        let floor = newest.number.saturating_sub(self.capacity as u64);
+        self.canonical
+            .iter()
+            .zip(self.canonical.iter().skip(1))
+            .flat_map(|(newer, older)| (older.number + 1..newer.number).rev())
+            .filter(|number| *number > floor)
+            .take(limit)
+            .collect()
+    }
+
+    /// Canonical and orphaned blocks for display, newest height first; at each height the
+    /// canonical block comes before its orphans. `true` marks an orphan.
+    pub fn tape(&self) -> Vec<(&BlockInfo, bool)> {
+        let oldest = self.canonical.back().map_or(0, |block| block.number);
+        let mut rows: Vec<(&BlockInfo, bool)> = self
+            .canonical
+            .iter()
+            .map(|block| (block, false))
+            .chain(
+                self.orphaned
+                    .iter()
+                    .filter(|block| block.number >= oldest)
+                    .map(|block| (block, true)),
+            )
+            .collect();
+        rows.sort_by_key(|(block, orphaned)| (std::cmp::Reverse(block.number), *orphaned));
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlockHistory;
+    use crate::data::BlockInfo;
+    use alloy::primitives::{Address, B256};
+
+    fn block(number: u64, hash: &str, parent_hash: &str) -> BlockInfo {
+        BlockInfo {
+            number,
+            hash: hash.to_string(),
+            parent_hash: parent_hash.to_string(),
+            state_root: B256::ZERO,
+            timestamp: 0,
+            tx_count: 0,
+            gas_used: 0,
+            gas_limit: 0,
+            blobs: vec![],
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
+            base_fee_per_gas: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        }
+    }
+
+    fn numbers_and_hashes(history: &BlockHistory) -> Vec<(u64, &str)> {
+        history
+            .iter()
+            .map(|block| (block.number, block.hash.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn reorg_orphans_replaced_blocks_and_leaves_a_hole_to_refill() {
+        let mut history = BlockHistory::new(8);
+        for (number, hash, parent) in [(10, "a10", "a9"), (11, "a11", "a10"), (12, "a12", "a11")] {
+            assert_eq!(history.insert(block(number, hash, parent)), 0);
+        }
+        // Same block again is a no-op
+        assert_eq!(history.insert(block(12, "a12", "a11")), 0);
+
+        // b13 builds on a replacement for block 12
+        assert_eq!(history.insert(block(13, "b13", "b12")), 1);
+        assert_eq!(
+            numbers_and_hashes(&history),
+            [(13, "b13"), (11, "a11"), (10, "a10")]
+        );
+        assert_eq!(history.missing(10), [12]);
+
+        // b12 sits on b11, so a11 is orphaned too and 11 becomes the hole
+        assert_eq!(history.insert(block(12, "b12", "b11")), 1);
+        assert_eq!(history.missing(10), [11]);
+        assert_eq!(history.insert(block(11, "b11", "a10")), 0);
+        assert!(history.missing(10).is_empty());
+
+        let tape: Vec<(u64, bool)> = history
+            .tape()
+            .into_iter()
+            .map(|(block, orphaned)| (block.number, orphaned))
+            .collect();
+        assert_eq!(
+            tape,
+            [
+                (13, false),
+                (12, false),
+                (12, true),
+                (11, false),
+                (11, true),
+                (10, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn capacity_trims_the_oldest_blocks_and_bounds_the_holes() {
+        let mut history = BlockHistory::new(3);
+        for number in [20, 21, 22, 23] {
+            history.insert(block(
+                number,
+                &format!("h{}", number),
+                &format!("h{}", number - 1),
+            ));
+        }
+        assert_eq!(
+            history.iter().map(|block| block.number).collect::<Vec<_>>(),
+            [23, 22, 21]
+        );
+
+        history.insert(block(30, "h30", "h29"));
+        assert_eq!(history.missing(10), [29, 28]);
+
+        // Shrinking keeps the newest and narrows the window holes are looked for in
+        history.set_capacity(2);
+        assert_eq!(
+            history.iter().map(|block| block.number).collect::<Vec<_>>(),
+            [30, 23]
+        );
+        assert_eq!(history.missing(10), [29]);
+    }
+}
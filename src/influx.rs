@@ -0,0 +1,117 @@
+//! `--influx`: each refreshed chain as one InfluxDB line-protocol point, appended to a file
+//! or sent to a `udp://host:port` listener such as Telegraf's `socket_listener`
+use crate::error::{FutureSightError, Result};
+use crate::otlp::{GAUGES, Observation};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    net::UdpSocket,
+    path::Path,
+};
+
+const MEASUREMENT: &str = env!("CARGO_PKG_NAME");
+
+pub enum InfluxSink {
+    File(BufWriter<File>),
+    Udp(UdpSocket),
+}
+
+impl InfluxSink {
+    /// `udp://host:port` for a socket; anything else is a file path, appended to.
+    pub fn open(target: &str) -> Result<Self> {
+        if let Some(addr) = target.strip_prefix("udp://") {
+            return crate::statsd::connect_udp(addr)
+                .map(Self::Udp)
+                .map_err(|e| FutureSightError::Config(format!("--influx {}: {}", target, e)));
+        }
+        let path = Path::new(target);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| {
+                FutureSightError::Storage(format!(
+                    "cannot open influx file '{}': {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        Ok(Self::File(BufWriter::new(file)))
+    }
+
+    /// File write errors are returned like the recorder's; UDP sends are fire-and-forget.
+    pub fn write(&mut self, observations: &[Observation]) -> Result<()> {
+        match self {
+            Self::File(out) => observations
+                .iter()
+                .try_for_each(|obs| writeln!(out, "{}", point(obs)))
+                .and_then(|()| out.flush())
+                .map_err(|e| FutureSightError::Storage(format!("cannot write influx file: {}", e))),
+            Self::Udp(socket) => {
+                // One point per datagram keeps each under the MTU
+                for obs in observations {
+                    let _ = socket.send(point(obs).as_bytes());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// `futuresight,chain=<label> status="…",up=1,… <ns>`: fields named after the OTLP gauges
+/// without the `futuresight.` prefix, dots as underscores.
+fn point(obs: &Observation) -> String {
+    let fields = GAUGES.iter().filter_map(|(name, _, _, extract)| {
+        let field = name.trim_start_matches("futuresight.").replace('.', "_");
+        extract(obs).map(|value| format!("{}={}", field, value))
+    });
+    let fields: Vec<String> = std::iter::once(format!("status=\"{}\"", obs.metrics.status))
+        .chain(fields)
+        .collect();
+    format!(
+        "{},chain={} {} {}",
+        MEASUREMENT,
+        escape_tag(&obs.chain),
+        fields.join(","),
+        obs.end_ns
+    )
+}
+
+/// Tag values escape commas, equals signs, and spaces with a backslash.
+fn escape_tag(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, ',' | '=' | ' ') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::point;
+    use crate::otlp::{Observation, Snapshot};
+    use std::time::Duration;
+
+    #[test]
+    fn writes_one_point_per_observation() {
+        let obs = Observation {
+            chain: "mainnet host,a=b".to_string(),
+            end_ns: 2_000_000_000,
+            duration: Duration::from_millis(250),
+            metrics: Snapshot {
+                status: "connected",
+                block_height: Some(42),
+                base_fee_gwei: Some(1.5),
+                ..Snapshot::default()
+            },
+        };
+        assert_eq!(
+            point(&obs),
+            "futuresight,chain=mainnet\\ host\\,a\\=b status=\"connected\",up=1,\
+             collect_duration=250,block_height=42,base_fee=1.5 2000000000"
+        );
+    }
+}
@@ -0,0 +1,445 @@
+//! Keybindings: the single table both input dispatch and the `?` help overlay read from
+use crossterm::event::KeyCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Navigation,
+    Actions,
+    Views,
+    EndpointPicker,
+    ProfilePicker,
+    LayoutEditor,
+    AlertsTab,
+}
+
+impl Category {
+    pub const ALL: [Category; 7] = [
+        Category::Navigation,
+        Category::Actions,
+        Category::Views,
+        Category::AlertsTab,
+        Category::EndpointPicker,
+        Category::ProfilePicker,
+        Category::LayoutEditor,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Category::Navigation => "Navigation",
+            Category::Actions => "Actions",
+            Category::Views => "Views",
+            Category::EndpointPicker => "Endpoint picker",
+            Category::ProfilePicker => "Profile picker",
+            Category::LayoutEditor => "Layout editor",
+            Category::AlertsTab => "Alerts tab",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    NextNetwork,
+    PrevNetwork,
+    ToggleHostFilter,
+    OpenEndpointPicker,
+    OpenProfilePicker,
+    Quit,
+    AcknowledgeAlerts,
+    TogglePause,
+    NextTab,
+    ToggleHelp,
+    EditLayout,
+    SearchBlocks,
+    NextMatch,
+    PrevMatch,
+    ClearSearch,
+    ZoomIn,
+    ZoomOut,
+    PanBack,
+    PanForward,
+    SelectOlder,
+    SelectNewer,
+    SwitchTape,
+    OpenExplorer,
+    PickerUp,
+    PickerDown,
+    PickerToggleTarget,
+    PickerCustomUrl,
+    PickerSwitch,
+    PickerCancel,
+    ProfileUp,
+    ProfileDown,
+    ProfileSwitch,
+    ProfileCancel,
+    LayoutUp,
+    LayoutDown,
+    LayoutToggle,
+    LayoutMoveUp,
+    LayoutMoveDown,
+    LayoutClose,
+    AlertKindUp,
+    AlertKindDown,
+    ToggleMute,
+    Snooze,
+}
+
+pub struct Binding {
+    pub keys: &'static [KeyCode],
+    pub action: Action,
+    pub category: Category,
+    pub description: &'static str,
+}
+
+pub const KEYMAP: &[Binding] = &[
+    Binding {
+        keys: &[KeyCode::Char('n')],
+        action: Action::NextNetwork,
+        category: Category::Navigation,
+        description: "show the next configured network",
+    },
+    Binding {
+        keys: &[KeyCode::Char('N')],
+        action: Action::PrevNetwork,
+        category: Category::Navigation,
+        description: "show the previous configured network",
+    },
+    Binding {
+        keys: &[KeyCode::Down, KeyCode::Char('j')],
+        action: Action::SelectOlder,
+        category: Category::Navigation,
+        description: "select the next older block in the tape",
+    },
+    Binding {
+        keys: &[KeyCode::Up, KeyCode::Char('k')],
+        action: Action::SelectNewer,
+        category: Category::Navigation,
+        description: "select the next newer block in the tape",
+    },
+    Binding {
+        keys: &[KeyCode::Char('t')],
+        action: Action::SwitchTape,
+        category: Category::Navigation,
+        description: "move the selection between the host and rollup tapes",
+    },
+    Binding {
+        keys: &[KeyCode::Char('o')],
+        action: Action::OpenExplorer,
+        category: Category::Actions,
+        description: "open the selected block in the block explorer",
+    },
+    Binding {
+        keys: &[KeyCode::Char('f')],
+        action: Action::ToggleHostFilter,
+        category: Category::Actions,
+        description: "toggle the host contract tx-pool filter",
+    },
+    Binding {
+        keys: &[KeyCode::Char('u')],
+        action: Action::OpenEndpointPicker,
+        category: Category::Actions,
+        description: "switch the RPC endpoint of the shown network",
+    },
+    Binding {
+        keys: &[KeyCode::Char('p')],
+        action: Action::OpenProfilePicker,
+        category: Category::Actions,
+        description: "switch to another config file profile",
+    },
+    Binding {
+        keys: &[KeyCode::Char('a')],
+        action: Action::AcknowledgeAlerts,
+        category: Category::Actions,
+        description: "acknowledge active alerts (collapses the banner)",
+    },
+    Binding {
+        keys: &[KeyCode::Char(' ')],
+        action: Action::TogglePause,
+        category: Category::Actions,
+        description: "pause refreshing and alert checks, or resume",
+    },
+    Binding {
+        keys: &[KeyCode::Char('q'), KeyCode::Esc],
+        action: Action::Quit,
+        category: Category::Actions,
+        description: "quit",
+    },
+    Binding {
+        keys: &[KeyCode::Tab],
+        action: Action::NextTab,
+        category: Category::Views,
+        description: "cycle the Dashboard, Gas, Alerts, Gas Users, Blobs, Watchlist, and Diagnostics tabs",
+    },
+    Binding {
+        keys: &[KeyCode::Char('?')],
+        action: Action::ToggleHelp,
+        category: Category::Views,
+        description: "show or hide this help",
+    },
+    Binding {
+        keys: &[KeyCode::Char('L')],
+        action: Action::EditLayout,
+        category: Category::Views,
+        description: "show, hide, and reorder panels",
+    },
+    Binding {
+        keys: &[KeyCode::Char('/')],
+        action: Action::SearchBlocks,
+        category: Category::Views,
+        description: "search the block tapes",
+    },
+    Binding {
+        keys: &[KeyCode::Char(']')],
+        action: Action::NextMatch,
+        category: Category::Views,
+        description: "select the next search match",
+    },
+    Binding {
+        keys: &[KeyCode::Char('[')],
+        action: Action::PrevMatch,
+        category: Category::Views,
+        description: "select the previous search match",
+    },
+    Binding {
+        keys: &[KeyCode::Backspace],
+        action: Action::ClearSearch,
+        category: Category::Views,
+        description: "clear the search and selection",
+    },
+    Binding {
+        keys: &[KeyCode::Char('+'), KeyCode::Char('=')],
+        action: Action::ZoomIn,
+        category: Category::Views,
+        description: "zoom the gas and block-time charts in",
+    },
+    Binding {
+        keys: &[KeyCode::Char('-')],
+        action: Action::ZoomOut,
+        category: Category::Views,
+        description: "zoom the charts out (up to a week)",
+    },
+    Binding {
+        keys: &[KeyCode::Left],
+        action: Action::PanBack,
+        category: Category::Views,
+        description: "pan the charts back in time",
+    },
+    Binding {
+        keys: &[KeyCode::Right],
+        action: Action::PanForward,
+        category: Category::Views,
+        description: "pan the charts toward now",
+    },
+    Binding {
+        keys: &[KeyCode::Up, KeyCode::Char('k')],
+        action: Action::AlertKindUp,
+        category: Category::AlertsTab,
+        description: "previous alert type",
+    },
+    Binding {
+        keys: &[KeyCode::Down, KeyCode::Char('j')],
+        action: Action::AlertKindDown,
+        category: Category::AlertsTab,
+        description: "next alert type",
+    },
+    Binding {
+        keys: &[KeyCode::Char('m')],
+        action: Action::ToggleMute,
+        category: Category::AlertsTab,
+        description: "mute the selected alert type, or unmute it",
+    },
+    Binding {
+        keys: &[KeyCode::Char('s')],
+        action: Action::Snooze,
+        category: Category::AlertsTab,
+        description: "snooze the selected alert type for a number of minutes",
+    },
+    Binding {
+        keys: &[KeyCode::Up, KeyCode::Char('k')],
+        action: Action::PickerUp,
+        category: Category::EndpointPicker,
+        description: "previous URL",
+    },
+    Binding {
+        keys: &[KeyCode::Down, KeyCode::Char('j')],
+        action: Action::PickerDown,
+        category: Category::EndpointPicker,
+        description: "next URL",
+    },
+    Binding {
+        keys: &[KeyCode::Tab],
+        action: Action::PickerToggleTarget,
+        category: Category::EndpointPicker,
+        description: "switch between host and rollup",
+    },
+    Binding {
+        keys: &[KeyCode::Char('e')],
+        action: Action::PickerCustomUrl,
+        category: Category::EndpointPicker,
+        description: "type a custom URL",
+    },
+    Binding {
+        keys: &[KeyCode::Enter],
+        action: Action::PickerSwitch,
+        category: Category::EndpointPicker,
+        description: "reconnect to the selected URL",
+    },
+    Binding {
+        keys: &[KeyCode::Esc, KeyCode::Char('q')],
+        action: Action::PickerCancel,
+        category: Category::EndpointPicker,
+        description: "close the picker",
+    },
+    Binding {
+        keys: &[KeyCode::Up, KeyCode::Char('k')],
+        action: Action::ProfileUp,
+        category: Category::ProfilePicker,
+        description: "previous profile",
+    },
+    Binding {
+        keys: &[KeyCode::Down, KeyCode::Char('j')],
+        action: Action::ProfileDown,
+        category: Category::ProfilePicker,
+        description: "next profile",
+    },
+    Binding {
+        keys: &[KeyCode::Enter],
+        action: Action::ProfileSwitch,
+        category: Category::ProfilePicker,
+        description: "rebuild the networks from the selected profile",
+    },
+    Binding {
+        keys: &[KeyCode::Esc, KeyCode::Char('q')],
+        action: Action::ProfileCancel,
+        category: Category::ProfilePicker,
+        description: "close the picker",
+    },
+    Binding {
+        keys: &[KeyCode::Up, KeyCode::Char('k')],
+        action: Action::LayoutUp,
+        category: Category::LayoutEditor,
+        description: "previous panel",
+    },
+    Binding {
+        keys: &[KeyCode::Down, KeyCode::Char('j')],
+        action: Action::LayoutDown,
+        category: Category::LayoutEditor,
+        description: "next panel",
+    },
+    Binding {
+        keys: &[KeyCode::Char(' ')],
+        action: Action::LayoutToggle,
+        category: Category::LayoutEditor,
+        description: "show or hide the selected panel",
+    },
+    Binding {
+        keys: &[KeyCode::Char('K')],
+        action: Action::LayoutMoveUp,
+        category: Category::LayoutEditor,
+        description: "move the selected panel up",
+    },
+    Binding {
+        keys: &[KeyCode::Char('J')],
+        action: Action::LayoutMoveDown,
+        category: Category::LayoutEditor,
+        description: "move the selected panel down",
+    },
+    Binding {
+        keys: &[KeyCode::Enter, KeyCode::Esc, KeyCode::Char('L')],
+        action: Action::LayoutClose,
+        category: Category::LayoutEditor,
+        description: "close the editor",
+    },
+];
+
+/// The action bound to `code` within `category`.
+pub fn action_for(category: Category, code: KeyCode) -> Option<Action> {
+    KEYMAP
+        .iter()
+        .find(|binding| binding.category == category && binding.keys.contains(&code))
+        .map(|binding| binding.action)
+}
+
+/// The dashboard's global bindings: everything outside modal contexts.
+pub fn dashboard_action(code: KeyCode) -> Option<Action> {
+    [Category::Navigation, Category::Actions, Category::Views]
+        .into_iter()
+        .find_map(|category| action_for(category, code))
+}
+
+/// Keys bound to `action`, formatted for display, e.g. "q / Esc".
+pub fn keys_for(action: Action) -> String {
+    KEYMAP
+        .iter()
+        .find(|binding| binding.action == action)
+        .map(|binding| {
+            binding
+                .keys
+                .iter()
+                .map(|key| key_label(*key))
+                .collect::<Vec<_>>()
+                .join(" / ")
+        })
+        .unwrap_or_default()
+}
+
+pub fn key_label(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, Category, KEYMAP, action_for, dashboard_action, keys_for};
+    use crossterm::event::KeyCode;
+
+    #[test]
+    fn keys_are_unique_within_each_context() {
+        let contexts: [&[Category]; 5] = [
+            &[Category::Navigation, Category::Actions, Category::Views],
+            &[Category::EndpointPicker],
+            &[Category::ProfilePicker],
+            &[Category::LayoutEditor],
+            &[Category::AlertsTab],
+        ];
+        for context in contexts {
+            let mut seen = Vec::new();
+            for binding in KEYMAP.iter().filter(|b| context.contains(&b.category)) {
+                for key in binding.keys {
+                    assert!(
+                        !seen.contains(key),
+                        "{:?} bound twice in {:?}",
+                        key,
+                        context
+                    );
+                    seen.push(*key);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lookups_respect_context() {
+        assert_eq!(dashboard_action(KeyCode::Esc), Some(Action::Quit));
+        assert_eq!(
+            action_for(Category::EndpointPicker, KeyCode::Esc),
+            Some(Action::PickerCancel)
+        );
+        assert_eq!(dashboard_action(KeyCode::Tab), Some(Action::NextTab));
+        assert_eq!(
+            action_for(Category::EndpointPicker, KeyCode::Tab),
+            Some(Action::PickerToggleTarget)
+        );
+        assert_eq!(keys_for(Action::Quit), "q / Esc");
+    }
+}
@@ -1,177 +1,597 @@
+mod aa;
+mod address_book;
+mod alerts;
+mod app;
+mod availability;
+mod bandwidth;
+mod beacon;
+mod block_size;
+mod builders;
+mod bundles;
+mod capabilities;
+mod chains;
+mod chart;
+mod check;
+mod cli;
+mod compare;
 mod config;
 mod data;
+mod deployments;
+mod diagnose;
+mod email;
+mod error;
+mod explorer;
+mod forks;
+mod gas_oracle;
+mod history;
+mod http;
+mod influx;
+mod jwt;
+mod keymap;
+mod mirror;
+mod network;
+mod notify;
+mod orders;
+mod otlp;
+mod paging;
+mod probes;
+mod proofs;
+mod record;
+mod replay;
+mod report;
+mod retention;
+mod rpc_stats;
+mod sequencer;
+mod session;
+mod settings;
+mod state;
+mod statsd;
+mod stream;
+mod thresholds;
+mod timeseries;
+mod trace;
+mod transfers;
+mod tx_types;
+mod txpool_stream;
 mod ui;
+mod units;
+mod watchlist;
 
-use clap::{Parser, value_parser};
+use address_book::AddressBook;
+use alloy::primitives::Address;
+use app::{App, Flow, Message};
+use builders::BuilderLabels;
+use clap::Parser;
+use cli::{
+    CheckArgs, Cli, Command, ExportArgs, MirrorArgs, RangeArgs, ReplayArgs, ReportArgs, WatchArgs,
+};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyModifiers,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use data::{Config, MetricsCollector};
-use eyre::Result;
-use ratatui::{Terminal, backend::CrosstermBackend};
+use error::{FutureSightError, Result};
+use futures::FutureExt;
+use http::HttpOptions;
+use keymap::Action;
+use mirror::Mirror;
+use network::{ChainRole, Network};
+use ratatui::{Terminal, backend::CrosstermBackend, layout::Rect};
+use record::Sample;
+use replay::Replay;
+use settings::{NetworkSettings, Settings};
 use std::{
+    collections::HashMap,
     io::{Stdout, stdout},
     time::{Duration, Instant},
 };
-use ui::Dashboard;
+use thresholds::{ChainThresholds, DelayThresholds};
 
 type CrosstermTerminal = Terminal<CrosstermBackend<Stdout>>;
 
-#[derive(Parser, Debug)]
-#[command(
-    name = env!("CARGO_PKG_NAME"),
-    version = env!("CARGO_PKG_VERSION"),
-    about = "FutureSight is a terminal dashboard for Ethereum RPC and tx-pool telemetry.",
-    next_line_help = true
-)]
-struct Cli {
-    /// Host (L1) JSON-RPC endpoint.
-    #[arg(
-        long = "host-rpc-url",
-        env = "HOST_RPC_URL",
-        default_value = "https://host-rpc.parmigiana.signet.sh"
-    )]
-    host_rpc_url: String,
-
-    /// Rollup (L2) JSON-RPC endpoint.
-    #[arg(
-        long = "rollup-rpc-url",
-        env = "ROLLUP_RPC_URL",
-        default_value = "https://rpc.parmigiana.signet.sh"
-    )]
-    rollup_rpc_url: String,
-
-    /// Seconds before a block delay alert is shown.
-    #[arg(
-        long = "block-delay-secs",
-        env = "BLOCK_DELAY_SECS",
-        default_value_t = crate::config::BLOCK_DELAY_DEFAULT,
-        value_parser = value_parser!(u64).range(1..)
-    )]
-    block_delay_secs: u64,
-
-    /// Metrics refresh interval in seconds.
-    #[arg(
-        long,
-        short,
-        env = "REFRESH_INTERVAL",
-        default_value_t = crate::config::DEFAULT_REFRESH_INTERVAL,
-        value_parser = value_parser!(u64).range(1..)
-    )]
-    refresh_interval: u64,
-
-    /// Base URL for the network tx-pool service.
-    #[arg(long, env = "TXPOOL_URL")]
-    txpool_url: Option<String>,
-
-    /// Maximum tx-pool rows rendered per panel.
-    #[arg(
-        long = "txpool-max-rows",
-        env = "TXPOOL_MAX_ROWS",
-        default_value_t = crate::config::DEFAULT_TXPOOL_MAX_ROWS
-    )]
-    txpool_max_rows: usize,
-
-    /// Disable fetching and displaying tx-pool transactions.
-    #[arg(long = "no-txpool-list", default_value_t = false)]
-    txpool_disable_list: bool,
-
-    /// Number of recent blocks retained in memory.
-    #[arg(
-        long = "max-block-history",
-        env = "MAX_BLOCK_HISTORY",
-        default_value_t = crate::config::DEFAULT_MAX_BLOCK_HISTORY
-    )]
-    max_block_history: usize,
-}
-
 #[tokio::main]
-async fn main() -> Result<()> {
-    run(Cli::parse()).await
+async fn main() {
+    let code = match run(Cli::parse().into_command()).await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            e.exit_code()
+        }
+    };
+    std::process::exit(code);
 }
 
-async fn run(cli: Cli) -> Result<()> {
-    if cli.txpool_max_rows == 0 {
-        return Err(eyre::eyre!("--txpool-max-rows must be at least 1"));
+/// Run a subcommand to its exit code.
+async fn run(command: Command) -> Result<i32> {
+    match command {
+        Command::Watch(args) => Ok(run_watch(args).await?.detailed_exit_code()),
+        Command::Check(args) => {
+            let detailed = args.detailed_exit_codes;
+            let status = run_check(args).await;
+            Ok(if detailed {
+                status.detailed_exit_code()
+            } else {
+                status.exit_code()
+            })
+        }
+        Command::Report(args) => run_report(args).map(|()| 0),
+        Command::Export(args) => run_export(args).map(|()| 0),
+        Command::Completions(args) => {
+            cli::write_completions(args.shell, &mut std::io::stdout());
+            Ok(0)
+        }
+        Command::Mirror(args) => run_mirror(args).await.map(|()| 0),
+        Command::Replay(args) => run_replay(args).map(|()| 0),
     }
-    if cli.max_block_history == 0 {
-        return Err(eyre::eyre!("--max-block-history must be at least 1"));
+}
+
+async fn run_mirror(args: MirrorArgs) -> Result<()> {
+    let mut mirror = Mirror::spawn(&args.url, &args.http.options()?)?;
+    let mut terminal = TerminalSession::enter()?;
+    loop {
+        mirror.drain();
+        terminal.draw(|frame| ui::render_mirror(frame, &mirror))?;
+        if viewer_quit(&mut terminal)? {
+            return Ok(());
+        }
     }
+}
 
-    let mut dashboard = Dashboard::new(cli.refresh_interval);
+fn run_replay(args: ReplayArgs) -> Result<()> {
+    let (samples, _, _) = load_range(&args.range)?;
+    let record = args.range.record.display().to_string();
+    let mut replay = Replay::new(record, samples, args.speed)?;
     let mut terminal = TerminalSession::enter()?;
+    let mut last = Instant::now();
+    loop {
+        let now = Instant::now();
+        replay.advance(now - last);
+        last = now;
+        terminal.draw(|frame| ui::render_replay(frame, &replay))?;
+        if viewer_quit(&mut terminal)? {
+            return Ok(());
+        }
+    }
+}
 
-    let mut host_collector = MetricsCollector::new_with_txpool(
-        Config {
-            rpc_url: cli.host_rpc_url.clone(),
-            block_delay_threshold: cli.block_delay_secs,
-            max_block_history: cli.max_block_history,
-            txpool_max_rows: cli.txpool_max_rows,
-            txpool_fetch_list: !cli.txpool_disable_list,
-            txpool_watch_signet_host_contracts: true,
-        },
-        cli.txpool_url.clone(),
-    )?;
-
-    let mut rollup_collector = MetricsCollector::new_with_txpool(
-        Config {
-            rpc_url: cli.rollup_rpc_url.clone(),
-            block_delay_threshold: cli.block_delay_secs,
-            max_block_history: cli.max_block_history,
-            txpool_max_rows: cli.txpool_max_rows,
-            txpool_fetch_list: !cli.txpool_disable_list,
-            txpool_watch_signet_host_contracts: false,
+/// Wait up to 200ms for input on a read-only screen (`mirror`, `replay`); true once the
+/// user quits.
+fn viewer_quit(terminal: &mut TerminalSession) -> Result<bool> {
+    if !event::poll(Duration::from_millis(200)).map_err(FutureSightError::Terminal)? {
+        return Ok(false);
+    }
+    match event::read().map_err(FutureSightError::Terminal)? {
+        #[cfg(unix)]
+        Event::Key(key)
+            if key.code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL) =>
+        {
+            terminal.suspend()?
+        }
+        Event::Key(key) if keymap::dashboard_action(key.code) == Some(Action::Quit) => {
+            return Ok(true);
+        }
+        Event::Resize(columns, rows) => terminal.resize(columns, rows)?,
+        _ => {}
+    }
+    Ok(false)
+}
+
+/// Probe once and print a single status line. Setup failures such as a malformed RPC URL
+/// or an unreadable CA bundle are reported as disconnected, so monitoring sees CRITICAL
+/// rather than a bare error exiting 1 (WARNING).
+async fn run_check(args: CheckArgs) -> check::CheckStatus {
+    let rpc_url = args.rpc_url.clone();
+    let max_block_age = args.max_block_age;
+    let mut collector = match check_collector(args) {
+        Ok(collector) => collector,
+        Err(e) => {
+            let status = check::CheckStatus::Disconnected;
+            println!("{} - {}: {}", status.label(), rpc_url, e);
+            return status;
+        }
+    };
+    let metrics = collector.collect_metrics().await;
+
+    let (status, summary) = check::evaluate(metrics, max_block_age, record::unix_now());
+    println!("{} - {}", status.label(), summary);
+    status
+}
+
+fn check_collector(args: CheckArgs) -> Result<MetricsCollector> {
+    MetricsCollector::new(Config {
+        rpc_url: args.rpc_url,
+        block_delay: DelayThresholds {
+            warning: args.max_block_age,
+            critical: args.max_block_age,
         },
-        cli.txpool_url.clone(),
-    )?;
+        expected_block_time: None,
+        stale_after: Duration::from_secs(args.max_block_age),
+        gas_alert_gwei: None,
+        gas_spike_multiplier: None,
+        max_block_history: 1,
+        txpool_max_rows: 1,
+        txpool_fetch_list: false,
+        txpool_watch_signet_host_contracts: false,
+        txpool_stream: None,
+        fetch_receipts: false,
+        http: args.http.options()?,
+    })
+}
 
-    tokio::join!(
-        host_collector.collect_metrics(),
-        rollup_collector.collect_metrics()
-    );
+/// Samples from the recording that fall inside the requested range, plus the resolved bounds.
+fn load_range(range: &RangeArgs) -> Result<(Vec<Sample>, u64, u64)> {
+    let now = record::unix_now();
+    let from = report::parse_time(&range.from, now)?;
+    let to = report::parse_time(&range.to, now)?;
+    if from > to {
+        return Err(FutureSightError::Config(
+            "--from must not be after --to".to_string(),
+        ));
+    }
 
-    let refresh_every = Duration::from_secs(cli.refresh_interval);
-    let ui_tick = Duration::from_millis(200);
-    let mut last_refresh = Instant::now();
+    let samples = record::read_samples(&range.record)?
+        .into_iter()
+        .filter(|sample| sample.ts >= from && sample.ts <= to)
+        .collect();
+    Ok((samples, from, to))
+}
 
-    loop {
-        if last_refresh.elapsed() >= refresh_every {
-            tokio::join!(
-                host_collector.collect_metrics(),
-                rollup_collector.collect_metrics()
-            );
-            last_refresh = Instant::now();
-        }
+fn run_report(args: ReportArgs) -> Result<()> {
+    let (samples, from, to) = load_range(&args.range)?;
+    let reports = report::summarize(&samples, from, to);
+    print!("{}", report::render(&reports, from, to, args.format));
+    Ok(())
+}
+
+fn run_export(args: ExportArgs) -> Result<()> {
+    let (samples, _, _) = load_range(&args.range)?;
+    record::export(&samples, args.format, stdout().lock())
+}
+
+/// The config file's networks, or a single one built from the RPC URL flags.
+fn configured_networks(cli: &WatchArgs, networks: Vec<NetworkSettings>) -> Vec<NetworkSettings> {
+    if networks.is_empty() {
+        vec![NetworkSettings {
+            name: "default".to_string(),
+            host_rpc_url: cli.host_rpc_url.clone(),
+            rollup_rpc_url: Some(cli.rollup_rpc_url.clone()),
+            txpool_url: cli.txpool_url.clone(),
+            host_compare_rpc_url: cli.compare_rpc_url.clone(),
+            rollup_compare_rpc_url: cli.rollup_compare_rpc_url.clone(),
+            beacon_url: cli.beacon_url.clone(),
+            gas_oracles: Vec::new(),
+            host_explorer_url: cli.host_explorer_url.clone(),
+            rollup_explorer_url: cli.rollup_explorer_url.clone(),
+            host_gas_alert_gwei: None,
+            rollup_gas_alert_gwei: None,
+            gas_spike_multiplier: None,
+            host_expected_block_time: None,
+            rollup_expected_block_time: None,
+            large_transfer_wei: None,
+            block_delay_secs: None,
+            block_delay_warn_secs: None,
+            stale_after_secs: None,
+        }]
+    } else {
+        networks
+    }
+}
 
-        host_collector.check_staleness();
-        rollup_collector.check_staleness();
+/// One chain's alert thresholds: the network's config file keys win over the flags, which
+/// apply to every network.
+fn chain_thresholds(
+    cli: &WatchArgs,
+    network: &NetworkSettings,
+    role: ChainRole,
+) -> Result<ChainThresholds> {
+    let (gas_alert_gwei, expected_block_time) = match role {
+        ChainRole::Host => (
+            network.host_gas_alert_gwei.or(cli.host_gas_alert_gwei),
+            network.host_expected_block_time.or(cli.expected_block_time),
+        ),
+        ChainRole::Rollup => (
+            network.rollup_gas_alert_gwei.or(cli.rollup_gas_alert_gwei),
+            network
+                .rollup_expected_block_time
+                .or(cli.rollup_expected_block_time)
+                .or(cli.expected_block_time),
+        ),
+    };
+    let block_delay = DelayThresholds::resolve(
+        network.block_delay_warn_secs.or(cli.block_delay_warn_secs),
+        network.block_delay_secs.or(cli.block_delay_secs),
+        expected_block_time,
+    );
+    if block_delay.warning >= block_delay.critical {
+        return Err(FutureSightError::Config(format!(
+            "block delay warning ({}s) must be below --block-delay-secs ({}s)",
+            block_delay.warning, block_delay.critical
+        )));
+    }
+    if network.stale_after_secs == Some(0) {
+        return Err(FutureSightError::Config(format!(
+            "network '{}': stale_after_secs must be at least 1",
+            network.name
+        )));
+    }
+    Ok(ChainThresholds {
+        block_delay,
+        expected_block_time,
+        stale_after: network
+            .stale_after_secs
+            .map_or_else(|| cli.stale_after(), Duration::from_secs),
+        gas_alert_gwei,
+        gas_spike_multiplier: network.gas_spike_multiplier.or(cli.gas_spike_multiplier),
+        large_transfer_wei: network.large_transfer_wei.or(cli.large_transfer_wei),
+    })
+}
 
-        terminal.draw(|frame| {
-            dashboard.render(
-                frame,
-                host_collector.get_metrics(),
-                rollup_collector.get_metrics(),
+/// Apply the thresholds of a reloaded config file to the running networks, matched by name.
+/// Returns what changed per chain; nothing is applied if any network's thresholds are invalid.
+fn reload_thresholds(
+    cli: &WatchArgs,
+    settings: &Settings,
+    networks: &mut [Network],
+) -> Result<Vec<String>> {
+    let multi = networks.len() > 1;
+    let mut updates = Vec::new();
+    for configured in configured_networks(cli, settings.networks.clone()) {
+        let Some(index) = networks
+            .iter()
+            .position(|network| network.name == configured.name)
+        else {
+            continue;
+        };
+        for role in [ChainRole::Host, ChainRole::Rollup] {
+            let Some(old) = networks[index].thresholds(role) else {
+                continue;
+            };
+            let new = chain_thresholds(cli, &configured, role)?;
+            let changes = old.changes(&new);
+            if !changes.is_empty() {
+                updates.push((index, role, new, changes));
+            }
+        }
+    }
+    Ok(updates
+        .into_iter()
+        .map(|(index, role, thresholds, changes)| {
+            let network = &mut networks[index];
+            network.set_thresholds(role, thresholds);
+            format!(
+                "{} {}",
+                network.chain_label(role, multi),
+                changes.join(", ")
             )
-        })?;
+        })
+        .collect())
+}
+
+/// Networks from the config file, or a single network built from the RPC URL flags.
+fn build_networks(
+    cli: &WatchArgs,
+    settings: Settings,
+    address_book: &AddressBook,
+    http: &HttpOptions,
+) -> Result<Vec<Network>> {
+    let configured = configured_networks(cli, settings.networks);
+
+    if let Some(probe) = settings.probes.iter().find(|probe| {
+        probe
+            .network
+            .as_ref()
+            .is_some_and(|name| !configured.iter().any(|network| &network.name == name))
+    }) {
+        return Err(FutureSightError::Config(format!(
+            "probe '{}' names unknown network '{}'",
+            probe.label,
+            probe.network.as_deref().unwrap_or_default()
+        )));
+    }
+    let probes_for = |network: &str, role: ChainRole| {
+        settings
+            .probes
+            .iter()
+            .filter(|probe| probe.applies_to(network, role))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    let builder_labels = builder_labels(address_book, &settings.builders);
+    let slo = settings.slo;
+    let forks = &settings.forks;
+    let collector = |rpc_url: String,
+                     txpool_url: Option<String>,
+                     is_host: bool,
+                     thresholds: ChainThresholds| {
+        let mut collector = MetricsCollector::new_with_txpool(
+            Config {
+                rpc_url,
+                gas_alert_gwei: thresholds.gas_alert_gwei,
+                gas_spike_multiplier: thresholds.gas_spike_multiplier,
+                block_delay: thresholds.block_delay,
+                expected_block_time: thresholds.expected_block_time,
+                stale_after: thresholds.stale_after,
+                max_block_history: cli.max_block_history.max(cli.backfill),
+                txpool_max_rows: cli.txpool_max_rows,
+                txpool_fetch_list: !cli.txpool_disable_list,
+                txpool_watch_signet_host_contracts: is_host,
+                txpool_stream: cli.txpool_stream.clone(),
+                fetch_receipts: cli.receipts,
+                http: http.clone(),
+            },
+            txpool_url,
+        )?;
+        collector.set_builder_labels(builder_labels.clone());
+        collector.set_trace_blocks(cli.enable_trace);
+        collector.set_slo(slo);
+        collector.set_forks(forks.clone());
+        collector.set_thresholds(thresholds);
+        Ok::<_, FutureSightError>(collector)
+    };
 
-        if event::poll(ui_tick)?
-            && let Event::Key(key) = event::read()?
+    let mut networks: Vec<Network> = Vec::with_capacity(configured.len());
+    for network in configured {
+        if networks
+            .iter()
+            .any(|existing| existing.name == network.name)
         {
-            match key.code {
-                KeyCode::Char('q') | KeyCode::Esc => dashboard.quit(),
-                KeyCode::Char('f') => host_collector.toggle_host_contract_filter(),
-                _ => {}
+            return Err(FutureSightError::Config(format!(
+                "duplicate network name '{}'",
+                network.name
+            )));
+        }
+        let host_thresholds = chain_thresholds(cli, &network, ChainRole::Host)?;
+        let rollup_thresholds = network
+            .rollup_rpc_url
+            .is_some()
+            .then(|| chain_thresholds(cli, &network, ChainRole::Rollup))
+            .transpose()?;
+        let mut host = collector(
+            network.host_rpc_url,
+            network.txpool_url.clone(),
+            true,
+            host_thresholds,
+        )?;
+        let mut rollup = network
+            .rollup_rpc_url
+            .zip(rollup_thresholds)
+            .map(|(url, thresholds)| collector(url, network.txpool_url, false, thresholds))
+            .transpose()?;
+        host.set_probes(
+            probes_for(&network.name, ChainRole::Host),
+            cli.watch_slots.clone(),
+        );
+        host.set_verified_accounts(cli.verify_accounts.clone());
+        host.set_jitter_limit(cli.max_block_jitter);
+        host.set_block_size_limit(cli.block_size_limit);
+        if let Some(url) = network.host_compare_rpc_url {
+            host.set_compare_rpc(url, cli.compare_max_lag)?;
+        }
+        if let Some(url) = network.beacon_url {
+            host.set_beacon_url(url)?;
+        }
+        host.set_gas_oracles(if network.gas_oracles.is_empty() {
+            cli.gas_oracles.clone()
+        } else {
+            network.gas_oracles
+        })?;
+        if let Some(rollup) = &mut rollup {
+            rollup.set_probes(
+                probes_for(&network.name, ChainRole::Rollup),
+                cli.rollup_watch_slots.clone(),
+            );
+            rollup.set_verified_accounts(cli.rollup_verify_accounts.clone());
+            rollup.set_jitter_limit(cli.rollup_max_block_jitter.or(cli.max_block_jitter));
+            rollup.set_block_size_limit(cli.rollup_block_size_limit);
+            if let Some(url) = network.rollup_compare_rpc_url {
+                rollup.set_compare_rpc(url, cli.compare_max_lag)?;
             }
         }
+        let mut built = Network::new(network.name, host, rollup);
+        built.host_explorer = network.host_explorer_url;
+        built.rollup_explorer = network.rollup_explorer_url;
+        built.http = http.clone();
+        networks.push(built);
+    }
+    Ok(networks)
+}
+
+/// Built-in builder labels, then address book labels, then config `[builders]`.
+fn builder_labels(
+    address_book: &AddressBook,
+    builders: &HashMap<Address, String>,
+) -> BuilderLabels {
+    BuilderLabels::with_overrides(address_book.entries().chain(builders.clone()))
+}
+
+/// RPC URLs offered by the endpoint picker: config `endpoints`, every network's URLs,
+/// then the URL flags, without duplicates.
+fn endpoint_options(cli: &WatchArgs, settings: &Settings) -> Vec<String> {
+    let mut options: Vec<String> = Vec::new();
+    let candidates = settings
+        .endpoints
+        .iter()
+        .chain(settings.networks.iter().flat_map(|network| {
+            std::iter::once(&network.host_rpc_url).chain(network.rollup_rpc_url.as_ref())
+        }))
+        .chain([&cli.host_rpc_url, &cli.rollup_rpc_url]);
+    for url in candidates {
+        if !options.contains(url) {
+            options.push(url.clone());
+        }
+    }
+    options
+}
 
-        if dashboard.should_quit {
+async fn run_watch(cli: WatchArgs) -> Result<check::CheckStatus> {
+    let backfill = cli.backfill;
+    let mut app = App::new(cli).await?;
+    let mut terminal = TerminalSession::enter()?;
+
+    if backfill > 0 {
+        app.set_notice(format!("backfilling {} blocks…", backfill));
+        terminal.draw(|frame| app.draw(frame))?;
+        app.backfill(backfill).await;
+    }
+    app.collect_all().await;
+
+    let ui_tick = Duration::from_millis(200);
+    // Raw mode turns Ctrl-Z into a key press; this catches `kill -TSTP` from elsewhere
+    #[cfg(unix)]
+    let mut sigtstp =
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::from_raw(libc::SIGTSTP))
+            .map_err(FutureSightError::Terminal)?;
+
+    loop {
+        if app.update(Message::Tick)? == Flow::Quit {
             break;
         }
+
+        #[cfg(unix)]
+        if let Some(Some(())) = sigtstp.recv().now_or_never() {
+            terminal.suspend()?;
+            app.update(Message::Redraw)?;
+        }
+
+        if app.frame_due(Instant::now()) {
+            terminal.draw(|frame| app.draw(frame))?;
+        }
+
+        // Wake for the next frame or pass, whichever comes first, unless input arrives
+        let wait = app.until_frame(Instant::now()).min(ui_tick);
+        if !event::poll(wait).map_err(FutureSightError::Terminal)? {
+            continue;
+        }
+        let message = match event::read().map_err(FutureSightError::Terminal)? {
+            Event::Key(key) => Message::Key(key),
+            Event::Paste(text) => Message::Paste(text),
+            Event::Resize(columns, rows) => {
+                terminal.resize(columns, rows)?;
+                Message::Redraw
+            }
+            _ => continue,
+        };
+        match app.update(message)? {
+            Flow::Continue => {}
+            #[cfg(unix)]
+            Flow::Suspend => {
+                terminal.suspend()?;
+                app.update(Message::Redraw)?;
+            }
+            #[cfg(not(unix))]
+            Flow::Suspend => {}
+            Flow::Quit => break,
+        }
     }
 
-    Ok(())
+    // Leave the alternate screen first so the summary stays in the shell's scrollback
+    drop(terminal);
+    app.finish()
 }
 
 struct TerminalSession {
@@ -180,31 +600,70 @@ struct TerminalSession {
 
 impl TerminalSession {
     fn enter() -> Result<Self> {
-        enable_raw_mode()?;
         let mut out = stdout();
-        execute!(out, EnterAlternateScreen, EnableMouseCapture)?;
+        Self::take_over(&mut out)?;
         let backend = CrosstermBackend::new(out);
-        let terminal = Terminal::new(backend)?;
+        let terminal = Terminal::new(backend).map_err(FutureSightError::Terminal)?;
         Ok(Self { terminal })
     }
 
+    fn take_over(out: &mut impl std::io::Write) -> Result<()> {
+        enable_raw_mode().map_err(FutureSightError::Terminal)?;
+        execute!(
+            out,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )
+        .map_err(FutureSightError::Terminal)?;
+        Ok(())
+    }
+
+    fn hand_back(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(
+            self.terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            DisableBracketedPaste
+        );
+        let _ = self.terminal.show_cursor();
+    }
+
+    /// Give the shell its terminal back and stop until `fg` sends SIGCONT, then retake it.
+    #[cfg(unix)]
+    fn suspend(&mut self) -> Result<()> {
+        self.hand_back();
+        // SIGSTOP, because SIGTSTP is caught by our own listener
+        unsafe {
+            libc::raise(libc::SIGSTOP);
+        }
+        Self::take_over(self.terminal.backend_mut())?;
+        self.terminal.clear().map_err(FutureSightError::Terminal)?;
+        Ok(())
+    }
+
     fn draw<F>(&mut self, render_fn: F) -> Result<()>
     where
         F: FnOnce(&mut ratatui::Frame),
     {
-        self.terminal.draw(render_fn)?;
+        self.terminal
+            .draw(render_fn)
+            .map_err(FutureSightError::Terminal)?;
+        Ok(())
+    }
+
+    /// Adopt the new terminal size and clear, so no cells from the old layout survive.
+    fn resize(&mut self, columns: u16, rows: u16) -> Result<()> {
+        self.terminal
+            .resize(Rect::new(0, 0, columns, rows))
+            .map_err(FutureSightError::Terminal)?;
         Ok(())
     }
 }
 
 impl Drop for TerminalSession {
     fn drop(&mut self) {
-        let _ = disable_raw_mode();
-        let _ = execute!(
-            self.terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        );
-        let _ = self.terminal.show_cursor();
+        self.hand_back();
     }
 }
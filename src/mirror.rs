@@ -0,0 +1,251 @@
+//! `futuresight mirror <url>`: a read-only view of another instance's `--stream-listen`
+//! events, so a dashboard running on a server can be watched without reaching its RPCs
+use crate::alerts::AlertKind;
+use crate::config::{MIRROR_ALERT_ROWS, TXPOOL_STREAM_RETRY, TXPOOL_TIMEOUT_SECS};
+use crate::error::{FutureSightError, Result};
+use crate::http::HttpOptions;
+use crate::record::Sample;
+use crate::txpool_stream::SseParser;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use url::Url;
+
+/// An `event: alert` from the remote instance.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RemoteAlert {
+    pub chain: String,
+    pub kind: String, // AlertKind::name
+    pub severity: String,
+    pub started: u64, // unix seconds
+    pub summary: String,
+}
+
+impl RemoteAlert {
+    /// The kind's label, or its raw name if this build doesn't know it.
+    pub fn label(&self) -> &str {
+        AlertKind::from_str(&self.kind, false)
+            .map(AlertKind::label)
+            .unwrap_or(&self.kind)
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.severity == "critical"
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MirrorMessage {
+    Connected,
+    Disconnected(String),
+    Sample(Box<Sample>),
+    Alert(RemoteAlert),
+}
+
+impl MirrorMessage {
+    /// A stream event as a message; `None` for events this build doesn't understand.
+    fn parse(event: Option<&str>, data: &str) -> Option<Self> {
+        match event? {
+            "sample" => serde_json::from_str(data).ok().map(Self::Sample),
+            "alert" => serde_json::from_str(data).ok().map(Self::Alert),
+            _ => None,
+        }
+    }
+}
+
+/// The remote's chains and recent alerts, fed by a background subscription.
+#[derive(Debug)]
+pub struct Mirror {
+    pub url: String,
+    pub chains: Vec<Sample>,           // in the order first seen
+    pub alerts: VecDeque<RemoteAlert>, // newest first
+    pub connected: bool,
+    pub error: Option<String>,
+    pub last_event: Option<Instant>,
+    messages: UnboundedReceiver<MirrorMessage>,
+}
+
+impl Mirror {
+    pub fn spawn(url: &str, options: &HttpOptions) -> Result<Self> {
+        let url = events_url(url)?;
+        // No overall timeout: the response body stays open for as long as the stream runs
+        let http = options
+            .client_builder()?
+            .connect_timeout(Duration::from_secs(TXPOOL_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| FutureSightError::Config(e.to_string()))?;
+        let (sender, messages) = mpsc::unbounded_channel();
+        tokio::spawn(subscribe(http, url.clone(), sender));
+        Ok(Self {
+            url,
+            chains: Vec::new(),
+            alerts: VecDeque::new(),
+            connected: false,
+            error: None,
+            last_event: None,
+            messages,
+        })
+    }
+
+    /// Apply the messages received since the last call.
+    pub fn drain(&mut self) {
+        while let Ok(message) = self.messages.try_recv() {
+            self.apply(message, Instant::now());
+        }
+    }
+
+    fn apply(&mut self, message: MirrorMessage, now: Instant) {
+        match message {
+            MirrorMessage::Connected => {
+                self.connected = true;
+                self.error = None;
+            }
+            MirrorMessage::Disconnected(reason) => {
+                self.connected = false;
+                self.error = Some(reason);
+            }
+            MirrorMessage::Sample(sample) => {
+                self.last_event = Some(now);
+                match self
+                    .chains
+                    .iter_mut()
+                    .find(|chain| chain.chain == sample.chain)
+                {
+                    Some(chain) => *chain = *sample,
+                    None => self.chains.push(*sample),
+                }
+            }
+            MirrorMessage::Alert(alert) => {
+                self.last_event = Some(now);
+                self.alerts.push_front(alert);
+                self.alerts.truncate(MIRROR_ALERT_ROWS);
+            }
+        }
+    }
+}
+
+/// The stream URL: a bare `http://host:port` gets the `/events` path.
+fn events_url(raw: &str) -> Result<String> {
+    let mut url = Url::parse(raw)
+        .map_err(|e| FutureSightError::Config(format!("invalid mirror URL '{}': {}", raw, e)))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(FutureSightError::Config(format!(
+            "mirror URL '{}' must be http or https",
+            raw
+        )));
+    }
+    if url.path() == "/" {
+        url.set_path("/events");
+    }
+    Ok(url.to_string())
+}
+
+async fn subscribe(http: reqwest::Client, url: String, sender: UnboundedSender<MirrorMessage>) {
+    loop {
+        let reason = match read_stream(&http, &url, &sender).await {
+            Ok(()) => "stream closed".to_string(),
+            Err(e) => e.to_string(),
+        };
+        if sender.send(MirrorMessage::Disconnected(reason)).is_err() {
+            return;
+        }
+        tokio::time::sleep(TXPOOL_STREAM_RETRY).await;
+    }
+}
+
+async fn read_stream(
+    http: &reqwest::Client,
+    url: &str,
+    sender: &UnboundedSender<MirrorMessage>,
+) -> Result<()> {
+    let stream_error = |e: reqwest::Error| FutureSightError::Config(e.to_string());
+    let closed = |_| FutureSightError::Config("mirror receiver dropped".to_string());
+    let mut resp = http
+        .get(url)
+        .header(reqwest::header::ACCEPT, "text/event-stream")
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(stream_error)?;
+    sender.send(MirrorMessage::Connected).map_err(closed)?;
+    let mut parser = SseParser::default();
+    while let Some(chunk) = resp.chunk().await.map_err(stream_error)? {
+        for (event, data) in parser.push(&chunk) {
+            if let Some(message) = MirrorMessage::parse(event.as_deref(), &data) {
+                sender.send(message).map_err(closed)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Mirror, MirrorMessage, events_url};
+    use crate::config::MIRROR_ALERT_ROWS;
+    use std::{collections::VecDeque, time::Instant};
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn applies_samples_per_chain_and_keeps_recent_alerts() {
+        let (_sender, messages) = mpsc::unbounded_channel();
+        let mut mirror = Mirror {
+            url: String::new(),
+            chains: Vec::new(),
+            alerts: VecDeque::new(),
+            connected: false,
+            error: None,
+            last_event: None,
+            messages,
+        };
+        let sample = |chain: &str, block: u64| {
+            MirrorMessage::parse(
+                Some("sample"),
+                &format!(
+                    r#"{{"ts":1,"chain":"{}","status":"connected","block_number":{}}}"#,
+                    chain, block
+                ),
+            )
+            .unwrap()
+        };
+        let now = Instant::now();
+        mirror.apply(sample("host", 1), now);
+        mirror.apply(sample("rollup", 7), now);
+        mirror.apply(sample("host", 2), now);
+        let blocks: Vec<_> = mirror
+            .chains
+            .iter()
+            .map(|chain| (chain.chain.as_str(), chain.block_number))
+            .collect();
+        assert_eq!(blocks, [("host", Some(2)), ("rollup", Some(7))]);
+
+        let alert = r#"{"chain":"host","kind":"block-delay","severity":"critical","started":5,"summary":"95s"}"#;
+        for _ in 0..=MIRROR_ALERT_ROWS {
+            let message = MirrorMessage::parse(Some("alert"), alert).unwrap();
+            mirror.apply(message, now);
+        }
+        assert_eq!(mirror.alerts.len(), MIRROR_ALERT_ROWS);
+        assert_eq!(mirror.alerts[0].label(), "block delay");
+        assert!(mirror.alerts[0].is_critical());
+
+        assert_eq!(MirrorMessage::parse(None, "{}"), None);
+        assert_eq!(MirrorMessage::parse(Some("alert"), "{}"), None);
+    }
+
+    #[test]
+    fn defaults_the_stream_path() {
+        assert_eq!(
+            events_url("http://server:9470").unwrap(),
+            "http://server:9470/events"
+        );
+        assert_eq!(
+            events_url("https://server/futuresight/events").unwrap(),
+            "https://server/futuresight/events"
+        );
+        assert!(events_url("server:9470").is_err());
+    }
+}
@@ -0,0 +1,407 @@
+//! A named host/rollup pair and its refresh schedule
+use crate::builders::BuilderLabels;
+use crate::data::{MetricsCollector, SignetMetrics, SignetRpcClient};
+use crate::error::{FutureSightError, Result};
+use crate::explorer;
+use crate::http::HttpOptions;
+use crate::retention::Limits;
+use crate::sequencer;
+use crate::thresholds::ChainThresholds;
+use crate::watchlist::Watchlist;
+use futures::FutureExt;
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+/// Which side of a network a collector serves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChainRole {
+    Host,
+    Rollup,
+}
+
+impl ChainRole {
+    pub fn label(self) -> &'static str {
+        match self {
+            ChainRole::Host => "host",
+            ChainRole::Rollup => "rollup",
+        }
+    }
+}
+
+pub struct Network {
+    pub name: String,
+    host: Chain,
+    rollup: Option<Chain>,
+    /// Block explorer URL templates with `{number}` / `{hash}` placeholders.
+    pub host_explorer: Option<String>,
+    pub rollup_explorer: Option<String>,
+    pub http: HttpOptions, // for clients built after startup, like endpoint switches
+}
+
+/// Which chains of a network finished a collection since the last check.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Refreshed {
+    pub host: bool,
+    pub rollup: bool,
+}
+
+type Edit = Box<dyn FnOnce(&mut MetricsCollector)>;
+
+/// One chain's collector: at hand between polls, lent to a task while it collects so the
+/// dashboard keeps drawing and reading keys.
+struct Chain {
+    collector: Option<MetricsCollector>, // None while lent to `collecting`
+    collecting: Option<Collecting>,
+    last_refresh: Option<Instant>, // None = due now
+    thresholds: ChainThresholds,   // as last applied, for reporting what a reload changed
+}
+
+struct Collecting {
+    task: JoinHandle<MetricsCollector>,
+    snapshot: SignetMetrics, // shown until the task hands the collector back
+    queued: Vec<Edit>,       // changes made meanwhile, applied on return
+    poll_on_return: bool,    // the endpoint was switched, so the result is already stale
+}
+
+impl Chain {
+    fn new(collector: MetricsCollector) -> Self {
+        Self {
+            thresholds: collector.thresholds(),
+            collector: Some(collector),
+            collecting: None,
+            last_refresh: None,
+        }
+    }
+
+    fn metrics(&self) -> &SignetMetrics {
+        match (&self.collector, &self.collecting) {
+            (Some(collector), _) => collector.get_metrics(),
+            (None, Some(collecting)) => &collecting.snapshot,
+            (None, None) => unreachable!("collector is neither held nor collecting"),
+        }
+    }
+
+    /// Change the collector now, or once the running collection hands it back.
+    fn edit(&mut self, edit: impl FnOnce(&mut MetricsCollector) + 'static) {
+        match (&mut self.collector, &mut self.collecting) {
+            (Some(collector), _) => edit(collector),
+            (None, Some(collecting)) => collecting.queued.push(Box::new(edit)),
+            (None, None) => unreachable!("collector is neither held nor collecting"),
+        }
+    }
+
+    fn is_due(&self, every: Duration) -> bool {
+        self.collector.as_ref().is_some_and(|collector| {
+            self.last_refresh
+                .is_none_or(|last| last.elapsed() >= collector.poll_interval(every))
+        })
+    }
+
+    fn start(&mut self) {
+        let Some(mut collector) = self.collector.take() else {
+            return;
+        };
+        collector.mark_collecting();
+        let snapshot = collector.get_metrics().clone();
+        let task = tokio::spawn(async move {
+            collector.collect_metrics().await;
+            collector
+        });
+        self.collecting = Some(Collecting {
+            task,
+            snapshot,
+            queued: Vec::new(),
+            poll_on_return: false,
+        });
+    }
+
+    fn is_running(&self) -> bool {
+        self.collecting
+            .as_ref()
+            .is_some_and(|collecting| !collecting.task.is_finished())
+    }
+
+    /// Take the collector back if its collection is done. Returns whether it was.
+    fn finish(&mut self) -> bool {
+        let Some(result) = self
+            .collecting
+            .as_mut()
+            .and_then(|collecting| (&mut collecting.task).now_or_never())
+        else {
+            return false;
+        };
+        self.receive(result);
+        true
+    }
+
+    /// Wait for a running collection, for work that needs the collector itself.
+    async fn settle(&mut self) -> &mut MetricsCollector {
+        if let Some(collecting) = &mut self.collecting {
+            let result = (&mut collecting.task).await;
+            self.receive(result);
+        }
+        self.collector
+            .as_mut()
+            .expect("collector is back after settling")
+    }
+
+    fn receive(&mut self, result: std::result::Result<MetricsCollector, tokio::task::JoinError>) {
+        let collecting = self.collecting.take().expect("a collection was running");
+        // A panic in the task is a bug in collection; surface it as if it ran inline
+        let mut collector = result.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()));
+        for edit in collecting.queued {
+            edit(&mut collector);
+        }
+        self.last_refresh = (!collecting.poll_on_return).then(Instant::now);
+        self.collector = Some(collector);
+    }
+
+    fn switch_endpoint(&mut self, client: SignetRpcClient, rpc_url: String) {
+        self.edit(move |collector| collector.switch_endpoint(client, rpc_url));
+        match &mut self.collecting {
+            Some(collecting) => collecting.poll_on_return = true,
+            None => self.last_refresh = None,
+        }
+    }
+
+    fn set_thresholds(&mut self, thresholds: ChainThresholds) {
+        self.thresholds = thresholds;
+        self.edit(move |collector| collector.set_thresholds(thresholds));
+        // Judge a hanging poll by the new levels too
+        if let Some(collecting) = &mut self.collecting {
+            collecting.snapshot.set_thresholds(&thresholds);
+        }
+    }
+
+    fn check_staleness(&mut self) {
+        match (&mut self.collector, &mut self.collecting) {
+            (Some(collector), _) => collector.check_staleness(),
+            // A hanging poll still turns the chain stale on screen
+            (None, Some(collecting)) => collecting.snapshot.check_staleness(),
+            (None, None) => {}
+        }
+    }
+}
+
+impl Network {
+    pub fn new(name: String, host: MetricsCollector, rollup: Option<MetricsCollector>) -> Self {
+        Self {
+            name,
+            host: Chain::new(host),
+            rollup: rollup.map(Chain::new),
+            host_explorer: None,
+            rollup_explorer: None,
+            http: HttpOptions::default(),
+        }
+    }
+
+    /// "host"/"rollup", prefixed with the network name when several are configured
+    /// (`multi`). Used to label recorded samples, exports, and alerts.
+    pub fn chain_label(&self, role: ChainRole, multi: bool) -> String {
+        if multi {
+            format!("{}/{}", self.name, role.label())
+        } else {
+            role.label().to_string()
+        }
+    }
+
+    pub fn has_rollup(&self) -> bool {
+        self.rollup.is_some()
+    }
+
+    /// Collect every chain once, regardless of schedule.
+    pub async fn collect_all(&mut self) {
+        let Self { host, rollup, .. } = self;
+        tokio::join!(
+            async {
+                host.settle().await.collect_metrics().await;
+                host.last_refresh = Some(Instant::now());
+            },
+            async {
+                if let Some(rollup) = rollup {
+                    rollup.settle().await.collect_metrics().await;
+                    rollup.last_refresh = Some(Instant::now());
+                }
+            }
+        );
+    }
+
+    /// Backfill every chain's block history with its newest `count` blocks.
+    /// Returns the blocks added to the host and rollup.
+    pub async fn backfill(&mut self, count: usize) -> (usize, usize) {
+        let Self { host, rollup, .. } = self;
+        tokio::join!(
+            async { host.settle().await.backfill(count).await.unwrap_or(0) },
+            async {
+                match rollup {
+                    Some(rollup) => rollup.settle().await.backfill(count).await.unwrap_or(0),
+                    None => 0,
+                }
+            }
+        )
+    }
+
+    /// Start collecting, in the background, the chains whose poll interval has elapsed.
+    ///
+    /// Each chain backs off independently while its endpoint is rate limiting us. `true` if
+    /// any chain started.
+    pub fn start_due(&mut self, every: Duration) -> bool {
+        let mut started = false;
+        for chain in std::iter::once(&mut self.host).chain(&mut self.rollup) {
+            if chain.is_due(every) {
+                chain.start();
+                started = true;
+            }
+        }
+        started
+    }
+
+    /// Whether either chain's collection task is still running. A finished one that hasn't
+    /// been taken yet, like while the dashboard is paused, doesn't count.
+    pub fn is_collecting(&self) -> bool {
+        std::iter::once(&self.host)
+            .chain(&self.rollup)
+            .any(Chain::is_running)
+    }
+
+    /// The chains whose background collection has finished since the last call.
+    pub fn take_finished(&mut self) -> Refreshed {
+        Refreshed {
+            host: self.host.finish(),
+            rollup: self.rollup.as_mut().is_some_and(Chain::finish),
+        }
+    }
+
+    /// Repoint one chain at `rpc_url` and collect it on the next refresh pass.
+    pub fn switch_endpoint(&mut self, role: ChainRole, rpc_url: String) -> Result<()> {
+        let chain = match role {
+            ChainRole::Host => &mut self.host,
+            ChainRole::Rollup => self.rollup.as_mut().ok_or_else(|| {
+                FutureSightError::Config(format!("network '{}' has no rollup", self.name))
+            })?,
+        };
+        let client = SignetRpcClient::new(rpc_url.clone(), &self.http)?;
+        chain.switch_endpoint(client, rpc_url);
+        Ok(())
+    }
+
+    pub fn metrics(&self, role: ChainRole) -> Option<&SignetMetrics> {
+        match role {
+            ChainRole::Host => Some(self.host_metrics()),
+            ChainRole::Rollup => self.rollup_metrics(),
+        }
+    }
+
+    /// The configured explorer template for `role`, else a well-known one for its chain ID.
+    pub fn explorer(&self, role: ChainRole) -> Option<&str> {
+        let configured = match role {
+            ChainRole::Host => self.host_explorer.as_deref(),
+            ChainRole::Rollup => self.rollup_explorer.as_deref(),
+        };
+        configured.or_else(|| {
+            self.metrics(role)?
+                .chain_id
+                .and_then(explorer::default_template)
+        })
+    }
+
+    pub fn set_builder_labels(&mut self, labels: &BuilderLabels) {
+        for chain in std::iter::once(&mut self.host).chain(&mut self.rollup) {
+            let labels = labels.clone();
+            chain.edit(move |collector| collector.set_builder_labels(labels));
+        }
+    }
+
+    pub fn set_watchlist(&mut self, watchlist: &Watchlist) {
+        for chain in std::iter::once(&mut self.host).chain(&mut self.rollup) {
+            let watchlist = watchlist.clone();
+            chain.edit(move |collector| collector.set_watchlist(watchlist));
+        }
+    }
+
+    pub fn thresholds(&self, role: ChainRole) -> Option<ChainThresholds> {
+        match role {
+            ChainRole::Host => Some(self.host.thresholds),
+            ChainRole::Rollup => self.rollup.as_ref().map(|rollup| rollup.thresholds),
+        }
+    }
+
+    /// Apply `thresholds` to one chain; a network without a rollup ignores rollup ones.
+    pub fn set_thresholds(&mut self, role: ChainRole, thresholds: ChainThresholds) {
+        match role {
+            ChainRole::Host => self.host.set_thresholds(thresholds),
+            ChainRole::Rollup => {
+                if let Some(rollup) = &mut self.rollup {
+                    rollup.set_thresholds(thresholds);
+                }
+            }
+        }
+    }
+
+    pub fn set_host_contract_filter(&mut self, active: bool) {
+        self.host
+            .edit(move |collector| collector.set_host_contract_filter(active));
+        // Reflect the toggle right away, even while the collector is out
+        if let Some(collecting) = &mut self.host.collecting {
+            collecting.snapshot.host_contract_filter_active = active;
+        }
+    }
+
+    pub fn toggle_host_contract_filter(&mut self) {
+        self.set_host_contract_filter(!self.host_metrics().host_contract_filter_active);
+    }
+
+    /// Trim one chain's buffers to `limits`, now or when its collection returns.
+    pub fn retain(&mut self, role: ChainRole, limits: Limits) {
+        let chain = match role {
+            ChainRole::Host => &mut self.host,
+            ChainRole::Rollup => match &mut self.rollup {
+                Some(rollup) => rollup,
+                None => return,
+            },
+        };
+        chain.edit(move |collector| collector.retain(&limits));
+    }
+
+    pub fn check_staleness(&mut self) {
+        self.host.check_staleness();
+        if let Some(rollup) = &mut self.rollup {
+            rollup.check_staleness();
+        }
+    }
+
+    /// Apply pushed tx-pool items; `true` if any arrived. A chain that is collecting keeps
+    /// its stream messages queued until it is back.
+    pub fn drain_txpool_streams(&mut self) -> bool {
+        let mut received = false;
+        for chain in std::iter::once(&mut self.host).chain(&mut self.rollup) {
+            if let Some(collector) = &mut chain.collector {
+                received |= collector.drain_txpool_stream();
+            }
+        }
+        received
+    }
+
+    /// Re-judge the rollup sequencer's lag against the host at `now` (unix seconds).
+    pub fn check_sequencer(&mut self, now: u64) {
+        let Some(rollup) = &mut self.rollup else {
+            return;
+        };
+        let lag = sequencer::sequencer_lag(self.host.metrics(), rollup.metrics(), now);
+        match (&mut rollup.collector, &mut rollup.collecting) {
+            (Some(collector), _) => collector.set_sequencer_lag(lag),
+            (None, Some(collecting)) => collecting.snapshot.sequencer_lag = lag,
+            (None, None) => {}
+        }
+    }
+
+    pub fn host_metrics(&self) -> &SignetMetrics {
+        self.host.metrics()
+    }
+
+    pub fn rollup_metrics(&self) -> Option<&SignetMetrics> {
+        self.rollup.as_ref().map(Chain::metrics)
+    }
+}
@@ -0,0 +1,184 @@
+//! Operator notifications and recovery hooks for fired alerts
+use crate::alerts::{Alert, AlertHistory, AlertKind};
+use std::collections::HashSet;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Rings the terminal bell, or runs a sound command, when an alert fires as or escalates
+/// to critical.
+pub struct Bell {
+    command: Option<String>,
+}
+
+impl Bell {
+    pub fn new(command: Option<String>) -> Self {
+        Self { command }
+    }
+
+    /// Ring once if any of `fired` is critical.
+    pub fn notify(&self, fired: &[Alert]) {
+        if !fired.iter().any(Alert::is_critical) {
+            return;
+        }
+
+        match &self.command {
+            Some(command) => {
+                // Don't block the render loop on the sound finishing; reap it off-thread
+                let spawned = Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn();
+                if let Ok(mut child) = spawned {
+                    std::thread::spawn(move || child.wait());
+                }
+            }
+            None => {
+                let mut out = std::io::stdout();
+                let _ = out.write_all(b"\x07");
+                let _ = out.flush();
+            }
+        }
+    }
+}
+
+/// Runs a recovery command once per alert of the chosen kinds that stays active for
+/// `after`, e.g. to restart a stuck node. Alert details are passed in `FUTURESIGHT_ALERT_*`
+/// environment variables.
+pub struct AlertHook {
+    command: String,
+    kinds: Vec<AlertKind>, // empty = any critical alert
+    after: Duration,
+    ran: HashSet<(String, AlertKind, u64)>, // (chain, kind, started) of active alerts handled
+}
+
+impl AlertHook {
+    pub fn new(command: String, kinds: Vec<AlertKind>, after: Duration) -> Self {
+        Self {
+            command,
+            kinds,
+            after,
+            ran: HashSet::new(),
+        }
+    }
+
+    /// Run the command for every alert that became due since the last call.
+    pub fn poll(&mut self, alerts: &AlertHistory, now: u64) {
+        for alert in self.due(alerts, now) {
+            self.run(&alert, now);
+        }
+    }
+
+    /// Active alerts of a watched kind that have lasted `after` and weren't handled yet.
+    fn due(&mut self, alerts: &AlertHistory, now: u64) -> Vec<Alert> {
+        let active: Vec<&Alert> = alerts.iter().filter(|alert| alert.is_active()).collect();
+        // Forget alerts that ended, so the same condition firing again runs the command again
+        self.ran.retain(|(chain, kind, started)| {
+            active.iter().any(|alert| {
+                alert.chain == *chain && alert.kind == *kind && alert.started == *started
+            })
+        });
+
+        let mut due = Vec::new();
+        for alert in active {
+            let watched = if self.kinds.is_empty() {
+                alert.is_critical()
+            } else {
+                self.kinds.contains(&alert.kind)
+            };
+            let lasted = now.saturating_sub(alert.started) >= self.after.as_secs();
+            // A silenced kind runs the command once unmuted, if still active by then
+            if watched
+                && lasted
+                && !alerts.is_silenced(alert.kind)
+                && self
+                    .ran
+                    .insert((alert.chain.clone(), alert.kind, alert.started))
+            {
+                due.push(alert.clone());
+            }
+        }
+        due
+    }
+
+    fn run(&self, alert: &Alert, now: u64) {
+        let spawned = Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .env("FUTURESIGHT_ALERT_KIND", alert.kind.name())
+            .env("FUTURESIGHT_ALERT_LABEL", alert.kind.label())
+            .env("FUTURESIGHT_ALERT_CHAIN", &alert.chain)
+            .env("FUTURESIGHT_ALERT_STARTED", alert.started.to_string())
+            .env(
+                "FUTURESIGHT_ALERT_DURATION",
+                now.saturating_sub(alert.started).to_string(),
+            )
+            .env("FUTURESIGHT_ALERT_VALUE", alert.summary())
+            .env(
+                "FUTURESIGHT_ALERT_CRITICAL",
+                if alert.is_critical() { "1" } else { "0" },
+            )
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        if let Ok(mut child) = spawned {
+            std::thread::spawn(move || child.wait());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AlertHook;
+    use crate::alerts::{AlertHistory, AlertKind};
+    use crate::data::{Config, ConnectionStatus, SignetMetrics};
+    use crate::thresholds::DelayThresholds;
+    use std::time::Duration;
+
+    #[test]
+    fn hook_runs_once_per_alert_after_it_persists() {
+        let mut metrics = SignetMetrics::new(Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            block_delay: DelayThresholds {
+                warning: 30,
+                critical: 60,
+            },
+            expected_block_time: None,
+            stale_after: Duration::from_secs(15),
+            gas_alert_gwei: None,
+            gas_spike_multiplier: None,
+            max_block_history: 1,
+            txpool_max_rows: 1,
+            txpool_fetch_list: false,
+            txpool_watch_signet_host_contracts: false,
+            txpool_stream: None,
+            fetch_receipts: false,
+            http: Default::default(),
+        });
+        metrics.connection_status = ConnectionStatus::Connected;
+        metrics.latest_block_timestamp = Some(1_000);
+        let mut alerts = AlertHistory::default();
+        let mut hook = AlertHook::new(
+            "true".to_string(),
+            vec![AlertKind::BlockDelay],
+            Duration::from_secs(120),
+        );
+
+        alerts.observe("host", &metrics, 1_070);
+        assert!(hook.due(&alerts, 1_100).is_empty());
+        alerts.observe("host", &metrics, 1_190);
+        assert_eq!(hook.due(&alerts, 1_190).len(), 1);
+        assert!(hook.due(&alerts, 1_250).is_empty());
+
+        // A new block clears the alert; the next delay is a new alert and runs again
+        metrics.latest_block_timestamp = Some(1_300);
+        alerts.observe("host", &metrics, 1_300);
+        assert!(hook.due(&alerts, 1_300).is_empty());
+        alerts.observe("host", &metrics, 1_370);
+        assert_eq!(hook.due(&alerts, 1_490).len(), 1);
+    }
+}
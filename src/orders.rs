@@ -0,0 +1,149 @@
+//! Signet order fills, decoded from `Filled` events of the chain's orders contract
+use crate::config::ORDER_FILLS_KEPT;
+use alloy::primitives::{Address, B256, Bytes, LogData, U256};
+use alloy::sol_types::SolEvent;
+use std::collections::VecDeque;
+
+alloy::sol! {
+    struct Output {
+        address token;
+        uint256 amount;
+        address recipient;
+        uint32 chainId;
+    }
+
+    event Filled(Output[] outputs);
+}
+
+/// `Filled` topic0, emitted by both the host and rollup orders contracts.
+pub const FILLED_TOPIC: B256 = Filled::SIGNATURE_HASH;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillOutput {
+    pub token: Address,
+    pub amount: U256,
+    pub recipient: Address,
+    pub chain_id: u32, // chain the output is delivered on
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderFill {
+    pub block: u64,
+    pub tx_hash: String,
+    pub outputs: Vec<FillOutput>,
+}
+
+/// Fills seen since startup, newest first, and the newest block scanned for them.
+#[derive(Debug, Clone, Default)]
+pub struct OrderFlow {
+    pub fills: VecDeque<OrderFill>,
+    pub scanned_to: Option<u64>,
+}
+
+impl OrderFlow {
+    /// Add fills from the blocks up to `scanned_to`, dropping the oldest past
+    /// [`ORDER_FILLS_KEPT`].
+    pub fn record(&mut self, mut fills: Vec<OrderFill>, scanned_to: u64) {
+        fills.sort_by_key(|fill| std::cmp::Reverse(fill.block));
+        for fill in fills.into_iter().rev() {
+            self.fills.push_front(fill);
+        }
+        self.fills.truncate(ORDER_FILLS_KEPT);
+        self.scanned_to = Some(scanned_to);
+    }
+
+    /// Forget fills above `block`, after a reorg replaced the blocks they were in, so the
+    /// replacements are scanned again.
+    pub fn rewind(&mut self, block: u64) {
+        self.fills.retain(|fill| fill.block <= block);
+        self.scanned_to = self.scanned_to.map(|scanned| scanned.min(block));
+    }
+
+    /// Fills in blocks at or after `block`.
+    pub fn filled_since(&self, block: u64) -> usize {
+        self.fills.iter().filter(|fill| fill.block >= block).count()
+    }
+}
+
+/// Decode `eth_getLogs` results, skipping logs removed by a reorg and non-`Filled` logs.
+pub fn parse_fills(logs: &[serde_json::Value]) -> Vec<OrderFill> {
+    logs.iter().filter_map(parse_fill).collect()
+}
+
+fn parse_fill(log: &serde_json::Value) -> Option<OrderFill> {
+    if log.get("removed").and_then(|v| v.as_bool()) == Some(true) {
+        return None;
+    }
+    let topics = log
+        .get("topics")?
+        .as_array()?
+        .iter()
+        .map(|topic| topic.as_str()?.parse::<B256>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let data: Bytes = log.get("data")?.as_str()?.parse().ok()?;
+    let filled = Filled::decode_log_data(&LogData::new(topics, data)?).ok()?;
+    Some(OrderFill {
+        block: crate::data::hex_to_u64(log.get("blockNumber")?.as_str()?)?,
+        tx_hash: log.get("transactionHash")?.as_str()?.to_string(),
+        outputs: filled
+            .outputs
+            .into_iter()
+            .map(|output| FillOutput {
+                token: output.token,
+                amount: output.amount,
+                recipient: output.recipient,
+                chain_id: output.chainId,
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FILLED_TOPIC, Filled, OrderFlow, Output, parse_fills};
+    use alloy::primitives::{Address, U256};
+    use alloy::sol_types::SolEvent;
+
+    #[test]
+    fn decodes_filled_logs_and_counts_recent_fills() {
+        let event = Filled {
+            outputs: vec![Output {
+                token: Address::with_last_byte(0xaa),
+                amount: U256::from(5u64),
+                recipient: Address::with_last_byte(0xbb),
+                chainId: 1,
+            }],
+        };
+        let data = event.encode_log_data();
+        let log = |block: &str, removed: bool| {
+            serde_json::json!({
+                "topics": [FILLED_TOPIC.to_string()],
+                "data": data.data.to_string(),
+                "blockNumber": block,
+                "transactionHash": "0x01",
+                "removed": removed,
+            })
+        };
+        let fills = parse_fills(&[
+            log("0xa", false),
+            log("0xb", true),
+            serde_json::json!({"topics": [], "data": "0x"}),
+        ]);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].block, 10);
+        assert_eq!(fills[0].outputs[0].amount, U256::from(5u64));
+        assert_eq!(fills[0].outputs[0].chain_id, 1);
+
+        let mut flow = OrderFlow::default();
+        let mut older = fills[0].clone();
+        older.block = 8;
+        flow.record(vec![older, fills[0].clone()], 10);
+        assert_eq!(flow.fills[0].block, 10);
+        assert_eq!(flow.filled_since(9), 1);
+        assert_eq!(flow.scanned_to, Some(10));
+
+        flow.rewind(9);
+        assert_eq!(flow.fills.len(), 1);
+        assert_eq!(flow.scanned_to, Some(9));
+    }
+}
@@ -0,0 +1,343 @@
+//! OpenTelemetry export over OTLP/HTTP with JSON encoding
+//!
+//! Each refreshed chain yields one collection-cycle span and a set of gauges, posted to
+//! `<endpoint>/v1/traces` and `<endpoint>/v1/metrics` in the background.
+use crate::data::{ConnectionStatus, SignetMetrics};
+use crate::error::{FutureSightError, Result};
+use crate::http::HttpOptions;
+use crate::units;
+use serde_json::{Value, json};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const SCOPE: &str = env!("CARGO_PKG_NAME");
+
+/// One chain's observations from a collection cycle.
+#[derive(Debug, Clone)]
+pub struct Observation {
+    pub chain: String,
+    pub end_ns: u64,
+    pub duration: Duration,
+    pub metrics: Snapshot,
+}
+
+/// The subset of [`SignetMetrics`] exported as gauges.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot {
+    pub rpc_url: String,
+    pub status: &'static str,
+    pub error: Option<String>,
+    pub block_height: Option<u64>,
+    pub block_age_secs: Option<u64>,
+    pub base_fee_gwei: Option<f64>,
+    pub gas_price_gwei: Option<f64>,
+    pub gas_utilization_pct: Option<f64>,
+    pub txpool_size: Option<u64>,
+}
+
+impl Snapshot {
+    pub fn from_metrics(metrics: &SignetMetrics, now: u64) -> Self {
+        let (status, error) = match &metrics.connection_status {
+            ConnectionStatus::Connected => ("connected", None),
+            ConnectionStatus::Stale => ("stale", None),
+            ConnectionStatus::Disconnected => ("disconnected", None),
+            ConnectionStatus::RateLimited(e) => ("rate_limited", Some(e.clone())),
+            ConnectionStatus::Error(e) => ("error", Some(e.clone())),
+        };
+        Self {
+            rpc_url: metrics.rpc_url.clone(),
+            status,
+            error,
+            block_height: metrics.chain_height(),
+            block_age_secs: metrics
+                .latest_block_timestamp
+                .map(|ts| now.saturating_sub(ts)),
+            base_fee_gwei: metrics.base_fee_per_gas.map(units::to_gwei),
+            gas_price_gwei: metrics.gas_price.map(units::to_gwei),
+            gas_utilization_pct: metrics.gas_utilization_ma_n,
+            txpool_size: metrics
+                .txpool
+                .as_ref()
+                .and_then(|txpool| txpool.transactions_cache.count()),
+        }
+    }
+}
+
+impl Observation {
+    /// Observe a chain just after its collection finished.
+    pub fn new(chain: String, metrics: &SignetMetrics) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self {
+            chain,
+            end_ns: now.as_nanos() as u64,
+            duration: metrics.last_latency().unwrap_or_default(),
+            metrics: Snapshot::from_metrics(metrics, now.as_secs()),
+        }
+    }
+}
+
+pub struct OtlpExporter {
+    client: reqwest::Client,
+    traces_url: String,
+    metrics_url: String,
+}
+
+impl OtlpExporter {
+    /// `endpoint` is the collector base URL, e.g. `http://localhost:4318`.
+    pub fn new(endpoint: &str, options: &HttpOptions) -> Result<Self> {
+        let base = endpoint.trim_end_matches('/');
+        url::Url::parse(base)
+            .map_err(|e| FutureSightError::Config(format!("invalid --otlp-endpoint: {}", e)))?;
+        let client = options
+            .client_builder()?
+            .timeout(Duration::from_secs(crate::config::RPC_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| FutureSightError::Config(e.to_string()))?;
+        Ok(Self {
+            client,
+            traces_url: format!("{}/v1/traces", base),
+            metrics_url: format!("{}/v1/metrics", base),
+        })
+    }
+
+    /// Post spans and gauges for `observations` without blocking the caller.
+    /// Export failures are dropped: the collector being down must not affect the dashboard.
+    pub fn export(&self, observations: &[Observation]) {
+        if observations.is_empty() {
+            return;
+        }
+        for (url, body) in [
+            (self.traces_url.clone(), traces_payload(observations)),
+            (self.metrics_url.clone(), metrics_payload(observations)),
+        ] {
+            let request = self.client.post(url).json(&body);
+            tokio::spawn(async move {
+                let _ = request.send().await;
+            });
+        }
+    }
+}
+
+pub type Extract = fn(&Observation) -> Option<f64>;
+
+/// Every exported gauge as (name, unit, description, value), shared with the StatsD sink.
+pub const GAUGES: [(&str, &str, &str, Extract); 8] = [
+    (
+        "futuresight.up",
+        "1",
+        "1 if the last collection succeeded",
+        |o| {
+            Some(if matches!(o.metrics.status, "connected" | "stale") {
+                1.0
+            } else {
+                0.0
+            })
+        },
+    ),
+    (
+        "futuresight.collect.duration",
+        "ms",
+        "collection cycle duration",
+        |o| Some(o.duration.as_secs_f64() * 1000.0),
+    ),
+    (
+        "futuresight.block.height",
+        "1",
+        "latest block number",
+        |o| o.metrics.block_height.map(|h| h as f64),
+    ),
+    (
+        "futuresight.block.age",
+        "s",
+        "seconds since the latest block",
+        |o| o.metrics.block_age_secs.map(|s| s as f64),
+    ),
+    (
+        "futuresight.base_fee",
+        "Gwei",
+        "latest block base fee",
+        |o| o.metrics.base_fee_gwei,
+    ),
+    ("futuresight.gas_price", "Gwei", "eth_gasPrice", |o| {
+        o.metrics.gas_price_gwei
+    }),
+    (
+        "futuresight.gas.utilization",
+        "%",
+        "average gas used vs limit",
+        |o| o.metrics.gas_utilization_pct,
+    ),
+    (
+        "futuresight.txpool.size",
+        "1",
+        "pending tx-pool transactions",
+        |o| o.metrics.txpool_size.map(|n| n as f64),
+    ),
+];
+
+fn resource() -> Value {
+    json!({ "attributes": [string_attr("service.name", SCOPE)] })
+}
+
+fn scope() -> Value {
+    json!({ "name": SCOPE, "version": env!("CARGO_PKG_VERSION") })
+}
+
+fn string_attr(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// A random lowercase-hex id of `bytes` bytes; OTLP/JSON encodes trace and span ids as hex.
+fn random_id(bytes: usize) -> String {
+    (0..bytes.div_ceil(8))
+        .map(|_| format!("{:016x}", RandomState::new().build_hasher().finish()))
+        .collect::<String>()[..bytes * 2]
+        .to_string()
+}
+
+/// One `collect` span per observation, each its own trace.
+pub fn traces_payload(observations: &[Observation]) -> Value {
+    let spans: Vec<Value> = observations
+        .iter()
+        .map(|obs| {
+            let start_ns = obs.end_ns.saturating_sub(obs.duration.as_nanos() as u64);
+            let mut attributes = vec![
+                string_attr("futuresight.chain", &obs.chain),
+                string_attr("futuresight.rpc_url", &obs.metrics.rpc_url),
+                string_attr("futuresight.status", obs.metrics.status),
+            ];
+            if let Some(height) = obs.metrics.block_height {
+                attributes.push(json!({
+                    "key": "futuresight.block_height",
+                    "value": { "intValue": height.to_string() }
+                }));
+            }
+            // STATUS_CODE_OK = 1, STATUS_CODE_ERROR = 2
+            let status = match &obs.metrics.error {
+                Some(error) => json!({ "code": 2, "message": error }),
+                None => json!({ "code": 1 }),
+            };
+            json!({
+                "traceId": random_id(16),
+                "spanId": random_id(8),
+                "name": "collect",
+                "kind": 1, // SPAN_KIND_INTERNAL
+                "startTimeUnixNano": start_ns.to_string(),
+                "endTimeUnixNano": obs.end_ns.to_string(),
+                "attributes": attributes,
+                "status": status,
+            })
+        })
+        .collect();
+
+    json!({
+        "resourceSpans": [{
+            "resource": resource(),
+            "scopeSpans": [{ "scope": scope(), "spans": spans }],
+        }]
+    })
+}
+
+/// Gauges for every observation, one data point per chain.
+pub fn metrics_payload(observations: &[Observation]) -> Value {
+    let metrics: Vec<Value> = GAUGES
+        .iter()
+        .filter_map(|(name, unit, description, extract)| {
+            let points: Vec<Value> = observations
+                .iter()
+                .filter_map(|obs| {
+                    extract(obs).map(|value| {
+                        json!({
+                            "timeUnixNano": obs.end_ns.to_string(),
+                            "asDouble": value,
+                            "attributes": [string_attr("futuresight.chain", &obs.chain)],
+                        })
+                    })
+                })
+                .collect();
+            (!points.is_empty()).then(|| {
+                json!({
+                    "name": name,
+                    "unit": unit,
+                    "description": description,
+                    "gauge": { "dataPoints": points },
+                })
+            })
+        })
+        .collect();
+
+    json!({
+        "resourceMetrics": [{
+            "resource": resource(),
+            "scopeMetrics": [{ "scope": scope(), "metrics": metrics }],
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Observation, Snapshot, metrics_payload, random_id, traces_payload};
+    use std::time::Duration;
+
+    fn observation(error: Option<&str>) -> Observation {
+        Observation {
+            chain: "host".to_string(),
+            end_ns: 2_000_000_000,
+            duration: Duration::from_millis(250),
+            metrics: Snapshot {
+                rpc_url: "http://localhost:8545".to_string(),
+                status: if error.is_some() {
+                    "error"
+                } else {
+                    "connected"
+                },
+                error: error.map(str::to_string),
+                block_height: Some(42),
+                base_fee_gwei: Some(1.5),
+                ..Snapshot::default()
+            },
+        }
+    }
+
+    #[test]
+    fn span_covers_the_collection_and_carries_status() {
+        let payload = traces_payload(&[observation(None), observation(Some("refused"))]);
+        let spans = &payload["resourceSpans"][0]["scopeSpans"][0]["spans"];
+        assert_eq!(spans[0]["startTimeUnixNano"], "1750000000");
+        assert_eq!(spans[0]["endTimeUnixNano"], "2000000000");
+        assert_eq!(spans[0]["status"]["code"], 1);
+        assert_eq!(spans[1]["status"]["code"], 2);
+        assert_eq!(spans[1]["status"]["message"], "refused");
+        assert_eq!(spans[0]["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(random_id(8).len(), 16);
+    }
+
+    #[test]
+    fn gauges_skip_missing_values() {
+        let payload = metrics_payload(&[observation(None)]);
+        let metrics = payload["resourceMetrics"][0]["scopeMetrics"][0]["metrics"]
+            .as_array()
+            .unwrap();
+        let names: Vec<&str> = metrics
+            .iter()
+            .map(|m| m["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "futuresight.up",
+                "futuresight.collect.duration",
+                "futuresight.block.height",
+                "futuresight.base_fee",
+            ]
+        );
+        let point = &metrics[2]["gauge"]["dataPoints"][0];
+        assert_eq!(point["asDouble"], 42.0);
+        assert_eq!(point["attributes"][0]["value"]["stringValue"], "host");
+    }
+}
@@ -0,0 +1,383 @@
+//! Incident services as alert sinks: PagerDuty Events v2 and Opsgenie alerts are opened
+//! when an alert fires and resolved when its condition clears (`[alerts.pagerduty]`,
+//! `[alerts.opsgenie]` in `config.toml`)
+use crate::alerts::{Alert, AlertHistory, Severity};
+use crate::error::{FutureSightError, Result};
+use crate::http::HttpOptions;
+use crate::settings::AlertSettings;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+
+const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+const OPSGENIE_API_URL: &str = "https://api.opsgenie.com";
+/// Opsgenie truncates longer alert messages
+const OPSGENIE_MESSAGE_CHARS: usize = 130;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PagerDutySettings {
+    /// Integration key of an Events API v2 integration.
+    pub routing_key: String,
+    /// Also page for warnings; by default only critical alerts open incidents.
+    #[serde(default)]
+    pub warnings: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpsgenieSettings {
+    pub api_key: String,
+    /// `https://api.eu.opsgenie.com` for EU accounts.
+    pub api_url: Option<String>,
+    #[serde(default)]
+    pub warnings: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Service {
+    PagerDuty { routing_key: String },
+    Opsgenie { api_key: String, api_url: String },
+}
+
+impl Service {
+    fn name(&self) -> &'static str {
+        match self {
+            Service::PagerDuty { .. } => "PagerDuty",
+            Service::Opsgenie { .. } => "Opsgenie",
+        }
+    }
+
+    /// URL and body for `event`, plus the `Authorization` header if the API takes one.
+    fn request(&self, event: &PageEvent) -> (String, Option<String>, Value) {
+        match self {
+            Service::PagerDuty { routing_key } => (
+                PAGERDUTY_EVENTS_URL.to_string(),
+                None,
+                pagerduty_body(routing_key, event),
+            ),
+            Service::Opsgenie { api_key, api_url } => {
+                let (path, body) = opsgenie_request(event);
+                (
+                    format!("{}{}", api_url.trim_end_matches('/'), path),
+                    Some(format!("GenieKey {}", api_key)),
+                    body,
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PageEvent {
+    /// Open the incident, or raise its severity when the alert escalates.
+    Trigger(Alert),
+    Resolve(Alert),
+}
+
+impl PageEvent {
+    fn alert(&self) -> &Alert {
+        match self {
+            PageEvent::Trigger(alert) | PageEvent::Resolve(alert) => alert,
+        }
+    }
+}
+
+/// The same key for every event of one alert, so the service groups them.
+fn dedup_key(alert: &Alert) -> String {
+    format!(
+        "futuresight:{}:{}:{}",
+        alert.chain,
+        alert.kind.name(),
+        alert.started
+    )
+}
+
+/// "connection lost on host", with the alert's detail when it has one.
+fn title(alert: &Alert) -> String {
+    let summary = alert.summary();
+    match summary.is_empty() {
+        true => format!("{} on {}", alert.kind.label(), alert.chain),
+        false => format!("{} on {}: {}", alert.kind.label(), alert.chain, summary),
+    }
+}
+
+fn pagerduty_body(routing_key: &str, event: &PageEvent) -> Value {
+    let alert = event.alert();
+    match event {
+        PageEvent::Trigger(_) => json!({
+            "routing_key": routing_key,
+            "event_action": "trigger",
+            "dedup_key": dedup_key(alert),
+            "payload": {
+                "summary": title(alert),
+                "source": "futuresight",
+                "severity": match alert.severity {
+                    Severity::Critical => "critical",
+                    Severity::Warning => "warning",
+                },
+                "component": alert.chain,
+                "class": alert.kind.name(),
+                "custom_details": { "started": alert.started, "peak": alert.kind.fmt_value(alert.peak) },
+            },
+        }),
+        PageEvent::Resolve(_) => json!({
+            "routing_key": routing_key,
+            "event_action": "resolve",
+            "dedup_key": dedup_key(alert),
+        }),
+    }
+}
+
+/// Path under the API URL and body: create an alert aliased by the dedup key, or close it.
+fn opsgenie_request(event: &PageEvent) -> (String, Value) {
+    let alert = event.alert();
+    match event {
+        PageEvent::Trigger(_) => (
+            "/v2/alerts".to_string(),
+            json!({
+                "message": title(alert).chars().take(OPSGENIE_MESSAGE_CHARS).collect::<String>(),
+                "alias": dedup_key(alert),
+                "description": title(alert),
+                "priority": match alert.severity {
+                    Severity::Critical => "P1",
+                    Severity::Warning => "P3",
+                },
+                "source": "futuresight",
+                "tags": [alert.kind.name(), alert.chain],
+            }),
+        ),
+        PageEvent::Resolve(_) => (
+            format!(
+                "/v2/alerts/{}/close?identifierType=alias",
+                url::form_urlencoded::byte_serialize(dedup_key(alert).as_bytes())
+                    .collect::<String>()
+            ),
+            json!({ "source": "futuresight" }),
+        ),
+    }
+}
+
+/// Alerts a service has an open incident for, by dedup key, with the severity sent.
+#[derive(Debug, Default)]
+struct Incidents {
+    open: HashMap<String, Severity>,
+    warnings: bool,
+}
+
+impl Incidents {
+    /// Events that bring the service in line with `alerts`: triggers for alerts that
+    /// started or escalated, resolves for those that ended. Silenced kinds don't open
+    /// incidents, but ones already open still resolve.
+    fn events(&mut self, alerts: &AlertHistory) -> Vec<PageEvent> {
+        let mut events = Vec::new();
+        for alert in alerts.iter() {
+            let key = dedup_key(alert);
+            match (alert.is_active(), self.open.get(&key)) {
+                (false, Some(_)) => {
+                    self.open.remove(&key);
+                    events.push(PageEvent::Resolve(alert.clone()));
+                }
+                (true, sent)
+                    if sent.is_none_or(|severity| alert.severity > *severity)
+                        && (self.warnings || alert.is_critical())
+                        && !alerts.is_silenced(alert.kind) =>
+                {
+                    self.open.insert(key, alert.severity);
+                    events.push(PageEvent::Trigger(alert.clone()));
+                }
+                _ => {}
+            }
+        }
+        events
+    }
+}
+
+/// One service's incident tracking, with a task sending its events in order.
+pub struct Pager {
+    name: &'static str,
+    incidents: Incidents,
+    events: UnboundedSender<PageEvent>,
+    errors: UnboundedReceiver<String>,
+}
+
+impl Pager {
+    fn new(service: Service, warnings: bool, http: &HttpOptions) -> Result<Self> {
+        let client = http
+            .client_builder()?
+            .timeout(Duration::from_secs(crate::config::RPC_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| FutureSightError::Config(e.to_string()))?;
+        let (events, mut queue) = unbounded_channel::<PageEvent>();
+        let (failed, errors) = unbounded_channel();
+        let name = service.name();
+        tokio::spawn(async move {
+            while let Some(event) = queue.recv().await {
+                let (url, authorization, body) = service.request(&event);
+                let mut request = client.post(url).json(&body);
+                if let Some(authorization) = authorization {
+                    request = request.header(reqwest::header::AUTHORIZATION, authorization);
+                }
+                let outcome = match request.send().await {
+                    Ok(response) if response.status().is_success() => continue,
+                    Ok(response) => format!("HTTP {}", response.status()),
+                    Err(e) => e.to_string(),
+                };
+                let _ = failed.send(format!("{} for {}", outcome, title(event.alert())));
+            }
+        });
+        Ok(Self {
+            name,
+            incidents: Incidents {
+                open: HashMap::new(),
+                warnings,
+            },
+            events,
+            errors,
+        })
+    }
+
+    /// Queue the events `alerts` calls for. Returns the latest delivery failure since the
+    /// last call; failed events aren't retried.
+    pub fn poll(&mut self, alerts: &AlertHistory) -> Option<String> {
+        for event in self.incidents.events(alerts) {
+            let _ = self.events.send(event);
+        }
+        let mut latest = None;
+        while let Ok(error) = self.errors.try_recv() {
+            latest = Some(error);
+        }
+        latest.map(|error| format!("{}: {}", self.name, error))
+    }
+}
+
+/// A pager for each service configured under `[alerts]`.
+pub fn pagers(settings: &AlertSettings, http: &HttpOptions) -> Result<Vec<Pager>> {
+    let mut pagers = Vec::new();
+    if let Some(pagerduty) = &settings.pagerduty {
+        let service = Service::PagerDuty {
+            routing_key: pagerduty.routing_key.clone(),
+        };
+        pagers.push(Pager::new(service, pagerduty.warnings, http)?);
+    }
+    if let Some(opsgenie) = &settings.opsgenie {
+        let service = Service::Opsgenie {
+            api_key: opsgenie.api_key.clone(),
+            api_url: opsgenie
+                .api_url
+                .clone()
+                .unwrap_or_else(|| OPSGENIE_API_URL.to_string()),
+        };
+        pagers.push(Pager::new(service, opsgenie.warnings, http)?);
+    }
+    Ok(pagers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Incidents, PageEvent, opsgenie_request, pagerduty_body};
+    use crate::alerts::{Alert, AlertHistory, AlertKind, Severity, Silence};
+    use crate::data::{Config, ConnectionStatus, SignetMetrics};
+    use crate::thresholds::DelayThresholds;
+    use std::time::Duration;
+
+    fn metrics(latest_block: u64) -> SignetMetrics {
+        let mut metrics = SignetMetrics::new(Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            block_delay: DelayThresholds {
+                warning: 30,
+                critical: 60,
+            },
+            expected_block_time: None,
+            stale_after: Duration::from_secs(15),
+            gas_alert_gwei: None,
+            gas_spike_multiplier: None,
+            max_block_history: 1,
+            txpool_max_rows: 1,
+            txpool_fetch_list: false,
+            txpool_watch_signet_host_contracts: false,
+            txpool_stream: None,
+            fetch_receipts: false,
+            http: Default::default(),
+        });
+        metrics.connection_status = ConnectionStatus::Connected;
+        metrics.latest_block_timestamp = Some(latest_block);
+        metrics
+    }
+
+    #[test]
+    fn opens_escalates_and_resolves_incidents() {
+        let mut history = AlertHistory::default();
+        let mut incidents = Incidents {
+            warnings: false,
+            ..Incidents::default()
+        };
+        let kinds = |events: Vec<PageEvent>| {
+            events
+                .iter()
+                .map(|event| match event {
+                    PageEvent::Trigger(alert) => ("trigger", alert.severity),
+                    PageEvent::Resolve(alert) => ("resolve", alert.severity),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // A block delay warning doesn't page without `warnings`; past critical it does, once
+        history.observe("host", &metrics(1_000), 1_040);
+        assert!(incidents.events(&history).is_empty());
+        history.observe("host", &metrics(1_000), 1_070);
+        assert_eq!(
+            kinds(incidents.events(&history)),
+            [("trigger", Severity::Critical)]
+        );
+        history.observe("host", &metrics(1_000), 1_080);
+        assert!(incidents.events(&history).is_empty());
+        history.observe("host", &metrics(1_090), 1_090);
+        assert_eq!(
+            kinds(incidents.events(&history)),
+            [("resolve", Severity::Critical)]
+        );
+
+        // Muted kinds don't open incidents
+        history.silence(AlertKind::BlockDelay, Silence::Muted);
+        history.observe("host", &metrics(1_090), 1_200);
+        assert!(incidents.events(&history).is_empty());
+    }
+
+    #[test]
+    fn builds_service_requests_with_one_key_per_alert() {
+        let alert = Alert {
+            chain: "host".to_string(),
+            kind: AlertKind::BlockDelay,
+            started: 1_700_000_000,
+            ended: None,
+            peak: 95.0,
+            severity: Severity::Critical,
+            detail: None,
+            acknowledged: false,
+        };
+        let trigger = pagerduty_body("key", &PageEvent::Trigger(alert.clone()));
+        assert_eq!(trigger["event_action"], "trigger");
+        assert_eq!(trigger["payload"]["severity"], "critical");
+        assert_eq!(trigger["payload"]["summary"], "block delay on host: 95s");
+        let resolve = pagerduty_body("key", &PageEvent::Resolve(alert.clone()));
+        assert_eq!(resolve["dedup_key"], trigger["dedup_key"]);
+        assert_eq!(
+            resolve["dedup_key"],
+            "futuresight:host:block-delay:1700000000"
+        );
+
+        let (path, body) = opsgenie_request(&PageEvent::Trigger(alert.clone()));
+        assert_eq!(
+            (path.as_str(), &body["priority"]),
+            ("/v2/alerts", &"P1".into())
+        );
+        let (path, _) = opsgenie_request(&PageEvent::Resolve(alert));
+        assert_eq!(
+            path,
+            "/v2/alerts/futuresight%3Ahost%3Ablock-delay%3A1700000000/close?identifierType=alias"
+        );
+    }
+}
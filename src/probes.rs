@@ -0,0 +1,317 @@
+//! Config-defined `eth_call` probes and watched storage slots, decoded into labelled values
+//! for the Custom Probes panel
+use crate::config::PROBE_PRECISION;
+use crate::network::ChainRole;
+use crate::units;
+use alloy::dyn_abi::{DynSolType, DynSolValue};
+use alloy::primitives::{Address, B256, Bytes, U256, hex};
+use serde::{Deserialize, Deserializer};
+use std::time::Instant;
+
+/// One `[[probes]]` entry: a read-only call made every collection cycle.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Probe {
+    pub label: String,
+    /// Network to probe; unset probes every configured network.
+    #[serde(default)]
+    pub network: Option<String>,
+    #[serde(default = "default_chain")]
+    pub chain: ChainRole,
+    pub to: Address,
+    /// Hex calldata, selector included.
+    pub data: Bytes,
+    /// Solidity return type, e.g. `uint256` or `(uint80,int256,uint256,uint256,uint80)`.
+    #[serde(default = "default_decode", deserialize_with = "solidity_type")]
+    pub decode: DynSolType,
+    /// Element of a tuple return to show; unset shows them all.
+    #[serde(default)]
+    pub field: Option<usize>,
+    /// Scale integers down by `10^decimals`, as for token amounts and price feeds.
+    #[serde(default)]
+    pub decimals: u8,
+}
+
+fn default_chain() -> ChainRole {
+    ChainRole::Host
+}
+
+fn default_decode() -> DynSolType {
+    DynSolType::Uint(256)
+}
+
+fn solidity_type<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DynSolType, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    DynSolType::parse(&name).map_err(serde::de::Error::custom)
+}
+
+impl Probe {
+    /// Whether this probe belongs to `role` of the network named `network`.
+    pub fn applies_to(&self, network: &str, role: ChainRole) -> bool {
+        self.chain == role && self.network.as_deref().is_none_or(|name| name == network)
+    }
+
+    /// The call's return data as display text, or why it can't be shown.
+    pub fn decode(&self, output: &[u8]) -> Result<String, String> {
+        let value = match &self.decode {
+            DynSolType::Tuple(_) => self.decode.abi_decode_params(output),
+            single => single.abi_decode(output),
+        }
+        .map_err(|e| format!("cannot decode as {}: {}", self.decode, e))?;
+        let value = match (self.field, value) {
+            (None, value) => value,
+            (Some(field), DynSolValue::Tuple(mut fields)) if field < fields.len() => {
+                fields.swap_remove(field)
+            }
+            (Some(field), _) => return Err(format!("{} has no field {}", self.decode, field)),
+        };
+        Ok(format_value(&value, self.decimals))
+    }
+}
+
+/// The outcome of one probe in the latest collection cycle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeReading {
+    pub label: String,
+    pub value: Result<String, String>,
+}
+
+/// A storage slot read with `eth_getStorageAt` every cycle: `--watch-slot <address>:<slot>`,
+/// optionally `:<type>` to decode the word as a single-word Solidity type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotWatch {
+    pub address: Address,
+    pub slot: B256,
+    pub decode: Option<DynSolType>,
+}
+
+impl SlotWatch {
+    /// Parse `<address>:<slot>[:<type>]`; the slot is decimal or 0x-prefixed hex.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let mut parts = raw.splitn(3, ':');
+        let (Some(address), Some(slot)) = (parts.next(), parts.next()) else {
+            return Err(format!("'{}' is not <address>:<slot>[:<type>]", raw));
+        };
+        let address = address
+            .parse::<Address>()
+            .map_err(|e| format!("bad address '{}': {}", address, e))?;
+        let slot = slot
+            .parse::<U256>()
+            .map_err(|e| format!("bad slot '{}': {}", slot, e))?;
+        let decode = parts
+            .next()
+            .map(|name| match DynSolType::parse(name) {
+                Ok(
+                    ty @ (DynSolType::Address
+                    | DynSolType::Bool
+                    | DynSolType::Uint(_)
+                    | DynSolType::Int(_)
+                    | DynSolType::FixedBytes(_)),
+                ) => Ok(ty),
+                Ok(_) => Err(format!("'{}' does not fit in one storage word", name)),
+                Err(e) => Err(format!("bad type '{}': {}", name, e)),
+            })
+            .transpose()?;
+        Ok(Self {
+            address,
+            slot: slot.into(),
+            decode,
+        })
+    }
+
+    /// `0xC02a…6Cc2[8]`: the contract, and the slot in decimal unless it is a hash.
+    pub fn label(&self) -> String {
+        let slot = U256::from_be_bytes(self.slot.0);
+        if slot < U256::from(1_000_000) {
+            format!("{:#}[{}]", self.address, slot)
+        } else {
+            format!("{:#}[{:#}]", self.address, self.slot)
+        }
+    }
+}
+
+/// The latest value of a watched slot and when it last changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotReading {
+    pub watch: SlotWatch,
+    pub value: Option<B256>,
+    pub error: Option<String>, // the latest read failed
+    pub changed_at: Option<Instant>,
+    pub changes: u32, // since the first read
+}
+
+impl SlotReading {
+    pub fn new(watch: SlotWatch) -> Self {
+        Self {
+            watch,
+            value: None,
+            error: None,
+            changed_at: None,
+            changes: 0,
+        }
+    }
+
+    pub fn observe(&mut self, result: Result<B256, String>, now: Instant) {
+        match result {
+            Ok(value) => {
+                if self.value.is_some_and(|previous| previous != value) {
+                    self.changed_at = Some(now);
+                    self.changes += 1;
+                }
+                self.value = Some(value);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// The word with leading zero bytes dropped: `0x01`, `0x0` for an empty slot.
+    pub fn raw(&self) -> Option<String> {
+        self.value.map(|word| {
+            let digits = hex::encode(word).trim_start_matches('0').to_string();
+            format!("0x{}", if digits.is_empty() { "0" } else { &digits })
+        })
+    }
+
+    pub fn decoded(&self) -> Option<String> {
+        let (ty, word) = (self.watch.decode.as_ref()?, self.value?);
+        Some(match ty.abi_decode(word.as_slice()) {
+            Ok(value) => format_value(&value, 0),
+            Err(e) => format!("not {}: {}", ty, e),
+        })
+    }
+}
+
+fn format_value(value: &DynSolValue, decimals: u8) -> String {
+    let list = |values: &[DynSolValue]| {
+        values
+            .iter()
+            .map(|value| format_value(value, decimals))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match value {
+        DynSolValue::Uint(value, _) => format_uint(*value, decimals),
+        DynSolValue::Int(value, _) if value.is_negative() => {
+            format!("-{}", format_uint(value.unsigned_abs(), decimals))
+        }
+        DynSolValue::Int(value, _) => format_uint(value.unsigned_abs(), decimals),
+        DynSolValue::Bool(value) => value.to_string(),
+        DynSolValue::Address(address) => address.to_checksum(None),
+        DynSolValue::FixedBytes(word, size) => hex::encode_prefixed(&word[..*size]),
+        DynSolValue::Bytes(bytes) => hex::encode_prefixed(bytes),
+        DynSolValue::String(text) => text.clone(),
+        DynSolValue::Tuple(values) => format!("({})", list(values)),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            format!("[{}]", list(values))
+        }
+        other => format!("{:?}", other),
+    }
+}
+
+fn format_uint(value: U256, decimals: u8) -> String {
+    match u128::try_from(value) {
+        // 10^38 is the largest power of ten a u128 holds
+        Ok(value) if decimals <= 38 => {
+            units::format_units(value, decimals.into(), PROBE_PRECISION, Some(','))
+        }
+        _ => alloy::primitives::utils::format_units(value, decimals)
+            .unwrap_or_else(|_| value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Probe, SlotReading, SlotWatch};
+    use crate::network::ChainRole;
+    use alloy::dyn_abi::{DynSolType, DynSolValue};
+    use alloy::primitives::{Address, B256, Bytes, I256, U256};
+    use std::time::{Duration, Instant};
+
+    fn probe(decode: &str, field: Option<usize>, decimals: u8) -> Probe {
+        Probe {
+            label: "probe".to_string(),
+            network: None,
+            chain: ChainRole::Host,
+            to: Address::ZERO,
+            data: Bytes::new(),
+            decode: DynSolType::parse(decode).unwrap(),
+            field,
+            decimals,
+        }
+    }
+
+    #[test]
+    fn decodes_return_data_for_display() {
+        let supply = DynSolValue::Uint(U256::from(1_234_567_890_000u64), 256).abi_encode();
+        assert_eq!(
+            probe("uint256", None, 6).decode(&supply).unwrap(),
+            "1,234,567.8900"
+        );
+        assert_eq!(
+            probe("uint256", None, 0).decode(&supply).unwrap(),
+            "1,234,567,890,000"
+        );
+
+        let name = DynSolValue::String("Wrapped Ether".to_string()).abi_encode();
+        assert_eq!(
+            probe("string", None, 0).decode(&name).unwrap(),
+            "Wrapped Ether"
+        );
+
+        // Chainlink latestRoundData: (roundId, answer, startedAt, updatedAt, answeredInRound)
+        let round = DynSolValue::Tuple(vec![
+            DynSolValue::Uint(U256::from(7), 80),
+            DynSolValue::Int(I256::try_from(-250_000_000i64).unwrap(), 256),
+            DynSolValue::Uint(U256::ZERO, 256),
+            DynSolValue::Uint(U256::ZERO, 256),
+            DynSolValue::Uint(U256::from(7), 80),
+        ])
+        .abi_encode_params();
+        let feed = "(uint80,int256,uint256,uint256,uint80)";
+        assert_eq!(probe(feed, Some(1), 8).decode(&round).unwrap(), "-2.5000");
+        assert_eq!(
+            probe(feed, None, 0).decode(&round).unwrap(),
+            "(7, -250,000,000, 0, 0, 7)"
+        );
+        assert!(probe(feed, Some(5), 0).decode(&round).is_err());
+        assert!(probe("uint256", None, 0).decode(&[0x01]).is_err());
+    }
+
+    #[test]
+    fn slot_watches_parse_and_track_changes() {
+        // EIP-1967 implementation slot of a proxy
+        let watch = SlotWatch::parse(
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2:\
+             0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc:address",
+        )
+        .unwrap();
+        assert_eq!(watch.decode, Some(DynSolType::Address));
+        assert_eq!(watch.label(), "0xC02a…6Cc2[0x3608…2bbc]");
+        let flag = SlotWatch::parse("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2:8").unwrap();
+        assert_eq!((flag.slot, &flag.decode), (B256::with_last_byte(8), &None));
+        assert_eq!(flag.label(), "0xC02a…6Cc2[8]");
+        assert!(SlotWatch::parse("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2").is_err());
+        assert!(SlotWatch::parse("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2:1:string").is_err());
+
+        let start = Instant::now();
+        let mut reading = SlotReading::new(watch);
+        reading.observe(Ok(B256::ZERO), start);
+        assert_eq!(
+            (reading.raw().unwrap().as_str(), reading.changes),
+            ("0x0", 0)
+        );
+        reading.observe(Err("timeout".to_string()), start + Duration::from_secs(1));
+        assert_eq!(reading.value, Some(B256::ZERO));
+        let implementation = Address::with_last_byte(0xaa).into_word();
+        let later = start + Duration::from_secs(2);
+        reading.observe(Ok(implementation), later);
+        assert_eq!((reading.changed_at, reading.changes), (Some(later), 1));
+        assert_eq!(reading.error, None);
+        assert_eq!(reading.raw().unwrap(), "0xaa");
+        assert_eq!(
+            reading.decoded().unwrap(),
+            "0x00000000000000000000000000000000000000AA"
+        );
+    }
+}
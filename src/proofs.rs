@@ -0,0 +1,144 @@
+//! `eth_getProof` checks of watched accounts against the state root of the block they were
+//! read at, to catch an endpoint serving state its own headers don't commit to
+use alloy::primitives::{Address, B256, keccak256};
+use alloy::rlp;
+use alloy::rpc::types::EIP1186AccountProofResponse;
+use alloy::trie::{EMPTY_ROOT_HASH, KECCAK_EMPTY, Nibbles, TrieAccount, proof::verify_proof};
+
+/// The latest check of one watched account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofCheck {
+    pub address: Address,
+    pub block: u64,
+    pub outcome: ProofOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProofOutcome {
+    Verified,
+    Mismatch(String), // the proof doesn't lead from the state root to the claimed values
+    Failed(String),   // no proof to check, e.g. the request failed
+}
+
+impl ProofCheck {
+    pub fn is_mismatch(&self) -> bool {
+        matches!(self.outcome, ProofOutcome::Mismatch(_))
+    }
+}
+
+/// Check the account proof in `response` against `state_root`, then each storage proof
+/// against the account's storage root.
+pub fn verify(state_root: B256, response: &EIP1186AccountProofResponse) -> Result<(), String> {
+    let account = TrieAccount {
+        nonce: response.nonce,
+        balance: response.balance,
+        storage_root: response.storage_hash,
+        code_hash: response.code_hash,
+    };
+    // Absent accounts are proven by exclusion; nodes report their hashes as empty or zero
+    let absent = account.nonce == 0
+        && account.balance.is_zero()
+        && (account.code_hash == KECCAK_EMPTY || account.code_hash.is_zero())
+        && (account.storage_root == EMPTY_ROOT_HASH || account.storage_root.is_zero());
+    verify_proof(
+        state_root,
+        Nibbles::unpack(keccak256(response.address)),
+        (!absent).then(|| rlp::encode(account)),
+        &response.account_proof,
+    )
+    .map_err(|e| format!("account proof: {}", e))?;
+
+    for slot in &response.storage_proof {
+        let key = slot.key.as_b256();
+        verify_proof(
+            response.storage_hash,
+            Nibbles::unpack(keccak256(key)),
+            (!slot.value.is_zero()).then(|| rlp::encode(slot.value)),
+            &slot.proof,
+        )
+        .map_err(|e| format!("storage proof for slot {}: {}", key, e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::verify;
+    use alloy::primitives::{Address, B256, U256, keccak256};
+    use alloy::rlp;
+    use alloy::rpc::types::{EIP1186AccountProofResponse, EIP1186StorageProof};
+    use alloy::trie::{HashBuilder, KECCAK_EMPTY, Nibbles, TrieAccount, proof::ProofRetainer};
+
+    /// The root of a trie holding `entries`, and the proof of the first one.
+    fn prove(mut entries: Vec<(B256, Vec<u8>)>) -> (B256, Vec<alloy::primitives::Bytes>) {
+        let target = Nibbles::unpack(entries[0].0);
+        entries.sort();
+        let mut builder =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::from_iter([target]));
+        for (key, value) in &entries {
+            builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = builder.root();
+        let proof = builder
+            .take_proof_nodes()
+            .matching_nodes_sorted(&target)
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect();
+        (root, proof)
+    }
+
+    #[test]
+    fn proofs_must_lead_from_the_state_root_to_the_claimed_state() {
+        let address = Address::with_last_byte(0xaa);
+        let slot = B256::with_last_byte(8);
+        let (storage_root, storage_proof) = prove(vec![
+            (keccak256(slot), rlp::encode(U256::from(42))),
+            (keccak256(B256::ZERO), rlp::encode(U256::from(7))),
+        ]);
+        let account = TrieAccount {
+            nonce: 3,
+            balance: U256::from(1_000),
+            storage_root,
+            code_hash: KECCAK_EMPTY,
+        };
+        let (state_root, account_proof) = prove(vec![
+            (keccak256(address), rlp::encode(account)),
+            (
+                keccak256(Address::with_last_byte(0xbb)),
+                rlp::encode(TrieAccount::default()),
+            ),
+        ]);
+        let mut response = EIP1186AccountProofResponse {
+            address,
+            balance: account.balance,
+            code_hash: account.code_hash,
+            nonce: account.nonce,
+            storage_hash: storage_root,
+            account_proof,
+            storage_proof: vec![EIP1186StorageProof {
+                key: slot.into(),
+                value: U256::from(42),
+                proof: storage_proof,
+            }],
+        };
+        assert_eq!(verify(state_root, &response), Ok(()));
+
+        // A lying endpoint: the same proof nodes with a different balance or slot value
+        response.storage_proof[0].value = U256::from(43);
+        assert!(
+            verify(state_root, &response)
+                .unwrap_err()
+                .starts_with("storage proof")
+        );
+        response.storage_proof[0].value = U256::from(42);
+        response.balance = U256::from(2_000);
+        assert!(
+            verify(state_root, &response)
+                .unwrap_err()
+                .starts_with("account proof")
+        );
+        response.balance = account.balance;
+        assert!(verify(B256::with_last_byte(1), &response).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+//! Append-only recording of per-cycle metrics samples (JSON lines)
+use crate::data::{ConnectionStatus, SignetMetrics};
+use crate::error::{FutureSightError, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+/// One chain's metrics at the end of a collection cycle.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    pub ts: u64, // unix seconds
+    pub chain: String,
+    pub status: String,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub block_number: Option<u64>,
+    #[serde(default)]
+    pub latest_block_timestamp: Option<u64>,
+    #[serde(default)]
+    pub base_fee_per_gas: Option<u128>, // wei
+    #[serde(default)]
+    pub gas_price: Option<u128>, // wei
+    #[serde(default)]
+    pub gas_utilization: Option<f64>, // percent 0..=100
+    #[serde(default)]
+    pub block_delay_alert: bool,
+}
+
+impl Sample {
+    pub fn from_metrics(chain: &str, metrics: &SignetMetrics, ts: u64) -> Self {
+        let (status, error) = match &metrics.connection_status {
+            ConnectionStatus::Connected => ("connected", None),
+            ConnectionStatus::Stale => ("stale", None),
+            ConnectionStatus::Disconnected => ("disconnected", None),
+            ConnectionStatus::RateLimited(e) => ("rate_limited", Some(e.clone())),
+            ConnectionStatus::Error(e) => ("error", Some(e.clone())),
+        };
+        let block_delay_alert = metrics
+            .latest_block_timestamp
+            .map(|block_ts| ts.saturating_sub(block_ts) > metrics.block_delay.critical)
+            .unwrap_or(false);
+
+        Self {
+            ts,
+            chain: chain.to_string(),
+            status: status.to_string(),
+            error,
+            block_number: metrics.chain_height(),
+            latest_block_timestamp: metrics.latest_block_timestamp,
+            base_fee_per_gas: metrics.base_fee_per_gas,
+            gas_price: metrics.gas_price,
+            gas_utilization: metrics.gas_utilization_ma_n,
+            block_delay_alert,
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.status == "connected"
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+}
+
+pub struct Recorder {
+    out: BufWriter<File>,
+}
+
+impl Recorder {
+    /// Open `path` for appending, creating it if needed.
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| {
+                FutureSightError::Storage(format!(
+                    "cannot open record file '{}': {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        Ok(Self {
+            out: BufWriter::new(file),
+        })
+    }
+
+    pub fn record(&mut self, chain: &str, metrics: &SignetMetrics) -> Result<()> {
+        let sample = Sample::from_metrics(chain, metrics, unix_now());
+        serde_json::to_writer(&mut self.out, &sample)
+            .map_err(std::io::Error::from)
+            .and_then(|()| self.out.write_all(b"\n"))
+            .and_then(|()| self.out.flush())
+            .map_err(|e| FutureSightError::Storage(format!("cannot write record file: {}", e)))
+    }
+}
+
+/// Read every sample from a recording, skipping lines that fail to parse.
+pub fn read_samples(path: &Path) -> Result<Vec<Sample>> {
+    let storage_error = |e: std::io::Error| {
+        FutureSightError::Storage(format!(
+            "cannot read record file '{}': {}",
+            path.display(),
+            e
+        ))
+    };
+    let file = File::open(path).map_err(storage_error)?;
+    let mut samples = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(storage_error)?;
+        if let Ok(sample) = serde_json::from_str::<Sample>(&line) {
+            samples.push(sample);
+        }
+    }
+    Ok(samples)
+}
+
+/// Write samples to `out` in the requested export format.
+pub fn export<W: Write>(samples: &[Sample], format: ExportFormat, out: W) -> Result<()> {
+    write_export(samples, format, out)
+        .map_err(|e| FutureSightError::Storage(format!("cannot write export: {}", e)))
+}
+
+fn write_export<W: Write>(
+    samples: &[Sample],
+    format: ExportFormat,
+    mut out: W,
+) -> std::io::Result<()> {
+    match format {
+        ExportFormat::Jsonl => {
+            for sample in samples {
+                serde_json::to_writer(&mut out, sample)?;
+                out.write_all(b"\n")?;
+            }
+        }
+        ExportFormat::Csv => {
+            writeln!(
+                out,
+                "ts,chain,status,block_number,latest_block_timestamp,base_fee_per_gas,gas_price,gas_utilization,block_delay_alert,error"
+            )?;
+            for s in samples {
+                writeln!(
+                    out,
+                    "{},{},{},{},{},{},{},{},{},{}",
+                    s.ts,
+                    csv_field(&s.chain),
+                    s.status,
+                    opt_field(s.block_number),
+                    opt_field(s.latest_block_timestamp),
+                    opt_field(s.base_fee_per_gas),
+                    opt_field(s.gas_price),
+                    opt_field(s.gas_utilization),
+                    s.block_delay_alert,
+                    s.error.as_deref().map(csv_field).unwrap_or_default(),
+                )?;
+            }
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn opt_field<T: std::fmt::Display>(value: Option<T>) -> String {
+    value.map(|inner| inner.to_string()).unwrap_or_default()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|value| value.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ExportFormat, Sample, export};
+
+    #[test]
+    fn csv_export_quotes_error_text() {
+        let sample = Sample {
+            ts: 5,
+            chain: "rollup".to_string(),
+            status: "error".to_string(),
+            error: Some("Chain ID: timed out, \"retrying\"".to_string()),
+            block_number: Some(7),
+            latest_block_timestamp: None,
+            base_fee_per_gas: None,
+            gas_price: Some(3),
+            gas_utilization: None,
+            block_delay_alert: false,
+        };
+        let mut out = Vec::new();
+        export(&[sample], ExportFormat::Csv, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(
+            text.lines().nth(1),
+            Some("5,rollup,error,7,,,3,,false,\"Chain ID: timed out, \"\"retrying\"\"\"")
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! `futuresight replay`: steps through a `--record` recording at a multiple of real time,
+//! showing each chain's newest sample as of the replay clock
+use crate::error::{FutureSightError, Result};
+use crate::record::Sample;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct Replay {
+    pub record: String,
+    pub speed: f64,          // recorded seconds per real second
+    pub chains: Vec<Sample>, // in the order first seen
+    samples: Vec<Sample>,    // sorted by ts
+    applied: usize,
+    elapsed: f64, // recorded seconds since the first sample
+}
+
+impl Replay {
+    pub fn new(record: String, mut samples: Vec<Sample>, speed: f64) -> Result<Self> {
+        if samples.is_empty() {
+            return Err(FutureSightError::Config(format!(
+                "no samples to replay in '{}' for that range",
+                record
+            )));
+        }
+        samples.sort_by_key(|sample| sample.ts);
+        let mut replay = Self {
+            record,
+            speed,
+            chains: Vec::new(),
+            samples,
+            applied: 0,
+            elapsed: 0.0,
+        };
+        replay.apply_due();
+        Ok(replay)
+    }
+
+    pub fn start(&self) -> u64 {
+        self.samples[0].ts
+    }
+
+    pub fn end(&self) -> u64 {
+        self.samples[self.samples.len() - 1].ts
+    }
+
+    /// The replay clock, in recorded unix seconds.
+    pub fn position(&self) -> u64 {
+        (self.start() + self.elapsed as u64).min(self.end())
+    }
+
+    pub fn applied(&self) -> usize {
+        self.applied
+    }
+
+    pub fn total(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn finished(&self) -> bool {
+        self.applied == self.samples.len()
+    }
+
+    /// Move the replay clock on by `real` wall time and apply the samples it passed.
+    pub fn advance(&mut self, real: Duration) {
+        self.elapsed += real.as_secs_f64() * self.speed;
+        self.apply_due();
+    }
+
+    fn apply_due(&mut self) {
+        let position = self.position();
+        while let Some(sample) = self
+            .samples
+            .get(self.applied)
+            .filter(|sample| sample.ts <= position)
+        {
+            match self
+                .chains
+                .iter_mut()
+                .find(|chain| chain.chain == sample.chain)
+            {
+                Some(chain) => *chain = sample.clone(),
+                None => self.chains.push(sample.clone()),
+            }
+            self.applied += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Replay;
+    use crate::record::Sample;
+    use std::time::Duration;
+
+    fn sample(ts: u64, chain: &str, block: u64) -> Sample {
+        Sample {
+            ts,
+            chain: chain.to_string(),
+            status: "connected".to_string(),
+            error: None,
+            block_number: Some(block),
+            latest_block_timestamp: Some(ts),
+            base_fee_per_gas: None,
+            gas_price: None,
+            gas_utilization: None,
+            block_delay_alert: false,
+        }
+    }
+
+    #[test]
+    fn plays_samples_at_the_requested_speed() {
+        let samples = vec![
+            sample(130, "host", 3),
+            sample(100, "host", 1),
+            sample(110, "rollup", 7),
+        ];
+        let mut replay = Replay::new("rec.jsonl".to_string(), samples, 10.0).unwrap();
+        let blocks = |replay: &Replay| {
+            replay
+                .chains
+                .iter()
+                .map(|chain| (chain.chain.clone(), chain.block_number))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(replay.position(), 100);
+        assert_eq!(blocks(&replay), [("host".to_string(), Some(1))]);
+
+        replay.advance(Duration::from_secs(1));
+        assert_eq!(replay.position(), 110);
+        assert_eq!(replay.applied(), 2);
+        assert!(!replay.finished());
+
+        replay.advance(Duration::from_secs(60));
+        assert_eq!(replay.position(), 130);
+        assert!(replay.finished());
+        assert_eq!(
+            blocks(&replay),
+            [
+                ("host".to_string(), Some(3)),
+                ("rollup".to_string(), Some(7))
+            ]
+        );
+    }
+
+    #[test]
+    fn an_empty_range_is_an_error() {
+        assert!(Replay::new("rec.jsonl".to_string(), Vec::new(), 60.0).is_err());
+    }
+}
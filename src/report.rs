@@ -0,0 +1,316 @@
+//! Time-range summaries over a metrics recording
+use crate::error::{FutureSightError, Result};
+use crate::record::Sample;
+use clap::ValueEnum;
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    time::{Duration, UNIX_EPOCH},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Text,
+    Markdown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DowntimeWindow {
+    pub start: u64,
+    pub end: u64,
+    pub cause: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainReport {
+    pub chain: String,
+    pub samples: usize,
+    pub uptime_pct: f64,
+    pub blocks_produced: Option<u64>,
+    pub avg_block_time_secs: Option<f64>,
+    pub base_fee_min: Option<u128>,
+    pub base_fee_avg: Option<f64>,
+    pub base_fee_max: Option<u128>,
+    pub avg_utilization: Option<f64>,
+    pub downtime: Vec<DowntimeWindow>,
+    pub alerts_fired: usize,
+}
+
+/// Summarize samples with `from <= ts <= to`, one report per chain in first-seen order.
+pub fn summarize(samples: &[Sample], from: u64, to: u64) -> Vec<ChainReport> {
+    let mut order: Vec<&str> = Vec::new();
+    let mut by_chain: BTreeMap<&str, Vec<&Sample>> = BTreeMap::new();
+    for sample in samples.iter().filter(|s| s.ts >= from && s.ts <= to) {
+        if !by_chain.contains_key(sample.chain.as_str()) {
+            order.push(&sample.chain);
+        }
+        by_chain.entry(&sample.chain).or_default().push(sample);
+    }
+
+    order
+        .into_iter()
+        .map(|chain| summarize_chain(chain, &by_chain[chain]))
+        .collect()
+}
+
+fn summarize_chain(chain: &str, samples: &[&Sample]) -> ChainReport {
+    let connected = samples.iter().filter(|s| s.is_connected()).count();
+
+    let heights = samples.iter().filter_map(|s| s.block_number);
+    let blocks_produced = heights
+        .clone()
+        .min()
+        .zip(heights.max())
+        .map(|(lo, hi)| hi - lo);
+    let block_times = samples.iter().filter_map(|s| s.latest_block_timestamp);
+    let avg_block_time_secs = match (
+        blocks_produced,
+        block_times.clone().min(),
+        block_times.max(),
+    ) {
+        (Some(blocks), Some(first), Some(last)) if blocks > 0 => {
+            Some((last - first) as f64 / blocks as f64)
+        }
+        _ => None,
+    };
+
+    let base_fees: Vec<u128> = samples.iter().filter_map(|s| s.base_fee_per_gas).collect();
+    let utilization: Vec<f64> = samples.iter().filter_map(|s| s.gas_utilization).collect();
+
+    let mut downtime = Vec::new();
+    let mut open: Option<DowntimeWindow> = None;
+    for sample in samples {
+        match (&mut open, sample.is_connected()) {
+            (None, false) => {
+                open = Some(DowntimeWindow {
+                    start: sample.ts,
+                    end: sample.ts,
+                    cause: sample
+                        .error
+                        .clone()
+                        .unwrap_or_else(|| sample.status.clone()),
+                })
+            }
+            (Some(window), false) => window.end = sample.ts,
+            (Some(window), true) => {
+                window.end = sample.ts;
+                downtime.extend(open.take());
+            }
+            (None, true) => {}
+        }
+    }
+    downtime.extend(open);
+
+    let alerts_fired = samples
+        .windows(2)
+        .filter(|pair| !pair[0].block_delay_alert && pair[1].block_delay_alert)
+        .count()
+        + samples
+            .first()
+            .map(|s| s.block_delay_alert as usize)
+            .unwrap_or(0);
+
+    ChainReport {
+        chain: chain.to_string(),
+        samples: samples.len(),
+        uptime_pct: if samples.is_empty() {
+            0.0
+        } else {
+            connected as f64 * 100.0 / samples.len() as f64
+        },
+        blocks_produced,
+        avg_block_time_secs,
+        base_fee_min: base_fees.iter().min().copied(),
+        base_fee_avg: (!base_fees.is_empty())
+            .then(|| base_fees.iter().map(|&x| x as f64).sum::<f64>() / base_fees.len() as f64),
+        base_fee_max: base_fees.iter().max().copied(),
+        avg_utilization: (!utilization.is_empty())
+            .then(|| utilization.iter().sum::<f64>() / utilization.len() as f64),
+        downtime,
+        alerts_fired,
+    }
+}
+
+pub fn render(reports: &[ChainReport], from: u64, to: u64, format: ReportFormat) -> String {
+    let mut out = String::new();
+    let md = format == ReportFormat::Markdown;
+    let range = format!("{} → {}", fmt_ts(from), fmt_ts(to));
+
+    if md {
+        let _ = writeln!(out, "# FutureSight report\n\n_{}_\n", range);
+    } else {
+        let _ = writeln!(out, "FutureSight report  {}\n", range);
+    }
+
+    if reports.is_empty() {
+        let _ = writeln!(out, "No samples recorded in this range.");
+        return out;
+    }
+
+    for report in reports {
+        let rows = [
+            ("Samples", report.samples.to_string()),
+            ("Uptime", format!("{:.2}%", report.uptime_pct)),
+            ("Blocks produced", opt(report.blocks_produced)),
+            (
+                "Avg block time",
+                report
+                    .avg_block_time_secs
+                    .map(|value| format!("{:.2}s", value))
+                    .unwrap_or_else(|| "N/A".to_string()),
+            ),
+            (
+                "Base fee min/avg/max",
+                format!(
+                    "{} / {} / {}",
+                    gwei(report.base_fee_min.map(|v| v as f64)),
+                    gwei(report.base_fee_avg),
+                    gwei(report.base_fee_max.map(|v| v as f64)),
+                ),
+            ),
+            (
+                "Avg utilization",
+                report
+                    .avg_utilization
+                    .map(|value| format!("{:.1}%", value))
+                    .unwrap_or_else(|| "N/A".to_string()),
+            ),
+            ("Block delay alerts", report.alerts_fired.to_string()),
+            ("Downtime windows", report.downtime.len().to_string()),
+        ];
+
+        if md {
+            let _ = writeln!(
+                out,
+                "## {}\n\n| Metric | Value |\n| --- | --- |",
+                report.chain
+            );
+            for (label, value) in rows {
+                let _ = writeln!(out, "| {} | {} |", label, value);
+            }
+            for window in &report.downtime {
+                let _ = writeln!(
+                    out,
+                    "\n- down {} → {} ({}s): {}",
+                    fmt_ts(window.start),
+                    fmt_ts(window.end),
+                    window.end - window.start,
+                    window.cause
+                );
+            }
+            let _ = writeln!(out);
+        } else {
+            let _ = writeln!(out, "[{}]", report.chain);
+            for (label, value) in rows {
+                let _ = writeln!(out, "  {:<22}{}", label, value);
+            }
+            for window in &report.downtime {
+                let _ = writeln!(
+                    out,
+                    "    down {} → {} ({}s): {}",
+                    fmt_ts(window.start),
+                    fmt_ts(window.end),
+                    window.end - window.start,
+                    window.cause
+                );
+            }
+            let _ = writeln!(out);
+        }
+    }
+
+    out
+}
+
+/// Parse `now`, unix seconds, an RFC 3339 timestamp, or a relative duration such as `24h` (ago).
+pub fn parse_time(input: &str, now: u64) -> Result<u64> {
+    let input = input.trim();
+    if input == "now" {
+        return Ok(now);
+    }
+    if let Ok(secs) = input.parse::<u64>() {
+        return Ok(secs);
+    }
+    if let Ok(time) = humantime::parse_rfc3339_weak(input) {
+        return Ok(time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs()));
+    }
+    if let Ok(ago) = humantime::parse_duration(input) {
+        return Ok(now.saturating_sub(ago.as_secs()));
+    }
+    Err(FutureSightError::Config(format!(
+        "invalid time '{}': expected now, unix seconds, RFC 3339, or a duration like 24h",
+        input
+    )))
+}
+
+pub fn fmt_ts(ts: u64) -> String {
+    humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_secs(ts)).to_string()
+}
+
+fn opt<T: std::fmt::Display>(value: Option<T>) -> String {
+    value
+        .map(|inner| inner.to_string())
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+fn gwei(wei: Option<f64>) -> String {
+    wei.map(|value| format!("{:.3}g", value / 1_000_000_000.0))
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_time, summarize};
+    use crate::record::Sample;
+
+    fn sample(ts: u64, status: &str, block: u64, delay_alert: bool) -> Sample {
+        Sample {
+            ts,
+            chain: "host".to_string(),
+            status: status.to_string(),
+            error: None,
+            block_number: Some(block),
+            latest_block_timestamp: Some(block * 12),
+            base_fee_per_gas: Some(block as u128 * 1_000_000_000),
+            gas_price: None,
+            gas_utilization: Some(50.0),
+            block_delay_alert: delay_alert,
+        }
+    }
+
+    #[test]
+    fn summarize_reports_blocks_downtime_and_alerts() {
+        let samples = vec![
+            sample(100, "connected", 10, false),
+            sample(110, "error", 10, false),
+            sample(120, "error", 10, true),
+            sample(130, "connected", 12, false),
+            sample(140, "connected", 14, true),
+            sample(999, "connected", 99, false),
+        ];
+        let reports = summarize(&samples, 100, 140);
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.samples, 5);
+        assert_eq!(report.blocks_produced, Some(4));
+        assert_eq!(report.avg_block_time_secs, Some(12.0));
+        assert_eq!(report.base_fee_min, Some(10_000_000_000));
+        assert_eq!(report.base_fee_max, Some(14_000_000_000));
+        assert_eq!(report.downtime.len(), 1);
+        assert_eq!(
+            (report.downtime[0].start, report.downtime[0].end),
+            (110, 130)
+        );
+        assert_eq!(report.alerts_fired, 2);
+    }
+
+    #[test]
+    fn parse_time_accepts_relative_and_absolute_inputs() {
+        assert_eq!(parse_time("now", 1_000).unwrap(), 1_000);
+        assert_eq!(parse_time("1h", 7_200).unwrap(), 3_600);
+        assert_eq!(parse_time("42", 7_200).unwrap(), 42);
+        assert_eq!(parse_time("1970-01-01T00:01:00Z", 0).unwrap(), 60);
+        assert!(parse_time("yesterday-ish", 0).is_err());
+    }
+}
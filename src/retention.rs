@@ -0,0 +1,252 @@
+//! `--max-memory-mb`: approximate sizes of the buffers that grow over a session, and
+//! trimming of their oldest entries once the total passes the budget. Sizes count each
+//! entry's struct plus the strings and vectors it owns, not allocator overhead or
+//! spare capacity, so the process itself uses somewhat more
+use crate::alerts::{Alert, AlertHistory};
+use crate::config::{RETENTION_MIN_ENTRIES, RETENTION_TARGET};
+use crate::data::{BlockInfo, GasUser, SignetMetrics, TxPoolTx};
+use crate::network::{ChainRole, Network};
+use crate::orders::{FillOutput, OrderFill};
+use crate::timeseries::Point;
+use alloy::eips::eip4844::{Blob, BlobTransactionSidecarItem};
+use std::mem::size_of;
+use std::time::Instant;
+
+/// The per-chain buffers that are measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Store {
+    Blocks,
+    Series,
+    Fills,
+    Bundles,
+    Outages,
+    TxPool, // replaced every poll and capped by --txpool-max-rows, so never trimmed
+}
+
+impl Store {
+    pub const ALL: [Store; 6] = [
+        Store::Blocks,
+        Store::Series,
+        Store::Fills,
+        Store::Bundles,
+        Store::Outages,
+        Store::TxPool,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Store::Blocks => "blocks",
+            Store::Series => "series",
+            Store::Fills => "fills",
+            Store::Bundles => "bundles",
+            Store::Outages => "outages",
+            Store::TxPool => "tx pool",
+        }
+    }
+}
+
+/// A store's entry count and approximate size.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Usage {
+    pub entries: usize,
+    pub bytes: usize,
+}
+
+impl Usage {
+    fn of<I: IntoIterator>(entries: I, size: impl Fn(I::Item) -> usize) -> Self {
+        entries
+            .into_iter()
+            .fold(Usage::default(), |usage, entry| Usage {
+                entries: usage.entries + 1,
+                bytes: usage.bytes + size(entry),
+            })
+    }
+}
+
+/// Every store of one chain, in [`Store::ALL`] order.
+pub fn chain_usage(metrics: &SignetMetrics) -> Vec<(Store, Usage)> {
+    Store::ALL
+        .into_iter()
+        .map(|store| (store, store_usage(metrics, store)))
+        .collect()
+}
+
+fn store_usage(metrics: &SignetMetrics, store: Store) -> Usage {
+    match store {
+        Store::Blocks => Usage::of(
+            metrics
+                .block_history
+                .iter()
+                .chain(metrics.block_history.orphaned()),
+            block_bytes,
+        ),
+        Store::Series => {
+            let points = metrics.timeseries.point_count();
+            Usage {
+                entries: points,
+                bytes: points * size_of::<Point>(),
+            }
+        }
+        Store::Fills => Usage::of(&metrics.order_flow.fills, fill_bytes),
+        Store::Bundles => Usage::of(metrics.bundle_sims.ids(), |id| {
+            size_of::<(String, crate::bundles::BundleSimStatus)>() + id.len()
+        }),
+        Store::Outages => Usage::of(&metrics.availability.log, |outage| {
+            size_of_val(outage) + outage.cause.len()
+        }),
+        Store::TxPool => Usage::of(
+            metrics
+                .txpool
+                .iter()
+                .flat_map(|txpool| &txpool.transactions),
+            |_| size_of::<TxPoolTx>(),
+        ),
+    }
+}
+
+fn block_bytes(block: &BlockInfo) -> usize {
+    size_of::<BlockInfo>()
+        + block.hash.len()
+        + block.parent_hash.len()
+        + block.extra_data.len()
+        + block.builder.len()
+        // Each sidecar item boxes its 128 KiB blob
+        + block.blobs.len() * (size_of::<BlobTransactionSidecarItem>() + size_of::<Blob>())
+        + block
+            .receipts
+            .as_ref()
+            .map_or(0, |receipts| receipts.gas_users.len() * size_of::<GasUser>())
+}
+
+fn fill_bytes(fill: &OrderFill) -> usize {
+    size_of::<OrderFill>() + fill.tx_hash.len() + fill.outputs.len() * size_of::<FillOutput>()
+}
+
+fn alert_bytes(alert: &Alert) -> usize {
+    size_of::<Alert>() + alert.chain.len() + alert.detail.as_ref().map_or(0, String::len)
+}
+
+/// How many entries of each trimmable store a chain keeps after a trim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    pub blocks: usize,
+    pub series: usize, // points per metric and resolution
+    pub fills: usize,
+    pub bundles: usize,
+    pub outages: usize,
+}
+
+impl Limits {
+    /// `keep` (0.0–1.0) of what `metrics` holds now, newest first. Absolute counts, so
+    /// applying the same limits twice trims nothing more.
+    pub fn new(metrics: &SignetMetrics, keep: f64) -> Self {
+        let count = |store| store_usage(metrics, store).entries;
+        Self {
+            blocks: keep_entries(metrics.block_history.len(), keep),
+            series: keep_entries(metrics.timeseries.longest(), keep),
+            fills: keep_entries(count(Store::Fills), keep),
+            bundles: keep_entries(count(Store::Bundles), keep),
+            outages: keep_entries(count(Store::Outages), keep),
+        }
+    }
+}
+
+fn keep_entries(entries: usize, keep: f64) -> usize {
+    ((entries as f64 * keep).ceil() as usize).max(RETENTION_MIN_ENTRIES.min(entries))
+}
+
+/// Share of every store to keep so `total` bytes drop back under the budget, or `None`
+/// while within it.
+pub fn keep_share(total: usize, budget: usize) -> Option<f64> {
+    (total > budget).then(|| budget as f64 * RETENTION_TARGET / total as f64)
+}
+
+/// The latest measurement, for the Diagnostics tab.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryReport {
+    pub total: usize,  // bytes across every chain and the alert history
+    pub alerts: Usage, // shared by all chains
+    pub budget: Option<usize>,
+    pub trims: usize,
+    pub last_trim: Option<Instant>,
+}
+
+/// The `--max-memory-mb` budget and what enforcing it has done this session.
+pub struct Retention {
+    budget: Option<usize>, // bytes
+    trims: usize,
+    last_trim: Option<Instant>,
+}
+
+impl Retention {
+    pub fn new(max_memory_mb: Option<u64>) -> Self {
+        Self {
+            budget: max_memory_mb.map(|mb| (mb * 1_000_000) as usize),
+            trims: 0,
+            last_trim: None,
+        }
+    }
+
+    /// Measure every chain and the alert history, trimming each by the same share when
+    /// the total is over budget. Returns the usage measured before trimming.
+    pub fn enforce(
+        &mut self,
+        networks: &mut [Network],
+        alert_history: &mut AlertHistory,
+    ) -> MemoryReport {
+        let alerts = Usage::of(alert_history.iter(), alert_bytes);
+        let total = networks
+            .iter()
+            .flat_map(|network| {
+                [ChainRole::Host, ChainRole::Rollup]
+                    .into_iter()
+                    .filter_map(|role| network.metrics(role))
+            })
+            .flat_map(chain_usage)
+            .map(|(_, usage)| usage.bytes)
+            .sum::<usize>()
+            + alerts.bytes;
+
+        if let Some(keep) = self.budget.and_then(|budget| keep_share(total, budget)) {
+            for network in networks.iter_mut() {
+                for role in [ChainRole::Host, ChainRole::Rollup] {
+                    if let Some(limits) = network.metrics(role).map(|m| Limits::new(m, keep)) {
+                        network.retain(role, limits);
+                    }
+                }
+            }
+            alert_history.truncate(keep_entries(alerts.entries, keep));
+            self.trims += 1;
+            self.last_trim = Some(Instant::now());
+        }
+
+        MemoryReport {
+            total,
+            alerts,
+            budget: self.budget,
+            trims: self.trims,
+            last_trim: self.last_trim,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{keep_entries, keep_share};
+
+    #[test]
+    fn trims_by_one_share_down_to_the_target() {
+        let mb = 1_000_000;
+        assert_eq!(keep_share(10 * mb, 64 * mb), None);
+        assert_eq!(keep_share(64 * mb, 64 * mb), None);
+        // 90% of the budget is 45% of what's held
+        assert_eq!(keep_share(128 * mb, 64 * mb), Some(0.45));
+
+        assert_eq!(keep_entries(1_000, 0.45), 450);
+        assert_eq!(keep_entries(101, 0.5), 51);
+        // Small stores keep their floor, and nothing is padded up to it
+        assert_eq!(keep_entries(20, 0.1), 8);
+        assert_eq!(keep_entries(3, 0.1), 3);
+        assert_eq!(keep_entries(0, 0.5), 0);
+    }
+}
@@ -0,0 +1,164 @@
+//! Per-method RPC call and failure counters for the Diagnostics tab
+use crate::record::unix_now;
+
+/// JSON-RPC methods issued by the collector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcMethod {
+    ChainId,
+    BlockNumber,
+    GasPrice,
+    GetBlock,
+    FeeHistory,
+    MaxPriorityFee,
+    BlobBaseFee,
+    Syncing,
+    TxPoolStatus,
+    BlockReceipts,
+    PendingBlock,
+    GetLogs,
+    Call,
+    StorageAt,
+    GetProof,
+    TraceBlock,
+    GetBalance,
+    GetNonce,
+    TxReceipt,
+    ClientVersion,
+}
+
+impl RpcMethod {
+    pub const ALL: [RpcMethod; 20] = [
+        RpcMethod::ChainId,
+        RpcMethod::BlockNumber,
+        RpcMethod::GasPrice,
+        RpcMethod::GetBlock,
+        RpcMethod::FeeHistory,
+        RpcMethod::MaxPriorityFee,
+        RpcMethod::BlobBaseFee,
+        RpcMethod::Syncing,
+        RpcMethod::TxPoolStatus,
+        RpcMethod::BlockReceipts,
+        RpcMethod::PendingBlock,
+        RpcMethod::GetLogs,
+        RpcMethod::Call,
+        RpcMethod::StorageAt,
+        RpcMethod::GetProof,
+        RpcMethod::TraceBlock,
+        RpcMethod::GetBalance,
+        RpcMethod::GetNonce,
+        RpcMethod::TxReceipt,
+        RpcMethod::ClientVersion,
+    ];
+
+    /// Methods skipped once the endpoint reports them unsupported. All but
+    /// `eth_getBlockReceipts`, `eth_getProof`, and `debug_traceBlockByNumber`, which are
+    /// opt-in, and `eth_getLogs`, which needs a block range, are probed at startup.
+    pub const OPTIONAL: [RpcMethod; 10] = [
+        RpcMethod::FeeHistory,
+        RpcMethod::MaxPriorityFee,
+        RpcMethod::BlobBaseFee,
+        RpcMethod::Syncing,
+        RpcMethod::TxPoolStatus,
+        RpcMethod::BlockReceipts,
+        RpcMethod::PendingBlock,
+        RpcMethod::GetLogs,
+        RpcMethod::GetProof,
+        RpcMethod::TraceBlock,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RpcMethod::ChainId => "eth_chainId",
+            RpcMethod::BlockNumber => "eth_blockNumber",
+            RpcMethod::GasPrice => "eth_gasPrice",
+            RpcMethod::GetBlock => "eth_getBlockByNumber",
+            RpcMethod::FeeHistory => "eth_feeHistory",
+            RpcMethod::MaxPriorityFee => "eth_maxPriorityFeePerGas",
+            RpcMethod::BlobBaseFee => "eth_blobBaseFee",
+            RpcMethod::Syncing => "eth_syncing",
+            RpcMethod::TxPoolStatus => "txpool_status",
+            RpcMethod::BlockReceipts => "eth_getBlockReceipts",
+            RpcMethod::PendingBlock => "eth_getBlockByNumber(pending)",
+            RpcMethod::GetLogs => "eth_getLogs",
+            RpcMethod::Call => "eth_call",
+            RpcMethod::StorageAt => "eth_getStorageAt",
+            RpcMethod::GetProof => "eth_getProof",
+            RpcMethod::TraceBlock => "debug_traceBlockByNumber",
+            RpcMethod::GetBalance => "eth_getBalance",
+            RpcMethod::GetNonce => "eth_getTransactionCount",
+            RpcMethod::TxReceipt => "eth_getTransactionReceipt",
+            RpcMethod::ClientVersion => "web3_clientVersion",
+        }
+    }
+
+    pub fn is_optional(self) -> bool {
+        Self::OPTIONAL.contains(&self)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MethodStats {
+    pub calls: u64,
+    pub failures: u64,
+    pub last_error: Option<(u64, String)>, // (unix seconds, message)
+}
+
+impl MethodStats {
+    pub fn failure_pct(&self) -> Option<f64> {
+        (self.calls > 0).then(|| self.failures as f64 * 100.0 / self.calls as f64)
+    }
+}
+
+/// Session totals for every [`RpcMethod`], in [`RpcMethod::ALL`] order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RpcStats {
+    methods: [MethodStats; RpcMethod::ALL.len()],
+}
+
+impl RpcStats {
+    pub fn record<T, E: std::fmt::Display>(&mut self, method: RpcMethod, result: &Result<T, E>) {
+        let stats = &mut self.methods[method as usize];
+        stats.calls += 1;
+        if let Err(e) = result {
+            stats.failures += 1;
+            stats.last_error = Some((unix_now(), e.to_string()));
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (RpcMethod, &MethodStats)> {
+        RpcMethod::ALL.into_iter().zip(self.methods.iter())
+    }
+
+    pub fn total_failures(&self) -> u64 {
+        self.methods.iter().map(|stats| stats.failures).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RpcMethod, RpcStats};
+
+    #[test]
+    fn failures_are_counted_per_method() {
+        let mut stats = RpcStats::default();
+        stats.record(RpcMethod::BlockNumber, &Ok::<_, String>(1));
+        stats.record(RpcMethod::FeeHistory, &Err::<(), _>("method not found"));
+        stats.record(RpcMethod::FeeHistory, &Ok::<_, String>(()));
+        let get = |method| stats.iter().find(|(m, _)| *m == method).unwrap().1;
+
+        let fee_history = get(RpcMethod::FeeHistory);
+        assert_eq!((fee_history.calls, fee_history.failures), (2, 1));
+        assert_eq!(fee_history.failure_pct(), Some(50.0));
+        assert_eq!(
+            fee_history.last_error.as_ref().map(|(_, e)| e.as_str()),
+            Some("method not found")
+        );
+        assert_eq!(get(RpcMethod::BlockNumber).failures, 0);
+        assert_eq!(get(RpcMethod::GasPrice).failure_pct(), None);
+        assert_eq!(stats.total_failures(), 1);
+        assert_eq!(
+            stats.iter().map(|(method, _)| method).collect::<Vec<_>>(),
+            RpcMethod::ALL
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Rollup sequencer lag, judged against the rollup's block cadence and the host's progress
+use crate::config::{SEQUENCER_STALL_HOST_BLOCKS, SEQUENCER_STALL_INTERVALS};
+use crate::data::SignetMetrics;
+use crate::history::BlockHistory;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SequencerLag {
+    pub since_block_secs: u64,       // wall clock since the newest rollup block
+    pub expected_interval_secs: f64, // median rollup block interval, else the host's
+    pub host_blocks_since: u64,      // host blocks landed after the newest rollup block
+}
+
+impl SequencerLag {
+    /// Several rollup intervals without a block while the host keeps producing them, so
+    /// the sequencer rather than the host (or our connection to it) is stuck.
+    pub fn is_stalled(&self) -> bool {
+        self.since_block_secs as f64 >= self.expected_interval_secs * SEQUENCER_STALL_INTERVALS
+            && self.host_blocks_since >= SEQUENCER_STALL_HOST_BLOCKS
+    }
+}
+
+/// `None` until both chains have blocks and a block interval is configured or can be
+/// estimated.
+pub fn sequencer_lag(
+    host: &SignetMetrics,
+    rollup: &SignetMetrics,
+    now: u64,
+) -> Option<SequencerLag> {
+    let newest = rollup.block_history.front()?;
+    let expected_interval_secs = rollup
+        .expected_block_time
+        .or_else(|| median_interval(&rollup.block_history))
+        .or_else(|| median_interval(&host.block_history))?;
+    let host_blocks_since = host
+        .block_history
+        .iter()
+        .take_while(|block| block.timestamp > newest.timestamp)
+        .count() as u64;
+    Some(SequencerLag {
+        since_block_secs: now.saturating_sub(newest.timestamp),
+        expected_interval_secs,
+        host_blocks_since,
+    })
+}
+
+/// Median gap between consecutive blocks, in seconds.
+fn median_interval(history: &BlockHistory) -> Option<f64> {
+    let mut gaps: Vec<u64> = history
+        .iter()
+        .zip(history.iter().skip(1))
+        .filter(|(newer, older)| newer.number == older.number + 1)
+        .map(|(newer, older)| newer.timestamp.saturating_sub(older.timestamp))
+        .filter(|gap| *gap > 0)
+        .collect();
+    if gaps.is_empty() {
+        return None;
+    }
+    gaps.sort_unstable();
+    Some(gaps[gaps.len() / 2] as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sequencer_lag;
+    use crate::data::{BlockInfo, Config, SignetMetrics};
+    use crate::thresholds::DelayThresholds;
+    use alloy::primitives::{Address, B256};
+    use std::time::Duration;
+
+    fn chain(blocks: &[(u64, u64)]) -> SignetMetrics {
+        let mut metrics = SignetMetrics::new(Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            block_delay: DelayThresholds {
+                warning: 30,
+                critical: 60,
+            },
+            expected_block_time: None,
+            stale_after: Duration::from_secs(15),
+            gas_alert_gwei: None,
+            gas_spike_multiplier: None,
+            max_block_history: 16,
+            txpool_max_rows: 1,
+            txpool_fetch_list: false,
+            txpool_watch_signet_host_contracts: false,
+            txpool_stream: None,
+            fetch_receipts: false,
+            http: Default::default(),
+        });
+        for (number, timestamp) in blocks {
+            metrics.block_history.insert(BlockInfo {
+                number: *number,
+                hash: format!("h{}", number),
+                parent_hash: format!("h{}", number - 1),
+                state_root: B256::ZERO,
+                timestamp: *timestamp,
+                tx_count: 0,
+                gas_used: 0,
+                gas_limit: 0,
+                blobs: vec![],
+                fee_recipient: Address::ZERO,
+                extra_data: String::new(),
+                builder: String::new(),
+                tip_revenue_wei: None,
+                base_fee_per_gas: None,
+                blob_gas_used: None,
+                excess_blob_gas: None,
+                withdrawals_count: None,
+                size: None,
+                calldata_bytes: None,
+                receipts: None,
+            });
+        }
+        metrics
+    }
+
+    #[test]
+    fn stalled_only_when_the_host_keeps_moving() {
+        let rollup = chain(&[(10, 1_000), (11, 1_012), (12, 1_024)]);
+        let host_moving = chain(&[(100, 1_024), (101, 1_036), (102, 1_048), (103, 1_060)]);
+        let host_stuck = chain(&[(100, 1_024)]);
+
+        let lag = sequencer_lag(&host_moving, &rollup, 1_062).unwrap();
+        assert_eq!(lag.expected_interval_secs, 12.0);
+        assert_eq!(lag.host_blocks_since, 3);
+        assert!(lag.is_stalled());
+
+        // Fewer than three intervals, or no host progress, is not a sequencer stall
+        assert!(
+            !sequencer_lag(&host_moving, &rollup, 1_050)
+                .unwrap()
+                .is_stalled()
+        );
+        assert!(
+            !sequencer_lag(&host_stuck, &rollup, 1_100)
+                .unwrap()
+                .is_stalled()
+        );
+        assert!(sequencer_lag(&host_stuck, &chain(&[]), 1_100).is_none());
+    }
+}
@@ -0,0 +1,170 @@
+//! Whole-session aggregates, kept as blocks arrive so the summary printed on quit doesn't
+//! depend on the bounded block history
+use crate::data::{BlockInfo, SignetMetrics};
+use crate::units;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionStats {
+    pub blocks: u64,
+    lowest: Option<(u64, u64)>, // (number, timestamp)
+    highest: Option<(u64, u64)>,
+    pub base_fee: FeeRange,  // per block
+    pub gas_price: FeeRange, // per collection cycle
+}
+
+/// Minimum, maximum, and mean of a fee in wei.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FeeRange {
+    pub min: Option<u128>,
+    pub max: Option<u128>,
+    sum: u128,
+    count: u64,
+}
+
+impl FeeRange {
+    pub fn observe(&mut self, fee: u128) {
+        self.min = Some(self.min.map_or(fee, |min| min.min(fee)));
+        self.max = Some(self.max.map_or(fee, |max| max.max(fee)));
+        self.sum = self.sum.saturating_add(fee);
+        self.count += 1;
+    }
+
+    pub fn avg(&self) -> Option<u128> {
+        (self.count > 0).then(|| self.sum / self.count as u128)
+    }
+}
+
+impl SessionStats {
+    pub fn observe(&mut self, block: &BlockInfo) {
+        self.blocks += 1;
+        let point = (block.number, block.timestamp);
+        if self.lowest.is_none_or(|(number, _)| block.number < number) {
+            self.lowest = Some(point);
+        }
+        if self.highest.is_none_or(|(number, _)| block.number > number) {
+            self.highest = Some(point);
+        }
+        if let Some(fee) = block.base_fee_per_gas {
+            self.base_fee.observe(fee);
+        }
+    }
+
+    /// Mean seconds between blocks across the lowest and highest block seen.
+    pub fn avg_block_time(&self) -> Option<f64> {
+        let ((low, low_ts), (high, high_ts)) = (self.lowest?, self.highest?);
+        (high > low).then(|| high_ts.saturating_sub(low_ts) as f64 / (high - low) as f64)
+    }
+}
+
+/// Plain-text summary for the normal screen after the dashboard exits.
+pub fn render_summary(
+    elapsed: Duration,
+    chains: &[(String, &SignetMetrics)],
+    alerts_fired: usize,
+) -> String {
+    let elapsed = Duration::from_secs(elapsed.as_secs());
+    let mut out = format!("Session {}\n", humantime::format_duration(elapsed));
+    let width = chains
+        .iter()
+        .map(|(chain, _)| chain.len())
+        .max()
+        .unwrap_or(0);
+    for (chain, metrics) in chains {
+        let stats = &metrics.session;
+        let base_fee = match (stats.base_fee.min, stats.base_fee.max) {
+            (Some(min), Some(max)) => format!(
+                "{}g–{}g",
+                units::format_gwei_auto(min),
+                units::format_gwei_auto(max)
+            ),
+            _ => "--".to_string(),
+        };
+        out.push_str(&format!(
+            "  {:<width$}  {} blocks  avg block {}  base fee {}  RPC errors {}\n",
+            chain,
+            units::format_count(stats.blocks),
+            stats
+                .avg_block_time()
+                .map(|secs| match metrics.blocks_expected(secs) {
+                    Some(blocks) => format!("{:.1}s ({:.2}× expected)", secs, blocks),
+                    None => format!("{:.1}s", secs),
+                })
+                .unwrap_or_else(|| "--".to_string()),
+            base_fee,
+            units::format_count(metrics.rpc_stats.total_failures()),
+            width = width
+        ));
+    }
+    out.push_str(&format!("  alerts fired {}\n", alerts_fired));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FeeRange, SessionStats};
+    use crate::data::BlockInfo;
+    use alloy::primitives::{Address, B256};
+
+    fn block(number: u64, timestamp: u64, base_fee: Option<u128>) -> BlockInfo {
+        BlockInfo {
+            number,
+            hash: String::new(),
+            parent_hash: String::new(),
+            state_root: B256::ZERO,
+            timestamp,
+            tx_count: 0,
+            gas_used: 0,
+            gas_limit: 0,
+            blobs: Vec::new(),
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: String::new(),
+            tip_revenue_wei: None,
+            base_fee_per_gas: base_fee,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        }
+    }
+
+    #[test]
+    fn aggregates_blocks_in_any_order() {
+        let mut stats = SessionStats::default();
+        assert_eq!(stats.avg_block_time(), None);
+        stats.observe(&block(10, 120, Some(7)));
+        assert_eq!(stats.avg_block_time(), None);
+        // Backfill delivers older blocks after newer ones
+        stats.observe(&block(7, 84, None));
+        stats.observe(&block(8, 96, Some(3)));
+
+        assert_eq!(stats.blocks, 3);
+        assert_eq!(stats.avg_block_time(), Some(12.0));
+        assert_eq!((stats.base_fee.min, stats.base_fee.max), (Some(3), Some(7)));
+        assert_eq!(stats.base_fee.avg(), Some(5));
+    }
+
+    #[test]
+    fn gas_price_range_covers_every_refresh_from_the_first() {
+        let mut range = FeeRange::default();
+        assert_eq!((range.min, range.avg(), range.max), (None, None, None));
+
+        // The first sample is the minimum, mean, and maximum at once
+        range.observe(20);
+        assert_eq!(
+            (range.min, range.avg(), range.max),
+            (Some(20), Some(20), Some(20))
+        );
+
+        for gas_price in [35, 5, 20] {
+            range.observe(gas_price);
+        }
+        assert_eq!(
+            (range.min, range.avg(), range.max),
+            (Some(5), Some(20), Some(35))
+        );
+    }
+}
@@ -0,0 +1,395 @@
+//! Optional TOML configuration file
+use crate::availability::Slo;
+use crate::chains;
+use crate::email::EmailSettings;
+use crate::error::{FutureSightError, Result};
+use crate::forks::Fork;
+use crate::paging::{OpsgenieSettings, PagerDutySettings};
+use crate::probes::Probe;
+use crate::ui::Panel;
+use crate::units;
+use alloy::primitives::Address;
+use serde::{Deserialize, Deserializer};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Settings loaded from `config.toml`. Everything is optional; CLI flags supply the defaults.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// Named networks collected concurrently; the dashboard shows one at a time.
+    pub networks: Vec<NetworkSettings>,
+    /// Extra RPC URLs offered by the `u` endpoint picker, alongside every network's own URLs.
+    pub endpoints: Vec<String>,
+    /// Extra fee-recipient → builder labels, merged over the built-in table.
+    pub builders: HashMap<Address, String>,
+    /// Network names by chain ID, shown in the feed panel over the built-in registry's.
+    #[serde(deserialize_with = "chains::by_chain_id")]
+    pub chains: BTreeMap<u64, String>,
+    /// Chain panels to show, top to bottom; unset shows them all in the default order.
+    pub panels: Option<Vec<Panel>>,
+    /// `eth_call` probes shown in the Custom Probes panel.
+    pub probes: Vec<Probe>,
+    /// Availability objective for every chain; the error budget is shown under Uptime.
+    pub slo: Option<Slo>,
+    /// Upcoming upgrades counted down in the Feed panel of chains with a matching chain id.
+    pub forks: Vec<Fork>,
+    /// Alert channels beyond the dashboard, e.g. `[alerts.email]`.
+    pub alerts: AlertSettings,
+    /// Named environments selected with `--profile` or the `P` picker.
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// A named variant of the top-level settings, e.g. `[profiles.mainnet]`. Each key it sets
+/// replaces the top-level one; `builders` and `chains` are merged over the top-level labels.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    pub networks: Option<Vec<NetworkSettings>>,
+    pub endpoints: Option<Vec<String>>,
+    pub builders: HashMap<Address, String>,
+    #[serde(deserialize_with = "chains::by_chain_id")]
+    pub chains: BTreeMap<u64, String>,
+    pub panels: Option<Vec<Panel>>,
+    pub probes: Option<Vec<Probe>>,
+    pub slo: Option<Slo>,
+    pub forks: Option<Vec<Fork>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertSettings {
+    pub email: Option<EmailSettings>,
+    pub pagerduty: Option<PagerDutySettings>,
+    pub opsgenie: Option<OpsgenieSettings>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkSettings {
+    pub name: String,
+    pub host_rpc_url: String,
+    #[serde(default)]
+    pub rollup_rpc_url: Option<String>,
+    #[serde(default)]
+    pub txpool_url: Option<String>,
+    /// Second endpoints cross-checked against the primary ones, like `--compare-rpc`.
+    #[serde(default)]
+    pub host_compare_rpc_url: Option<String>,
+    #[serde(default)]
+    pub rollup_compare_rpc_url: Option<String>,
+    /// Beacon API of the host chain's consensus client, like `--beacon-url`.
+    #[serde(default)]
+    pub beacon_url: Option<String>,
+    /// External gas oracles for the host chain, like `--gas-oracle`.
+    #[serde(default)]
+    pub gas_oracles: Vec<String>,
+    /// Block explorer URL templates, e.g. `https://etherscan.io/block/{number}`.
+    #[serde(default)]
+    pub host_explorer_url: Option<String>,
+    #[serde(default)]
+    pub rollup_explorer_url: Option<String>,
+    /// Gas alert thresholds; unset ones are scaled to the observed fee baseline.
+    #[serde(default)]
+    pub host_gas_alert_gwei: Option<f64>,
+    #[serde(default)]
+    pub rollup_gas_alert_gwei: Option<f64>,
+    #[serde(default)]
+    pub gas_spike_multiplier: Option<f64>,
+    /// Seconds per block, for block delay thresholds and relative delays.
+    #[serde(default)]
+    pub host_expected_block_time: Option<f64>,
+    #[serde(default)]
+    pub rollup_expected_block_time: Option<f64>,
+    /// Large transfer alert threshold, like `--large-transfer-eth`: ETH in the file, wei here.
+    #[serde(
+        default,
+        rename = "large_transfer_eth",
+        deserialize_with = "eth_amount"
+    )]
+    pub large_transfer_wei: Option<u128>,
+    /// Block delay levels and staleness in seconds, like the flags of the same names.
+    #[serde(default)]
+    pub block_delay_secs: Option<u64>,
+    #[serde(default)]
+    pub block_delay_warn_secs: Option<u64>,
+    #[serde(default)]
+    pub stale_after_secs: Option<u64>,
+}
+
+impl Settings {
+    pub fn parse(raw: &str) -> Result<Self> {
+        toml::from_str(raw).map_err(|e| FutureSightError::Config(e.to_string()))
+    }
+
+    pub fn profile_names(&self) -> Vec<String> {
+        self.profiles.keys().cloned().collect()
+    }
+
+    /// These settings with profile `name` applied over the top-level keys.
+    pub fn with_profile(&self, name: &str) -> Result<Self> {
+        let profile = self.profiles.get(name).ok_or_else(|| {
+            FutureSightError::Config(match self.profiles.len() {
+                0 => format!("unknown profile '{}': the config defines none", name),
+                _ => format!(
+                    "unknown profile '{}' (have {})",
+                    name,
+                    self.profile_names().join(", ")
+                ),
+            })
+        })?;
+        let mut settings = self.clone();
+        if let Some(networks) = &profile.networks {
+            settings.networks = networks.clone();
+        }
+        if let Some(endpoints) = &profile.endpoints {
+            settings.endpoints = endpoints.clone();
+        }
+        settings.builders.extend(profile.builders.clone());
+        settings.chains.extend(profile.chains.clone());
+        if let Some(panels) = &profile.panels {
+            settings.panels = Some(panels.clone());
+        }
+        if let Some(probes) = &profile.probes {
+            settings.probes = probes.clone();
+        }
+        if let Some(slo) = profile.slo {
+            settings.slo = Some(slo);
+        }
+        if let Some(forks) = &profile.forks {
+            settings.forks = forks.clone();
+        }
+        Ok(settings)
+    }
+
+    /// Load settings from an explicit path (which must exist), or from the default
+    /// location if present, or fall back to defaults.
+    pub fn load(explicit: Option<&Path>) -> Result<Self> {
+        let path = match explicit {
+            Some(path) => path.to_path_buf(),
+            None => match default_config_path().filter(|path| path.exists()) {
+                Some(path) => path,
+                None => return Ok(Self::default()),
+            },
+        };
+        let raw = fs::read_to_string(&path).map_err(|e| {
+            FutureSightError::Config(format!("cannot read config '{}': {}", path.display(), e))
+        })?;
+        Self::parse(&raw).map_err(|e| {
+            FutureSightError::Config(format!("invalid config '{}': {}", path.display(), e))
+        })
+    }
+}
+
+/// A config file path and the modification time of the version last loaded, so threshold
+/// changes apply while running.
+///
+/// Polled once per loop pass, like [`crate::address_book::AddressBookFile`] and
+/// [`crate::watchlist::WatchlistFile`], rather than watched with inotify/FSEvents: a `stat`
+/// every pass costs nothing next to the RPC polling, it sees editors that save by renaming
+/// a new file over the old one and files that don't exist yet, and it keeps working on
+/// network and container mounts where change events are unreliable or never arrive.
+#[derive(Debug)]
+pub struct SettingsFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl SettingsFile {
+    /// Start from the version on disk now, which the caller has just loaded.
+    pub fn new(path: PathBuf) -> Self {
+        let modified = modified(&path);
+        Self { path, modified }
+    }
+
+    /// Re-read the file if its modification time changed since the last poll. A file that
+    /// disappears keeps the settings in use; `None` means nothing changed.
+    pub fn poll(&mut self) -> Option<Result<Settings>> {
+        let modified = modified(&self.path);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        modified.map(|_| Settings::load(Some(&self.path)))
+    }
+}
+
+/// An ETH amount as wei. TOML floats are read back through their shortest decimal form,
+/// so `0.1` is exactly 10^17 wei; a quoted string like `"0.1"` works too.
+fn eth_amount<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u128>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Amount {
+        Number(f64),
+        Text(String),
+    }
+    let raw = match Amount::deserialize(deserializer)? {
+        Amount::Number(eth) => eth.to_string(),
+        Amount::Text(eth) => eth,
+    };
+    units::parse_eth(&raw)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// `$XDG_CONFIG_HOME/futuresight/config.toml`, or `~/.config/futuresight/config.toml`.
+pub fn default_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join(env!("CARGO_PKG_NAME")).join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Settings;
+    use crate::network::ChainRole;
+    use crate::ui::Panel;
+    use alloy::primitives::Address;
+
+    #[test]
+    fn parses_network_list() {
+        let settings = Settings::parse(
+            r#"
+            endpoints = ["http://localhost:8545"]
+            panels = ["connection", "gas", "blocks"]
+
+            [[networks]]
+            name = "parmigiana"
+            host_rpc_url = "https://host-rpc.parmigiana.signet.sh"
+            rollup_rpc_url = "https://rpc.parmigiana.signet.sh"
+            txpool_url = "https://transactions.parmigiana.signet.sh"
+
+            [[networks]]
+            name = "sepolia"
+            host_rpc_url = "https://rpc.sepolia.org"
+
+            [builders]
+            "0x00000000000000000000000000000000000000aa" = "local builder"
+
+            [[probes]]
+            label = "WETH supply"
+            chain = "rollup"
+            to = "0x00000000000000000000000000000000000000bb"
+            data = "0x18160ddd"
+            decimals = 18
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.endpoints, vec!["http://localhost:8545"]);
+        assert_eq!(
+            settings.panels,
+            Some(vec![Panel::Connection, Panel::Gas, Panel::Blocks])
+        );
+        assert_eq!(settings.networks.len(), 2);
+        assert_eq!(settings.networks[1].name, "sepolia");
+        assert_eq!(settings.networks[1].rollup_rpc_url, None);
+        assert_eq!(
+            settings.builders[&Address::with_last_byte(0xaa)],
+            "local builder"
+        );
+        let probe = &settings.probes[0];
+        assert!(probe.applies_to("sepolia", ChainRole::Rollup));
+        assert!(!probe.applies_to("sepolia", ChainRole::Host));
+        assert_eq!((probe.data.len(), probe.decimals), (4, 18));
+    }
+
+    #[test]
+    fn profiles_replace_the_keys_they_set() {
+        let settings = Settings::parse(
+            r#"
+            panels = ["connection", "gas"]
+            slo = { target = 99.5, window = "24h" }
+
+            [[networks]]
+            name = "local"
+            host_rpc_url = "http://localhost:8545"
+
+            [profiles.pecorino]
+            panels = ["blocks"]
+
+            [[profiles.pecorino.networks]]
+            name = "pecorino"
+            host_rpc_url = "https://host-rpc.pecorino.signet.sh"
+            host_gas_alert_gwei = 5.0
+
+            [profiles.mainnet.builders]
+            "0x00000000000000000000000000000000000000aa" = "mainnet builder"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(settings.profile_names(), ["mainnet", "pecorino"]);
+
+        let pecorino = settings.with_profile("pecorino").unwrap();
+        assert_eq!(pecorino.networks[0].name, "pecorino");
+        assert_eq!(pecorino.networks[0].host_gas_alert_gwei, Some(5.0));
+        assert_eq!(pecorino.panels, Some(vec![Panel::Blocks]));
+        // Keys a profile leaves out keep their top-level values
+        let mainnet = settings.with_profile("mainnet").unwrap();
+        assert_eq!(mainnet.networks[0].name, "local");
+        assert_eq!(mainnet.panels, Some(vec![Panel::Connection, Panel::Gas]));
+        assert_eq!(mainnet.builders.len(), 1);
+        assert_eq!(mainnet.slo.map(|slo| slo.target), Some(99.5));
+
+        assert!(settings.with_profile("devnet").is_err());
+    }
+
+    #[test]
+    fn large_transfer_thresholds_are_exact_wei() {
+        let settings = Settings::parse(
+            r#"
+            [[networks]]
+            name = "a"
+            host_rpc_url = "http://a"
+            large_transfer_eth = 0.1
+
+            [[networks]]
+            name = "b"
+            host_rpc_url = "http://b"
+            large_transfer_eth = "2.5"
+            "#,
+        )
+        .unwrap();
+        let wei: Vec<_> = settings
+            .networks
+            .iter()
+            .map(|network| network.large_transfer_wei)
+            .collect();
+        assert_eq!(
+            wei,
+            [
+                Some(100_000_000_000_000_000),
+                Some(2_500_000_000_000_000_000)
+            ]
+        );
+        assert!(
+            Settings::parse(
+                "[[networks]]\nname = \"a\"\nhost_rpc_url = \"http://a\"\nlarge_transfer_eth = 0"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn empty_file_and_unknown_keys() {
+        assert_eq!(Settings::parse("").unwrap(), Settings::default());
+        assert!(Settings::parse("refresh = 3").is_err());
+        assert!(Settings::parse(r#"panels = ["mempool"]"#).is_err());
+        let bad_type = r#"[[probes]]
+            label = "x"
+            to = "0x00000000000000000000000000000000000000bb"
+            data = "0x"
+            decode = "uint257""#;
+        assert!(Settings::parse(bad_type).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+//! Dashboard state persisted across restarts
+use crate::error::{FutureSightError, Result};
+use crate::ui::Tab;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// UI state written on quit and restored on launch.
+///
+/// Every field is optional so state files written by older versions keep loading
+/// and unset values defer to the CLI/config defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DashboardState {
+    pub host_contract_filter_active: Option<bool>,
+    /// Name of the network shown when several are configured.
+    pub selected_network: Option<String>,
+    pub tab: Option<Tab>,
+    /// Chart zoom level (index) and pan in seconds.
+    pub chart_zoom: Option<usize>,
+    pub chart_pan: Option<u64>,
+    /// Selected row of the Alerts tab's alert type list.
+    pub alert_kind: Option<usize>,
+    pub paused: Option<bool>,
+}
+
+impl DashboardState {
+    /// Load state from `path`, falling back to defaults if the file is missing or unreadable.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write state to `path` atomically, creating parent directories as needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let storage_error = |e: std::io::Error| {
+            FutureSightError::Storage(format!("cannot save state '{}': {}", path.display(), e))
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(storage_error)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        let json = serde_json::to_vec_pretty(self).map_err(|e| storage_error(e.into()))?;
+        fs::write(&tmp, json).map_err(storage_error)?;
+        fs::rename(&tmp, path).map_err(storage_error)?;
+        Ok(())
+    }
+}
+
+/// `$XDG_STATE_HOME/futuresight/state.json`, or `~/.local/state/futuresight/state.json`.
+pub fn default_state_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_STATE_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("state"))
+        })?;
+    Some(base.join(env!("CARGO_PKG_NAME")).join("state.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DashboardState;
+    use crate::ui::Tab;
+
+    #[test]
+    fn state_round_trips_and_tolerates_missing_files() {
+        let dir = std::env::temp_dir().join(format!("futuresight-state-{}", std::process::id()));
+        let path = dir.join("state.json");
+        assert_eq!(DashboardState::load(&path), DashboardState::default());
+
+        let state = DashboardState {
+            host_contract_filter_active: Some(false),
+            selected_network: Some("sepolia".to_string()),
+            tab: Some(Tab::GasUsers),
+            chart_zoom: Some(3),
+            chart_pan: Some(1_800),
+            alert_kind: Some(2),
+            paused: Some(true),
+        };
+        state.save(&path).unwrap();
+        assert_eq!(DashboardState::load(&path), state);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn older_state_files_keep_loading() {
+        let state: DashboardState =
+            serde_json::from_str(r#"{"selected_network":"sepolia","tab":"gas-users"}"#).unwrap();
+        assert_eq!(state.tab, Some(Tab::GasUsers));
+        assert_eq!(state.paused, None);
+    }
+}
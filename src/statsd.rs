@@ -0,0 +1,160 @@
+//! `--statsd`: the OTLP gauges and fired alerts sent over UDP in DogStatsD format, for a
+//! Datadog agent (or any StatsD server that accepts `#tag` suffixes)
+//!
+//! Each refreshed chain sends the [`GAUGES`] tagged `chain:<label>`, with the collection
+//! duration as a timer; each fired alert sends an event. Datagrams are fire-and-forget.
+use crate::alerts::{Alert, Severity};
+use crate::config::STATSD_MAX_PACKET;
+use crate::error::{FutureSightError, Result};
+use crate::otlp::{GAUGES, Observation};
+use std::{
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket},
+};
+
+pub struct StatsdSink {
+    socket: UdpSocket,
+}
+
+impl StatsdSink {
+    pub fn new(addr: &str) -> Result<Self> {
+        let socket = connect_udp(addr)
+            .map_err(|e| FutureSightError::Config(format!("--statsd {}: {}", addr, e)))?;
+        Ok(Self { socket })
+    }
+
+    pub fn gauges(&self, observations: &[Observation]) {
+        let lines: Vec<String> = observations.iter().flat_map(metric_lines).collect();
+        self.send(&lines);
+    }
+
+    pub fn events(&self, fired: &[Alert]) {
+        let lines: Vec<String> = fired.iter().map(event_line).collect();
+        self.send(&lines);
+    }
+
+    /// Drops what the agent doesn't take: no agent listening must not affect the dashboard.
+    fn send(&self, lines: &[String]) {
+        for packet in packets(lines, STATSD_MAX_PACKET) {
+            let _ = self.socket.send(packet.as_bytes());
+        }
+    }
+}
+
+/// Resolve `addr` (`host:port`) once and connect a non-blocking UDP socket to it.
+pub fn connect_udp(addr: &str) -> io::Result<UdpSocket> {
+    let target = addr
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no addresses"))?;
+    let local: SocketAddr = match target {
+        SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    };
+    let socket = UdpSocket::bind(local)?;
+    socket.connect(target)?;
+    socket.set_nonblocking(true)?;
+    Ok(socket)
+}
+
+/// One line per gauge the observation has a value for; millisecond units become timers.
+fn metric_lines(obs: &Observation) -> Vec<String> {
+    GAUGES
+        .iter()
+        .filter_map(|(name, unit, _, extract)| {
+            let kind = if *unit == "ms" { "ms" } else { "g" };
+            extract(obs)
+                .map(|value| format!("{}:{}|{}|#chain:{}", name, value, kind, tag(&obs.chain)))
+        })
+        .collect()
+}
+
+/// A DogStatsD event: `_e{title bytes,text bytes}:title|text|t:alert type|...`.
+fn event_line(alert: &Alert) -> String {
+    let title = format!("{} {}", alert.chain, alert.kind.label());
+    let text = alert.summary().replace('\n', "\\n");
+    let alert_type = match alert.severity {
+        Severity::Critical => "error",
+        Severity::Warning => "warning",
+    };
+    format!(
+        "_e{{{},{}}}:{}|{}|d:{}|t:{}|s:{}|#chain:{},kind:{}",
+        title.len(),
+        text.len(),
+        title,
+        text,
+        alert.started,
+        alert_type,
+        env!("CARGO_PKG_NAME"),
+        tag(&alert.chain),
+        alert.kind.name()
+    )
+}
+
+/// A tag value without the characters DogStatsD uses as separators.
+fn tag(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if matches!(c, '|' | ',' | '#') || c.is_whitespace() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Newline-joined lines, as few datagrams as fit under `max` bytes. A longer line goes alone.
+fn packets(lines: &[String], max: usize) -> Vec<String> {
+    let mut packets: Vec<String> = Vec::new();
+    for line in lines {
+        match packets.last_mut() {
+            Some(packet) if packet.len() + 1 + line.len() <= max => {
+                packet.push('\n');
+                packet.push_str(line);
+            }
+            _ => packets.push(line.clone()),
+        }
+    }
+    packets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{metric_lines, packets, tag};
+    use crate::otlp::{Observation, Snapshot};
+    use std::time::Duration;
+
+    #[test]
+    fn formats_gauges_and_timers_with_chain_tags() {
+        let obs = Observation {
+            chain: "mainnet host".to_string(),
+            end_ns: 0,
+            duration: Duration::from_millis(250),
+            metrics: Snapshot {
+                status: "connected",
+                block_height: Some(42),
+                ..Snapshot::default()
+            },
+        };
+        assert_eq!(
+            metric_lines(&obs),
+            [
+                "futuresight.up:1|g|#chain:mainnet_host",
+                "futuresight.collect.duration:250|ms|#chain:mainnet_host",
+                "futuresight.block.height:42|g|#chain:mainnet_host",
+            ]
+        );
+        assert_eq!(tag("a|b,c#d"), "a_b_c_d");
+    }
+
+    #[test]
+    fn packs_lines_into_datagrams() {
+        let lines: Vec<String> = ["aaaa", "bbbb", "cccc", "dddddddddddd"]
+            .map(str::to_string)
+            .to_vec();
+        assert_eq!(packets(&lines, 9), ["aaaa\nbbbb", "cccc", "dddddddddddd"]);
+        assert!(packets(&[], 9).is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+//! `--stream-listen`: server-sent events carrying each chain's snapshot as it's collected,
+//! so a web page or another terminal can mirror the dashboard remotely
+//!
+//! `GET /events` answers with `text/event-stream`. Every collection pushes an `event:
+//! sample` frame holding a recording [`Sample`]; alerts that fire push an `event: alert`.
+//! New clients get the newest sample of every chain first, so they needn't wait a refresh.
+use crate::alerts::{Alert, Severity};
+use crate::config::STREAM_KEEPALIVE_SECS;
+use crate::data::SignetMetrics;
+use crate::error::{FutureSightError, Result};
+use crate::record::Sample;
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::broadcast::{self, error::RecvError},
+    time::{interval, timeout},
+};
+
+const CLIENT_BUFFER: usize = 256; // frames a slow client may fall behind before skipping
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Newest `sample` frame per chain, replayed to each client as it connects.
+type Latest = Arc<Mutex<BTreeMap<String, String>>>;
+
+pub struct StreamServer {
+    frames: broadcast::Sender<String>,
+    latest: Latest,
+}
+
+impl StreamServer {
+    /// Bind now, so a taken port fails at startup, and accept clients in the background.
+    pub async fn bind(addr: SocketAddr) -> Result<Self> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| FutureSightError::Config(format!("cannot listen on {}: {}", addr, e)))?;
+        let (frames, _) = broadcast::channel(CLIENT_BUFFER);
+        let latest = Latest::default();
+        tokio::spawn(accept(listener, frames.clone(), latest.clone()));
+        Ok(Self { frames, latest })
+    }
+
+    pub fn publish_sample(&self, chain: &str, metrics: &SignetMetrics, ts: u64) {
+        let data = serde_json::to_string(&Sample::from_metrics(chain, metrics, ts))
+            .expect("samples serialize");
+        let frame = frame("sample", &data);
+        if let Ok(mut latest) = self.latest.lock() {
+            latest.insert(chain.to_string(), frame.clone());
+        }
+        let _ = self.frames.send(frame); // no clients connected
+    }
+
+    pub fn publish_alert(&self, alert: &Alert) {
+        let data = json!({
+            "chain": alert.chain,
+            "kind": alert.kind.name(),
+            "severity": match alert.severity {
+                Severity::Critical => "critical",
+                Severity::Warning => "warning",
+            },
+            "started": alert.started,
+            "summary": alert.summary(),
+        });
+        let _ = self.frames.send(frame("alert", &data.to_string()));
+    }
+}
+
+/// One SSE frame. `data` is single-line JSON, so it needs no `data:` continuation lines.
+fn frame(event: &str, data: &str) -> String {
+    format!("event: {}\ndata: {}\n\n", event, data)
+}
+
+/// Whether a request line asks for the event stream: `GET /events`, query string ignored.
+fn wants_events(request_line: &str) -> bool {
+    let mut parts = request_line.split_whitespace();
+    let (Some("GET"), Some(target)) = (parts.next(), parts.next()) else {
+        return false;
+    };
+    target.split('?').next() == Some("/events")
+}
+
+async fn accept(listener: TcpListener, frames: broadcast::Sender<String>, latest: Latest) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(serve(stream, frames.subscribe(), latest.clone()));
+            }
+            // Out of file descriptors, say; don't spin on it
+            Err(_) => tokio::time::sleep(Duration::from_millis(100)).await,
+        }
+    }
+}
+
+async fn serve(stream: TcpStream, mut frames: broadcast::Receiver<String>, latest: Latest) {
+    let mut stream = BufReader::new(stream);
+    let Ok(Some(request_line)) = timeout(REQUEST_TIMEOUT, read_head(&mut stream)).await else {
+        return;
+    };
+    if !wants_events(&request_line) {
+        let _ = stream
+            .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+            .await;
+        return;
+    }
+    let backlog: String = latest
+        .lock()
+        .map(|latest| latest.values().cloned().collect())
+        .unwrap_or_default();
+    let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\
+                Access-Control-Allow-Origin: *\r\nConnection: keep-alive\r\n\r\n";
+    if stream
+        .write_all(format!("{}{}", head, backlog).as_bytes())
+        .await
+        .is_err()
+    {
+        return;
+    }
+    // Comment lines keep proxies from timing out an idle stream and notice gone clients
+    let mut keepalive = interval(Duration::from_secs(STREAM_KEEPALIVE_SECS));
+    loop {
+        let out = tokio::select! {
+            received = frames.recv() => match received {
+                Ok(frame) => frame,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return,
+            },
+            _ = keepalive.tick() => ":\n\n".to_string(),
+        };
+        if stream.write_all(out.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// The request line, after reading through the blank line that ends the headers.
+async fn read_head(stream: &mut BufReader<TcpStream>) -> Option<String> {
+    let mut request_line = String::new();
+    stream.read_line(&mut request_line).await.ok()?;
+    let mut header = String::new();
+    loop {
+        header.clear();
+        match stream.read_line(&mut header).await {
+            Ok(0) | Err(_) => return None,
+            Ok(_) if header.trim().is_empty() => return Some(request_line),
+            Ok(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{frame, wants_events};
+
+    #[test]
+    fn frames_events_and_routes_only_the_event_path() {
+        assert_eq!(
+            frame("sample", r#"{"chain":"host"}"#),
+            "event: sample\ndata: {\"chain\":\"host\"}\n\n"
+        );
+        assert!(wants_events("GET /events HTTP/1.1\r\n"));
+        assert!(wants_events("GET /events?chain=host HTTP/1.1\r\n"));
+        assert!(!wants_events("POST /events HTTP/1.1\r\n"));
+        assert!(!wants_events("GET / HTTP/1.1\r\n"));
+        assert!(!wants_events(""));
+    }
+}
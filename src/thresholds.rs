@@ -0,0 +1,338 @@
+//! Alert thresholds: block delay levels, and gas thresholds either configured or scaled to
+//! a chain's observed fee baseline
+use crate::config::{
+    BLOCK_DELAY_DEFAULT, BLOCK_DELAY_EXPECTED_BLOCKS, GAS_ALERT_HIGH_GWEI,
+    GAS_BASELINE_HIGH_FACTOR, GAS_BASELINE_SPIKE_HEADROOM, GAS_BASELINE_WINDOW,
+    GAS_SPIKE_MULTIPLIER,
+};
+use crate::units::format_eth;
+use std::time::{Duration, Instant};
+
+/// Seconds since the newest block at which the block delay alert turns yellow, then red.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DelayThresholds {
+    pub warning: u64,
+    pub critical: u64,
+}
+
+impl DelayThresholds {
+    /// Configured levels, else critical at [`BLOCK_DELAY_EXPECTED_BLOCKS`] expected block
+    /// times (or [`BLOCK_DELAY_DEFAULT`] without one) and warning at half of critical.
+    pub fn resolve(
+        warning: Option<u64>,
+        critical: Option<u64>,
+        expected_block_time: Option<f64>,
+    ) -> Self {
+        let critical = critical.unwrap_or_else(|| {
+            expected_block_time.map_or(BLOCK_DELAY_DEFAULT, |secs| {
+                (secs * BLOCK_DELAY_EXPECTED_BLOCKS).ceil().max(1.0) as u64
+            })
+        });
+        Self {
+            warning: warning.unwrap_or(critical / 2),
+            critical,
+        }
+    }
+}
+
+/// Where a chain's current gas thresholds came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdSource {
+    Configured,
+    Default, // mainnet constants, while the baseline is still being observed
+    Baseline,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GasThresholds {
+    pub high_gwei: f64,        // HighBaseFee fires at or above this base fee
+    pub spike_multiplier: f64, // FeeSpike fires at or above this base fee / MA ratio
+    pub source: ThresholdSource,
+}
+
+impl Default for GasThresholds {
+    fn default() -> Self {
+        Self {
+            high_gwei: GAS_ALERT_HIGH_GWEI,
+            spike_multiplier: GAS_SPIKE_MULTIPLIER,
+            source: ThresholdSource::Default,
+        }
+    }
+}
+
+/// Collects base fees for [`GAS_BASELINE_WINDOW`] after the first sample, then fixes
+/// the thresholds not set by `--*-gas-alert-gwei` / `--gas-spike-multiplier`.
+#[derive(Debug, Clone)]
+pub struct GasBaseline {
+    high_gwei: Option<f64>,
+    spike_multiplier: Option<f64>,
+    started: Option<Instant>,
+    base_fees_gwei: Vec<f64>,
+    peak_multiplier: f64,
+    scaled: Option<(f64, f64)>, // (high gwei, spike multiplier) once the window ends
+    thresholds: GasThresholds,
+}
+
+impl GasBaseline {
+    pub fn new(high_gwei: Option<f64>, spike_multiplier: Option<f64>) -> Self {
+        let mut baseline = Self {
+            high_gwei,
+            spike_multiplier,
+            started: None,
+            base_fees_gwei: Vec::new(),
+            peak_multiplier: 0.0,
+            scaled: None,
+            thresholds: GasThresholds::default(),
+        };
+        baseline.resolve();
+        baseline
+    }
+
+    pub fn thresholds(&self) -> GasThresholds {
+        self.thresholds
+    }
+
+    /// Replace the configured levels, keeping the scaled baseline (or the observation
+    /// still under way) for whichever is left unset.
+    pub fn reconfigure(&mut self, high_gwei: Option<f64>, spike_multiplier: Option<f64>) {
+        self.high_gwei = high_gwei;
+        self.spike_multiplier = spike_multiplier;
+        self.resolve();
+    }
+
+    fn resolve(&mut self) {
+        let defaults = GasThresholds::default();
+        let scaled = |pick: fn((f64, f64)) -> f64, default: f64| self.scaled.map_or(default, pick);
+        self.thresholds = GasThresholds {
+            high_gwei: self
+                .high_gwei
+                .unwrap_or_else(|| scaled(|(high, _)| high, defaults.high_gwei)),
+            spike_multiplier: self
+                .spike_multiplier
+                .unwrap_or_else(|| scaled(|(_, spike)| spike, defaults.spike_multiplier)),
+            source: match (self.high_gwei, self.spike_multiplier, self.scaled) {
+                (Some(_), Some(_), _) => ThresholdSource::Configured,
+                (_, _, Some(_)) => ThresholdSource::Baseline,
+                _ => ThresholdSource::Default,
+            },
+        };
+    }
+
+    /// Add a cycle's base fee and spike ratio; returns the thresholds to apply from now on.
+    pub fn observe(
+        &mut self,
+        base_fee_gwei: f64,
+        multiplier: Option<f64>,
+        now: Instant,
+    ) -> GasThresholds {
+        if self.thresholds.source != ThresholdSource::Default {
+            return self.thresholds;
+        }
+        let started = *self.started.get_or_insert(now);
+        self.base_fees_gwei.push(base_fee_gwei);
+        if let Some(multiplier) = multiplier {
+            self.peak_multiplier = self.peak_multiplier.max(multiplier);
+        }
+        if now.saturating_duration_since(started) >= GAS_BASELINE_WINDOW
+            && let Some(scaled) = scaled_thresholds(&self.base_fees_gwei, self.peak_multiplier)
+        {
+            self.scaled = Some(scaled);
+            self.base_fees_gwei = Vec::new();
+            self.resolve();
+        }
+        self.thresholds
+    }
+}
+
+/// Every alert threshold of one chain, from the flags and the config file; the config
+/// file's can change while running.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChainThresholds {
+    pub block_delay: DelayThresholds,
+    pub expected_block_time: Option<f64>, // seconds
+    pub stale_after: Duration,
+    pub gas_alert_gwei: Option<f64>, // None = scale to the observed baseline
+    pub gas_spike_multiplier: Option<f64>,
+    pub large_transfer_wei: Option<u128>, // None = off
+}
+
+impl ChainThresholds {
+    /// What differs in `new`, one entry per threshold, e.g. `block delay 30s/60s → 45s/90s`.
+    pub fn changes(&self, new: &Self) -> Vec<String> {
+        let mut changes = Vec::new();
+        let mut compare = |name: &str, old: String, new: String| {
+            if old != new {
+                changes.push(format!("{} {} → {}", name, old, new));
+            }
+        };
+        let delay = |delay: &DelayThresholds| format!("{}s/{}s", delay.warning, delay.critical);
+        let or = |value: Option<String>, unset: &str| value.unwrap_or_else(|| unset.to_string());
+        compare(
+            "block delay",
+            delay(&self.block_delay),
+            delay(&new.block_delay),
+        );
+        let block_time = |secs: Option<f64>| or(secs.map(|secs| format!("{}s", secs)), "unset");
+        compare(
+            "block time",
+            block_time(self.expected_block_time),
+            block_time(new.expected_block_time),
+        );
+        compare(
+            "stale after",
+            format!("{}s", self.stale_after.as_secs()),
+            format!("{}s", new.stale_after.as_secs()),
+        );
+        let gwei = |gwei: Option<f64>| or(gwei.map(|gwei| format!("{} gwei", gwei)), "auto");
+        compare(
+            "gas alert",
+            gwei(self.gas_alert_gwei),
+            gwei(new.gas_alert_gwei),
+        );
+        let spike = |multiplier: Option<f64>| or(multiplier.map(|m| format!("{}x", m)), "auto");
+        compare(
+            "fee spike",
+            spike(self.gas_spike_multiplier),
+            spike(new.gas_spike_multiplier),
+        );
+        let eth =
+            |wei: Option<u128>| or(wei.map(|wei| format!("{} ETH", format_eth(wei, 2))), "off");
+        compare(
+            "large transfer",
+            eth(self.large_transfer_wei),
+            eth(new.large_transfer_wei),
+        );
+        changes
+    }
+}
+
+/// High-fee threshold as a multiple of the median base fee, and a spike multiplier above
+/// the largest swing seen during the baseline (never below the mainnet default).
+fn scaled_thresholds(base_fees_gwei: &[f64], peak_multiplier: f64) -> Option<(f64, f64)> {
+    let mut sorted: Vec<f64> = base_fees_gwei
+        .iter()
+        .copied()
+        .filter(|fee| *fee > 0.0)
+        .collect();
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.sort_by(f64::total_cmp);
+    let median = sorted[sorted.len() / 2];
+    Some((
+        median * GAS_BASELINE_HIGH_FACTOR,
+        (peak_multiplier * GAS_BASELINE_SPIKE_HEADROOM).max(GAS_SPIKE_MULTIPLIER),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ChainThresholds, DelayThresholds, GasBaseline, ThresholdSource, scaled_thresholds,
+    };
+    use crate::config::{GAS_ALERT_HIGH_GWEI, GAS_BASELINE_WINDOW};
+    use crate::units::WEI_PER_ETH;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn block_delay_scales_to_the_expected_block_time() {
+        let resolve = |warning, critical, expected| {
+            let thresholds = DelayThresholds::resolve(warning, critical, expected);
+            (thresholds.warning, thresholds.critical)
+        };
+        assert_eq!(resolve(None, None, None), (30, 60));
+        assert_eq!(resolve(None, None, Some(2.0)), (5, 10));
+        assert_eq!(resolve(None, None, Some(0.25)), (1, 2));
+        // Set levels win over the expected block time
+        assert_eq!(resolve(Some(20), None, Some(12.0)), (20, 60));
+        assert_eq!(resolve(None, Some(90), Some(12.0)), (45, 90));
+    }
+
+    #[test]
+    fn baseline_scales_to_the_observed_fees_once_the_window_ends() {
+        let start = Instant::now();
+        let mut baseline = GasBaseline::new(None, None);
+        let early = baseline.observe(0.002, Some(1.1), start);
+        assert_eq!(early.source, ThresholdSource::Default);
+        assert_eq!(early.high_gwei, GAS_ALERT_HIGH_GWEI);
+
+        baseline.observe(0.004, Some(2.4), start + Duration::from_secs(60));
+        let scaled = baseline.observe(0.003, None, start + GAS_BASELINE_WINDOW);
+        assert_eq!(scaled.source, ThresholdSource::Baseline);
+        assert!((scaled.high_gwei - 0.015).abs() < 1e-12);
+        assert!((scaled.spike_multiplier - 3.0).abs() < 1e-12);
+
+        // Later cycles no longer move the thresholds
+        let later = baseline.observe(50.0, Some(9.0), start + GAS_BASELINE_WINDOW * 2);
+        assert_eq!(later, scaled);
+    }
+
+    #[test]
+    fn configured_thresholds_win_over_the_baseline() {
+        let start = Instant::now();
+        let mut both = GasBaseline::new(Some(30.0), Some(1.5));
+        assert_eq!(both.thresholds().source, ThresholdSource::Configured);
+        assert_eq!(both.observe(1.0, None, start).high_gwei, 30.0);
+
+        let mut high_only = GasBaseline::new(Some(30.0), None);
+        high_only.observe(1.0, Some(1.2), start);
+        let scaled = high_only.observe(1.0, None, start + GAS_BASELINE_WINDOW);
+        assert_eq!(scaled.high_gwei, 30.0);
+        assert_eq!(scaled.spike_multiplier, 2.0);
+
+        assert_eq!(scaled_thresholds(&[0.0, 0.0], 1.0), None);
+    }
+
+    #[test]
+    fn reconfiguring_keeps_the_scaled_baseline() {
+        let start = Instant::now();
+        let mut baseline = GasBaseline::new(None, None);
+        baseline.observe(1.0, Some(1.2), start);
+        let scaled = baseline.observe(1.0, None, start + GAS_BASELINE_WINDOW);
+        assert_eq!(scaled.source, ThresholdSource::Baseline);
+
+        baseline.reconfigure(Some(30.0), Some(4.0));
+        assert_eq!(baseline.thresholds().source, ThresholdSource::Configured);
+        baseline.reconfigure(Some(30.0), None);
+        assert_eq!(baseline.thresholds().high_gwei, 30.0);
+        assert_eq!(
+            baseline.thresholds().spike_multiplier,
+            scaled.spike_multiplier
+        );
+        baseline.reconfigure(None, None);
+        assert_eq!(baseline.thresholds(), scaled);
+    }
+
+    #[test]
+    fn lists_changed_chain_thresholds() {
+        let old = ChainThresholds {
+            block_delay: DelayThresholds {
+                warning: 30,
+                critical: 60,
+            },
+            expected_block_time: Some(12.0),
+            stale_after: Duration::from_secs(14),
+            gas_alert_gwei: None,
+            gas_spike_multiplier: Some(2.0),
+            large_transfer_wei: None,
+        };
+        let new = ChainThresholds {
+            block_delay: DelayThresholds {
+                warning: 45,
+                critical: 90,
+            },
+            gas_alert_gwei: Some(5.0),
+            large_transfer_wei: Some(100 * WEI_PER_ETH),
+            ..old
+        };
+        assert_eq!(
+            old.changes(&new),
+            [
+                "block delay 30s/60s → 45s/90s",
+                "gas alert auto → 5 gwei",
+                "large transfer off → 100.00 ETH"
+            ]
+        );
+        assert!(old.changes(&old).is_empty());
+    }
+}
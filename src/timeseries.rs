@@ -0,0 +1,254 @@
+//! Per-metric ring buffers at three resolutions (every sample, then 1-minute and 15-minute
+//! aggregates), so charts, moving averages, and volatility read from one store instead of
+//! each feature keeping its own `VecDeque`
+use crate::config::{TIMESERIES_MINUTE_POINTS, TIMESERIES_QUARTER_POINTS, TIMESERIES_RAW_POINTS};
+use std::collections::{HashMap, VecDeque};
+
+/// Metrics sampled once per collection cycle, or once per new block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metric {
+    GasPrice,         // wei
+    BaseFee,          // wei
+    Latency,          // collection cycle, microseconds
+    BlockTime,        // seconds between new heads, at the head's timestamp
+    BlockSize,        // bytes per new head, at its timestamp
+    Calldata,         // calldata bytes per head whose full body was fetched
+    PoolTransactions, // tx-pool cache counts
+    PoolBundles,
+    PoolOrders,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Raw,     // each sample, at the refresh cadence (5s or faster)
+    Minute,  // 1-minute aggregates
+    Quarter, // 15-minute aggregates
+}
+
+impl Resolution {
+    pub const ALL: [Resolution; 3] = [Resolution::Raw, Resolution::Minute, Resolution::Quarter];
+
+    /// Seconds each point covers; raw points cover an instant.
+    pub fn width(self) -> u64 {
+        match self {
+            Resolution::Raw => 0,
+            Resolution::Minute => 60,
+            Resolution::Quarter => 900,
+        }
+    }
+
+    fn capacity(self) -> usize {
+        match self {
+            Resolution::Raw => TIMESERIES_RAW_POINTS,
+            Resolution::Minute => TIMESERIES_MINUTE_POINTS,
+            Resolution::Quarter => TIMESERIES_QUARTER_POINTS,
+        }
+    }
+}
+
+/// The samples that fell in one bucket; a raw point holds a single sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub at: u64, // unix seconds: the sample time, or the bucket's start
+    pub min: f64,
+    pub max: f64,
+    pub last: f64,
+    sum: f64,
+    pub count: u32,
+}
+
+impl Point {
+    fn new(at: u64, value: f64) -> Self {
+        Self {
+            at,
+            min: value,
+            max: value,
+            last: value,
+            sum: value,
+            count: 1,
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.last = value;
+        self.sum += value;
+        self.count += 1;
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+/// One metric at every resolution, oldest point first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Series {
+    raw: VecDeque<Point>,
+    minute: VecDeque<Point>,
+    quarter: VecDeque<Point>,
+}
+
+impl Series {
+    pub fn record(&mut self, at: u64, value: f64) {
+        for resolution in Resolution::ALL {
+            let width = resolution.width();
+            let capacity = resolution.capacity();
+            let points = self.points_mut(resolution);
+            let start = if width == 0 { at } else { at - at % width };
+            match points.back_mut() {
+                Some(point) if width > 0 && point.at == start => point.add(value),
+                _ => points.push_back(Point::new(start, value)),
+            }
+            while points.len() > capacity {
+                points.pop_front();
+            }
+        }
+    }
+
+    pub fn points(&self, resolution: Resolution) -> &VecDeque<Point> {
+        match resolution {
+            Resolution::Raw => &self.raw,
+            Resolution::Minute => &self.minute,
+            Resolution::Quarter => &self.quarter,
+        }
+    }
+
+    fn points_mut(&mut self, resolution: Resolution) -> &mut VecDeque<Point> {
+        match resolution {
+            Resolution::Raw => &mut self.raw,
+            Resolution::Minute => &mut self.minute,
+            Resolution::Quarter => &mut self.quarter,
+        }
+    }
+
+    /// Points from the last `window` seconds before `now`, picked as in [`Series::range`].
+    pub fn window(&self, window: u64, now: u64) -> impl Iterator<Item = &Point> {
+        self.range(now.saturating_sub(window), u64::MAX)
+    }
+
+    /// Points overlapping `since..until` (unix seconds), at the finest resolution that
+    /// reaches back to `since`. When none does, the one reaching back furthest.
+    pub fn range(&self, since: u64, until: u64) -> impl Iterator<Item = &Point> {
+        let oldest = |resolution| self.points(resolution).front().map(|point| point.at);
+        let resolution = Resolution::ALL
+            .into_iter()
+            .find(|&resolution| oldest(resolution).is_some_and(|at| at <= since))
+            .or_else(|| {
+                Resolution::ALL
+                    .into_iter()
+                    .filter_map(|resolution| Some((oldest(resolution)?, resolution)))
+                    .min_by_key(|(at, _)| *at)
+                    .map(|(_, resolution)| resolution)
+            })
+            .unwrap_or(Resolution::Raw);
+        self.points(resolution)
+            .iter()
+            .filter(move |point| point.at + resolution.width().max(1) > since && point.at < until)
+    }
+
+    /// Moving average of every sample in the window.
+    pub fn mean_over(&self, window: u64, now: u64) -> Option<f64> {
+        let (sum, count) = self
+            .window(window, now)
+            .fold((0.0, 0), |(sum, count), point| {
+                (sum + point.sum, count + point.count)
+            });
+        (count > 0).then(|| sum / count as f64)
+    }
+
+    /// Standard deviation of the window's points over their mean; `None` below two points
+    /// or when the mean is zero.
+    pub fn volatility(&self, window: u64, now: u64) -> Option<f64> {
+        let means: Vec<f64> = self.window(window, now).map(Point::mean).collect();
+        if means.len() < 2 {
+            return None;
+        }
+        let mean = means.iter().sum::<f64>() / means.len() as f64;
+        let variance = means
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / means.len() as f64;
+        (mean != 0.0).then(|| variance.sqrt() / mean.abs())
+    }
+}
+
+/// Every metric's series for one chain.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimeSeries {
+    series: HashMap<Metric, Series>,
+}
+
+impl TimeSeries {
+    pub fn record(&mut self, metric: Metric, at: u64, value: f64) {
+        self.series.entry(metric).or_default().record(at, value);
+    }
+
+    pub fn get(&self, metric: Metric) -> Option<&Series> {
+        self.series.get(&metric)
+    }
+
+    /// Points held across every metric and resolution.
+    pub fn point_count(&self) -> usize {
+        self.rings().map(VecDeque::len).sum()
+    }
+
+    /// Points in the fullest ring.
+    pub fn longest(&self) -> usize {
+        self.rings().map(VecDeque::len).max().unwrap_or(0)
+    }
+
+    /// Drop the oldest points of any ring holding more than `points`. Coarse rings are
+    /// shorter, so they keep their longer reach.
+    pub fn truncate(&mut self, points: usize) {
+        for series in self.series.values_mut() {
+            for resolution in Resolution::ALL {
+                let ring = series.points_mut(resolution);
+                let excess = ring.len().saturating_sub(points);
+                ring.drain(..excess);
+            }
+        }
+    }
+
+    fn rings(&self) -> impl Iterator<Item = &VecDeque<Point>> {
+        self.series.values().flat_map(|series| {
+            Resolution::ALL
+                .into_iter()
+                .map(|resolution| series.points(resolution))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Resolution, Series};
+
+    #[test]
+    fn aggregates_samples_per_resolution() {
+        let mut series = Series::default();
+        // Every 5s for 20 minutes from a quarter-hour boundary: 1.0 for 15 minutes, then 4.0
+        let start = 1_700_000_100;
+        for i in 0..240u64 {
+            let at = start + i * 5;
+            series.record(at, if at - start < 900 { 1.0 } else { 4.0 });
+        }
+        assert_eq!(series.points(Resolution::Raw).len(), 240);
+        let minutes = series.points(Resolution::Minute);
+        assert_eq!(minutes.len(), 20);
+        assert_eq!((minutes[0].at, minutes[0].count), (start, 12));
+        let quarters = series.points(Resolution::Quarter);
+        assert_eq!(quarters.len(), 2);
+        assert_eq!((quarters[0].mean(), quarters[1].mean()), (1.0, 4.0));
+        assert_eq!(series.points(Resolution::Raw).back().unwrap().last, 4.0);
+
+        let now = start + 1_200;
+        // The last five minutes are all 4.0; the last ten minutes are half and half
+        assert_eq!(series.mean_over(300, now), Some(4.0));
+        assert_eq!(series.mean_over(600, now), Some(2.5));
+        assert_eq!(series.volatility(300, now), Some(0.0));
+        assert_eq!(series.volatility(600, now), Some(0.6));
+        assert_eq!(Series::default().mean_over(300, now), None);
+    }
+}
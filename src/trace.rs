@@ -0,0 +1,147 @@
+//! `--enable-trace`: a `callTracer` run over the newest block (`debug_traceBlockByNumber`),
+//! boiled down to call counts, reverts, and the internal call targets that used the most gas
+use crate::config::TRACE_TARGETS;
+use alloy::primitives::Address;
+use serde_json::Value;
+
+/// Gas used by internal calls into one address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallTarget {
+    pub address: Address,
+    pub calls: usize,
+    pub gas_used: u64, // including the calls each one made in turn
+}
+
+/// What the newest block's transactions did, by call frame.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockTrace {
+    pub number: u64,
+    pub top_level: Vec<(String, usize)>, // transactions per frame type (CALL, CREATE), most first
+    pub internal_calls: usize,
+    pub reverted_txs: usize,
+    pub reverted_internal: usize,
+    pub targets: Vec<CallTarget>, // heaviest first, at most TRACE_TARGETS
+}
+
+impl BlockTrace {
+    pub fn transactions(&self) -> usize {
+        self.top_level.iter().map(|(_, count)| count).sum()
+    }
+}
+
+/// Summarize a `debug_traceBlockByNumber` result: one `{"result": frame}` per transaction,
+/// or bare frames from clients that leave out the wrapper. `None` if it isn't an array.
+pub fn summarize(number: u64, result: &Value) -> Option<BlockTrace> {
+    let mut trace = BlockTrace {
+        number,
+        ..BlockTrace::default()
+    };
+    for tx in result.as_array()? {
+        let Some(root) = tx.get("result").or_else(|| tx.get("type").map(|_| tx)) else {
+            continue; // the tracer failed on this transaction
+        };
+        let kind = root.get("type").and_then(Value::as_str).unwrap_or("CALL");
+        match trace.top_level.iter_mut().find(|(name, _)| name == kind) {
+            Some((_, count)) => *count += 1,
+            None => trace.top_level.push((kind.to_string(), 1)),
+        }
+        if is_reverted(root) {
+            trace.reverted_txs += 1;
+        }
+
+        let mut frames: Vec<&Value> = subcalls(root).collect();
+        while let Some(frame) = frames.pop() {
+            trace.internal_calls += 1;
+            if is_reverted(frame) {
+                trace.reverted_internal += 1;
+            }
+            let address = frame
+                .get("to")
+                .and_then(Value::as_str)
+                .and_then(|to| to.parse::<Address>().ok());
+            let gas_used = frame
+                .get("gasUsed")
+                .and_then(Value::as_str)
+                .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+                .unwrap_or(0);
+            if let Some(address) = address {
+                match trace.targets.iter_mut().find(|t| t.address == address) {
+                    Some(target) => {
+                        target.calls += 1;
+                        target.gas_used += gas_used;
+                    }
+                    None => trace.targets.push(CallTarget {
+                        address,
+                        calls: 1,
+                        gas_used,
+                    }),
+                }
+            }
+            frames.extend(subcalls(frame));
+        }
+    }
+    trace
+        .top_level
+        .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    trace
+        .targets
+        .sort_by_key(|target| std::cmp::Reverse(target.gas_used));
+    trace.targets.truncate(TRACE_TARGETS);
+    Some(trace)
+}
+
+fn subcalls(frame: &Value) -> impl Iterator<Item = &Value> {
+    frame
+        .get("calls")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+fn is_reverted(frame: &Value) -> bool {
+    frame.get("error").is_some_and(|error| !error.is_null())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::summarize;
+    use alloy::primitives::Address;
+    use serde_json::json;
+
+    #[test]
+    fn counts_frames_and_ranks_internal_targets() {
+        let pool = Address::with_last_byte(0xaa);
+        let token = Address::with_last_byte(0xbb);
+        let result = json!([
+            {"txHash": "0x01", "result": {
+                "type": "CALL", "to": Address::with_last_byte(1), "gasUsed": "0x10000",
+                "calls": [
+                    {"type": "CALL", "to": pool, "gasUsed": "0x8000", "calls": [
+                        {"type": "STATICCALL", "to": token, "gasUsed": "0x1000"},
+                        {"type": "CALL", "to": token, "gasUsed": "0x2000", "error": "execution reverted"}
+                    ]},
+                    {"type": "DELEGATECALL", "to": pool, "gasUsed": "0x800"}
+                ]
+            }},
+            {"txHash": "0x02", "result": {"type": "CREATE", "gasUsed": "0x20000", "error": "out of gas"}},
+            {"type": "CALL", "to": pool, "gasUsed": "0x5208"},
+            {"txHash": "0x03", "error": "tracer timed out"}
+        ]);
+        let trace = summarize(7, &result).unwrap();
+        assert_eq!(trace.number, 7);
+        assert_eq!(
+            trace.top_level,
+            [("CALL".to_string(), 2), ("CREATE".to_string(), 1)]
+        );
+        assert_eq!(trace.transactions(), 3);
+        assert_eq!((trace.internal_calls, trace.reverted_internal), (4, 1));
+        assert_eq!(trace.reverted_txs, 1);
+        let targets: Vec<_> = trace
+            .targets
+            .iter()
+            .map(|target| (target.address, target.calls, target.gas_used))
+            .collect();
+        assert_eq!(targets, [(pool, 2, 0x8800), (token, 2, 0x3000)]);
+        assert_eq!(summarize(7, &json!({"error": "nope"})), None);
+    }
+}
@@ -0,0 +1,81 @@
+//! `--large-transfer-eth`: the largest plain value transfer in a block's full body, for the
+//! significant transfer alert
+use alloy::primitives::{Address, B256, U256};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LargeTransfer {
+    pub block: u64,
+    pub at: u64, // block timestamp
+    pub hash: B256,
+    pub from: Address,
+    pub to: Option<Address>, // None for a contract creation
+    pub value: U256,         // wei
+}
+
+/// The transaction moving the most ETH in a block fetched with full transaction objects,
+/// if it moves at least `min_wei`. Only top-level `value` counts, not internal transfers.
+pub fn largest_transfer(block: &Value, min_wei: U256) -> Option<LargeTransfer> {
+    let quantity = |key: &str| {
+        block
+            .get(key)
+            .and_then(Value::as_str)
+            .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+    };
+    let (number, timestamp) = (quantity("number")?, quantity("timestamp")?);
+    block
+        .get("transactions")?
+        .as_array()?
+        .iter()
+        .filter_map(|tx| {
+            let value =
+                U256::from_str_radix(tx.get("value")?.as_str()?.trim_start_matches("0x"), 16)
+                    .ok()?;
+            Some(LargeTransfer {
+                block: number,
+                at: timestamp,
+                hash: tx.get("hash")?.as_str()?.parse().ok()?,
+                from: tx.get("from")?.as_str()?.parse().ok()?,
+                to: tx
+                    .get("to")
+                    .and_then(Value::as_str)
+                    .and_then(|to| to.parse().ok()),
+                value,
+            })
+        })
+        .filter(|transfer| transfer.value >= min_wei)
+        .max_by_key(|transfer| transfer.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::largest_transfer;
+    use alloy::primitives::{Address, B256, U256};
+    use serde_json::json;
+
+    #[test]
+    fn picks_the_largest_transfer_over_the_threshold() {
+        let eth = |whole: u64| U256::from(whole) * U256::from(10u64.pow(18));
+        let tx = |byte: u8, value: U256| {
+            json!({
+                "hash": B256::repeat_byte(byte),
+                "from": Address::with_last_byte(byte),
+                "to": Address::with_last_byte(0xff),
+                "value": format!("{:#x}", value),
+            })
+        };
+        let block = json!({
+            "number": "0x10",
+            "timestamp": "0x64",
+            "transactions": [tx(1, eth(5)), tx(2, eth(120)), tx(3, eth(40)), "0xignored"]
+        });
+        let largest = largest_transfer(&block, eth(100)).unwrap();
+        assert_eq!(largest.hash, B256::repeat_byte(2));
+        assert_eq!(largest.from, Address::with_last_byte(2));
+        assert_eq!(
+            (largest.block, largest.at, largest.value),
+            (16, 100, eth(120))
+        );
+        assert_eq!(largest_transfer(&block, eth(200)), None);
+    }
+}
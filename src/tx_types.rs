@@ -0,0 +1,132 @@
+//! Transactions by EIP-2718 type: counted from the newest block's full body, and weighed by
+//! gas used from its receipts when `--receipts` fetched them
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum TxType {
+    Legacy,
+    AccessList, // EIP-2930
+    DynamicFee, // EIP-1559
+    Blob,       // EIP-4844
+    SetCode,    // EIP-7702
+    Other(u8),  // chain-specific, e.g. deposits
+}
+
+impl TxType {
+    pub fn from_byte(byte: u8) -> Self {
+        match byte {
+            0 => TxType::Legacy,
+            1 => TxType::AccessList,
+            2 => TxType::DynamicFee,
+            3 => TxType::Blob,
+            4 => TxType::SetCode,
+            other => TxType::Other(other),
+        }
+    }
+
+    /// The `type` field of a transaction or receipt; a missing one is legacy.
+    pub fn of(value: &Value) -> Option<Self> {
+        match value.get("type").and_then(Value::as_str) {
+            None => Some(TxType::Legacy),
+            Some(hex) => u8::from_str_radix(hex.trim_start_matches("0x"), 16)
+                .ok()
+                .map(TxType::from_byte),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            TxType::Legacy => "legacy".to_string(),
+            TxType::AccessList => "2930".to_string(),
+            TxType::DynamicFee => "1559".to_string(),
+            TxType::Blob => "blob".to_string(),
+            TxType::SetCode => "7702".to_string(),
+            TxType::Other(byte) => format!("0x{:x}", byte),
+        }
+    }
+}
+
+/// Transactions per type in one block, in [`TxType`] order, types with none left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TxTypeMix {
+    pub number: u64,
+    pub counts: Vec<(TxType, usize)>,
+}
+
+impl TxTypeMix {
+    /// Count a block fetched with full transaction objects. `None` if it has no
+    /// `transactions` array or lists only hashes.
+    pub fn of_block(block: &Value) -> Option<Self> {
+        let number = block
+            .get("number")
+            .and_then(Value::as_str)
+            .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())?;
+        let transactions = block.get("transactions")?.as_array()?;
+        if transactions.iter().any(Value::is_string) {
+            return None;
+        }
+        Some(Self {
+            number,
+            counts: tally(
+                transactions
+                    .iter()
+                    .filter_map(TxType::of)
+                    .map(|kind| (kind, 1)),
+            ),
+        })
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().map(|(_, count)| count).sum()
+    }
+}
+
+/// Sum `amounts` per type, in [`TxType`] order.
+pub fn tally<T: std::ops::AddAssign + Copy>(
+    amounts: impl Iterator<Item = (TxType, T)>,
+) -> Vec<(TxType, T)> {
+    let mut totals: Vec<(TxType, T)> = Vec::new();
+    for (kind, amount) in amounts {
+        match totals.iter_mut().find(|(existing, _)| *existing == kind) {
+            Some((_, total)) => *total += amount,
+            None => totals.push((kind, amount)),
+        }
+    }
+    totals.sort_by_key(|(kind, _)| *kind);
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TxType, TxTypeMix};
+    use serde_json::json;
+
+    #[test]
+    fn counts_full_transactions_by_type() {
+        let block = json!({
+            "number": "0x10",
+            "transactions": [
+                {"type": "0x2"}, {"type": "0x0"}, {"type": "0x2"}, {"type": "0x3"},
+                {"type": "0x4"}, {"type": "0x7e"}, {}
+            ]
+        });
+        let mix = TxTypeMix::of_block(&block).unwrap();
+        assert_eq!(mix.number, 16);
+        assert_eq!(
+            mix.counts,
+            [
+                (TxType::Legacy, 2),
+                (TxType::DynamicFee, 2),
+                (TxType::Blob, 1),
+                (TxType::SetCode, 1),
+                (TxType::Other(0x7e), 1),
+            ]
+        );
+        assert_eq!(mix.total(), 7);
+        assert_eq!(TxType::Other(0x7e).label(), "0x7e");
+        // Hash-only bodies can't be classified
+        let hashes = json!({"number": "0x10", "transactions": ["0xabc"]});
+        assert_eq!(TxTypeMix::of_block(&hashes), None);
+    }
+}
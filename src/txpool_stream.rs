@@ -0,0 +1,201 @@
+//! Server-sent event push from the tx-pool webservice, so new transactions, bundles, and
+//! orders show up between polls
+use crate::config::{TXPOOL_STREAM_RETRY, TXPOOL_TIMEOUT_SECS};
+use crate::error::{FutureSightError, Result};
+use crate::http::HttpOptions;
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamKind {
+    Transaction,
+    Bundle,
+    Order,
+}
+
+impl StreamKind {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.to_ascii_lowercase().trim_end_matches('s') {
+            "transaction" | "tx" => Some(Self::Transaction),
+            "bundle" => Some(Self::Bundle),
+            "order" | "signedorder" | "signed_order" => Some(Self::Order),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamMessage {
+    Connected,
+    Disconnected(String),
+    Event(StreamKind, Value),
+}
+
+/// Incremental `text/event-stream` parser: feed it body chunks, get back complete events.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// `(event name, data)` for every event completed by `chunk`.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<(Option<String>, String)> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        // Split on whole lines only, so a chunk ending mid-character stays intact
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let raw: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&raw);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push((self.event.take(), self.data.join("\n")));
+                }
+                self.event = None;
+                self.data.clear();
+                continue;
+            }
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => self.event = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                _ => {} // comments (": keepalive"), id, retry
+            }
+        }
+        events
+    }
+}
+
+/// What an event carries: the SSE event name, else a `type` or `kind` field in its data.
+/// Data wrapped as `{"transaction": {...}}` is unwrapped.
+pub fn classify(event: Option<&str>, data: &str) -> Option<(StreamKind, Value)> {
+    let json: Value = serde_json::from_str(data).ok()?;
+    let kind = event.and_then(StreamKind::parse).or_else(|| {
+        ["type", "kind"]
+            .iter()
+            .find_map(|key| StreamKind::parse(json.get(key)?.as_str()?))
+    })?;
+    let key = match kind {
+        StreamKind::Transaction => "transaction",
+        StreamKind::Bundle => "bundle",
+        StreamKind::Order => "order",
+    };
+    let payload = json.get(key).cloned().unwrap_or(json);
+    Some((kind, payload))
+}
+
+/// Stream connection state and the number of events applied, for the Flow Radar.
+#[derive(Debug, Clone, Default)]
+pub struct StreamStatus {
+    pub connected: bool,
+    pub events: u64,
+    pub last_event: Option<Instant>,
+    pub error: Option<String>,
+}
+
+/// A background subscription that reconnects until the receiving side is dropped.
+#[derive(Debug)]
+pub struct TxPoolStream {
+    messages: UnboundedReceiver<StreamMessage>,
+}
+
+impl TxPoolStream {
+    pub fn spawn(url: String, options: &HttpOptions) -> Result<Self> {
+        // No overall timeout: the response body stays open for as long as the stream runs
+        let http = options
+            .client_builder()?
+            .connect_timeout(Duration::from_secs(TXPOOL_TIMEOUT_SECS))
+            .user_agent(format!(
+                "{}/{}",
+                env!("CARGO_PKG_NAME"),
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build()
+            .map_err(|e| FutureSightError::Config(e.to_string()))?;
+        let (sender, messages) = mpsc::unbounded_channel();
+        tokio::spawn(subscribe(http, url, sender));
+        Ok(Self { messages })
+    }
+
+    /// Messages received since the last call.
+    pub fn drain(&mut self) -> Vec<StreamMessage> {
+        std::iter::from_fn(|| self.messages.try_recv().ok()).collect()
+    }
+}
+
+async fn subscribe(http: reqwest::Client, url: String, sender: UnboundedSender<StreamMessage>) {
+    loop {
+        let reason = match read_stream(&http, &url, &sender).await {
+            Ok(()) => "stream closed".to_string(),
+            Err(e) => e.to_string(),
+        };
+        if sender.send(StreamMessage::Disconnected(reason)).is_err() {
+            return;
+        }
+        tokio::time::sleep(TXPOOL_STREAM_RETRY).await;
+    }
+}
+
+async fn read_stream(
+    http: &reqwest::Client,
+    url: &str,
+    sender: &UnboundedSender<StreamMessage>,
+) -> Result<()> {
+    let txpool_error = |e: reqwest::Error| FutureSightError::TxPool(e.to_string());
+    let closed = |_| FutureSightError::TxPool("stream receiver dropped".to_string());
+    let mut resp = http
+        .get(url)
+        .header(reqwest::header::ACCEPT, "text/event-stream")
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(txpool_error)?;
+    sender.send(StreamMessage::Connected).map_err(closed)?;
+    let mut parser = SseParser::default();
+    while let Some(chunk) = resp.chunk().await.map_err(txpool_error)? {
+        for (event, data) in parser.push(&chunk) {
+            if let Some((kind, payload)) = classify(event.as_deref(), &data) {
+                sender
+                    .send(StreamMessage::Event(kind, payload))
+                    .map_err(closed)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SseParser, StreamKind, classify};
+
+    #[test]
+    fn parses_events_split_across_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b": keepalive\n\nevent: bun").is_empty());
+        let events =
+            parser.push(b"dle\r\ndata: {\"id\":\"a\"}\r\n\r\ndata: {\"type\":\"order\"}\n\n");
+        assert_eq!(
+            events,
+            vec![
+                (Some("bundle".to_string()), "{\"id\":\"a\"}".to_string()),
+                (None, "{\"type\":\"order\"}".to_string()),
+            ]
+        );
+
+        let (kind, payload) = classify(Some("bundles"), &events[0].1).unwrap();
+        assert_eq!(kind, StreamKind::Bundle);
+        assert_eq!(payload["id"], "a");
+        assert_eq!(classify(None, &events[1].1).unwrap().0, StreamKind::Order);
+        assert_eq!(
+            classify(None, r#"{"kind":"tx","transaction":{"hash":"0x1"}}"#)
+                .unwrap()
+                .1["hash"],
+            "0x1"
+        );
+        assert!(classify(Some("ping"), "{}").is_none());
+    }
+}
@@ -1,32 +1,245 @@
-use alloy::primitives::{Address, B256, U256};
+use alloy::eips::eip4844::BYTES_PER_BLOB;
+use alloy::primitives::{B256, U256};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Gauge, Paragraph, Row, Sparkline, Table, Wrap},
+    widgets::{
+        Axis, Block, Borders, Cell, Chart, Clear, Dataset, GraphType, Paragraph, Row, Sparkline,
+        Table, Wrap,
+    },
 };
+use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 
-use crate::config::{GAS_ALERT_HIGH_GWEI, GAS_SPIKE_MULTIPLIER, STALE_AFTER};
-use crate::data::{ConnectionStatus, SignetMetrics, SuggestedFeeTier, TxPoolMetrics, TxPoolTx};
+mod blocks;
+mod connection;
+mod gas;
+mod input;
+mod mirror;
+mod panels;
+mod probes;
+mod replay;
+mod search;
+mod txpool;
+mod widget;
+
+pub use input::{InputOutcome, PROMPT_KEYS, TextInput};
+pub use mirror::render_mirror;
+pub use panels::{Panel, PanelLayout};
+pub use replay::render_replay;
+pub use search::{BlockQuery, SEARCH_SYNTAX};
+
+use blocks::BlocksWidget;
+use connection::ConnectionWidget;
+use gas::GasWidget;
+use probes::ProbesWidget;
+use txpool::TxPoolWidget;
+use widget::MetricsWidget;
+
+use crate::address_book::AddressBook;
+use crate::alerts::{Alert, AlertHistory, AlertKind, Silence};
+use crate::availability::AvailabilityStats;
+use crate::beacon;
+use crate::capabilities::{Capabilities, Support};
+use crate::chains::ChainRegistry;
+use crate::chart::ChartView;
+use crate::config::{CHART_GUIDE_HEADROOM, GAS_USERS_LEADERBOARD_ROWS};
+use crate::data::{BlockInfo, ConnectionStatus, GasUser, SignetMetrics};
+use crate::deployments::DeploymentFeed;
+use crate::keymap::{Action, Category, KEYMAP, keys_for};
+use crate::network::{ChainRole, Network};
+use crate::record::unix_now;
+use crate::retention::{self, MemoryReport};
+use crate::rpc_stats::{RpcMethod, RpcStats};
+use crate::timeseries::{Metric, Series};
+use crate::units::{self, WEI_PER_ETH, WEI_PER_GWEI};
+use crate::watchlist::{AccountChange, Activity, AddressTx, TxDirection};
+
+/// Chain columns at least this wide split into a status pane and a history pane.
+const TWO_PANE_MIN_WIDTH: u16 = 140;
+
+/// One chain as rendered in a column.
+struct ChainView<'a> {
+    role: ChainRole,
+    metrics: &'a SignetMetrics,
+    label: &'static str,
+    alert_key: String, // chain name used in the alert history
+}
+
+/// Top-level views, cycled with Tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Tab {
+    Dashboard,
+    Gas,
+    Alerts,
+    GasUsers,
+    Blobs,
+    Deployments,
+    Watchlist,
+    Diagnostics,
+}
+
+impl Tab {
+    pub fn next(self) -> Self {
+        match self {
+            Tab::Dashboard => Tab::Gas,
+            Tab::Gas => Tab::Alerts,
+            Tab::Alerts => Tab::GasUsers,
+            Tab::GasUsers => Tab::Blobs,
+            Tab::Blobs => Tab::Deployments,
+            Tab::Deployments => Tab::Watchlist,
+            Tab::Watchlist => Tab::Diagnostics,
+            Tab::Diagnostics => Tab::Dashboard,
+        }
+    }
 
-#[derive(Clone, Copy)]
-enum ChainTipSyncStatus {
-    Synced,
-    Diverged { direction: &'static str, diff: u64 },
-    Unknown,
+    fn title(self) -> &'static str {
+        match self {
+            Tab::Dashboard => "Dashboard",
+            Tab::Gas => "Gas",
+            Tab::Alerts => "Alerts",
+            Tab::GasUsers => "Gas Users",
+            Tab::Blobs => "Blobs",
+            Tab::Deployments => "Deployments",
+            Tab::Watchlist => "Watchlist",
+            Tab::Diagnostics => "Diagnostics",
+        }
+    }
 }
 
 pub struct Dashboard {
     pub should_quit: bool,
+    pub tab: Tab,
+    pub endpoint_picker: Option<EndpointPicker>,
+    pub profile_picker: Option<ProfilePicker>,
+    /// Config file profile the networks were built from, if any.
+    pub profile: Option<String>,
+    pub prompt: Option<Prompt>,
+    pub show_help: bool,
+    pub panels: PanelLayout,
+    /// Selected row while the panel layout editor is open.
+    pub layout_editor: Option<usize>,
+    /// Active `/` search; matching blocks are highlighted in every block tape.
+    pub block_search: Option<BlockQuery>,
+    /// Block highlighted in a block tape, by chain and number.
+    pub selected_block: Option<(ChainRole, u64)>,
+    /// One-line message shown in the footer, e.g. the result of an endpoint switch.
+    pub notice: Option<String>,
+    /// Labels for contracts in the Gas Users tab.
+    pub address_book: AddressBook,
+    /// Network names for the chain IDs in the feed panels.
+    pub chains: ChainRegistry,
+    /// Session memory use and `--max-memory-mb` trims, measured after each refresh.
+    pub memory: Option<MemoryReport>,
+    /// Zoom and pan of the gas and block-time charts.
+    pub chart: ChartView,
+    /// Row of the Alerts tab's alert type list, an index into [`AlertKind::ALL`].
+    pub alert_kind: usize,
+    /// Refreshes and alert checks are on hold (Space).
+    pub paused: bool,
     refresh_interval: u64,
 }
 
+/// An open text prompt and what its submitted value is for.
+pub struct Prompt {
+    pub input: TextInput,
+    pub purpose: PromptPurpose,
+}
+
+pub enum PromptPurpose {
+    /// Custom RPC URL for the shown network's host or rollup.
+    Endpoint(ChainRole),
+    /// Block history search query.
+    BlockSearch,
+    /// Minutes to snooze an alert type for.
+    Snooze(AlertKind),
+}
+
+/// Modal list of known RPC URLs for repointing the shown network's host or rollup.
+pub struct EndpointPicker {
+    pub target: ChainRole,
+    pub options: Vec<String>,
+    pub selected: usize,
+}
+
+impl EndpointPicker {
+    /// Open on `target`, preselecting the URL it currently uses.
+    pub fn new(options: Vec<String>, target: ChainRole, current: &str) -> Self {
+        let selected = options.iter().position(|url| url == current).unwrap_or(0);
+        Self {
+            target,
+            options,
+            selected,
+        }
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        if self.options.is_empty() {
+            return;
+        }
+        let len = self.options.len() as isize;
+        self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+    }
+
+    pub fn choice(&self) -> Option<&str> {
+        self.options.get(self.selected).map(String::as_str)
+    }
+}
+
+/// Modal list of the config file's profiles.
+pub struct ProfilePicker {
+    pub options: Vec<String>,
+    pub selected: usize,
+}
+
+impl ProfilePicker {
+    /// Open with the `current` profile preselected.
+    pub fn new(options: Vec<String>, current: Option<&str>) -> Self {
+        let selected = options
+            .iter()
+            .position(|name| Some(name.as_str()) == current)
+            .unwrap_or(0);
+        Self { options, selected }
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        if self.options.is_empty() {
+            return;
+        }
+        let len = self.options.len() as isize;
+        self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+    }
+
+    pub fn choice(&self) -> Option<&str> {
+        self.options.get(self.selected).map(String::as_str)
+    }
+}
+
 impl Dashboard {
     pub fn new(refresh_interval: u64) -> Self {
         Self {
             should_quit: false,
+            tab: Tab::Dashboard,
+            endpoint_picker: None,
+            profile_picker: None,
+            profile: None,
+            prompt: None,
+            show_help: false,
+            panels: PanelLayout::default(),
+            layout_editor: None,
+            block_search: None,
+            selected_block: None,
+            notice: None,
+            address_book: AddressBook::default(),
+            chains: ChainRegistry::default(),
+            memory: None,
+            chart: ChartView::default(),
+            alert_kind: 0,
+            paused: false,
             refresh_interval,
         }
     }
@@ -35,49 +248,949 @@ impl Dashboard {
         self.should_quit = true;
     }
 
-    pub fn render(&self, frame: &mut Frame, host: &SignetMetrics, rollup: &SignetMetrics) {
+    /// The alert type selected in the Alerts tab.
+    pub fn selected_alert_kind(&self) -> AlertKind {
+        AlertKind::ALL[self.alert_kind]
+    }
+
+    pub fn move_alert_kind(&mut self, delta: isize) {
+        let len = AlertKind::ALL.len() as isize;
+        self.alert_kind = (self.alert_kind as isize + delta).rem_euclid(len) as usize;
+    }
+
+    /// Move the block selection `delta` rows within its tape, starting at the newest
+    /// host block.
+    pub fn move_block_selection(&mut self, network: &Network, delta: isize) {
+        let role = self
+            .selected_block
+            .map_or(ChainRole::Host, |(role, _)| role);
+        let Some(history) = network.metrics(role).map(|metrics| &metrics.block_history) else {
+            return;
+        };
+        if history.is_empty() {
+            return;
+        }
+        let index = match self
+            .selected_block
+            .and_then(|(_, number)| history.iter().position(|block| block.number == number))
+        {
+            Some(index) => (index as isize + delta).clamp(0, history.len() as isize - 1) as usize,
+            None => 0,
+        };
+        self.selected_block = Some((role, history[index].number));
+    }
+
+    /// Move the selection to the newest block of the other chain's tape.
+    pub fn switch_selected_tape(&mut self, network: &Network) {
+        let role = match self.selected_block {
+            Some((ChainRole::Host, _)) if network.has_rollup() => ChainRole::Rollup,
+            _ => ChainRole::Host,
+        };
+        self.selected_block = network
+            .metrics(role)
+            .and_then(|metrics| metrics.block_history.front())
+            .map(|block| (role, block.number));
+    }
+
+    /// Move the block selection `delta` matches along the active search, host tape first,
+    /// wrapping around. Returns false when nothing matches.
+    pub fn jump_to_match(&mut self, network: &Network, delta: isize) -> bool {
+        let Some(query) = &self.block_search else {
+            return false;
+        };
+        let chains = std::iter::once((ChainRole::Host, network.host_metrics()))
+            .chain(network.rollup_metrics().map(|m| (ChainRole::Rollup, m)));
+        let matches: Vec<(ChainRole, u64)> = chains
+            .flat_map(|(role, metrics)| {
+                metrics
+                    .block_history
+                    .iter()
+                    .filter(|block| query.matches(block))
+                    .map(move |block| (role, block.number))
+            })
+            .collect();
+        if matches.is_empty() {
+            self.selected_block = None;
+            return false;
+        }
+
+        let len = matches.len() as isize;
+        let next = match self
+            .selected_block
+            .and_then(|current| matches.iter().position(|m| *m == current))
+        {
+            Some(current) => (current as isize + delta).rem_euclid(len),
+            None if delta < 0 => len - 1,
+            None => 0,
+        };
+        self.selected_block = Some(matches[next as usize]);
+        true
+    }
+
+    pub fn render(
+        &self,
+        frame: &mut Frame,
+        networks: &[Network],
+        selected: usize,
+        alerts: &AlertHistory,
+    ) {
+        let network = &networks[selected];
+        let host = network.host_metrics();
+        let rollup = network.rollup_metrics();
+        let multi = networks.len() > 1;
+        let host_view = ChainView {
+            role: ChainRole::Host,
+            metrics: host,
+            label: "Host",
+            alert_key: network.chain_label(ChainRole::Host, multi),
+        };
+        let header_height = if networks.len() > 1 { 5 } else { 4 };
+        let banner_height = if alerts.unacknowledged().next().is_some() {
+            1
+        } else {
+            0
+        };
+
         let outer = Layout::default()
             .direction(Direction::Vertical)
             .margin(1)
             .constraints([
-                Constraint::Length(4),
+                Constraint::Length(header_height),
+                Constraint::Length(banner_height),
                 Constraint::Min(18),
                 Constraint::Length(3),
             ])
             .split(frame.area());
 
-        self.render_header(frame, outer[0], host, rollup);
+        self.render_header(frame, outer[0], networks, selected, alerts);
+        if banner_height > 0 {
+            frame.render_widget(Paragraph::new(alert_banner_line(alerts)), outer[1]);
+        }
+
+        match (self.tab, rollup) {
+            (Tab::Alerts, _) => self.render_alerts(frame, outer[2], alerts),
+            (Tab::Gas, _) => {
+                let mut chains = vec![(host_view.label, host)];
+                chains.extend(rollup.map(|rollup| ("Rollup", rollup)));
+                self.render_fee_charts(frame, outer[2], &chains);
+            }
+            (Tab::GasUsers, _) => {
+                let mut chains = vec![(host_view.label, host)];
+                chains.extend(rollup.map(|rollup| ("Rollup", rollup)));
+                self.render_gas_users(frame, outer[2], &chains);
+            }
+            (Tab::Blobs, _) => {
+                let mut chains = vec![(host_view.label, host)];
+                chains.extend(rollup.map(|rollup| ("Rollup", rollup)));
+                self.render_blobs(frame, outer[2], &chains);
+            }
+            (Tab::Deployments, _) => {
+                let mut chains = vec![(host_view.label, host)];
+                chains.extend(rollup.map(|rollup| ("Rollup", rollup)));
+                self.render_deployments(frame, outer[2], &chains);
+            }
+            (Tab::Watchlist, _) => {
+                let mut chains = vec![(host_view.label, host)];
+                chains.extend(rollup.map(|rollup| ("Rollup", rollup)));
+                self.render_watchlist(frame, outer[2], &chains);
+            }
+            (Tab::Diagnostics, _) => {
+                let mut chains = vec![(host_view.label, host)];
+                chains.extend(rollup.map(|rollup| ("Rollup", rollup)));
+                self.render_diagnostics(frame, outer[2], &chains);
+            }
+            (Tab::Dashboard, Some(rollup)) => {
+                let panels = if outer[2].width >= 160 {
+                    Layout::default()
+                        .direction(Direction::Horizontal)
+                        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .split(outer[2])
+                } else {
+                    Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .split(outer[2])
+                };
+
+                let rollup_view = ChainView {
+                    role: ChainRole::Rollup,
+                    metrics: rollup,
+                    label: "Rollup",
+                    alert_key: network.chain_label(ChainRole::Rollup, multi),
+                };
+                self.render_chain_column(frame, panels[0], &host_view, None, alerts);
+                self.render_chain_column(frame, panels[1], &rollup_view, Some(host), alerts);
+            }
+            (Tab::Dashboard, None) => {
+                self.render_chain_column(frame, outer[2], &host_view, None, alerts)
+            }
+        }
+        self.render_footer(frame, outer[3], networks, host, rollup, alerts);
+
+        if let Some(picker) = &self.endpoint_picker {
+            self.render_endpoint_picker(frame, picker);
+        }
+        if let Some(picker) = &self.profile_picker {
+            self.render_profile_picker(frame, picker);
+        }
+        if let Some(selected) = self.layout_editor {
+            self.render_layout_editor(frame, selected);
+        }
+        if let Some(prompt) = &self.prompt {
+            prompt.input.render(frame, frame.area());
+        }
+        if self.show_help {
+            self.render_help(frame);
+        }
+    }
+
+    fn render_alerts(&self, frame: &mut Frame, area: Rect, alerts: &AlertHistory) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(40), Constraint::Min(0)])
+            .split(area);
+        self.render_alert_kinds(frame, columns[0], alerts);
+
+        let block = shell_block("Alert History".to_string(), Color::Red);
+        let inner = block.inner(columns[1]);
+        frame.render_widget(block, columns[1]);
+
+        if alerts.iter().next().is_none() {
+            frame.render_widget(
+                Paragraph::new("(no alerts fired this session)")
+                    .style(Style::default().fg(Color::DarkGray)),
+                inner,
+            );
+            return;
+        }
+
+        let rows = inner.height.saturating_sub(1) as usize;
+        let table = alert_table(alerts.iter().take(rows), true);
+        frame.render_widget(table, inner);
+    }
+
+    /// Every alert type with whether it is muted or snoozed, the selected one highlighted.
+    fn render_alert_kinds(&self, frame: &mut Frame, area: Rect, alerts: &AlertHistory) {
+        let now = unix_now();
+        let lines: Vec<Line> = AlertKind::ALL
+            .into_iter()
+            .enumerate()
+            .map(|(index, kind)| {
+                let (state, color) = match alerts.silence_of(kind) {
+                    Some(silence) => (silence_label(silence, now), Color::Magenta),
+                    None => (String::new(), Color::Gray),
+                };
+                let style = if index == self.alert_kind {
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(color)
+                };
+                Line::from(Span::styled(
+                    format!("{:<22} {:>14}", kind.label(), state),
+                    style,
+                ))
+            })
+            .chain([
+                Line::default(),
+                Line::from(Span::styled(
+                    format!(
+                        "{} mute  {} snooze",
+                        keys_for(Action::ToggleMute),
+                        keys_for(Action::Snooze)
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).block(shell_block("Alert Types".to_string(), Color::Red)),
+            area,
+        );
+    }
+
+    /// Per-method RPC call and failure counts, one table per chain.
+    fn render_diagnostics(&self, frame: &mut Frame, area: Rect, chains: &[(&str, &SignetMetrics)]) {
+        let areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Ratio(1, chains.len() as u32);
+                chains.len()
+            ])
+            .split(area);
+
+        for ((label, metrics), area) in chains.iter().zip(areas.iter()) {
+            let failures = metrics.rpc_stats.total_failures();
+            let block = shell_block(
+                format!("RPC Diagnostics · {} · {}", label, metrics.rpc_url),
+                if failures > 0 {
+                    Color::Yellow
+                } else {
+                    Color::Green
+                },
+            );
+            let inner = block.inner(*area);
+            frame.render_widget(block, *area);
+            // The method table at its full height, the outage log below in what's left
+            let sections = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(metrics.rpc_stats.iter().count() as u16 + 1),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Min(0),
+                ])
+                .split(inner);
+            frame.render_widget(
+                rpc_stats_table(&metrics.rpc_stats, &metrics.capabilities),
+                sections[0],
+            );
+            frame.render_widget(Paragraph::new(traffic_line(metrics)), sections[1]);
+            frame.render_widget(
+                Paragraph::new(memory_line(metrics, self.memory.as_ref())),
+                sections[2],
+            );
+            let now = Instant::now();
+            frame.render_widget(Paragraph::new(outage_log_title(metrics, now)), sections[3]);
+            frame.render_widget(outage_table(&metrics.availability, now), sections[4]);
+        }
+    }
 
-        let panels = if outer[1].width >= 160 {
-            Layout::default()
+    /// Per-chain base fee over the chart window, in braille, with the alert thresholds as
+    /// guide lines: yellow where the fee turns yellow on the dashboard, red where the high
+    /// base fee alert fires. Block size and calldata bytes per block run underneath.
+    fn render_fee_charts(&self, frame: &mut Frame, area: Rect, chains: &[(&str, &SignetMetrics)]) {
+        let areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Ratio(1, chains.len() as u32);
+                chains.len()
+            ])
+            .split(area);
+
+        let (since, until) = self.chart.bounds(unix_now());
+        let span = (until - since) as f64;
+        for ((label, metrics), area) in chains.iter().zip(areas.iter()) {
+            let accent = panel_accent(label);
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(6), Constraint::Length(4)])
+                .split(*area);
+            let area = &rows[0];
+            let sizes = Layout::default()
                 .direction(Direction::Horizontal)
-                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-                .split(outer[1])
-        } else {
-            Layout::default()
+                .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+                .split(rows[1]);
+            for (section, metric, title) in [
+                (sizes[0], Metric::BlockSize, "Block size"),
+                (sizes[1], Metric::Calldata, "Calldata"),
+            ] {
+                render_chart(
+                    frame,
+                    section,
+                    &self.chart,
+                    title,
+                    metrics.timeseries.get(metric),
+                    accent,
+                    |bytes| units::format_bytes(bytes as u64),
+                );
+            }
+            let points: Vec<(f64, f64)> = metrics
+                .timeseries
+                .get(Metric::BaseFee)
+                .into_iter()
+                .flat_map(|series| series.range(since, until))
+                .map(|point| {
+                    (
+                        point.at.saturating_sub(since) as f64,
+                        point.mean() / WEI_PER_GWEI as f64,
+                    )
+                })
+                .collect();
+            let block = shell_block(
+                format!("Base Fee · {} · {}", label, self.chart.label()),
+                accent,
+            );
+            if points.is_empty() {
+                frame.render_widget(
+                    Paragraph::new("(no base fee samples in this window; +/- zoom, ←/→ pan)")
+                        .style(Style::default().fg(Color::DarkGray))
+                        .block(block),
+                    *area,
+                );
+                continue;
+            }
+
+            let low = points.iter().map(|(_, y)| *y).fold(f64::INFINITY, f64::min);
+            let high = points.iter().map(|(_, y)| *y).fold(0.0, f64::max);
+            // Guide lines far above the data would flatten it; those are listed instead
+            let thresholds = &metrics.gas_thresholds;
+            let guides: Vec<(&str, f64, Color)> = [
+                ("warn", thresholds.high_gwei * 0.5, Color::Yellow),
+                ("critical", thresholds.high_gwei, Color::Red),
+            ]
+            .into_iter()
+            .filter(|(_, gwei, _)| *gwei <= high.max(f64::MIN_POSITIVE) * CHART_GUIDE_HEADROOM)
+            .collect();
+            let top = guides.iter().map(|(_, gwei, _)| *gwei).fold(high, f64::max) * 1.05;
+            let bottom = (low * 0.95).min(top * 0.5);
+            let guide_points: Vec<[(f64, f64); 2]> = guides
+                .iter()
+                .map(|(_, gwei, _)| [(0.0, *gwei), (span, *gwei)])
+                .collect();
+
+            let mut datasets = vec![
+                Dataset::default()
+                    .name("base fee")
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(accent))
+                    .data(&points),
+            ];
+            for ((name, gwei, color), data) in guides.iter().zip(&guide_points) {
+                datasets.push(
+                    Dataset::default()
+                        .name(format!(
+                            "{} ≥{}g",
+                            name,
+                            units::format_gwei_auto(gwei_to_wei(*gwei))
+                        ))
+                        .marker(symbols::Marker::Braille)
+                        .graph_type(GraphType::Line)
+                        .style(Style::default().fg(*color))
+                        .data(data),
+                );
+            }
+            let axis_style = Style::default().fg(Color::DarkGray);
+            let chart = Chart::new(datasets)
+                .block(block)
+                .x_axis(
+                    Axis::default()
+                        .style(axis_style)
+                        .bounds([0.0, span])
+                        .labels(self.chart.axis_labels()),
+                )
+                .y_axis(
+                    Axis::default()
+                        .style(axis_style)
+                        .bounds([bottom, top])
+                        .labels([bottom, (bottom + top) / 2.0, top].map(|gwei| {
+                            format!("{}g", units::format_gwei_auto(gwei_to_wei(gwei)))
+                        })),
+                )
+                .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)));
+            frame.render_widget(chart, *area);
+        }
+    }
+
+    /// Per-chain leaderboard of the addresses whose transactions used the most gas in the
+    /// blocks with receipts.
+    fn render_gas_users(&self, frame: &mut Frame, area: Rect, chains: &[(&str, &SignetMetrics)]) {
+        let areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Ratio(1, chains.len() as u32);
+                chains.len()
+            ])
+            .split(area);
+
+        for ((label, metrics), area) in chains.iter().zip(areas.iter()) {
+            let blocks = metrics.receipt_totals().map_or(0, |(_, blocks)| blocks);
+            let block = shell_block(
+                format!("Top Gas Users · {} · {} block(s)", label, blocks),
+                panel_accent(label),
+            );
+            let inner = block.inner(*area);
+            frame.render_widget(block, *area);
+
+            let users = metrics.top_gas_users(GAS_USERS_LEADERBOARD_ROWS);
+            if users.is_empty() {
+                let hint = if metrics.capabilities.supports(RpcMethod::BlockReceipts) {
+                    "(no receipts yet; start with --receipts to fetch them)"
+                } else {
+                    "Unsupported by endpoint (eth_getBlockReceipts)"
+                };
+                frame.render_widget(
+                    Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)),
+                    inner,
+                );
+                continue;
+            }
+            frame.render_widget(gas_users_table(&users, &self.address_book), inner);
+        }
+    }
+
+    /// Blob-carrying blocks in each chain's history, one row per blob once its sidecar
+    /// was fetched from the beacon API.
+    fn render_blobs(&self, frame: &mut Frame, area: Rect, chains: &[(&str, &SignetMetrics)]) {
+        let areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Ratio(1, chains.len() as u32);
+                chains.len()
+            ])
+            .split(area);
+
+        for ((label, metrics), area) in chains.iter().zip(areas.iter()) {
+            let blocks: Vec<&BlockInfo> = metrics
+                .block_history
+                .iter()
+                .filter(|block| block.blob_count().unwrap_or(0) > 0)
+                .collect();
+            let blobs: u64 = blocks.iter().filter_map(|block| block.blob_count()).sum();
+            let mut title = format!("Blobs · {} · {} in {} block(s)", label, blobs, blocks.len());
+            if let Some(beacon) = &metrics.beacon {
+                title.push_str(&format!(" · {}", beacon.url));
+            }
+            let block = shell_block(title, panel_accent(label));
+            let inner = block.inner(*area);
+            frame.render_widget(block, *area);
+
+            let mut lines = Vec::new();
+            match &metrics.beacon {
+                None => lines.push(Line::styled(
+                    "Sidecars need a beacon API; start with --beacon-url to fetch them",
+                    Style::default().fg(Color::DarkGray),
+                )),
+                Some(beacon) => {
+                    if let Some(error) = &beacon.error {
+                        lines.push(Line::styled(
+                            format!("Beacon API: {}", error),
+                            Style::default().fg(Color::Red),
+                        ));
+                    }
+                }
+            }
+            if blocks.is_empty() {
+                lines.push(Line::styled(
+                    "(no blob-carrying blocks in history)",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            let layout = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-                .split(outer[1])
+                .constraints([Constraint::Length(lines.len() as u16), Constraint::Min(0)])
+                .split(inner);
+            frame.render_widget(Paragraph::new(lines), layout[0]);
+            if !blocks.is_empty() {
+                frame.render_widget(blobs_table(&blocks), layout[1]);
+            }
+        }
+    }
+
+    /// Contracts deployed by top-level creation transactions in each chain's new blocks,
+    /// newest first.
+    fn render_deployments(&self, frame: &mut Frame, area: Rect, chains: &[(&str, &SignetMetrics)]) {
+        let areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Ratio(1, chains.len() as u32);
+                chains.len()
+            ])
+            .split(area);
+
+        for ((label, metrics), area) in chains.iter().zip(areas.iter()) {
+            let feed = &metrics.deployments;
+            let block = shell_block(
+                format!("Deployments · {} · {} this session", label, feed.total),
+                panel_accent(label),
+            );
+            let inner = block.inner(*area);
+            frame.render_widget(block, *area);
+
+            if feed.deployments.is_empty() {
+                frame.render_widget(
+                    Paragraph::new("(no contract creations in the blocks seen yet)")
+                        .style(Style::default().fg(Color::DarkGray)),
+                    inner,
+                );
+                continue;
+            }
+            frame.render_widget(deployments_table(feed, &self.address_book), inner);
+        }
+    }
+
+    /// Each chain's readings of the watchlist file: balances and nonces of watched
+    /// addresses, where tracked transactions landed, log filter matches, and the
+    /// transactions and balance and nonce changes of the addresses seen, newest first.
+    fn render_watchlist(&self, frame: &mut Frame, area: Rect, chains: &[(&str, &SignetMetrics)]) {
+        let areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Ratio(1, chains.len() as u32);
+                chains.len()
+            ])
+            .split(area);
+
+        let gray = Style::default().fg(Color::DarkGray);
+        let heading = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        let error_span = |error: &Option<String>| {
+            Span::styled(
+                error
+                    .as_ref()
+                    .map(|error| format!("  {}", error))
+                    .unwrap_or_default(),
+                Style::default().fg(Color::Red),
+            )
         };
+        for ((label, metrics), area) in chains.iter().zip(areas.iter()) {
+            let readings = &metrics.watchlist;
+            let block = shell_block(format!("Watchlist · {}", label), panel_accent(label));
+            let mut lines = Vec::new();
+            if readings.is_empty() {
+                lines.push(Line::styled(
+                    "(empty; list addresses, transactions, and [[events]] in watchlist.toml, or \
+                     point --watchlist at one)",
+                    gray,
+                ));
+            }
+            if !readings.accounts.is_empty() {
+                lines.push(Line::styled("Accounts", heading));
+            }
+            for account in &readings.accounts {
+                let mut spans = vec![Span::styled(
+                    format!("  {:<20} ", self.address_book.name(account.address)),
+                    Style::default().fg(Color::White),
+                )];
+                match (account.balance, account.nonce, account.block) {
+                    (Some(balance), Some(nonce), Some(number)) => spans.extend([
+                        Span::styled(
+                            format!("{:>14} ETH", fmt_eth_short(&balance)),
+                            Style::default().fg(Color::Green),
+                        ),
+                        Span::styled(
+                            format!(
+                                "  nonce {}  at #{}",
+                                units::format_count(nonce),
+                                units::format_count(number)
+                            ),
+                            gray,
+                        ),
+                    ]),
+                    _ => spans.push(Span::styled("--", gray)),
+                }
+                spans.push(error_span(&account.error));
+                lines.push(Line::from(spans));
+            }
+            if !readings.transactions.is_empty() {
+                lines.push(Line::styled("Transactions", heading));
+            }
+            for tx in &readings.transactions {
+                let mut spans = vec![Span::styled(
+                    format!("  {:<20} ", short_hash(&tx.hash)),
+                    Style::default().fg(Color::White),
+                )];
+                spans.push(match tx.inclusion {
+                    Some(inclusion) => Span::styled(
+                        format!(
+                            "{} in #{}  {} gas  {} confirmations",
+                            if inclusion.succeeded {
+                                "✓ included"
+                            } else {
+                                "✗ reverted"
+                            },
+                            units::format_count(inclusion.block),
+                            units::format_count(inclusion.gas_used),
+                            metrics
+                                .block_number
+                                .map_or(0, |head| (head + 1).saturating_sub(inclusion.block))
+                        ),
+                        Style::default().fg(if inclusion.succeeded {
+                            Color::Green
+                        } else {
+                            Color::Red
+                        }),
+                    ),
+                    None => Span::styled("not seen on this chain", gray),
+                });
+                spans.push(error_span(&tx.error));
+                lines.push(Line::from(spans));
+            }
+            if !readings.events.is_empty() {
+                lines.push(Line::styled("Events", heading));
+            }
+            for event in &readings.events {
+                let mut spans = vec![
+                    Span::styled(
+                        format!("  {:<20} ", event.filter.name),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(
+                        format!("{} matches", units::format_count(event.matches)),
+                        Style::default().fg(if event.matches > 0 {
+                            Color::Yellow
+                        } else {
+                            Color::Gray
+                        }),
+                    ),
+                ];
+                if let Some(number) = event.last_block {
+                    spans.push(Span::styled(
+                        format!("  last in #{}", units::format_count(number)),
+                        gray,
+                    ));
+                }
+                if !metrics.capabilities.supports(RpcMethod::GetLogs) {
+                    spans.push(Span::styled(
+                        "  unsupported by endpoint (eth_getLogs)",
+                        gray,
+                    ));
+                }
+                spans.push(error_span(&event.error));
+                lines.push(Line::from(spans));
+            }
+            if !readings.activity.is_empty() {
+                lines.push(Line::styled("Activity", heading));
+            }
+            for activity in &readings.activity {
+                lines.push(match activity {
+                    Activity::Change(change) => {
+                        account_change_line(change, &self.address_book.name(change.address))
+                    }
+                    Activity::Tx(tx) => address_tx_line(tx, &self.address_book),
+                });
+            }
+            frame.render_widget(
+                Paragraph::new(lines)
+                    .block(block)
+                    .wrap(Wrap { trim: false }),
+                *area,
+            );
+        }
+    }
+
+    /// Keybinding reference generated from the keymap table.
+    fn render_help(&self, frame: &mut Frame) {
+        let key_style = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let heading_style = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+
+        let mut lines: Vec<Line> = Vec::new();
+        for category in Category::ALL {
+            lines.push(Line::from(Span::styled(category.title(), heading_style)));
+            for binding in KEYMAP.iter().filter(|binding| binding.category == category) {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:<10}", keys_for(binding.action)), key_style),
+                    Span::styled(binding.description, Style::default().fg(Color::Gray)),
+                ]));
+            }
+            lines.push(Line::default());
+        }
+        lines.push(Line::from(Span::styled(
+            "Block search terms",
+            heading_style,
+        )));
+        lines.push(Line::from(Span::styled(
+            format!("  {}", SEARCH_SYNTAX),
+            Style::default().fg(Color::Gray),
+        )));
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled("Text prompt", heading_style)));
+        for (keys, description) in PROMPT_KEYS {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<10}", keys), key_style),
+                Span::styled(*description, Style::default().fg(Color::Gray)),
+            ]));
+        }
+
+        let area = centered_rect(frame.area(), 64, lines.len() as u16 + 2);
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(lines).block(shell_block(
+                "Keybindings · any key closes".to_string(),
+                Color::Cyan,
+            )),
+            area,
+        );
+    }
+
+    fn render_layout_editor(&self, frame: &mut Frame, selected: usize) {
+        let entries = self.panels.entries();
+        let area = centered_rect(frame.area(), 48, entries.len() as u16 + 4);
+        let block = shell_block("Panel layout".to_string(), Color::Yellow);
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let mut lines: Vec<Line> = entries
+            .iter()
+            .enumerate()
+            .map(|(index, (panel, enabled))| {
+                let text = format!(
+                    "{} [{}] {}",
+                    if index == selected { ">" } else { " " },
+                    if *enabled { "x" } else { " " },
+                    panel.title()
+                );
+                let style = if index == selected {
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else if *enabled {
+                    Style::default().fg(Color::White)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                Line::from(Span::styled(text, style))
+            })
+            .collect();
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{} show/hide  {} / {} move  {} done",
+                keys_for(Action::LayoutToggle),
+                keys_for(Action::LayoutMoveUp),
+                keys_for(Action::LayoutMoveDown),
+                keys_for(Action::LayoutClose),
+            ),
+            Style::default().fg(Color::DarkGray),
+        )));
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_endpoint_picker(&self, frame: &mut Frame, picker: &EndpointPicker) {
+        let height = (picker.options.len() as u16 + 4).min(frame.area().height);
+        let area = centered_rect(frame.area(), 90, height);
+        let block = shell_block(
+            format!("Switch {} endpoint", picker.target.label()),
+            Color::Yellow,
+        );
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
 
-        self.render_host_panel(frame, panels[0], host);
-        self.render_rollup_panel(frame, panels[1], host, rollup);
-        self.render_footer(frame, outer[2], host, rollup);
+        // Keep the selection on screen when the list is taller than the terminal
+        let visible = (inner.height as usize).saturating_sub(1).max(1);
+        let first = picker
+            .selected
+            .saturating_sub(visible - 1)
+            .min(picker.options.len().saturating_sub(visible));
+        let mut lines: Vec<Line> = picker
+            .options
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .map(|(index, url)| {
+                let url = trim_middle(url, inner.width.saturating_sub(2) as usize);
+                if index == picker.selected {
+                    Line::from(Span::styled(
+                        format!("> {}", url),
+                        Style::default()
+                            .fg(Color::Black)
+                            .bg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::from(Span::styled(
+                        format!("  {}", url),
+                        Style::default().fg(Color::Gray),
+                    ))
+                }
+            })
+            .collect();
+        lines.push(Line::from(Span::styled(
+            "↑/↓ select  tab host/rollup  e custom URL  enter switch  esc cancel",
+            Style::default().fg(Color::DarkGray),
+        )));
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn render_profile_picker(&self, frame: &mut Frame, picker: &ProfilePicker) {
+        let height = (picker.options.len() as u16 + 4).min(frame.area().height);
+        let area = centered_rect(frame.area(), 50, height);
+        let block = shell_block("Switch profile".to_string(), Color::Yellow);
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let visible = (inner.height as usize).saturating_sub(1).max(1);
+        let first = picker
+            .selected
+            .saturating_sub(visible - 1)
+            .min(picker.options.len().saturating_sub(visible));
+        let mut lines: Vec<Line> = picker
+            .options
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .map(|(index, name)| {
+                let current = if self.profile.as_ref() == Some(name) {
+                    " (current)"
+                } else {
+                    ""
+                };
+                if index == picker.selected {
+                    Line::from(Span::styled(
+                        format!("> {}{}", name, current),
+                        Style::default()
+                            .fg(Color::Black)
+                            .bg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::from(Span::styled(
+                        format!("  {}{}", name, current),
+                        Style::default().fg(Color::Gray),
+                    ))
+                }
+            })
+            .collect();
+        lines.push(Line::from(Span::styled(
+            "↑/↓ select  enter switch  esc cancel",
+            Style::default().fg(Color::DarkGray),
+        )));
+        frame.render_widget(Paragraph::new(lines), inner);
     }
 
     fn render_header(
         &self,
         frame: &mut Frame,
         area: Rect,
-        host: &SignetMetrics,
-        rollup: &SignetMetrics,
+        networks: &[Network],
+        selected: usize,
+        alerts: &AlertHistory,
     ) {
+        let network = &networks[selected];
+        let host = network.host_metrics();
         let host_status = status_badge(&host.connection_status);
-        let rollup_status = status_badge(&rollup.connection_status);
-        let host_block = metric_or_na(host.chain_height());
-        let rollup_block = metric_or_na(rollup.chain_height());
+        let host_block = fmt_count_opt(host.chain_height());
+
+        let mut chain_row = vec![
+            status_chip("HOST", host_status.0, host_status.1),
+            Span::raw(" "),
+            Span::styled(
+                format!("#{}  age {}", host_block, block_age(host)),
+                Style::default().fg(Color::Gray),
+            ),
+        ];
+        if let Some(rollup) = network.rollup_metrics() {
+            let rollup_status = status_badge(&rollup.connection_status);
+            chain_row.extend([
+                Span::raw("    "),
+                status_chip("ROLLUP", rollup_status.0, rollup_status.1),
+                Span::raw(" "),
+                Span::styled(
+                    format!(
+                        "#{}  age {}",
+                        fmt_count_opt(rollup.chain_height()),
+                        block_age(rollup)
+                    ),
+                    Style::default().fg(Color::Gray),
+                ),
+            ]);
+        }
 
-        let lines = vec![
+        let mut lines = vec![
             Line::from(vec![
                 Span::styled(
                     " FUTURESIGHT ",
@@ -98,440 +1211,230 @@ impl Dashboard {
                     format!("v{}", env!("CARGO_PKG_VERSION")),
                     Style::default().fg(Color::DarkGray),
                 ),
-            ]),
-            Line::from(vec![
-                status_chip("HOST", host_status.0, host_status.1),
-                Span::raw(" "),
                 Span::styled(
-                    format!("#{}  age {}", host_block, block_age(host)),
-                    Style::default().fg(Color::Gray),
+                    self.profile
+                        .as_ref()
+                        .map(|profile| format!(" profile {}", profile))
+                        .unwrap_or_default(),
+                    Style::default().fg(Color::Yellow),
                 ),
-                Span::raw("    "),
-                status_chip("ROLLUP", rollup_status.0, rollup_status.1),
+                Span::raw("   "),
+                tab_span(Tab::Dashboard, self.tab, String::new()),
                 Span::raw(" "),
-                Span::styled(
-                    format!("#{}  age {}", rollup_block, block_age(rollup)),
-                    Style::default().fg(Color::Gray),
+                tab_span(Tab::Gas, self.tab, String::new()),
+                Span::raw(" "),
+                tab_span(
+                    Tab::Alerts,
+                    self.tab,
+                    match alerts.active_count() {
+                        0 => String::new(),
+                        active => format!(" ({})", active),
+                    },
                 ),
+                Span::raw(" "),
+                tab_span(Tab::GasUsers, self.tab, String::new()),
+                Span::raw(" "),
+                tab_span(Tab::Blobs, self.tab, String::new()),
+                Span::raw(" "),
+                tab_span(Tab::Deployments, self.tab, String::new()),
+                Span::raw(" "),
+                tab_span(Tab::Watchlist, self.tab, String::new()),
+                Span::raw(" "),
+                tab_span(Tab::Diagnostics, self.tab, String::new()),
             ]),
+            Line::from(chain_row),
         ];
+        if self.paused {
+            lines[1].spans.extend([
+                Span::raw("    "),
+                Span::styled(
+                    " PAUSED ",
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]);
+        }
+        if networks.len() > 1 {
+            lines.push(network_comparison_line(networks, selected));
+        }
 
+        let title = if networks.len() > 1 {
+            format!("Mission Control · {}", network.name)
+        } else {
+            "Mission Control".to_string()
+        };
         let paragraph = Paragraph::new(lines)
-            .block(shell_block("Mission Control".to_string(), Color::Blue))
+            .block(shell_block(title, Color::Blue))
             .wrap(Wrap { trim: true });
         frame.render_widget(paragraph, area);
     }
 
-    fn render_host_panel(&self, frame: &mut Frame, area: Rect, metrics: &SignetMetrics) {
-        let sections = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(6),
-                Constraint::Length(8),
-                Constraint::Min(8),
-            ])
-            .split(area);
-
-        self.render_summary(frame, sections[0], metrics, "Host", None);
-        self.render_gas(frame, sections[1], metrics, "Host");
-        self.render_block_history(frame, sections[2], metrics, "Host");
-    }
-
-    fn render_rollup_panel(
+    /// One chain's enabled panels. `host` is set for the rollup column, which alone has a
+    /// tx-pool panel and compares its tip against the host.
+    ///
+    /// Columns at least [`TWO_PANE_MIN_WIDTH`] wide split into two panes: status panels and
+    /// the chain's alerts on the left, tx-pool and block history on the right, so the
+    /// history gets the full column height.
+    fn render_chain_column(
         &self,
         frame: &mut Frame,
         area: Rect,
-        host: &SignetMetrics,
-        metrics: &SignetMetrics,
+        chain: &ChainView,
+        host: Option<&SignetMetrics>,
+        alerts: &AlertHistory,
     ) {
-        let sections = if area.height >= 34 {
-            Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(6),
-                    Constraint::Length(8),
-                    Constraint::Length(12),
-                    Constraint::Min(8),
-                ])
-                .split(area)
-        } else {
-            Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(6),
-                    Constraint::Length(8),
-                    Constraint::Length(10),
-                    Constraint::Min(6),
-                ])
-                .split(area)
-        };
+        let panels: Vec<Panel> = self
+            .panels
+            .enabled()
+            .filter(|panel| *panel != Panel::TxPool || host.is_some())
+            .filter(|panel| {
+                *panel != Panel::Probes
+                    || !chain.metrics.probes.is_empty()
+                    || !chain.metrics.slots.is_empty()
+                    || !chain.metrics.proofs.is_empty()
+            })
+            .collect();
+
+        if area.width < TWO_PANE_MIN_WIDTH {
+            self.render_panel_stack(frame, area, &panels, chain, host);
+            return;
+        }
 
-        self.render_summary(frame, sections[0], metrics, "Rollup", Some(host));
-        self.render_gas(frame, sections[1], metrics, "Rollup");
-        self.render_txpool(frame, sections[2], metrics, "Rollup");
-        self.render_block_history(frame, sections[3], metrics, "Rollup");
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(area);
+        let (status, history): (Vec<Panel>, Vec<Panel>) = panels
+            .into_iter()
+            .partition(|panel| matches!(panel, Panel::Connection | Panel::Gas | Panel::Probes));
+
+        let status_height: u16 = status
+            .iter()
+            .filter_map(|panel| {
+                self.panel_widget(*panel, chain, host)
+                    .preferred_height(area)
+            })
+            .sum();
+        let left = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(status_height), Constraint::Min(0)])
+            .split(panes[0]);
+        self.render_panel_stack(frame, left[0], &status, chain, host);
+        if left[1].height >= 3 {
+            self.render_chain_alerts(frame, left[1], chain, alerts);
+        }
+        self.render_panel_stack(frame, panes[1], &history, chain, host);
     }
 
-    fn render_summary(
+    /// `panels` top to bottom at their preferred heights; one without a preference (block
+    /// history) takes whatever height is left.
+    fn render_panel_stack(
         &self,
         frame: &mut Frame,
         area: Rect,
-        metrics: &SignetMetrics,
-        label: &str,
+        panels: &[Panel],
+        chain: &ChainView,
         host: Option<&SignetMetrics>,
     ) {
-        let accent = panel_accent(label);
-        let sync_status = host.map(|host_metrics| chain_tip_sync_status(host_metrics, metrics));
-        let feed_accent = match sync_status {
-            Some(ChainTipSyncStatus::Diverged { .. }) => Color::Red,
-            _ => accent,
-        };
-        let (status_text, status_style) = status_badge(&metrics.connection_status);
-        let rpc_width = area.width.saturating_sub(14) as usize;
-        let delay = block_delay(metrics);
-        let delay_style = match delay {
-            Some(value) if value > metrics.block_delay_threshold => {
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
-            }
-            Some(value) if value > metrics.block_delay_threshold / 2 => {
-                Style::default().fg(Color::Yellow)
-            }
-            Some(_) => Style::default().fg(Color::Green),
-            None => Style::default().fg(Color::DarkGray),
-        };
-
-        let mut lines = vec![
-            Line::from(vec![
-                status_chip(label, status_text, status_style),
-                Span::raw(" "),
-                Span::styled(
-                    format!("updated {}", relative_age(metrics.last_updated.elapsed())),
-                    Style::default().fg(Color::Gray),
-                ),
-                Span::raw("  "),
-                Span::styled(
-                    format!("stale after {}", relative_age(STALE_AFTER)),
-                    Style::default().fg(Color::DarkGray),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled("RPC ", Style::default().fg(Color::DarkGray)),
-                Span::styled(
-                    trim_middle(&metrics.rpc_url, rpc_width.max(24)),
-                    Style::default().fg(Color::White),
-                ),
-            ]),
-            Line::from(vec![
-                kv_span("Chain", metric_or_na(metrics.chain_id), Color::Cyan),
-                Span::raw("  "),
-                kv_span("Block", metric_or_na(metrics.chain_height()), accent),
-                Span::raw("  "),
-                kv_span("Age", block_age(metrics), Color::Yellow),
-                Span::raw("  "),
-                Span::styled("Delay ", Style::default().fg(Color::DarkGray)),
-                Span::styled(
-                    delay
-                        .map(|value| format!("{}s", value))
-                        .unwrap_or_else(|| "--".to_string()),
-                    delay_style,
-                ),
-            ]),
-        ];
-
-        if let Some(host_metrics) = host {
-            lines.push(chain_tip_comparison_line(
-                host_metrics,
-                metrics,
-                sync_status.unwrap_or(ChainTipSyncStatus::Unknown),
-            ));
-        }
-
-        let paragraph = Paragraph::new(lines)
-            .block(shell_block(format!("{} Feed", label), feed_accent))
-            .wrap(Wrap { trim: true });
-        frame.render_widget(paragraph, area);
-    }
-
-    fn render_gas(&self, frame: &mut Frame, area: Rect, metrics: &SignetMetrics, label: &str) {
-        let accent = panel_accent(label);
-        let block = shell_block(format!("{} Gas Deck", label), accent);
-        let inner = block.inner(area);
-        frame.render_widget(block, area);
-
-        if inner.height < 4 {
-            return;
+        let widgets: Vec<Box<dyn MetricsWidget + '_>> = panels
+            .iter()
+            .map(|panel| self.panel_widget(*panel, chain, host))
+            .collect();
+        let heights: Vec<Option<u16>> = widgets
+            .iter()
+            .map(|widget| widget.preferred_height(area))
+            .collect();
+        let mut constraints: Vec<Constraint> = heights
+            .iter()
+            .map(|height| match height {
+                Some(height) => Constraint::Length(*height),
+                None => Constraint::Min(6),
+            })
+            .collect();
+        if heights.iter().all(Option::is_some) {
+            constraints.push(Constraint::Min(0));
         }
-
-        let gas_layout = Layout::default()
+        let sections = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Length(1),
-                Constraint::Min(1),
-            ])
-            .split(inner);
-
-        let trend_mult = metrics.gas_volatility_5m.map(|value| 1.0 + value.max(-1.0));
-        let trend_style = match trend_mult {
-            Some(multiplier) if multiplier >= GAS_SPIKE_MULTIPLIER => {
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
-            }
-            Some(_) => Style::default().fg(Color::Green),
-            None => Style::default().fg(Color::DarkGray),
-        };
-
-        let gas_lines = vec![
-            Line::from(vec![
-                kv_span(
-                    "Base",
-                    fmt_gwei_opt(metrics.base_fee_per_gas),
-                    fee_style(metrics.base_fee_per_gas),
-                ),
-                Span::raw("  "),
-                kv_span(
-                    "Next",
-                    fmt_gwei_opt(metrics.next_base_fee_per_gas),
-                    Color::Cyan,
-                ),
-                Span::raw("  "),
-                kv_span("Legacy", fmt_gwei_opt(metrics.gas_price), Color::Gray),
-            ]),
-            Line::from(vec![
-                kv_span(
-                    "RPC tip",
-                    fmt_gwei_opt(metrics.max_priority_fee_suggested),
-                    Color::Magenta,
-                ),
-                Span::raw("  "),
-                kv_span(
-                    "Safe",
-                    fmt_fee_tier(metrics.suggested_fees.as_ref().map(|fees| &fees.safe)),
-                    Color::Green,
-                ),
-                Span::raw("  "),
-                kv_span(
-                    "Fast",
-                    fmt_fee_tier(metrics.suggested_fees.as_ref().map(|fees| &fees.fast)),
-                    Color::Red,
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled("Trend ", Style::default().fg(Color::DarkGray)),
-                Span::styled(
-                    trend_mult
-                        .map(|value| format!("x{:.2}", value))
-                        .unwrap_or_else(|| "--".to_string()),
-                    trend_style,
-                ),
-            ]),
-        ];
-
-        frame.render_widget(
-            Paragraph::new(gas_lines).wrap(Wrap { trim: true }),
-            gas_layout[0],
-        );
-
-        let utilization = metrics
-            .gas_utilization_ma_n
-            .unwrap_or_default()
-            .clamp(0.0, 100.0);
-        frame.render_widget(
-            Gauge::default()
-                .ratio(utilization / 100.0)
-                .label(format!("utilization {:.0}%", utilization))
-                .gauge_style(
-                    Style::default()
-                        .fg(accent)
-                        .bg(Color::DarkGray)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            gas_layout[1],
-        );
-
-        let trend_points = sparkline_points(metrics);
-        let sparkline = Sparkline::default()
-            .data(&trend_points)
-            .style(Style::default().fg(accent))
-            .max(trend_points.iter().copied().max().unwrap_or(1));
-        frame.render_widget(sparkline, gas_layout[2]);
-    }
-
-    fn render_txpool(&self, frame: &mut Frame, area: Rect, metrics: &SignetMetrics, label: &str) {
-        let accent = panel_accent(label);
-        let block = shell_block(format!("{} Flow Radar", label), accent);
-        let inner = block.inner(area);
-        frame.render_widget(block, area);
+            .constraints(constraints)
+            .split(area);
 
-        if inner.height < 3 {
-            return;
-        }
-
-        let layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Length(3), Constraint::Min(1)])
-            .split(inner);
-
-        match &metrics.txpool {
-            Some(txpool) => {
-                let summary = txpool_summary_lines(txpool, inner.width as usize);
-                frame.render_widget(Paragraph::new(summary).wrap(Wrap { trim: true }), layout[0]);
-                self.render_txpool_table(frame, layout[1], txpool);
-            }
-            None => {
-                let lines = vec![
-                    Line::from(vec![Span::styled(
-                        "No tx-pool service configured.",
-                        Style::default().fg(Color::Gray),
-                    )]),
-                    Line::from(vec![Span::styled(
-                        "Use --txpool-url to enable rollup flow metrics.",
-                        Style::default().fg(Color::DarkGray),
-                    )]),
-                ];
-                frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), inner);
-            }
+        for (widget, section) in widgets.iter().zip(sections.iter()) {
+            widget.render(frame, *section);
         }
     }
 
-    fn render_txpool_table(&self, frame: &mut Frame, area: Rect, txpool: &TxPoolMetrics) {
-        if area.height < 3 {
-            return;
-        }
-
-        if txpool.transactions.is_empty() {
-            let mut lines = vec![Line::from(vec![Span::styled(
-                "(no transactions in view)",
-                Style::default().fg(Color::DarkGray),
-            )])];
-            if let Some(error) = &txpool.error {
-                lines.push(Line::from(vec![Span::styled(
-                    trim_middle(error, area.width.saturating_sub(2) as usize),
-                    Style::default().fg(Color::Red),
-                )]));
-            }
-            frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), area);
-            return;
+    /// The widget drawing `panel` for `chain`; `host` is set for the rollup column.
+    fn panel_widget<'a>(
+        &'a self,
+        panel: Panel,
+        chain: &'a ChainView<'a>,
+        host: Option<&'a SignetMetrics>,
+    ) -> Box<dyn MetricsWidget + 'a> {
+        let (metrics, label) = (chain.metrics, chain.label);
+        match panel {
+            Panel::Connection => Box::new(ConnectionWidget::new(
+                metrics,
+                label,
+                host,
+                &self.chains,
+                self.refresh_interval,
+            )),
+            Panel::Gas => Box::new(GasWidget::new(metrics, label, &self.chart)),
+            Panel::TxPool => Box::new(TxPoolWidget::new(
+                metrics,
+                label,
+                host,
+                &self.address_book,
+                &self.chart,
+            )),
+            Panel::Blocks => Box::new(BlocksWidget::new(
+                chain,
+                self.block_search.as_ref(),
+                self.selected_block,
+                &self.address_book,
+            )),
+            Panel::Probes => Box::new(ProbesWidget::new(metrics, label)),
         }
-
-        let header = Row::new(vec!["hash", "route", "value", "fee", "gas", "n", "ty"]).style(
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
-        );
-
-        let max_rows = area.height.saturating_sub(2) as usize;
-        let rows = txpool
-            .transactions
-            .iter()
-            .take(max_rows)
-            .map(tx_row)
-            .collect::<Vec<_>>();
-
-        let table = Table::new(
-            rows,
-            [
-                Constraint::Length(14),
-                Constraint::Min(18),
-                Constraint::Length(10),
-                Constraint::Length(12),
-                Constraint::Length(8),
-                Constraint::Length(6),
-                Constraint::Length(4),
-            ],
-        )
-        .header(header)
-        .column_spacing(1);
-
-        frame.render_widget(table, area);
     }
 
-    fn render_block_history(
+    fn render_chain_alerts(
         &self,
         frame: &mut Frame,
         area: Rect,
-        metrics: &SignetMetrics,
-        label: &str,
+        chain: &ChainView,
+        alerts: &AlertHistory,
     ) {
-        let accent = panel_accent(label);
-        let block = shell_block(format!("{} Block Tape", label), accent);
+        let block = shell_block(format!("{} Alerts", chain.label), panel_accent(chain.label));
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
-        if inner.height < 3 {
-            return;
-        }
-
-        if metrics.block_history.is_empty() {
-            frame.render_widget(
-                Paragraph::new("(no blocks yet)").style(Style::default().fg(Color::DarkGray)),
-                inner,
-            );
-            return;
-        }
-
-        let header = Row::new(vec!["blk", "age", "tx", "gas", "base", "hash"]).style(
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
-        );
-
-        let max_rows = inner.height.saturating_sub(2) as usize;
-        let rows = metrics
-            .block_history
+        let mut matching = alerts
             .iter()
-            .take(max_rows)
-            .enumerate()
-            .map(|(index, block)| {
-                let row_style = if index == 0 {
-                    Style::default().fg(accent).add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default().fg(Color::Gray)
-                };
-                let gas_ratio = if block.gas_limit > 0 {
-                    (block.gas_used as f64 / block.gas_limit as f64) * 100.0
-                } else {
-                    0.0
-                };
-                let base_fee = block
-                    .base_fee_per_gas
-                    .map(|value| fmt_gwei_opt(Some(value)))
-                    .unwrap_or_else(|| "--".to_string());
-
-                Row::new(vec![
-                    Cell::from(format!("#{}", block.number)),
-                    Cell::from(relative_age_from_ts(block.timestamp)),
-                    Cell::from(block.tx_count.to_string()),
-                    Cell::from(format!("{:.0}%", gas_ratio)),
-                    Cell::from(base_fee),
-                    Cell::from(trim_middle(&block.hash, 14)),
-                ])
-                .style(row_style)
-            })
-            .collect::<Vec<_>>();
-
-        let table = Table::new(
-            rows,
-            [
-                Constraint::Length(10),
-                Constraint::Length(8),
-                Constraint::Length(6),
-                Constraint::Length(7),
-                Constraint::Length(10),
-                Constraint::Min(12),
-            ],
-        )
-        .header(header)
-        .column_spacing(1);
-
-        frame.render_widget(table, inner);
+            .filter(|alert| alert.chain == chain.alert_key)
+            .peekable();
+        if matching.peek().is_none() {
+            frame.render_widget(
+                Paragraph::new("(no alerts this session)")
+                    .style(Style::default().fg(Color::DarkGray)),
+                inner,
+            );
+            return;
+        }
+        let rows = inner.height.saturating_sub(1) as usize;
+        frame.render_widget(alert_table(matching.take(rows), false), inner);
     }
 
     fn render_footer(
         &self,
         frame: &mut Frame,
         area: Rect,
+        networks: &[Network],
         host: &SignetMetrics,
-        rollup: &SignetMetrics,
+        rollup: Option<&SignetMetrics>,
+        alerts: &AlertHistory,
     ) {
         let (filter_label, filter_color) = if host.host_contract_filter_active {
             (" filter:on", Color::Green)
@@ -539,28 +1442,59 @@ impl Dashboard {
             (" filter:off", Color::DarkGray)
         };
 
-        let lines = vec![Line::from(vec![
-            Span::styled("Controls ", Style::default().fg(Color::DarkGray)),
-            Span::styled("q", Style::default().fg(Color::Yellow)),
-            Span::styled(" quit", Style::default().fg(Color::Gray)),
-            Span::raw("  "),
-            Span::styled("f", Style::default().fg(Color::Yellow)),
-            Span::styled(filter_label, Style::default().fg(filter_color)),
-            Span::raw("  "),
+        let hint = |action: Action, label: &'static str, color: Color| {
+            [
+                Span::styled(keys_for(action), Style::default().fg(Color::Yellow)),
+                Span::styled(label, Style::default().fg(color)),
+                Span::raw("  "),
+            ]
+        };
+        let mut controls = vec![Span::styled(
+            "Controls ",
+            Style::default().fg(Color::DarkGray),
+        )];
+        controls.extend(hint(Action::ToggleHelp, " help", Color::Gray));
+        controls.extend(hint(Action::Quit, " quit", Color::Gray));
+        controls.extend(hint(Action::ToggleHostFilter, filter_label, filter_color));
+        controls.extend(hint(Action::OpenEndpointPicker, " endpoint", Color::Gray));
+        if networks.len() > 1 {
+            controls.extend(hint(Action::NextNetwork, " network", Color::Gray));
+        }
+
+        let updated = match rollup {
+            Some(rollup) => format!(
+                "host {} | rollup {}",
+                relative_age(host.last_updated.elapsed()),
+                relative_age(rollup.last_updated.elapsed())
+            ),
+            None => format!("host {}", relative_age(host.last_updated.elapsed())),
+        };
+        controls.extend([
             Span::styled(
                 format!("refresh {}s", self.refresh_interval),
                 Style::default().fg(Color::Cyan),
             ),
             Span::raw("  "),
-            Span::styled(
-                format!(
-                    "host {} | rollup {}",
-                    relative_age(host.last_updated.elapsed()),
-                    relative_age(rollup.last_updated.elapsed())
-                ),
-                Style::default().fg(Color::Gray),
-            ),
-        ])];
+            Span::styled(updated, Style::default().fg(Color::Gray)),
+        ]);
+        let now = unix_now();
+        let silenced: Vec<String> = alerts
+            .silences()
+            .map(|(kind, silence)| format!("{} {}", kind.name(), silence_label(silence, now)))
+            .collect();
+        if !silenced.is_empty() {
+            controls.extend([
+                Span::raw("  "),
+                Span::styled(silenced.join(", "), Style::default().fg(Color::Magenta)),
+            ]);
+        }
+        if let Some(notice) = &self.notice {
+            controls.extend([
+                Span::raw("  "),
+                Span::styled(notice.clone(), Style::default().fg(Color::Yellow)),
+            ]);
+        }
+        let lines = vec![Line::from(controls)];
 
         frame.render_widget(
             Paragraph::new(lines).block(shell_block("Flight Notes".to_string(), Color::DarkGray)),
@@ -569,6 +1503,436 @@ impl Dashboard {
     }
 }
 
+/// Per-blob rows: index, bytes used before the zero padding, and versioned hash. Blocks
+/// without fetched sidecars get one row with their header's blob count.
+fn blobs_table<'a>(blocks: &[&BlockInfo]) -> Table<'a> {
+    let mut rows = Vec::new();
+    for block in blocks {
+        let number = format!("#{}", units::format_count(block.number));
+        if block.blobs.is_empty() {
+            rows.push(
+                Row::new(vec![
+                    Cell::from(number),
+                    Cell::from("--"),
+                    Cell::from(""),
+                    Cell::from(format!(
+                        "{} blob(s), sidecars not fetched",
+                        block.blob_count().unwrap_or(0)
+                    )),
+                ])
+                .style(Style::default().fg(Color::DarkGray)),
+            );
+            continue;
+        }
+        for (i, item) in block.blobs.iter().enumerate() {
+            let used = beacon::used_bytes(item);
+            rows.push(
+                Row::new(vec![
+                    Cell::from(if i == 0 {
+                        number.clone()
+                    } else {
+                        String::new()
+                    }),
+                    Cell::from(item.index.to_string()),
+                    Cell::from(format!(
+                        "{} ({:.0}%)",
+                        units::format_bytes(used as u64),
+                        used as f64 * 100.0 / BYTES_PER_BLOB as f64
+                    )),
+                    Cell::from(beacon::versioned_hash(item).to_string()),
+                ])
+                .style(Style::default().fg(if i == 0 {
+                    Color::White
+                } else {
+                    Color::Gray
+                })),
+            );
+        }
+    }
+    Table::new(
+        rows,
+        [
+            Constraint::Length(11),
+            Constraint::Length(4),
+            Constraint::Length(16),
+            Constraint::Min(20),
+        ],
+    )
+    .header(
+        Row::new(vec!["block", "idx", "used", "versioned hash"]).style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .column_spacing(1)
+}
+
+/// Rank, name, gas used, share of block gas, and transaction count per address.
+fn gas_users_table<'a>(users: &[(GasUser, f64)], address_book: &AddressBook) -> Table<'a> {
+    let rows = users.iter().enumerate().map(|(rank, (user, share))| {
+        Row::new(vec![
+            Cell::from(format!("{}", rank + 1)),
+            Cell::from(address_book.name(user.address)),
+            Cell::from(user.address.to_string()),
+            Cell::from(format!("{:.1}M", user.gas_used as f64 / 1e6)),
+            Cell::from(format!("{:.1}%", share)),
+            Cell::from(user.txs.to_string()),
+        ])
+        .style(Style::default().fg(if rank == 0 { Color::White } else { Color::Gray }))
+    });
+    Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Length(24),
+            Constraint::Length(43),
+            Constraint::Length(9),
+            Constraint::Length(7),
+            Constraint::Length(5),
+        ],
+    )
+    .header(
+        Row::new(vec!["#", "name", "address", "gas", "share", "txs"]).style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .column_spacing(1)
+}
+
+fn deployments_table<'a>(feed: &DeploymentFeed, address_book: &AddressBook) -> Table<'a> {
+    let rows = feed.deployments.iter().map(|deployment| {
+        let (status, color) = match deployment.succeeded {
+            Some(true) => ("ok", Color::Green),
+            Some(false) => ("failed", Color::Red),
+            None => ("--", Color::DarkGray),
+        };
+        Row::new(vec![
+            Cell::from(format!("#{}", units::format_count(deployment.block))),
+            Cell::from(relative_age_from_ts(deployment.at)),
+            Cell::from(deployment.address.to_string()),
+            Cell::from(address_book.name(deployment.deployer)),
+            Cell::from(
+                deployment
+                    .gas_used
+                    .map_or("--".to_string(), units::format_count),
+            ),
+            Cell::from(units::format_bytes(deployment.init_code as u64)),
+            Cell::from(status).style(Style::default().fg(color)),
+            Cell::from(short_hash(&deployment.hash)),
+        ])
+        .style(Style::default().fg(Color::Gray))
+    });
+    Table::new(
+        rows,
+        [
+            Constraint::Length(11),
+            Constraint::Length(6),
+            Constraint::Length(43),
+            Constraint::Length(20),
+            Constraint::Length(10),
+            Constraint::Length(9),
+            Constraint::Length(6),
+            Constraint::Min(12),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "block", "age", "contract", "deployer", "gas", "code", "status", "tx",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .column_spacing(1)
+}
+
+/// Per-method call counts, failure rate, and the latest error message.
+/// "Outages · 3 this session · longest 2m", over the outage log.
+fn outage_log_title(metrics: &SignetMetrics, now: Instant) -> Line<'static> {
+    let stats = &metrics.availability;
+    Line::from(vec![
+        Span::styled(
+            "Outages",
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                " · {} this session · longest {}",
+                stats.outages,
+                relative_age(stats.longest_outage_at(now))
+            ),
+            Style::default().fg(Color::Gray),
+        ),
+    ])
+}
+
+/// The chain's recent outages, newest first: when each started, how long it lasted
+/// (still growing while ongoing), and the status that started it.
+fn outage_table(stats: &AvailabilityStats, now: Instant) -> Table<'static> {
+    let rows = stats
+        .log
+        .iter()
+        .map(|outage| {
+            let (lasted, style) = match outage.end {
+                Some(_) => (
+                    relative_age(outage.duration_at(now)),
+                    Style::default().fg(Color::Yellow),
+                ),
+                None => (
+                    format!("{} (ongoing)", relative_age(outage.duration_at(now))),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ),
+            };
+            Row::new(vec![
+                Cell::from(format!(
+                    "{} ago",
+                    relative_age(now.saturating_duration_since(outage.start))
+                )),
+                Cell::from(lasted),
+                Cell::from(outage.cause.clone()),
+            ])
+            .style(style)
+        })
+        .collect::<Vec<_>>();
+
+    Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(14),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new(vec!["started", "lasted", "cause"]).style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .column_spacing(1)
+}
+
+fn rpc_stats_table<'a>(stats: &'a RpcStats, capabilities: &Capabilities) -> Table<'a> {
+    let rows = stats
+        .iter()
+        .map(|(method, stats)| {
+            let style = match stats.failures {
+                _ if capabilities.get(method) == Support::Unsupported => {
+                    Style::default().fg(Color::DarkGray)
+                }
+                0 if stats.calls == 0 => Style::default().fg(Color::DarkGray),
+                0 => Style::default().fg(Color::Green),
+                failures if failures == stats.calls => Style::default().fg(Color::Red),
+                _ => Style::default().fg(Color::Yellow),
+            };
+            let last_error = match &stats.last_error {
+                Some((at, message)) => format!("{} ago  {}", relative_age_from_ts(*at), message),
+                None => "-".to_string(),
+            };
+            let support = match capabilities.get(method) {
+                Support::Supported if method.is_optional() => "yes",
+                Support::Supported => "required",
+                Support::Unsupported => "no",
+                Support::Unknown => "?",
+            };
+            Row::new(vec![
+                Cell::from(method.name()),
+                Cell::from(support),
+                Cell::from(stats.calls.to_string()),
+                Cell::from(stats.failures.to_string()),
+                Cell::from(
+                    stats
+                        .failure_pct()
+                        .map(|pct| format!("{:.1}%", pct))
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::from(last_error),
+            ])
+            .style(style)
+        })
+        .collect::<Vec<_>>();
+
+    Table::new(
+        rows,
+        [
+            Constraint::Length(25),
+            Constraint::Length(9),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "method",
+            "supported",
+            "calls",
+            "failed",
+            "fail%",
+            "last error",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .column_spacing(1)
+}
+
+/// Alerts as a table, newest first; `show_chain` adds the chain column.
+/// "muted", or "snoozed 12m" with the time left.
+fn silence_label(silence: Silence, now: u64) -> String {
+    match silence {
+        Silence::Muted => "muted".to_string(),
+        Silence::Snoozed { until } => format!(
+            "snoozed {}",
+            relative_age(Duration::from_secs(until.saturating_sub(now)))
+        ),
+    }
+}
+
+fn alert_table<'a>(alerts: impl Iterator<Item = &'a Alert>, show_chain: bool) -> Table<'a> {
+    let mut header = vec!["state", "alert", "started", "duration", "peak"];
+    let mut widths = vec![
+        Constraint::Length(9),
+        Constraint::Length(16),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Min(10),
+    ];
+    if show_chain {
+        header.insert(1, "chain");
+        widths.insert(1, Constraint::Length(20));
+    }
+
+    let now = unix_now();
+    let rows = alerts
+        .map(|alert| {
+            let (state, style) = match (alert.is_active(), alert.acknowledged) {
+                (true, false) => (
+                    "ACTIVE",
+                    Style::default()
+                        .fg(severity_color(alert))
+                        .add_modifier(Modifier::BOLD),
+                ),
+                (true, true) => ("ACKED", Style::default().fg(Color::Yellow)),
+                (false, _) => ("resolved", Style::default().fg(Color::DarkGray)),
+            };
+            let duration = alert.ended.unwrap_or(now).saturating_sub(alert.started);
+            let mut cells = vec![
+                Cell::from(state),
+                Cell::from(alert.kind.label()),
+                Cell::from(relative_age_from_ts(alert.started)),
+                Cell::from(relative_age(Duration::from_secs(duration))),
+                Cell::from(alert.summary()),
+            ];
+            if show_chain {
+                cells.insert(1, Cell::from(alert.chain.clone()));
+            }
+            Row::new(cells).style(style)
+        })
+        .collect::<Vec<_>>();
+
+    Table::new(rows, widths)
+        .header(
+            Row::new(header).style(
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .column_spacing(1)
+}
+
+/// Red for critical alerts, yellow for warnings.
+fn severity_color(alert: &Alert) -> Color {
+    if alert.is_critical() {
+        Color::Red
+    } else {
+        Color::Yellow
+    }
+}
+
+/// "⚠ 2 active alerts: host block delay 95s · rollup connection lost", with key hints.
+/// Red while any of them is critical, yellow for warnings only.
+fn alert_banner_line(alerts: &AlertHistory) -> Line<'static> {
+    let critical = alerts.unacknowledged().any(Alert::is_critical);
+    let pending: Vec<String> = alerts
+        .unacknowledged()
+        .map(|alert| {
+            let value = alert.summary();
+            if value.is_empty() {
+                format!("{} {}", alert.chain, alert.kind.label())
+            } else {
+                format!("{} {} {}", alert.chain, alert.kind.label(), value)
+            }
+        })
+        .collect();
+    let noun = if pending.len() == 1 {
+        "alert"
+    } else {
+        "alerts"
+    };
+    Line::from(vec![
+        Span::styled(
+            format!(
+                " ⚠ {} active {}: {} ",
+                pending.len(),
+                noun,
+                pending.join(" · ")
+            ),
+            if critical {
+                Style::default().fg(Color::White).bg(Color::Red)
+            } else {
+                Style::default().fg(Color::Black).bg(Color::Yellow)
+            }
+            .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "  {} acknowledge  {} history",
+                keys_for(Action::AcknowledgeAlerts),
+                keys_for(Action::NextTab)
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+}
+
+fn tab_span(tab: Tab, current: Tab, suffix: String) -> Span<'static> {
+    let style = if tab == current {
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::White)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Gray)
+    };
+    Span::styled(format!(" {}{} ", tab.title(), suffix), style)
+}
+
+/// A `width` x `height` rect centered in `area`, clamped to fit.
+fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
 fn shell_block(title: String, accent: Color) -> Block<'static> {
     Block::default()
         .title(Line::from(vec![Span::styled(
@@ -613,6 +1977,13 @@ fn status_badge(status: &ConnectionStatus) -> (&'static str, Style) {
                 .bg(Color::DarkGray)
                 .add_modifier(Modifier::BOLD),
         ),
+        ConnectionStatus::RateLimited(_) => (
+            "LIMITED",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ),
         ConnectionStatus::Error(_) => (
             "ERROR",
             Style::default()
@@ -637,180 +2008,197 @@ fn kv_span(label: &str, value: String, value_color: Color) -> Span<'static> {
     )
 }
 
-fn chain_tip_sync_status(host: &SignetMetrics, metrics: &SignetMetrics) -> ChainTipSyncStatus {
-    let host_tip = host.chain_height();
-    let rollup_tip = metrics.chain_height();
-
-    match (host_tip, rollup_tip) {
-        (Some(host_height), Some(rollup_height)) if host_height == rollup_height => {
-            ChainTipSyncStatus::Synced
-        }
-        (Some(host_height), Some(rollup_height)) => {
-            let (direction, diff) = if rollup_height < host_height {
-                ("behind", host_height - rollup_height)
-            } else {
-                ("ahead", rollup_height - host_height)
-            };
-            ChainTipSyncStatus::Diverged { direction, diff }
+/// One compact entry per network: name, host head block, and status, with the shown network highlighted.
+fn network_comparison_line(networks: &[Network], selected: usize) -> Line<'static> {
+    let mut spans = Vec::with_capacity(networks.len() * 4);
+    for (index, network) in networks.iter().enumerate() {
+        let host = network.host_metrics();
+        let (badge, badge_style) = status_badge(&host.connection_status);
+        let name_style = if index == selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        if index > 0 {
+            spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
         }
-        _ => ChainTipSyncStatus::Unknown,
+        spans.push(Span::styled(format!(" {} ", network.name), name_style));
+        spans.push(Span::styled(
+            format!(" #{} ", fmt_count_opt(host.chain_height())),
+            Style::default().fg(Color::Gray),
+        ));
+        spans.push(Span::styled(badge, badge_style));
     }
+    Line::from(spans)
 }
 
-fn chain_tip_comparison_line(
-    host: &SignetMetrics,
-    metrics: &SignetMetrics,
-    sync_status: ChainTipSyncStatus,
-) -> Line<'static> {
-    let host_tip = host.chain_height();
-    let rollup_tip = metrics.chain_height();
-
-    let sync_spans = match sync_status {
-        ChainTipSyncStatus::Synced => vec![
-            Span::styled("Synced ", Style::default().fg(Color::Green)),
-            Span::styled("at tip", Style::default().fg(Color::Green)),
-        ],
-        ChainTipSyncStatus::Diverged { direction, diff } => vec![
-            Span::styled(
-                format!(
-                    "{direction} by {diff} block{}",
-                    if diff == 1 { "" } else { "s" }
-                ),
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-            ),
-            Span::raw("  "),
-            Span::styled(
-                format!("Rollup {}", metric_or_na(rollup_tip)),
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-            ),
-        ],
-        ChainTipSyncStatus::Unknown => vec![Span::styled(
-            "Sync unknown",
-            Style::default().fg(Color::DarkGray),
-        )],
-    };
-
+/// "Compare backup.example head 1,234 (1 behind) · hash ✓ at 1,231": the --compare-rpc
+/// endpoint's head relative to ours and the latest height compared by hash.
+/// Session totals of what the chain's clients moved, and the latest cycle's download.
+fn traffic_line(metrics: &SignetMetrics) -> Line<'static> {
+    let (total, cycle) = (metrics.traffic, metrics.cycle_traffic);
+    let dim = Style::default().fg(Color::DarkGray);
     let mut spans = vec![
-        Span::styled("Host tip ", Style::default().fg(Color::DarkGray)),
-        Span::styled(metric_or_na(host_tip), Style::default().fg(Color::Cyan)),
-        Span::raw("  "),
+        Span::styled("Traffic ↓ ", dim),
+        Span::styled(
+            units::format_bytes(total.received),
+            Style::default().fg(Color::White),
+        ),
     ];
-    spans.extend(sync_spans);
+    if let Some(saved) = total.compression_savings().filter(|saved| *saved > 0.0) {
+        spans.push(Span::styled(
+            format!(
+                " ({} inflated, {:.0}% saved)",
+                units::format_bytes(total.decoded),
+                saved * 100.0
+            ),
+            Style::default().fg(Color::Green),
+        ));
+    }
+    spans.extend([
+        Span::styled("  ↑ ", dim),
+        Span::styled(
+            units::format_bytes(total.sent),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(
+            format!(
+                "  {} responses · last cycle ↓ {} in {}",
+                units::format_count(total.requests),
+                units::format_bytes(cycle.received),
+                cycle.requests
+            ),
+            dim,
+        ),
+    ]);
     Line::from(spans)
 }
 
-fn txpool_summary_lines(txpool: &TxPoolMetrics, width: usize) -> Vec<Line<'static>> {
-    let health_style = if txpool.healthy {
-        Style::default()
-            .fg(Color::Black)
-            .bg(Color::Green)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-            .fg(Color::White)
-            .bg(Color::Red)
-            .add_modifier(Modifier::BOLD)
+/// "Memory blocks 1.20 MB · series 96.0 kB · … = 1.31 MB  session 3.40 MB of 64.00 MB
+/// (alerts 2.1 kB)": this chain's buffers, then every chain's plus the alert history
+/// against --max-memory-mb.
+fn memory_line(metrics: &SignetMetrics, report: Option<&MemoryReport>) -> Line<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let bright = Style::default().fg(Color::White);
+    let usage = retention::chain_usage(metrics);
+    let mut spans = vec![Span::styled("Memory ", dim)];
+    for (i, (store, usage)) in usage
+        .iter()
+        .filter(|(_, usage)| usage.entries > 0)
+        .enumerate()
+    {
+        if i > 0 {
+            spans.push(Span::styled(" · ", dim));
+        }
+        spans.push(Span::styled(format!("{} ", store.label()), dim));
+        spans.push(Span::styled(
+            units::format_bytes(usage.bytes as u64),
+            bright,
+        ));
+    }
+    let chain_total: usize = usage.iter().map(|(_, usage)| usage.bytes).sum();
+    spans.push(Span::styled(
+        format!(" = {}", units::format_bytes(chain_total as u64)),
+        bright,
+    ));
+    let Some(report) = report else {
+        return Line::from(spans);
     };
-
-    let mut lines = vec![
-        Line::from(vec![
-            Span::styled(
-                format!(
-                    " {} ",
-                    if txpool.healthy {
-                        "HEALTHY"
-                    } else {
-                        "DEGRADED"
-                    }
-                ),
-                health_style,
-            ),
-            Span::raw(" "),
-            Span::styled(
-                trim_middle(&txpool.base_url, width.saturating_sub(20).max(22)),
-                Style::default().fg(Color::Gray),
-            ),
-        ]),
-        Line::from(vec![
-            kv_span("Tx", metric_or_na(txpool.transactions_cache), Color::Green),
-            Span::raw("  "),
-            kv_span(
-                "Bundles",
-                metric_or_na(txpool.bundles_cache),
-                Color::Magenta,
-            ),
-            Span::raw("  "),
-            kv_span(
-                "Orders",
-                metric_or_na(txpool.signed_orders_cache),
-                Color::Blue,
-            ),
-            Span::raw("  "),
-            kv_span(
-                "Updated",
-                relative_age(txpool.last_updated.elapsed()),
-                Color::Yellow,
+    let over = report.budget.is_some_and(|budget| report.total > budget);
+    spans.push(Span::styled(
+        format!(
+            "  session {}{} (alerts {})",
+            units::format_bytes(report.total as u64),
+            report.budget.map_or(String::new(), |budget| format!(
+                " of {}",
+                units::format_bytes(budget as u64)
+            )),
+            units::format_bytes(report.alerts.bytes as u64)
+        ),
+        Style::default().fg(if over { Color::Yellow } else { Color::DarkGray }),
+    ));
+    if let Some(last) = report.last_trim {
+        spans.push(Span::styled(
+            format!(
+                " · trimmed {}× (last {} ago)",
+                report.trims,
+                relative_age(last.elapsed())
             ),
-        ]),
-    ];
-
-    if let Some(error) = &txpool.error {
-        lines.push(Line::from(vec![Span::styled(
-            trim_middle(error, width.saturating_sub(2)),
-            Style::default().fg(Color::Red),
-        )]));
+            dim,
+        ));
     }
-
-    lines
+    Line::from(spans)
 }
 
-fn tx_row(tx: &TxPoolTx) -> Row<'static> {
-    let route = format!(
-        "{} -> {}",
-        short_addr(&tx.from),
-        tx.to
-            .as_ref()
-            .map(short_addr)
-            .unwrap_or_else(|| "--".to_string())
+/// A titled sparkline of `series` over the zoomed and panned window, one bar per
+/// column, scaled between the window's low and high so small moves stay visible.
+fn render_chart(
+    frame: &mut Frame,
+    area: Rect,
+    chart: &ChartView,
+    title: &str,
+    series: Option<&Series>,
+    color: Color,
+    fmt: impl Fn(f64) -> String,
+) {
+    let columns = series
+        .map(|series| chart.columns(series, unix_now(), area.width as usize))
+        .unwrap_or_default();
+    let values = columns.iter().flatten().copied();
+    let range = values.clone().reduce(f64::min).zip(values.reduce(f64::max));
+    let mut spans = vec![Span::styled(
+        format!("{} · {}", title, chart.label()),
+        Style::default().fg(Color::DarkGray),
+    )];
+    if let Some((low, high)) = range {
+        spans.push(Span::styled(
+            format!("  {}–{}", fmt(low), fmt(high)),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(area);
+    frame.render_widget(Paragraph::new(Line::from(spans)), layout[0]);
+
+    // Gaps stay empty, and a flat window sits at half height
+    let bars: Vec<u64> = columns
+        .iter()
+        .map(|value| match (value, range) {
+            (Some(value), Some((low, high))) if high > low => {
+                1 + ((value - low) / (high - low) * 99.0).round() as u64
+            }
+            (Some(_), _) => 50,
+            (None, _) => 0,
+        })
+        .collect();
+    frame.render_widget(
+        Sparkline::default()
+            .data(&bars)
+            .style(Style::default().fg(color))
+            .max(100),
+        layout[1],
     );
+}
 
-    Row::new(vec![
-        Cell::from(short_hash(&tx.hash)).style(Style::default().fg(Color::Cyan)),
-        Cell::from(trim_middle(&route, 26)).style(Style::default().fg(Color::White)),
-        Cell::from(fmt_eth_short(&tx.value)).style(Style::default().fg(Color::Yellow)),
-        Cell::from(fmt_tx_fee_gwei(tx)).style(Style::default().fg(Color::Magenta)),
-        Cell::from(
-            tx.gas_limit
-                .map(|value| value.to_string())
-                .unwrap_or_else(|| "--".to_string()),
-        )
-        .style(Style::default().fg(Color::Gray)),
-        Cell::from(tx.nonce.to_string()).style(Style::default().fg(Color::Gray)),
-        Cell::from(
-            tx.tx_type
-                .map(|value| value.to_string())
-                .unwrap_or_else(|| "--".to_string()),
-        )
-        .style(Style::default().fg(Color::DarkGray)),
-    ])
+/// Render values as a single-line block-character sparkline scaled to their max.
+fn inline_sparkline(values: &[u64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    values
+        .iter()
+        .map(|value| BARS[((value * 7) / max) as usize])
+        .collect()
 }
 
-fn sparkline_points(metrics: &SignetMetrics) -> Vec<u64> {
-    metrics
-        .fee_history
-        .as_ref()
-        .map(|history| {
-            history
-                .base_fees
-                .iter()
-                .rev()
-                .take(24)
-                .rev()
-                .map(|value| (*value / 1_000_000_000).max(1) as u64)
-                .collect::<Vec<_>>()
-        })
-        .filter(|points| !points.is_empty())
-        .unwrap_or_else(|| vec![0])
+fn fmt_count_opt(value: Option<u64>) -> String {
+    value
+        .map(units::format_count)
+        .unwrap_or_else(|| "N/A".to_string())
 }
 
 fn metric_or_na<T: std::fmt::Display>(value: Option<T>) -> String {
@@ -819,71 +2207,112 @@ fn metric_or_na<T: std::fmt::Display>(value: Option<T>) -> String {
         .unwrap_or_else(|| "N/A".to_string())
 }
 
-fn fee_style(value: Option<u128>) -> Color {
-    match value.map(|wei| (wei as f64) / 1_000_000_000.0) {
-        Some(gwei) if gwei >= GAS_ALERT_HIGH_GWEI => Color::Red,
-        Some(gwei) if gwei >= GAS_ALERT_HIGH_GWEI * 0.5 => Color::Yellow,
-        Some(_) => Color::Green,
-        None => Color::DarkGray,
-    }
-}
-
-fn fmt_fee_tier(tier: Option<&SuggestedFeeTier>) -> String {
-    match tier {
-        Some(value) if value.max_fee_per_gas > 0 && value.max_priority_fee_per_gas > 0 => format!(
-            "{}/{}",
-            fmt_gwei_opt(Some(value.max_fee_per_gas)),
-            fmt_gwei_opt(Some(value.max_priority_fee_per_gas))
-        ),
-        _ => "N/A".to_string(),
-    }
+fn gwei_to_wei(gwei: f64) -> u128 {
+    (gwei * WEI_PER_GWEI as f64) as u128
 }
 
 fn fmt_gwei_opt(wei: Option<u128>) -> String {
     match wei {
-        Some(value) => {
-            let gwei = (value as f64) / 1_000_000_000.0;
-            if gwei >= 100.0 {
-                format!("{:.0}g", gwei)
-            } else {
-                format!("{:.1}g", gwei)
-            }
-        }
+        Some(value) => format!("{}g", units::format_gwei_auto(value)),
         None => "N/A".to_string(),
     }
 }
 
-fn fmt_eth_short(value: &U256) -> String {
-    let wei = value.to::<u128>();
-    let eth = (wei as f64) / 1_000_000_000_000_000_000.0;
-    if eth >= 1.0 {
-        format!("{:.3}", eth)
-    } else {
-        format!("{:.5}", eth)
+/// "  vitalik.eth  #21,000,123  -1.5 ETH → 8.2 ETH  nonce 4→5  3m ago". The block is a
+/// range when the readings either side of the change were more than one block apart.
+fn account_change_line(change: &AccountChange, name: &str) -> Line<'static> {
+    let gray = Style::default().fg(Color::DarkGray);
+    let mut spans = vec![
+        Span::styled(
+            format!("  {:<20} ", name),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(
+            match change.exact_block() {
+                Some(block) => format!("#{}  ", units::format_count(block)),
+                None => format!(
+                    "#{}–{}  ",
+                    units::format_count(change.since + 1),
+                    units::format_count(change.block)
+                ),
+            },
+            gray,
+        ),
+    ];
+    if let Some((before, after)) = change.balance {
+        let (sign, delta, color) = if after >= before {
+            ("+", after - before, Color::Green)
+        } else {
+            ("-", before - after, Color::Red)
+        };
+        spans.push(Span::styled(
+            format!("{}{} ETH", sign, fmt_eth_short(&delta)),
+            Style::default().fg(color),
+        ));
+        spans.push(Span::styled(
+            format!(" → {} ETH  ", fmt_eth_short(&after)),
+            gray,
+        ));
+    }
+    if let Some((before, after)) = change.nonce {
+        spans.push(Span::styled(
+            format!("nonce {}→{}  ", before, after),
+            Style::default().fg(Color::Yellow),
+        ));
     }
+    spans.push(Span::styled(
+        format!("{} ago", relative_age_from_ts(change.at)),
+        gray,
+    ));
+    Line::from(spans)
 }
 
-fn fmt_tx_fee_gwei(tx: &TxPoolTx) -> String {
-    if let Some(max_fee) = tx.max_fee_per_gas {
-        let priority = tx.max_priority_fee_per_gas.unwrap_or(0);
-        format!(
-            "{}/{}",
-            fmt_gwei_opt(Some(max_fee)),
-            fmt_gwei_opt(Some(priority))
-        )
-    } else if let Some(gas_price) = tx.gas_price {
-        fmt_gwei_opt(Some(gas_price))
-    } else {
-        "N/A".to_string()
-    }
+/// "  vitalik.eth  #21,000,123  ← 1.5 ETH from 0x1234…abcd  tx 0xabcd…1234  5m ago".
+fn address_tx_line(tx: &AddressTx, book: &AddressBook) -> Line<'static> {
+    let gray = Style::default().fg(Color::DarkGray);
+    let (arrow, color) = match tx.direction {
+        TxDirection::In => ("←", Color::Green),
+        TxDirection::Out => ("→", Color::Red),
+        TxDirection::ToSelf => ("↺", Color::Gray),
+    };
+    let counterparty = match (tx.direction, tx.counterparty) {
+        (TxDirection::ToSelf, _) => "to itself".to_string(),
+        (_, None) => "creating a contract".to_string(),
+        (TxDirection::In, Some(from)) => format!("from {}", book.name(from)),
+        (TxDirection::Out, Some(to)) => format!("to {}", book.name(to)),
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("  {:<20} ", book.name(tx.address)),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(format!("#{}  ", units::format_count(tx.block)), gray),
+        Span::styled(
+            format!("{} {} ETH ", arrow, fmt_eth_short(&tx.value)),
+            Style::default().fg(color),
+        ),
+        Span::styled(
+            format!("{}  ", counterparty),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(
+            format!(
+                "tx {}  {} ago",
+                short_hash(&tx.hash),
+                relative_age_from_ts(tx.at)
+            ),
+            gray,
+        ),
+    ])
 }
 
-fn short_hash(hash: &B256) -> String {
-    trim_middle(&format!("{:#x}", hash), 12)
+fn fmt_eth_short(value: &U256) -> String {
+    let wei = value.saturating_to::<u128>();
+    units::format_eth(wei, if wei >= WEI_PER_ETH { 3 } else { 5 })
 }
 
-fn short_addr(address: &Address) -> String {
-    trim_middle(&format!("{:#x}", address), 14)
+fn short_hash(hash: &B256) -> String {
+    trim_middle(&format!("{:#x}", hash), 12)
 }
 
 fn trim_middle(value: &str, max: usize) -> String {
@@ -896,10 +2325,6 @@ fn trim_middle(value: &str, max: usize) -> String {
     format!("{}..{}", &value[..left], &value[value.len() - right..])
 }
 
-fn block_delay(metrics: &SignetMetrics) -> Option<u64> {
-    metrics.latest_block_timestamp.map(seconds_since)
-}
-
 fn block_age(metrics: &SignetMetrics) -> String {
     metrics
         .latest_block_timestamp
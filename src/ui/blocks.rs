@@ -0,0 +1,667 @@
+//! The block tape: recent blocks, newest first, with the window's builders, burn, tips,
+//! receipts, and trace summaries above them
+use super::widget::MetricsWidget;
+use super::{
+    BlockQuery, ChainView, fmt_gwei_opt, inline_sparkline, panel_accent, relative_age_from_ts,
+    shell_block, trim_middle,
+};
+use crate::aa::AaActivity;
+use crate::address_book::AddressBook;
+use crate::config::BLOCK_SIZE_ALERT_BLOCKS;
+use crate::data::{BlockInfo, PendingBlock, SignetMetrics};
+use crate::network::ChainRole;
+use crate::trace::BlockTrace;
+use crate::tx_types::{TxType, TxTypeMix};
+use crate::units::{self, WEI_PER_ETH, WEI_PER_GWEI};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Cell, Paragraph, Row, Table, TableState},
+};
+
+/// One chain's block history. Blocks matching the `/` search are highlighted and the
+/// selected block, if it is on this chain, is shown reversed.
+pub struct BlocksWidget<'a> {
+    chain: &'a ChainView<'a>,
+    block_search: Option<&'a BlockQuery>,
+    selected_block: Option<(ChainRole, u64)>,
+    address_book: &'a AddressBook,
+}
+
+impl<'a> BlocksWidget<'a> {
+    pub fn new(
+        chain: &'a ChainView<'a>,
+        block_search: Option<&'a BlockQuery>,
+        selected_block: Option<(ChainRole, u64)>,
+        address_book: &'a AddressBook,
+    ) -> Self {
+        Self {
+            chain,
+            block_search,
+            selected_block,
+            address_book,
+        }
+    }
+}
+
+impl MetricsWidget for BlocksWidget<'_> {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let chain = self.chain;
+        let (metrics, label) = (chain.metrics, chain.label);
+        let accent = panel_accent(label);
+        let title = match self.block_search {
+            Some(query) => {
+                let matches = metrics
+                    .block_history
+                    .iter()
+                    .filter(|block| query.matches(block))
+                    .count();
+                format!(
+                    "{} Block Tape · /{} · {} match(es)",
+                    label,
+                    query.raw(),
+                    matches
+                )
+            }
+            None => format!("{} Block Tape", label),
+        };
+        let title = match (metrics.reorg_count, metrics.last_reorg) {
+            (0, _) | (_, None) => title,
+            (count, Some(last)) => format!(
+                "{} · {} reorg(s), last {} deep {} ago",
+                title,
+                count,
+                last.depth,
+                relative_age_from_ts(last.at)
+            ),
+        };
+        let block = shell_block(title, accent);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if inner.height < 3 {
+            return;
+        }
+
+        if metrics.block_history.is_empty() {
+            frame.render_widget(
+                Paragraph::new("(no blocks yet)").style(Style::default().fg(Color::DarkGray)),
+                inner,
+            );
+            return;
+        }
+
+        let mut summary = vec![
+            builder_distribution_line(&metrics.builder_distribution, inner.width as usize),
+            burn_line(metrics, inner.width as usize),
+            tips_line(metrics),
+            blobs_line(metrics),
+        ];
+        // Receipts of the selected block, else the newest block that has them
+        let selected_number = self
+            .selected_block
+            .filter(|(role, _)| *role == chain.role)
+            .map(|(_, number)| number);
+        let with_receipts = metrics
+            .block_history
+            .iter()
+            .filter(|block| block.receipts.is_some());
+        if let Some(block) = with_receipts
+            .clone()
+            .find(|block| Some(block.number) == selected_number)
+            .or_else(|| with_receipts.clone().next())
+        {
+            summary.push(receipts_line(block));
+        }
+        if let Some(mix) = &metrics.tx_types {
+            let gas_by_type = metrics
+                .block_history
+                .iter()
+                .find(|block| block.number == mix.number)
+                .and_then(|block| block.receipts.as_ref())
+                .map(|receipts| receipts.gas_by_type.as_slice());
+            summary.push(tx_types_line(mix, gas_by_type));
+        }
+        if !metrics.aa.blocks.is_empty() {
+            summary.push(aa_line(&metrics.aa));
+        }
+        if let Some(line) = block_size_line(metrics) {
+            summary.push(line);
+        }
+        if let Some(trace) = &metrics.block_trace {
+            summary.extend(trace_lines(trace, self.address_book));
+        }
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(summary.len() as u16), Constraint::Min(2)])
+            .split(inner);
+        frame.render_widget(Paragraph::new(summary), layout[0]);
+
+        let header = Row::new(vec![
+            "blk", "age", "tx", "gas", "base", "blobs", "tips", "builder", "hash",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        let tape = metrics.block_history.tape();
+        let ghost = metrics.pending_block.as_ref().map(pending_block_row);
+        let ghost_rows = usize::from(ghost.is_some());
+        let rows = ghost
+            .into_iter()
+            .chain(tape.iter().enumerate().map(|(index, (block, orphaned))| {
+                let row_style = match self.block_search {
+                    _ if *orphaned => Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::CROSSED_OUT),
+                    Some(query) if query.matches(block) => Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                    Some(_) => Style::default().fg(Color::DarkGray),
+                    None if index == 0 => Style::default().fg(accent).add_modifier(Modifier::BOLD),
+                    None => Style::default().fg(Color::Gray),
+                };
+                let gas_ratio = if block.gas_limit > 0 {
+                    (block.gas_used as f64 / block.gas_limit as f64) * 100.0
+                } else {
+                    0.0
+                };
+                let base_fee = block
+                    .base_fee_per_gas
+                    .map(|value| fmt_gwei_opt(Some(value)))
+                    .unwrap_or_else(|| "--".to_string());
+
+                Row::new(vec![
+                    Cell::from(format!("#{}", units::format_count(block.number))),
+                    Cell::from(relative_age_from_ts(block.timestamp)),
+                    Cell::from(units::format_count(block.tx_count as u64)),
+                    Cell::from(format!("{:.0}%", gas_ratio)),
+                    Cell::from(base_fee),
+                    Cell::from(
+                        block
+                            .blob_count()
+                            .map(|count| count.to_string())
+                            .unwrap_or_else(|| "--".to_string()),
+                    ),
+                    Cell::from(
+                        block
+                            .tip_revenue_wei
+                            .map(fmt_eth_amount)
+                            .unwrap_or_else(|| "--".to_string()),
+                    ),
+                    Cell::from(trim_middle(&block.builder, 16)),
+                    Cell::from(trim_middle(&block.hash, 14)),
+                ])
+                .style(row_style)
+            }))
+            .collect::<Vec<_>>();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(11),
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Length(7),
+                Constraint::Length(10),
+                Constraint::Length(5),
+                Constraint::Length(7),
+                Constraint::Length(16),
+                Constraint::Min(12),
+            ],
+        )
+        .header(header)
+        .column_spacing(1)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        // The selected block scrolls into view and is shown reversed
+        let selected = self
+            .selected_block
+            .filter(|(role, _)| *role == chain.role)
+            .and_then(|(_, number)| {
+                tape.iter()
+                    .position(|(block, orphaned)| !orphaned && block.number == number)
+                    .map(|index| index + ghost_rows)
+            });
+        let mut state = TableState::default().with_selected(selected);
+        frame.render_stateful_widget(table, layout[1], &mut state);
+    }
+
+    /// The tape takes whatever height the other panels leave.
+    fn preferred_height(&self, _column: Rect) -> Option<u16> {
+        None
+    }
+}
+
+/// "Builders beaverbuild 9 · titan 6 · …", trimmed to fit `width`.
+fn builder_distribution_line(distribution: &[(String, usize)], width: usize) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        "Builders ",
+        Style::default().fg(Color::DarkGray),
+    )];
+    if distribution.is_empty() {
+        spans.push(Span::styled("--", Style::default().fg(Color::DarkGray)));
+        return Line::from(spans);
+    }
+
+    let mut used = "Builders ".len();
+    for (index, (label, count)) in distribution.iter().enumerate() {
+        let entry = format!("{} {}", trim_middle(label, 16), count);
+        let separator = if index == 0 { "" } else { " · " };
+        if used + separator.len() + entry.chars().count() > width {
+            spans.push(Span::styled(" …", Style::default().fg(Color::DarkGray)));
+            break;
+        }
+        used += separator.len() + entry.chars().count();
+        spans.push(Span::styled(
+            separator,
+            Style::default().fg(Color::DarkGray),
+        ));
+        spans.push(Span::styled(entry, Style::default().fg(Color::White)));
+    }
+    Line::from(spans)
+}
+
+/// "Burn 1.23 ETH/h  window 0.0412  session 0.1180 ▂▃▅▂", sparkline per block, oldest first.
+fn burn_line(metrics: &SignetMetrics, width: usize) -> Line<'static> {
+    let burn = &metrics.burn;
+    let rate = burn
+        .rate_wei_per_hour
+        .map(|rate| format!("{} ETH/h", fmt_eth_amount(rate as u128)))
+        .unwrap_or_else(|| "--".to_string());
+    let text = format!(
+        "Burn {}  window {}  session {}  ",
+        rate,
+        fmt_eth_amount(burn.window_wei),
+        fmt_eth_amount(burn.session_wei),
+    );
+    let per_block: Vec<u64> = metrics
+        .block_history
+        .iter()
+        .take(width.saturating_sub(text.chars().count()))
+        .rev()
+        .map(|block| (block.burned_wei().unwrap_or(0) / WEI_PER_GWEI) as u64)
+        .collect();
+
+    Line::from(vec![
+        Span::styled("Burn ", Style::default().fg(Color::DarkGray)),
+        Span::styled(rate, Style::default().fg(Color::LightRed)),
+        Span::styled("  window ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            fmt_eth_amount(burn.window_wei),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled("  session ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            fmt_eth_amount(burn.session_wei),
+            Style::default().fg(Color::White),
+        ),
+        Span::raw("  "),
+        Span::styled(
+            inline_sparkline(&per_block),
+            Style::default().fg(Color::LightRed),
+        ),
+    ])
+}
+
+/// "Tips avg 0.0123 ETH/blk over 24 blocks  last 0.0150", estimated from fee-history rewards.
+fn tips_line(metrics: &SignetMetrics) -> Line<'static> {
+    let label = Span::styled("Tips ", Style::default().fg(Color::DarkGray));
+    let Some((avg, blocks)) = metrics.avg_tip_revenue() else {
+        return Line::from(vec![
+            label,
+            Span::styled("--", Style::default().fg(Color::DarkGray)),
+        ]);
+    };
+    let last = metrics
+        .block_history
+        .front()
+        .and_then(|block| block.tip_revenue_wei);
+    Line::from(vec![
+        label,
+        Span::styled("avg ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!("{} ETH/blk", fmt_eth_amount(avg)),
+            Style::default().fg(Color::Magenta),
+        ),
+        Span::styled(
+            format!(" over {} blocks", blocks),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled("  last ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            last.map(fmt_eth_amount).unwrap_or_else(|| "--".to_string()),
+            Style::default().fg(Color::White),
+        ),
+    ])
+}
+
+/// Dimmed preview row for the block the node is building, above the landed blocks.
+fn pending_block_row(pending: &PendingBlock) -> Row<'static> {
+    let gas_ratio = if pending.gas_limit > 0 {
+        pending.gas_used as f64 * 100.0 / pending.gas_limit as f64
+    } else {
+        0.0
+    };
+    Row::new(vec![
+        Cell::from(format!("#{}", units::format_count(pending.number))),
+        Cell::from("pending"),
+        Cell::from(units::format_count(pending.tx_count as u64)),
+        Cell::from(format!("{:.0}%", gas_ratio)),
+        Cell::from(
+            pending
+                .base_fee_per_gas
+                .map(|value| fmt_gwei_opt(Some(value)))
+                .unwrap_or_else(|| "--".to_string()),
+        ),
+        Cell::from("--"),
+        Cell::from("--"),
+        Cell::from("--"),
+        Cell::from("(pending)"),
+    ])
+    .style(
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::ITALIC),
+    )
+}
+
+/// Success, revert, and deployment counts from one block's receipts.
+fn receipts_line(block: &BlockInfo) -> Line<'static> {
+    let summary = block.receipts.clone().unwrap_or_default();
+    let reverted_style = if summary.reverted > 0 {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("Receipts #{} ", units::format_count(block.number)),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled("ok ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            summary.succeeded.to_string(),
+            Style::default().fg(Color::Green),
+        ),
+        Span::styled("  reverted ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            match summary.revert_pct() {
+                Some(pct) => format!("{} ({:.1}%)", summary.reverted, pct),
+                None => "0".to_string(),
+            },
+            reverted_style,
+        ),
+        Span::styled("  created ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            summary.contract_creations.to_string(),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled("  avg price ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            fmt_gwei_opt(summary.avg_effective_gas_price),
+            Style::default().fg(Color::Cyan),
+        ),
+    ])
+}
+
+/// Transactions per type in the newest block, with each type's share of the gas used when
+/// the block's receipts were fetched.
+fn tx_types_line(mix: &TxTypeMix, gas_by_type: Option<&[(TxType, u64)]>) -> Line<'static> {
+    let total_gas: u64 = gas_by_type
+        .unwrap_or_default()
+        .iter()
+        .map(|(_, gas)| gas)
+        .sum();
+    let mut spans = vec![Span::styled(
+        format!("Tx types #{} ", units::format_count(mix.number)),
+        Style::default().fg(Color::DarkGray),
+    )];
+    if mix.counts.is_empty() {
+        spans.push(Span::styled(
+            "no transactions",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    for (kind, count) in &mix.counts {
+        let color = match kind {
+            TxType::Legacy => Color::Yellow,
+            TxType::Blob => Color::Magenta,
+            TxType::SetCode => Color::Cyan,
+            _ => Color::White,
+        };
+        spans.push(Span::styled(
+            format!("{} ", kind.label()),
+            Style::default().fg(Color::DarkGray),
+        ));
+        spans.push(Span::styled(count.to_string(), Style::default().fg(color)));
+        let gas = gas_by_type
+            .unwrap_or_default()
+            .iter()
+            .find(|(gas_kind, _)| gas_kind == kind)
+            .map_or(0, |(_, gas)| *gas);
+        spans.push(Span::styled(
+            if total_gas > 0 {
+                format!(" ({:.0}% gas)  ", gas as f64 * 100.0 / total_gas as f64)
+            } else {
+                "  ".to_string()
+            },
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if total_gas == 0 && mix.total() > 0 {
+        spans.push(Span::styled(
+            "(gas share needs --receipts)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// EIP-7702 and ERC-4337 use over the recent blocks counted, and their share of all
+/// transactions.
+fn aa_line(activity: &AaActivity) -> Line<'static> {
+    let totals = activity.totals();
+    let label = |text: String| Span::styled(text, Style::default().fg(Color::DarkGray));
+    let mut spans = vec![label(format!(
+        "AA activity {} blocks ",
+        activity.blocks.len()
+    ))];
+    if !totals.is_aa() {
+        spans.push(label("none".to_string()));
+        return Line::from(spans);
+    }
+    spans.extend([
+        label("7702 ".to_string()),
+        Span::styled(
+            units::format_count(totals.set_code as u64),
+            Style::default().fg(Color::Cyan),
+        ),
+        label(format!(
+            " ({} auths)  4337 ",
+            units::format_count(totals.authorizations as u64)
+        )),
+        Span::styled(
+            units::format_count(totals.entry_point as u64),
+            Style::default().fg(Color::Magenta),
+        ),
+        label(format!(
+            " calls ({} user ops)  ",
+            units::format_count(totals.user_ops as u64)
+        )),
+    ]);
+    if totals.transactions > 0 {
+        let share =
+            (totals.set_code + totals.entry_point) as f64 * 100.0 / totals.transactions as f64;
+        spans.push(Span::styled(
+            format!("{:.1}% of txs", share),
+            Style::default().fg(Color::White),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// The newest block's size and calldata, the window's average size, and how close the
+/// newest block came to `--block-size-limit`: yellow near it, red once the alert's run
+/// of near-limit blocks is reached.
+fn block_size_line(metrics: &SignetMetrics) -> Option<Line<'static>> {
+    let block = metrics.block_history.front()?;
+    let size = block.size?;
+    let sizes: Vec<u64> = metrics
+        .block_history
+        .iter()
+        .filter_map(|block| block.size)
+        .collect();
+    let color = match metrics.block_size_pressure {
+        Some(pressure) if pressure.blocks >= BLOCK_SIZE_ALERT_BLOCKS => Color::Red,
+        Some(_) => Color::Yellow,
+        None => Color::White,
+    };
+    let gray = Style::default().fg(Color::DarkGray);
+    let mut spans = vec![
+        Span::styled(
+            format!("Size #{} ", units::format_count(block.number)),
+            gray,
+        ),
+        Span::styled(units::format_bytes(size), Style::default().fg(color)),
+    ];
+    if let Some(calldata) = block.calldata_bytes {
+        spans.push(Span::styled(
+            format!(
+                "  calldata {} ({:.0}%)",
+                units::format_bytes(calldata),
+                calldata as f64 * 100.0 / size.max(1) as f64
+            ),
+            gray,
+        ));
+    }
+    spans.push(Span::styled(
+        format!(
+            "  avg {} over {} blocks",
+            units::format_bytes(sizes.iter().sum::<u64>() / sizes.len() as u64),
+            sizes.len()
+        ),
+        gray,
+    ));
+    if let Some(limit) = metrics.block_size_limit {
+        spans.push(Span::styled(
+            format!("  {:.0}% of limit", size as f64 * 100.0 / limit as f64),
+            Style::default().fg(color),
+        ));
+    }
+    Some(Line::from(spans))
+}
+
+/// Call counts and reverts from the newest block's trace, then its heaviest internal
+/// call targets.
+fn trace_lines(trace: &BlockTrace, address_book: &AddressBook) -> [Line<'static>; 2] {
+    let reverted = trace.reverted_txs + trace.reverted_internal;
+    let by_type = trace
+        .top_level
+        .iter()
+        .map(|(kind, count)| format!("{} {}", kind, count))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut targets = vec![Span::styled(
+        "Heaviest calls ",
+        Style::default().fg(Color::DarkGray),
+    )];
+    if trace.targets.is_empty() {
+        targets.push(Span::styled("--", Style::default().fg(Color::DarkGray)));
+    }
+    for target in &trace.targets {
+        targets.push(Span::styled(
+            address_book.name(target.address),
+            Style::default().fg(Color::Cyan),
+        ));
+        targets.push(Span::styled(
+            format!(" {:.2}M ×{}  ", target.gas_used as f64 / 1e6, target.calls),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    [
+        Line::from(vec![
+            Span::styled(
+                format!("Trace #{} ", units::format_count(trace.number)),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!("{} txs", trace.transactions()),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                if by_type.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", by_type)
+                },
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled("  internal ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                units::format_count(trace.internal_calls as u64),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled("  reverted ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!(
+                    "{} txs, {} calls",
+                    trace.reverted_txs, trace.reverted_internal
+                ),
+                Style::default().fg(if reverted > 0 {
+                    Color::Red
+                } else {
+                    Color::White
+                }),
+            ),
+        ]),
+        Line::from(targets),
+    ]
+}
+
+/// Blobs and withdrawals per block over the history, from the block headers.
+fn blobs_line(metrics: &SignetMetrics) -> Line<'static> {
+    let avg = |average: Option<(f64, usize)>| {
+        average
+            .map(|(avg, _)| format!("{:.1}/blk", avg))
+            .unwrap_or_else(|| "--".to_string())
+    };
+    let latest = metrics.block_history.front();
+    Line::from(vec![
+        Span::styled("Blobs ", Style::default().fg(Color::DarkGray)),
+        Span::styled("avg ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            avg(metrics.avg_per_block(BlockInfo::blob_count)),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled("  excess ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            latest
+                .and_then(|block| block.excess_blob_gas)
+                .map(|gas| gas.to_string())
+                .unwrap_or_else(|| "--".to_string()),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled("  Withdrawals ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            avg(metrics.avg_per_block(|block| block.withdrawals_count.map(|n| n as u64))),
+            Style::default().fg(Color::White),
+        ),
+    ])
+}
+
+/// ETH with precision that keeps small burns readable: 12.3, 1.234, 0.0412.
+fn fmt_eth_amount(wei: u128) -> String {
+    let precision = if wei >= 10 * WEI_PER_ETH {
+        1
+    } else if wei >= WEI_PER_ETH {
+        3
+    } else {
+        4
+    };
+    units::format_eth(wei, precision)
+}
@@ -0,0 +1,689 @@
+//! The connection panel: status, chain tip, feed freshness, and endpoint health of one chain
+use super::widget::MetricsWidget;
+use super::{
+    block_age, fmt_count_opt, inline_sparkline, kv_span, metric_or_na, panel_accent, relative_age,
+    seconds_since, shell_block, short_hash, status_badge, status_chip, trim_middle,
+};
+use crate::availability::{ErrorBudget, Slo};
+use crate::chains::ChainRegistry;
+use crate::compare::EndpointComparison;
+use crate::config::RPC_TIMEOUT_SECS;
+use crate::data::{BlockJitter, NodeInfo, NodeSync, SignetMetrics};
+use crate::diagnose::Diagnosis;
+use crate::forks::{self, Activation, Fork, ForkStatus};
+use crate::rpc_stats::RpcMethod;
+use crate::units;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy)]
+enum ChainTipSyncStatus {
+    Synced,
+    Diverged { direction: &'static str, diff: u64 },
+    Unknown,
+}
+
+/// One chain's connection summary. `host` is set for the rollup, whose tip is compared
+/// against the host's.
+pub struct ConnectionWidget<'a> {
+    metrics: &'a SignetMetrics,
+    label: &'static str,
+    host: Option<&'a SignetMetrics>,
+    chains: &'a ChainRegistry,
+    refresh_interval: u64, // seconds
+}
+
+impl<'a> ConnectionWidget<'a> {
+    pub fn new(
+        metrics: &'a SignetMetrics,
+        label: &'static str,
+        host: Option<&'a SignetMetrics>,
+        chains: &'a ChainRegistry,
+        refresh_interval: u64,
+    ) -> Self {
+        Self {
+            metrics,
+            label,
+            host,
+            chains,
+            refresh_interval,
+        }
+    }
+}
+
+impl MetricsWidget for ConnectionWidget<'_> {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let (metrics, label, host) = (self.metrics, self.label, self.host);
+        let accent = panel_accent(label);
+        let sync_status = host.map(|host_metrics| chain_tip_sync_status(host_metrics, metrics));
+        let feed_accent = match sync_status {
+            Some(ChainTipSyncStatus::Diverged { .. }) => Color::Red,
+            _ => accent,
+        };
+        let (status_text, status_style) = status_badge(&metrics.connection_status);
+        let rpc_width = area.width.saturating_sub(14) as usize;
+        let delay = block_delay(metrics);
+        let delay_style = match delay {
+            Some(value) if value > metrics.block_delay.critical => {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            }
+            Some(value) if value > metrics.block_delay.warning => {
+                Style::default().fg(Color::Yellow)
+            }
+            Some(_) => Style::default().fg(Color::Green),
+            None => Style::default().fg(Color::DarkGray),
+        };
+
+        let mut lines = vec![
+            Line::from(vec![
+                status_chip(label, status_text, status_style),
+                Span::raw(" "),
+                Span::styled(
+                    format!("updated {}", relative_age(metrics.last_updated.elapsed())),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::raw("  "),
+                poll_status(metrics, Duration::from_secs(self.refresh_interval)),
+                Span::raw("  "),
+                Span::styled(
+                    format!("stale after {}", relative_age(metrics.stale_after)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw("  "),
+                Span::styled(
+                    if metrics.poll_backoff > 1 {
+                        format!("backoff x{}", metrics.poll_backoff)
+                    } else {
+                        String::new()
+                    },
+                    Style::default().fg(Color::Magenta),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("RPC ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    trim_middle(&metrics.rpc_url, rpc_width.max(24)),
+                    Style::default().fg(Color::White),
+                ),
+            ]),
+            Line::from(vec![
+                chain_id_span(metrics.chain_id, self.chains),
+                Span::raw("  "),
+                kv_span("Block", fmt_count_opt(metrics.chain_height()), accent),
+                Span::styled(
+                    metrics
+                        .blocks_since_start()
+                        .map(|blocks| format!(" +{} since start", units::format_count(blocks)))
+                        .unwrap_or_default(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw("  "),
+                kv_span("Age", block_age(metrics), Color::Yellow),
+                Span::raw("  "),
+                Span::styled("Delay ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    delay
+                        .map(|value| format!("{}s", value))
+                        .unwrap_or_else(|| "--".to_string()),
+                    delay_style,
+                ),
+                Span::styled(
+                    delay
+                        .and_then(|value| metrics.blocks_expected(value as f64))
+                        .map(|blocks| format!(" ({:.1}× expected)", blocks))
+                        .unwrap_or_default(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+        ];
+
+        if let Some(jitter) = metrics.block_jitter {
+            lines.push(jitter_line(jitter, metrics.jitter_limit));
+        }
+        lines.push(availability_line(metrics));
+        if let Some(slo) = &metrics.slo {
+            let budget = metrics.availability.error_budget(slo, Instant::now());
+            lines.push(error_budget_line(slo, &budget));
+        }
+        lines.extend(metrics.chain_forks().map(|fork| fork_line(fork, metrics)));
+        lines.push(node_line(metrics));
+        if let Some(info) = &metrics.node_info {
+            lines.push(node_info_line(info, rpc_width));
+        }
+        lines.push(latency_line(
+            metrics,
+            area.width.saturating_sub(40) as usize,
+        ));
+
+        if let Some(diagnosis) = &metrics.diagnosis {
+            lines.push(diagnosis_line(diagnosis, rpc_width / 2));
+        }
+        if let Some(comparison) = &metrics.comparison {
+            lines.push(comparison_line(comparison, rpc_width / 2));
+        }
+        if let Some(host_metrics) = host {
+            lines.push(chain_tip_comparison_line(
+                host_metrics,
+                metrics,
+                sync_status.unwrap_or(ChainTipSyncStatus::Unknown),
+            ));
+        }
+
+        let paragraph = Paragraph::new(lines)
+            .block(shell_block(format!("{} Feed", label), feed_accent))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(paragraph, area);
+    }
+
+    /// Nine rows, plus one for each optional line that has something to show.
+    fn preferred_height(&self, _column: Rect) -> Option<u16> {
+        let metrics = self.metrics;
+        Some(
+            9 + metrics.comparison.is_some() as u16
+                + metrics.diagnosis.is_some() as u16
+                + metrics.slo.is_some() as u16
+                + metrics.chain_forks().count() as u16
+                + metrics.node_info.is_some() as u16,
+        )
+    }
+}
+
+fn chain_tip_sync_status(host: &SignetMetrics, metrics: &SignetMetrics) -> ChainTipSyncStatus {
+    let host_tip = host.chain_height();
+    let rollup_tip = metrics.chain_height();
+
+    match (host_tip, rollup_tip) {
+        (Some(host_height), Some(rollup_height)) if host_height == rollup_height => {
+            ChainTipSyncStatus::Synced
+        }
+        (Some(host_height), Some(rollup_height)) => {
+            let (direction, diff) = if rollup_height < host_height {
+                ("behind", host_height - rollup_height)
+            } else {
+                ("ahead", rollup_height - host_height)
+            };
+            ChainTipSyncStatus::Diverged { direction, diff }
+        }
+        _ => ChainTipSyncStatus::Unknown,
+    }
+}
+
+fn chain_tip_comparison_line(
+    host: &SignetMetrics,
+    metrics: &SignetMetrics,
+    sync_status: ChainTipSyncStatus,
+) -> Line<'static> {
+    let host_tip = host.chain_height();
+    let rollup_tip = metrics.chain_height();
+
+    let sync_spans = match sync_status {
+        ChainTipSyncStatus::Synced => vec![
+            Span::styled("Synced ", Style::default().fg(Color::Green)),
+            Span::styled("at tip", Style::default().fg(Color::Green)),
+        ],
+        ChainTipSyncStatus::Diverged { direction, diff } => vec![
+            Span::styled(
+                format!(
+                    "{direction} by {diff} block{}",
+                    if diff == 1 { "" } else { "s" }
+                ),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("Rollup {}", metric_or_na(rollup_tip)),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+        ],
+        ChainTipSyncStatus::Unknown => vec![Span::styled(
+            "Sync unknown",
+            Style::default().fg(Color::DarkGray),
+        )],
+    };
+
+    let mut spans = vec![
+        Span::styled("Host tip ", Style::default().fg(Color::DarkGray)),
+        Span::styled(metric_or_na(host_tip), Style::default().fg(Color::Cyan)),
+        Span::raw("  "),
+    ];
+    spans.extend(sync_spans);
+    if let Some(lag) = &metrics.sequencer_lag {
+        let (text, style) = if lag.is_stalled() {
+            (
+                format!("stalled {}s", lag.since_block_secs),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )
+        } else {
+            ("ok".to_string(), Style::default().fg(Color::Green))
+        };
+        spans.push(Span::styled(
+            "  Sequencer ",
+            Style::default().fg(Color::DarkGray),
+        ));
+        spans.push(Span::styled(text, style));
+        spans.push(Span::styled(
+            format!(" (~{:.0}s blocks)", lag.expected_interval_secs),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// "⠹ collecting 2s" while a cycle is in flight, else "next poll in 4s", counting down from
+/// the last update over the (backed-off) refresh interval.
+fn poll_status(metrics: &SignetMetrics, refresh_interval: Duration) -> Span<'static> {
+    const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+    if let Some(since) = metrics.collecting_since {
+        let elapsed = since.elapsed();
+        let frame = SPINNER[(elapsed.as_millis() / 100) as usize % SPINNER.len()];
+        return Span::styled(
+            format!("{} collecting {}", frame, relative_age(elapsed)),
+            Style::default().fg(Color::Cyan),
+        );
+    }
+    let remaining = (refresh_interval * metrics.poll_backoff)
+        .saturating_sub(metrics.last_updated.elapsed())
+        .as_secs_f64()
+        .ceil() as u64;
+    Span::styled(
+        if remaining > 0 {
+            format!("next poll in {}s", remaining)
+        } else {
+            "poll due".to_string()
+        },
+        Style::default().fg(Color::DarkGray),
+    )
+}
+
+/// `Diag DNS ✓ 4ms · TCP ✗ connection refused`: which layer of the failing connection broke.
+fn diagnosis_line(diagnosis: &Diagnosis, width: usize) -> Line<'static> {
+    let mut spans = vec![Span::styled("Diag ", Style::default().fg(Color::DarkGray))];
+    for (i, (layer, took)) in diagnosis.passed.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" · ", Style::default().fg(Color::DarkGray)));
+        }
+        spans.push(Span::styled(
+            format!("{} ✓ {}ms", layer.label(), took.as_millis()),
+            Style::default().fg(Color::Green),
+        ));
+    }
+    if !diagnosis.passed.is_empty() {
+        spans.push(Span::styled(" · ", Style::default().fg(Color::DarkGray)));
+    }
+    spans.push(match &diagnosis.failed {
+        Some((layer, error)) => Span::styled(
+            format!("{} ✗ {}", layer.label(), trim_middle(error, width.max(48))),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        None => Span::styled(
+            "network OK, failing at HTTP/RPC",
+            Style::default().fg(Color::Yellow),
+        ),
+    });
+    spans.push(Span::styled(
+        format!(" ({} ago)", relative_age(diagnosis.at.elapsed())),
+        Style::default().fg(Color::DarkGray),
+    ));
+    Line::from(spans)
+}
+
+fn comparison_line(comparison: &EndpointComparison, width: usize) -> Line<'static> {
+    let mut spans = vec![
+        Span::styled("Compare ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            trim_middle(&comparison.rpc_url, width.max(16)),
+            Style::default().fg(Color::White),
+        ),
+        Span::raw(" "),
+    ];
+    let lag_style = if comparison.is_lagging() {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::Gray)
+    };
+    spans.push(Span::styled(
+        match (comparison.head, comparison.lag()) {
+            (Some(head), Some(0)) => format!("head {} (level)", units::format_count(head)),
+            (Some(head), Some(lag)) if lag > 0 => {
+                format!("head {} ({} behind)", units::format_count(head), lag)
+            }
+            (Some(head), Some(lag)) => {
+                format!("head {} ({} ahead)", units::format_count(head), -lag)
+            }
+            (Some(head), None) => format!("head {}", units::format_count(head)),
+            (None, _) => "head --".to_string(),
+        },
+        lag_style,
+    ));
+    spans.push(match (&comparison.mismatch, comparison.checked) {
+        (Some(mismatch), _) => Span::styled(
+            format!(" · SPLIT at {}", units::format_count(mismatch.number)),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        (None, Some(checked)) => Span::styled(
+            format!(" · hash ✓ at {}", units::format_count(checked)),
+            Style::default().fg(Color::Green),
+        ),
+        (None, None) => Span::raw(""),
+    });
+    if let Some(error) = &comparison.error {
+        spans.push(Span::styled(
+            format!(" · {}", trim_middle(error, 40)),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// "Node synced · pool 12 pending / 3 queued", then any optional methods the endpoint
+/// doesn't implement.
+/// "Chain Signet Pecorino (14174)", highlighted when no one named the chain ID.
+fn chain_id_span(chain_id: Option<u64>, chains: &ChainRegistry) -> Span<'static> {
+    let Some(chain_id) = chain_id else {
+        return kv_span("Chain", "N/A".to_string(), Color::Cyan);
+    };
+    let style = match chains.name(chain_id) {
+        Some(_) => Style::default().fg(Color::Cyan),
+        None => Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    };
+    Span::styled(format!("Chain {}", chains.label(chain_id)), style)
+}
+
+/// The endpoint's client and genesis hash, "--" for whichever call failed.
+fn node_info_line(info: &NodeInfo, width: usize) -> Line<'static> {
+    Line::from(vec![
+        Span::styled("Client ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            info.client_name()
+                .map(|client| trim_middle(&client, width.saturating_sub(17).max(16)))
+                .unwrap_or_else(|| "--".to_string()),
+            Style::default().fg(Color::White),
+        ),
+        Span::raw("  "),
+        Span::styled("Genesis ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            info.genesis_hash
+                .as_ref()
+                .map(short_hash)
+                .unwrap_or_else(|| "--".to_string()),
+            Style::default().fg(Color::Cyan),
+        ),
+    ])
+}
+
+fn node_line(metrics: &SignetMetrics) -> Line<'static> {
+    let capabilities = &metrics.capabilities;
+    let (sync_text, sync_color) = match metrics.node_sync {
+        Some(NodeSync::Synced) => ("synced".to_string(), Color::Green),
+        Some(NodeSync::Syncing { current, highest }) => {
+            (format!("syncing {}/{}", current, highest), Color::Yellow)
+        }
+        None => ("--".to_string(), Color::DarkGray),
+    };
+    let mut spans = vec![Span::styled("Node ", Style::default().fg(Color::DarkGray))];
+    if capabilities.supports(RpcMethod::Syncing) {
+        spans.push(Span::styled(sync_text, Style::default().fg(sync_color)));
+    }
+    if capabilities.supports(RpcMethod::TxPoolStatus) {
+        spans.push(Span::styled(
+            match metrics.node_txpool {
+                Some((pending, queued)) => {
+                    format!(
+                        " · pool {} pending / {} queued",
+                        units::format_count(pending),
+                        units::format_count(queued)
+                    )
+                }
+                None => " · pool --".to_string(),
+            },
+            Style::default().fg(Color::Gray),
+        ));
+    }
+    let unsupported: Vec<&str> = capabilities
+        .unsupported()
+        .map(|method| method.name())
+        .collect();
+    if !unsupported.is_empty() {
+        spans.push(Span::styled(
+            format!("  unsupported by endpoint: {}", unsupported.join(", ")),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Block interval spread over the history window, yellow once past `--max-block-jitter`.
+fn jitter_line(jitter: BlockJitter, limit: Option<f64>) -> Line<'static> {
+    let over = limit.is_some_and(|limit| jitter.stddev >= limit);
+    Line::from(vec![
+        Span::styled("Jitter ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!("{:.1}s σ", jitter.stddev),
+            Style::default().fg(if over { Color::Yellow } else { Color::Green }),
+        ),
+        Span::styled(
+            format!(
+                "  worst ±{:.1}s  mean {:.1}s over {} intervals",
+                jitter.max_deviation, jitter.mean, jitter.intervals
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            limit
+                .map(|limit| format!("  limit {:.1}s", limit))
+                .unwrap_or_default(),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+}
+
+fn availability_line(metrics: &SignetMetrics) -> Line<'static> {
+    let stats = &metrics.availability;
+    let pct_color = |pct: f64| {
+        if pct >= 99.0 {
+            Color::Green
+        } else if pct >= 95.0 {
+            Color::Yellow
+        } else {
+            Color::Red
+        }
+    };
+    let fmt_pct = |pct: Option<f64>| {
+        pct.map(|value| format!("{:.1}%", value))
+            .unwrap_or_else(|| "--".to_string())
+    };
+    let session = stats.session_pct();
+    let hour = stats
+        .current_hour()
+        .and_then(|bucket| bucket.availability_pct());
+
+    Line::from(vec![
+        Span::styled("Uptime ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            fmt_pct(session),
+            Style::default().fg(session.map(pct_color).unwrap_or(Color::DarkGray)),
+        ),
+        Span::styled(" session", Style::default().fg(Color::DarkGray)),
+        Span::raw("  "),
+        Span::styled(
+            fmt_pct(hour),
+            Style::default().fg(hour.map(pct_color).unwrap_or(Color::DarkGray)),
+        ),
+        Span::styled(" hour", Style::default().fg(Color::DarkGray)),
+        Span::raw("  "),
+        Span::styled(
+            format!(
+                "{} outage{}",
+                stats.outages,
+                if stats.outages == 1 { "" } else { "s" }
+            ),
+            Style::default().fg(if stats.outages == 0 {
+                Color::Gray
+            } else {
+                Color::Yellow
+            }),
+        ),
+        Span::styled(
+            format!(
+                ", longest {}",
+                relative_age(stats.longest_outage_at(Instant::now()))
+            ),
+            Style::default().fg(Color::Gray),
+        ),
+    ])
+}
+
+/// "Fork Prague in ~3d 4h / 21,420 blocks" from the observed block time, then a green
+/// "ACTIVE" badge once the head reaches the activation block or timestamp.
+fn fork_line(fork: &Fork, metrics: &SignetMetrics) -> Line<'static> {
+    let mut spans = vec![
+        Span::styled("Fork ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!("{} ", fork.name),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ];
+    let (Some(height), Some(timestamp)) = (metrics.chain_height(), metrics.latest_block_timestamp)
+    else {
+        spans.push(Span::styled(
+            "waiting for the head",
+            Style::default().fg(Color::DarkGray),
+        ));
+        return Line::from(spans);
+    };
+    match fork.status(height, timestamp, metrics.block_time()) {
+        ForkStatus::Pending { secs, blocks } => {
+            let eta = [
+                secs.map(|secs| format!("~{}", forks::countdown(secs))),
+                blocks.map(|blocks| format!("{} blocks", units::format_count(blocks))),
+            ];
+            spans.push(Span::styled(
+                format!(
+                    "in {}",
+                    eta.into_iter().flatten().collect::<Vec<_>>().join(" / ")
+                ),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        ForkStatus::Active => {
+            spans.push(Span::styled(
+                " ACTIVE ",
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::styled(
+                match fork.activation {
+                    Activation::Block(block) => {
+                        format!(" since #{}", units::format_count(block))
+                    }
+                    Activation::Timestamp(at) => {
+                        format!(" since {} ago", forks::countdown(seconds_since(at) as f64))
+                    }
+                },
+                Style::default().fg(Color::Green),
+            ));
+        }
+    }
+    Line::from(spans)
+}
+
+/// "SLO 99.5% over 1day  budget 5m left of 7m (71%)  ~3h2m to exhaustion at this pace".
+fn error_budget_line(slo: &Slo, budget: &ErrorBudget) -> Line<'static> {
+    let remaining = budget.remaining_pct();
+    let color = if budget.remaining().is_zero() {
+        Color::Red
+    } else if remaining < 25.0 {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    let mut spans = vec![
+        Span::styled("SLO ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!(
+                "{}% over {}",
+                slo.target,
+                humantime::format_duration(slo.window)
+            ),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::raw("  "),
+    ];
+    if budget.remaining().is_zero() {
+        spans.push(Span::styled(
+            format!(
+                "budget exhausted, {} over",
+                relative_age(budget.spent.saturating_sub(budget.allowed))
+            ),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ));
+        return Line::from(spans);
+    }
+    spans.push(Span::styled(
+        format!(
+            "budget {} left of {} ({:.0}%)",
+            relative_age(budget.remaining()),
+            relative_age(budget.allowed),
+            remaining
+        ),
+        Style::default().fg(color),
+    ));
+    if let Some(exhausted_in) = budget.exhausted_in {
+        spans.push(Span::styled(
+            format!(
+                "  ~{} to exhaustion at this pace",
+                relative_age(exhausted_in)
+            ),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn latency_line(metrics: &SignetMetrics, chart_width: usize) -> Line<'static> {
+    let last = metrics.last_latency();
+    let p95 = metrics.latency_percentile(95.0);
+    let latency_color = |value: Option<Duration>| match value {
+        Some(d) if d.as_secs() >= RPC_TIMEOUT_SECS => Color::Red,
+        Some(d) if d.as_millis() >= 1_000 => Color::Yellow,
+        Some(_) => Color::Green,
+        None => Color::DarkGray,
+    };
+    let samples: Vec<u64> = metrics
+        .recent_latency(chart_width.clamp(8, 60))
+        .map(|d| d.as_millis() as u64)
+        .collect();
+
+    Line::from(vec![
+        Span::styled("Latency ", Style::default().fg(Color::DarkGray)),
+        Span::styled(fmt_latency(last), Style::default().fg(latency_color(last))),
+        Span::styled("  p95 ", Style::default().fg(Color::DarkGray)),
+        Span::styled(fmt_latency(p95), Style::default().fg(latency_color(p95))),
+        Span::raw("  "),
+        Span::styled(inline_sparkline(&samples), Style::default().fg(Color::Blue)),
+    ])
+}
+
+fn fmt_latency(value: Option<Duration>) -> String {
+    match value {
+        Some(d) if d.as_millis() >= 1_000 => format!("{:.1}s", d.as_secs_f64()),
+        Some(d) => format!("{}ms", d.as_millis()),
+        None => "--".to_string(),
+    }
+}
+
+fn block_delay(metrics: &SignetMetrics) -> Option<u64> {
+    metrics.latest_block_timestamp.map(seconds_since)
+}
@@ -0,0 +1,460 @@
+//! The gas panel: base fee against its thresholds, utilization, block fill, and fee charts
+use super::widget::MetricsWidget;
+use super::{fmt_gwei_opt, gwei_to_wei, kv_span, panel_accent, render_chart, shell_block};
+use crate::chart::ChartView;
+use crate::config::{BASE_FEE_PROJECTION_BLOCKS, GAS_MA_WINDOW_SECS, GAS_ORACLE_DISAGREEMENT_PCT};
+use crate::data::{
+    BaseFeeForecast, ELASTICITY_MULTIPLIER, FeeDirection, ForecastConfidence, SignetMetrics,
+    SuggestedFeeTier, TargetDeviation,
+};
+use crate::rpc_stats::RpcMethod;
+use crate::session::{FeeRange, SessionStats};
+use crate::thresholds::{GasThresholds, ThresholdSource};
+use crate::timeseries::Metric;
+use crate::units;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Gauge, Paragraph, Wrap},
+};
+
+pub struct GasWidget<'a> {
+    metrics: &'a SignetMetrics,
+    label: &'static str,
+    chart: &'a ChartView,
+}
+
+impl<'a> GasWidget<'a> {
+    pub fn new(metrics: &'a SignetMetrics, label: &'static str, chart: &'a ChartView) -> Self {
+        Self {
+            metrics,
+            label,
+            chart,
+        }
+    }
+
+    /// The newest block's gas used against its limit, with the EIP-1559 target marked.
+    fn render_block_fill(&self, frame: &mut Frame, area: Rect, metrics: &SignetMetrics) {
+        let Some(block) = metrics
+            .block_history
+            .front()
+            .filter(|block| block.gas_limit > 0)
+        else {
+            frame.render_widget(
+                Paragraph::new("Latest block --").style(Style::default().fg(Color::DarkGray)),
+                area,
+            );
+            return;
+        };
+        let ratio = (block.gas_used as f64 / block.gas_limit as f64).clamp(0.0, 1.0);
+        let target = 1.0 / ELASTICITY_MULTIPLIER as f64;
+        let color = if ratio >= 0.9 {
+            Color::Red
+        } else if ratio > target {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        let label = format!(
+            "#{} {:>3.0}% ",
+            units::format_count(block.number),
+            ratio * 100.0
+        );
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(label.chars().count() as u16),
+                Constraint::Min(4),
+            ])
+            .split(area);
+        frame.render_widget(
+            Paragraph::new(label).style(Style::default().fg(color)),
+            layout[0],
+        );
+
+        let bar = layout[1];
+        frame.render_widget(
+            Gauge::default()
+                .ratio(ratio)
+                .label("")
+                .gauge_style(Style::default().fg(color).bg(Color::DarkGray)),
+            bar,
+        );
+        // Gauge has no marker, so draw the target over the cell where it falls
+        let marker = (bar.width as f64 * target) as u16;
+        if marker < bar.width {
+            let filled = marker < (bar.width as f64 * ratio).round() as u16;
+            frame.render_widget(
+                Paragraph::new("┃").style(Style::default().fg(Color::White).bg(if filled {
+                    color
+                } else {
+                    Color::DarkGray
+                })),
+                Rect::new(bar.x + marker, bar.y, 1, 1),
+            );
+        }
+    }
+}
+
+impl MetricsWidget for GasWidget<'_> {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let (metrics, label) = (self.metrics, self.label);
+        let accent = panel_accent(label);
+        let block = shell_block(format!("{} Gas Deck", label), accent);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if inner.height < 4 {
+            return;
+        }
+
+        let oracles = (!metrics.gas_oracles.is_empty()) as u16;
+        let gas_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(5 + oracles),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(1),
+            ])
+            .split(inner);
+
+        let thresholds = &metrics.gas_thresholds;
+        let trend_mult = metrics.fee_spike_multiplier();
+        let trend_style = match trend_mult {
+            Some(multiplier) if multiplier >= thresholds.spike_multiplier => {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            }
+            Some(_) => Style::default().fg(Color::Green),
+            None => Style::default().fg(Color::DarkGray),
+        };
+
+        let mut gas_lines = vec![
+            Line::from(vec![
+                kv_span(
+                    "Base",
+                    fmt_gwei_opt(metrics.base_fee_per_gas),
+                    fee_style(metrics.base_fee_per_gas, thresholds),
+                ),
+                Span::raw("  "),
+                kv_span(
+                    "Next",
+                    fmt_gwei_opt(metrics.next_base_fee_per_gas),
+                    Color::Cyan,
+                ),
+                Span::raw("  "),
+                kv_span("Legacy", fmt_gwei_opt(metrics.gas_price), Color::Gray),
+                Span::raw("  "),
+                match metrics.blob_base_fee {
+                    Some(fee) => kv_span("Blob", fmt_gwei_opt(Some(fee)), Color::Blue),
+                    None => Span::raw(""),
+                },
+            ]),
+            Line::from(vec![
+                kv_span(
+                    "RPC tip",
+                    fmt_gwei_opt(metrics.max_priority_fee_suggested),
+                    Color::Magenta,
+                ),
+                Span::raw("  "),
+                kv_span(
+                    "Safe",
+                    fmt_fee_tier(metrics.suggested_fees.as_ref().map(|fees| &fees.safe)),
+                    Color::Green,
+                ),
+                Span::raw("  "),
+                kv_span(
+                    "Fast",
+                    fmt_fee_tier(metrics.suggested_fees.as_ref().map(|fees| &fees.fast)),
+                    Color::Red,
+                ),
+                Span::raw("  "),
+                match metrics.receipt_totals() {
+                    Some((totals, blocks)) => kv_span(
+                        "Reverts",
+                        format!(
+                            "{} ({}blk)",
+                            totals
+                                .revert_pct()
+                                .map(|pct| format!("{:.1}%", pct))
+                                .unwrap_or_else(|| "--".to_string()),
+                            blocks
+                        ),
+                        Color::LightRed,
+                    ),
+                    None => Span::raw(""),
+                },
+            ]),
+            Line::from(vec![
+                Span::styled("Trend ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    trend_mult
+                        .map(|value| format!("x{:.2}", value))
+                        .unwrap_or_else(|| "--".to_string()),
+                    trend_style,
+                ),
+                Span::raw("  "),
+                kv_span(
+                    &format!("+{}blk", BASE_FEE_PROJECTION_BLOCKS),
+                    fmt_gwei_opt(metrics.projected_base_fees.last().copied()),
+                    fee_style(metrics.projected_base_fees.last().copied(), thresholds),
+                ),
+                Span::raw("  "),
+                kv_span(
+                    &format!("MA{}m", GAS_MA_WINDOW_SECS / 60),
+                    match metrics.base_fee_ma(GAS_MA_WINDOW_SECS) {
+                        Some((ma, Some(volatility))) => format!(
+                            "{}g ±{:.0}%",
+                            units::format_gwei_auto(ma as u128),
+                            volatility * 100.0
+                        ),
+                        Some((ma, None)) => format!("{}g", units::format_gwei_auto(ma as u128)),
+                        None => "--".to_string(),
+                    },
+                    Color::Gray,
+                ),
+                Span::raw("  "),
+                kv_span("Alert", fmt_thresholds(thresholds), Color::DarkGray),
+            ]),
+            if metrics.capabilities.supports(RpcMethod::FeeHistory) {
+                forecast_line(metrics.base_fee_forecast.as_ref(), thresholds)
+            } else {
+                Line::from(Span::styled(
+                    "Forecast unsupported by endpoint (eth_feeHistory)",
+                    Style::default().fg(Color::DarkGray),
+                ))
+            },
+            session_fees_line(&metrics.session),
+        ];
+        if oracles > 0 {
+            gas_lines.push(gas_oracles_line(metrics));
+        }
+
+        frame.render_widget(
+            Paragraph::new(gas_lines).wrap(Wrap { trim: true }),
+            gas_layout[0],
+        );
+
+        let utilization = metrics
+            .gas_utilization_ma_n
+            .unwrap_or_default()
+            .clamp(0.0, 100.0);
+        frame.render_widget(
+            Gauge::default()
+                .ratio(utilization / 100.0)
+                .label(match metrics.target_deviation.as_ref() {
+                    Some(deviation) => format!(
+                        "utilization {:.0}% · {}",
+                        utilization,
+                        fmt_target_deviation(deviation)
+                    ),
+                    None => format!("utilization {:.0}%", utilization),
+                })
+                .gauge_style(
+                    Style::default()
+                        .fg(accent)
+                        .bg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            gas_layout[1],
+        );
+        self.render_block_fill(frame, gas_layout[2], metrics);
+
+        let charts = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+            .split(gas_layout[3]);
+        // The legacy gas price stands in on chains without a base fee
+        let fee = [Metric::BaseFee, Metric::GasPrice]
+            .into_iter()
+            .find_map(|metric| metrics.timeseries.get(metric));
+        render_chart(
+            frame,
+            charts[0],
+            self.chart,
+            "Base fee",
+            fee,
+            accent,
+            |wei| format!("{}g", units::format_gwei_auto(wei as u128)),
+        );
+        render_chart(
+            frame,
+            charts[1],
+            self.chart,
+            "Block time",
+            metrics.timeseries.get(Metric::BlockTime),
+            Color::Cyan,
+            |secs| format!("{:.1}s", secs),
+        );
+    }
+
+    fn preferred_height(&self, _column: Rect) -> Option<u16> {
+        Some(11 + !self.metrics.gas_oracles.is_empty() as u16)
+    }
+}
+
+fn fee_style(value: Option<u128>, thresholds: &GasThresholds) -> Color {
+    match value.map(units::to_gwei) {
+        Some(gwei) if gwei >= thresholds.high_gwei => Color::Red,
+        Some(gwei) if gwei >= thresholds.high_gwei * 0.5 => Color::Yellow,
+        Some(_) => Color::Green,
+        None => Color::DarkGray,
+    }
+}
+
+/// "≥25g x2.00 auto": the high base fee and spike thresholds, and where they came from.
+fn fmt_thresholds(thresholds: &GasThresholds) -> String {
+    let source = match thresholds.source {
+        ThresholdSource::Configured => "set",
+        ThresholdSource::Default => "default",
+        ThresholdSource::Baseline => "auto",
+    };
+    format!(
+        "≥{} x{:.2} {}",
+        fmt_gwei_opt(Some(gwei_to_wei(thresholds.high_gwei))),
+        thresholds.spike_multiplier,
+        source
+    )
+}
+
+/// "Session base 3.1/12.4/40.2g  legacy 2.0/5.5/9.9g  min/avg/max" since startup.
+fn session_fees_line(session: &SessionStats) -> Line<'static> {
+    let range = |fees: &FeeRange| match (fees.min, fees.avg(), fees.max) {
+        (Some(min), Some(avg), Some(max)) => format!(
+            "{}/{}/{}g",
+            units::format_gwei_auto(min),
+            units::format_gwei_auto(avg),
+            units::format_gwei_auto(max)
+        ),
+        _ => "--".to_string(),
+    };
+    Line::from(vec![
+        Span::styled("Session ", Style::default().fg(Color::DarkGray)),
+        kv_span("base", range(&session.base_fee), Color::White),
+        Span::raw("  "),
+        kv_span("legacy", range(&session.gas_price), Color::Gray),
+        Span::styled("  min/avg/max", Style::default().fg(Color::DarkGray)),
+    ])
+}
+
+/// "Forecast +10blk ~42.1g  38.2–47.9g  conf high", shaded brighter the better the fit.
+fn forecast_line(forecast: Option<&BaseFeeForecast>, thresholds: &GasThresholds) -> Line<'static> {
+    let label = Span::styled("Forecast ", Style::default().fg(Color::DarkGray));
+    let Some(forecast) = forecast else {
+        return Line::from(vec![
+            label,
+            Span::styled("--", Style::default().fg(Color::DarkGray)),
+        ]);
+    };
+
+    let (confidence, shade) = match forecast.confidence {
+        ForecastConfidence::High => (
+            "high",
+            Style::default()
+                .fg(fee_style(Some(forecast.expected), thresholds))
+                .add_modifier(Modifier::BOLD),
+        ),
+        ForecastConfidence::Medium => (
+            "medium",
+            Style::default().fg(fee_style(Some(forecast.expected), thresholds)),
+        ),
+        ForecastConfidence::Low => ("low", Style::default().fg(Color::DarkGray)),
+    };
+    Line::from(vec![
+        label,
+        Span::styled(
+            format!(
+                "+{}blk ~{}",
+                forecast.blocks,
+                fmt_gwei_opt(Some(forecast.expected))
+            ),
+            shade,
+        ),
+        Span::styled(
+            format!(
+                "  {}–{}",
+                fmt_gwei_opt(Some(forecast.low)).trim_end_matches('g'),
+                fmt_gwei_opt(Some(forecast.high))
+            ),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(
+            format!("  conf {}", confidence),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+}
+
+/// e.g. "+38% over target for 6 blocks → fee rising".
+fn fmt_target_deviation(deviation: &TargetDeviation) -> String {
+    let blocks = if deviation.blocks == 1 {
+        "1 block".to_string()
+    } else {
+        format!("{} blocks", deviation.blocks)
+    };
+    match deviation.direction {
+        FeeDirection::Rising => format!(
+            "+{:.0}% over target for {} → fee rising",
+            deviation.avg_pct, blocks
+        ),
+        FeeDirection::Falling => format!(
+            "{:.0}% under target for {} → fee falling",
+            deviation.avg_pct.abs(),
+            blocks
+        ),
+        FeeDirection::Flat => format!("at target for {} → fee flat", blocks),
+    }
+}
+
+/// Each --gas-oracle's tiers (max fees) and its widest gap from ours, red past
+/// `GAS_ORACLE_DISAGREEMENT_PCT`.
+fn gas_oracles_line(metrics: &SignetMetrics) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        "Oracles",
+        Style::default().fg(Color::DarkGray),
+    )];
+    for reading in &metrics.gas_oracles {
+        spans.push(Span::raw("  "));
+        let Some(quote) = &reading.quote else {
+            spans.push(kv_span(
+                &reading.name,
+                reading.error.clone().unwrap_or_else(|| "--".to_string()),
+                Color::DarkGray,
+            ));
+            continue;
+        };
+        let tiers = [quote.safe, quote.standard, quote.fast]
+            .map(|fee| fee.map_or("-".to_string(), units::format_gwei_auto))
+            .join("/");
+        spans.push(kv_span(&reading.name, format!("{}g", tiers), Color::Gray));
+        let gap = metrics
+            .suggested_fees
+            .as_ref()
+            .and_then(|ours| quote.disagreement(ours));
+        if let Some(gap) = gap {
+            let style = if gap.abs() >= GAS_ORACLE_DISAGREEMENT_PCT {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Green)
+            };
+            spans.push(Span::styled(format!(" Δ{:+.0}%", gap), style));
+        }
+        if reading.error.is_some() {
+            spans.push(Span::styled(" (stale)", Style::default().fg(Color::Yellow)));
+        }
+    }
+    Line::from(spans)
+}
+
+fn fmt_fee_tier(tier: Option<&SuggestedFeeTier>) -> String {
+    match tier {
+        Some(value) if value.max_fee_per_gas > 0 && value.max_priority_fee_per_gas > 0 => format!(
+            "{}/{}",
+            fmt_gwei_opt(Some(value.max_fee_per_gas)),
+            fmt_gwei_opt(Some(value.max_priority_fee_per_gas))
+        ),
+        _ => "N/A".to_string(),
+    }
+}
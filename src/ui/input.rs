@@ -0,0 +1,231 @@
+//! Modal single-line text input
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph},
+};
+
+/// Checks a candidate value on submit; `Err` keeps the prompt open and shows the message.
+pub type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+
+/// What a key press did to the prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputOutcome {
+    Pending,
+    Submitted(String),
+    Cancelled,
+}
+
+/// Editing keys accepted by [`TextInput::handle_key`], for the help overlay.
+pub const PROMPT_KEYS: &[(&str, &str)] = &[
+    ("Enter", "submit"),
+    ("Esc", "cancel"),
+    ("← / →", "move the cursor"),
+    ("Home / ^A", "start of line"),
+    ("End / ^E", "end of line"),
+    ("^U / ^K", "delete to start / end"),
+    ("^W", "delete the previous word"),
+];
+
+pub struct TextInput {
+    title: String,
+    value: Vec<char>,
+    cursor: usize, // char index, 0..=value.len()
+    validator: Option<Validator>,
+    error: Option<String>,
+}
+
+impl TextInput {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            value: Vec::new(),
+            cursor: 0,
+            validator: None,
+            error: None,
+        }
+    }
+
+    /// Prefill the value, with the cursor at the end.
+    pub fn with_value(mut self, value: &str) -> Self {
+        self.value = value.chars().collect();
+        self.cursor = self.value.len();
+        self
+    }
+
+    pub fn with_validator(
+        mut self,
+        validator: impl Fn(&str) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
+    pub fn value(&self) -> String {
+        self.value.iter().collect()
+    }
+
+    /// Readline-style editing: arrows/Home/End, Ctrl-A/E/U/K/W, Enter submits, Esc cancels.
+    pub fn handle_key(&mut self, key: KeyEvent) -> InputOutcome {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return InputOutcome::Cancelled,
+            KeyCode::Enter => return self.submit(),
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.value.len(),
+            KeyCode::Char('u') if ctrl => {
+                self.value.drain(..self.cursor);
+                self.cursor = 0;
+            }
+            KeyCode::Char('k') if ctrl => self.value.truncate(self.cursor),
+            KeyCode::Char('w') if ctrl => self.delete_word(),
+            KeyCode::Char(c) if !ctrl => self.insert(c),
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.value.remove(self.cursor);
+            }
+            KeyCode::Delete if self.cursor < self.value.len() => {
+                self.value.remove(self.cursor);
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.value.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.len(),
+            _ => return InputOutcome::Pending,
+        }
+        self.error = None;
+        InputOutcome::Pending
+    }
+
+    /// Insert pasted text at the cursor; line breaks and other control characters are dropped.
+    pub fn paste(&mut self, text: &str) {
+        for c in text.chars().filter(|c| !c.is_control()) {
+            self.insert(c);
+        }
+        self.error = None;
+    }
+
+    fn insert(&mut self, c: char) {
+        self.value.insert(self.cursor, c);
+        self.cursor += 1;
+    }
+
+    fn delete_word(&mut self) {
+        let mut start = self.cursor;
+        while start > 0 && self.value[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        while start > 0 && !self.value[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        self.value.drain(start..self.cursor);
+        self.cursor = start;
+    }
+
+    fn submit(&mut self) -> InputOutcome {
+        let value = self.value();
+        let value = value.trim();
+        if let Some(validator) = &self.validator
+            && let Err(message) = validator(value)
+        {
+            self.error = Some(message);
+            return InputOutcome::Pending;
+        }
+        InputOutcome::Submitted(value.to_string())
+    }
+
+    /// Draw the prompt centered over `area` and place the terminal cursor in it.
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let area = super::centered_rect(area, 90, 5);
+        let block = super::shell_block(self.title.clone(), Color::Yellow);
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+        if inner.height < 2 || inner.width < 2 {
+            return;
+        }
+
+        // Scroll horizontally so the cursor stays visible
+        let width = inner.width as usize - 1;
+        let offset = self.cursor.saturating_sub(width);
+        let visible: String = self.value.iter().skip(offset).take(width).collect();
+
+        let hint = match &self.error {
+            Some(error) => Span::styled(error.clone(), Style::default().fg(Color::Red)),
+            None => Span::styled(
+                "enter submit  esc cancel  ctrl-u clear",
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(Span::styled(visible, Style::default().fg(Color::White))),
+                Line::from(hint),
+            ]),
+            inner,
+        );
+        frame.set_cursor_position((inner.x + (self.cursor - offset) as u16, inner.y));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InputOutcome, TextInput};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn edits_at_the_cursor() {
+        let mut input = TextInput::new("t").with_value("http://host");
+        input.handle_key(key(KeyCode::Home));
+        input.handle_key(key(KeyCode::Right));
+        input.handle_key(key(KeyCode::Right));
+        input.handle_key(key(KeyCode::Right));
+        input.handle_key(key(KeyCode::Right));
+        input.paste("s\n");
+        assert_eq!(input.value(), "https://host");
+
+        input.handle_key(ctrl('e'));
+        input.handle_key(key(KeyCode::Backspace));
+        input.handle_key(key(KeyCode::Char('t')));
+        assert_eq!(input.value(), "https://host");
+
+        input.paste(" extra words");
+        input.handle_key(ctrl('w'));
+        assert_eq!(input.value(), "https://host extra ");
+        input.handle_key(ctrl('u'));
+        assert_eq!(input.value(), "");
+    }
+
+    #[test]
+    fn validator_blocks_submit_until_fixed() {
+        let mut input = TextInput::new("t").with_validator(|value| {
+            if value.starts_with("http") {
+                Ok(())
+            } else {
+                Err("must be http(s)".to_string())
+            }
+        });
+        input.paste("ws://x");
+        assert_eq!(input.handle_key(key(KeyCode::Enter)), InputOutcome::Pending);
+        assert_eq!(input.error.as_deref(), Some("must be http(s)"));
+
+        input.handle_key(ctrl('u'));
+        input.paste(" http://x ");
+        assert_eq!(
+            input.handle_key(key(KeyCode::Enter)),
+            InputOutcome::Submitted("http://x".to_string())
+        );
+        assert_eq!(input.handle_key(key(KeyCode::Esc)), InputOutcome::Cancelled);
+    }
+}
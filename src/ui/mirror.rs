@@ -0,0 +1,217 @@
+//! The `futuresight mirror` screen: the remote's chains as one table, then its alerts
+use super::{
+    fmt_count_opt, fmt_gwei_opt, keys_for, relative_age, relative_age_from_ts, shell_block,
+    status_badge,
+};
+use crate::data::ConnectionStatus;
+use crate::keymap::Action;
+use crate::mirror::Mirror;
+use crate::record::Sample;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Cell, Paragraph, Row, Table},
+};
+use std::time::Duration;
+
+pub fn render_mirror(frame: &mut Frame, mirror: &Mirror) {
+    let chains_height = mirror.chains.len().max(1) as u16 + 3; // borders and header
+    let [header, chains, alerts, footer] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(chains_height),
+            Constraint::Min(4),
+            Constraint::Length(3),
+        ])
+        .areas(frame.area());
+    render_header(frame, header, mirror);
+    frame.render_widget(
+        chains_table(&mirror.chains, crate::record::unix_now())
+            .block(shell_block("Chains".to_string(), Color::Cyan)),
+        chains,
+    );
+    frame.render_widget(
+        alerts_table(mirror).block(shell_block("Alerts".to_string(), Color::Yellow)),
+        alerts,
+    );
+    render_footer(frame, footer, mirror);
+}
+
+fn render_header(frame: &mut Frame, area: Rect, mirror: &Mirror) {
+    let (state, style) = match (mirror.connected, &mirror.error) {
+        (true, _) => ("LIVE", Style::default().fg(Color::Black).bg(Color::Green)),
+        (false, None) => (
+            "CONNECTING",
+            Style::default().fg(Color::Black).bg(Color::Gray),
+        ),
+        (false, Some(_)) => (
+            "RECONNECTING",
+            Style::default().fg(Color::Black).bg(Color::Yellow),
+        ),
+    };
+    let line = Line::from(vec![
+        Span::styled(
+            " FUTURESIGHT MIRROR ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(format!(" {} ", state), style.add_modifier(Modifier::BOLD)),
+        Span::raw(" "),
+        Span::styled(mirror.url.clone(), Style::default().fg(Color::Gray)),
+    ]);
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// One row per chain, with ages measured from `now` (unix seconds).
+pub(super) fn chains_table(chains: &[Sample], now: u64) -> Table<'static> {
+    let age = |ts: u64| relative_age(Duration::from_secs(now.saturating_sub(ts)));
+    let rows: Vec<Row> = chains
+        .iter()
+        .map(|sample| {
+            let (badge, style) = status_badge(&remote_status(sample));
+            let delay_color = if sample.block_delay_alert {
+                Color::Red
+            } else {
+                Color::Gray
+            };
+            Row::new(vec![
+                Cell::from(sample.chain.clone()),
+                Cell::from(Span::styled(format!(" {} ", badge), style)),
+                Cell::from(fmt_count_opt(sample.block_number)),
+                Cell::from(
+                    sample
+                        .latest_block_timestamp
+                        .map(age)
+                        .unwrap_or_else(|| "--".to_string()),
+                )
+                .style(Style::default().fg(delay_color)),
+                Cell::from(fmt_gwei_opt(sample.base_fee_per_gas)),
+                Cell::from(fmt_gwei_opt(sample.gas_price)),
+                Cell::from(
+                    sample
+                        .gas_utilization
+                        .map(|pct| format!("{:.1}%", pct))
+                        .unwrap_or_else(|| "N/A".to_string()),
+                ),
+                Cell::from(age(sample.ts)),
+                Cell::from(sample.error.clone().unwrap_or_default())
+                    .style(Style::default().fg(Color::Red)),
+            ])
+        })
+        .collect();
+    Table::new(
+        rows,
+        [
+            Constraint::Length(20),
+            Constraint::Length(11),
+            Constraint::Length(12),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new([
+            "chain",
+            "status",
+            "block",
+            "age",
+            "base fee",
+            "gas price",
+            "util",
+            "updated",
+            "error",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .column_spacing(1)
+}
+
+fn alerts_table(mirror: &Mirror) -> Table<'_> {
+    let rows: Vec<Row> = mirror
+        .alerts
+        .iter()
+        .map(|alert| {
+            let color = if alert.is_critical() {
+                Color::Red
+            } else {
+                Color::Yellow
+            };
+            Row::new(vec![
+                Cell::from(relative_age_from_ts(alert.started)),
+                Cell::from(alert.chain.clone()),
+                Cell::from(alert.label().to_string()),
+                Cell::from(alert.summary.clone()),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+    Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(20),
+            Constraint::Length(22),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new(["fired", "chain", "alert", "value"]).style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .column_spacing(1)
+}
+
+fn render_footer(frame: &mut Frame, area: Rect, mirror: &Mirror) {
+    let mut spans = vec![
+        Span::styled(keys_for(Action::Quit), Style::default().fg(Color::Yellow)),
+        Span::styled(" quit", Style::default().fg(Color::Gray)),
+        Span::raw("  "),
+        Span::styled(
+            match mirror.last_event {
+                Some(at) => format!("last event {} ago", relative_age(at.elapsed())),
+                None => "waiting for the first snapshot".to_string(),
+            },
+            Style::default().fg(Color::Gray),
+        ),
+    ];
+    if let Some(error) = &mirror.error {
+        spans.extend([
+            Span::raw("  "),
+            Span::styled(error.clone(), Style::default().fg(Color::Yellow)),
+        ]);
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(spans))
+            .block(shell_block("Flight Notes".to_string(), Color::DarkGray)),
+        area,
+    );
+}
+
+/// A sample's status string back as the dashboard's status, for the same badges.
+fn remote_status(sample: &Sample) -> ConnectionStatus {
+    let error = || sample.error.clone().unwrap_or_default();
+    match sample.status.as_str() {
+        "connected" => ConnectionStatus::Connected,
+        "stale" => ConnectionStatus::Stale,
+        "rate_limited" => ConnectionStatus::RateLimited(error()),
+        "error" => ConnectionStatus::Error(error()),
+        _ => ConnectionStatus::Disconnected,
+    }
+}
@@ -0,0 +1,128 @@
+//! Which chain panels are shown, and in what order
+use serde::Deserialize;
+
+/// A section of a chain column. Names match the config file's `panels` list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Panel {
+    Connection,
+    Gas,
+    TxPool,
+    Blocks,
+    Probes,
+}
+
+impl Panel {
+    pub const ALL: [Panel; 5] = [
+        Panel::Connection,
+        Panel::Gas,
+        Panel::TxPool,
+        Panel::Blocks,
+        Panel::Probes,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Panel::Connection => "connection",
+            Panel::Gas => "gas",
+            Panel::TxPool => "txpool",
+            Panel::Blocks => "blocks",
+            Panel::Probes => "probes",
+        }
+    }
+}
+
+/// Every panel in display order, each enabled or hidden.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanelLayout {
+    entries: Vec<(Panel, bool)>,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self::from_config(&Panel::ALL)
+    }
+}
+
+impl PanelLayout {
+    /// Listed panels are shown in the listed order; the rest follow, hidden, so the
+    /// layout editor can still turn them on. Repeats are ignored.
+    pub fn from_config(panels: &[Panel]) -> Self {
+        let mut entries: Vec<(Panel, bool)> = Vec::with_capacity(Panel::ALL.len());
+        for panel in panels {
+            if !entries.iter().any(|(existing, _)| existing == panel) {
+                entries.push((*panel, true));
+            }
+        }
+        for panel in Panel::ALL {
+            if !entries.iter().any(|(existing, _)| *existing == panel) {
+                entries.push((panel, false));
+            }
+        }
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[(Panel, bool)] {
+        &self.entries
+    }
+
+    pub fn enabled(&self) -> impl Iterator<Item = Panel> + '_ {
+        self.entries
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(panel, _)| *panel)
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        if let Some((_, enabled)) = self.entries.get_mut(index) {
+            *enabled = !*enabled;
+        }
+    }
+
+    /// Swap the entry at `index` with its neighbour `delta` away; returns its new index.
+    pub fn move_entry(&mut self, index: usize, delta: isize) -> usize {
+        let target = index as isize + delta;
+        if index >= self.entries.len() || target < 0 || target as usize >= self.entries.len() {
+            return index;
+        }
+        self.entries.swap(index, target as usize);
+        target as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Panel, PanelLayout};
+
+    #[test]
+    fn config_order_comes_first_and_unlisted_panels_are_hidden() {
+        let layout = PanelLayout::from_config(&[Panel::Blocks, Panel::Gas, Panel::Blocks]);
+        assert_eq!(
+            layout.entries(),
+            [
+                (Panel::Blocks, true),
+                (Panel::Gas, true),
+                (Panel::Connection, false),
+                (Panel::TxPool, false),
+                (Panel::Probes, false),
+            ]
+        );
+        assert_eq!(
+            layout.enabled().collect::<Vec<_>>(),
+            [Panel::Blocks, Panel::Gas]
+        );
+    }
+
+    #[test]
+    fn editing_toggles_and_reorders() {
+        let mut layout = PanelLayout::default();
+        layout.toggle(2);
+        assert_eq!(layout.move_entry(3, -1), 2);
+        assert_eq!(layout.move_entry(0, -1), 0);
+        assert_eq!(
+            layout.enabled().collect::<Vec<_>>(),
+            [Panel::Connection, Panel::Gas, Panel::Blocks, Panel::Probes]
+        );
+        assert_eq!(layout.entries()[3], (Panel::TxPool, false));
+    }
+}
@@ -0,0 +1,188 @@
+//! The custom probes panel: `[[probes]]` calls, watched storage slots, and account proofs
+use super::widget::MetricsWidget;
+use super::{panel_accent, relative_age, shell_block};
+use crate::config::SLOT_CHANGE_HIGHLIGHT_SECS;
+use crate::data::SignetMetrics;
+use crate::proofs::ProofOutcome;
+use crate::units;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Cell, Row, Table},
+};
+use std::time::{Duration, Instant};
+
+/// One row per custom probe (its label and decoded value, or the call's error in red),
+/// then one per watched slot: its value, the raw word when decoded, and when it last
+/// changed, highlighted for a while after a change. Then the state proof of each
+/// `--verify-account` account.
+pub struct ProbesWidget<'a> {
+    metrics: &'a SignetMetrics,
+    label: &'static str,
+}
+
+impl<'a> ProbesWidget<'a> {
+    pub fn new(metrics: &'a SignetMetrics, label: &'static str) -> Self {
+        Self { metrics, label }
+    }
+}
+
+impl MetricsWidget for ProbesWidget<'_> {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let (metrics, label) = (self.metrics, self.label);
+        let block = shell_block(format!("{} Custom Probes", label), panel_accent(label));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let now = Instant::now();
+        let mut rows: Vec<(String, Cell, Cell)> = Vec::new();
+        for probe in &metrics.probes {
+            let value = match &probe.value {
+                Ok(value) => Cell::from(value.clone()).style(Style::default().fg(Color::White)),
+                Err(e) => Cell::from(e.clone()).style(Style::default().fg(Color::Red)),
+            };
+            rows.push((probe.label.clone(), value, Cell::from("")));
+        }
+        for slot in &metrics.slots {
+            let recent = slot.changed_at.is_some_and(|at| {
+                now.saturating_duration_since(at) < Duration::from_secs(SLOT_CHANGE_HIGHLIGHT_SECS)
+            });
+            let value_style = if recent {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let (value, raw) = match (slot.decoded(), slot.raw()) {
+                (Some(decoded), raw) => (decoded, raw),
+                (None, raw) => (raw.unwrap_or_else(|| "--".to_string()), None),
+            };
+            let mut note = raw.into_iter().collect::<Vec<_>>();
+            if let Some(at) = slot.changed_at {
+                note.push(format!(
+                    "changed {} ago ({}×)",
+                    relative_age(now.saturating_duration_since(at)),
+                    slot.changes
+                ));
+            }
+            let note = match &slot.error {
+                Some(e) => Cell::from(e.clone()).style(Style::default().fg(Color::Red)),
+                None => Cell::from(note.join("  ")).style(Style::default().fg(Color::DarkGray)),
+            };
+            rows.push((
+                slot.watch.label(),
+                Cell::from(value).style(value_style),
+                note,
+            ));
+        }
+        for check in &metrics.proofs {
+            let block = units::format_count(check.block);
+            let (value, note) = match &check.outcome {
+                ProofOutcome::Verified => (
+                    Cell::from(format!("verified at block {}", block))
+                        .style(Style::default().fg(Color::Green)),
+                    Cell::from(""),
+                ),
+                ProofOutcome::Mismatch(reason) => (
+                    Cell::from(format!("MISMATCH at block {}", block))
+                        .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                    Cell::from(reason.clone()).style(Style::default().fg(Color::Red)),
+                ),
+                ProofOutcome::Failed(e) => (
+                    Cell::from("unchecked").style(Style::default().fg(Color::DarkGray)),
+                    Cell::from(e.clone()).style(Style::default().fg(Color::Red)),
+                ),
+            };
+            rows.push((format!("proof {:#}", check.address), value, note));
+        }
+
+        let label_width = rows
+            .iter()
+            .map(|(label, _, _)| label.chars().count())
+            .max()
+            .unwrap_or(0) as u16;
+        let rows = rows.into_iter().map(|(label, value, note)| {
+            Row::new(vec![
+                Cell::from(label).style(Style::default().fg(Color::Gray)),
+                value,
+                note,
+            ])
+        });
+        frame.render_widget(
+            Table::new(
+                rows,
+                [
+                    Constraint::Length(label_width),
+                    Constraint::Min(10),
+                    Constraint::Min(0),
+                ],
+            )
+            .column_spacing(1),
+            inner,
+        );
+    }
+
+    /// A row per probe, slot, and proof between the borders.
+    fn preferred_height(&self, _column: Rect) -> Option<u16> {
+        let metrics = self.metrics;
+        Some((metrics.probes.len() + metrics.slots.len() + metrics.proofs.len()) as u16 + 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProbesWidget;
+    use crate::data::{Config, SignetMetrics};
+    use crate::probes::ProbeReading;
+    use crate::thresholds::DelayThresholds;
+    use crate::ui::widget::MetricsWidget;
+    use ratatui::{Terminal, backend::TestBackend, layout::Rect};
+    use std::time::Duration;
+
+    #[test]
+    fn renders_a_row_per_probe_at_its_preferred_height() {
+        let mut metrics = SignetMetrics::new(Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            block_delay: DelayThresholds {
+                warning: 30,
+                critical: 60,
+            },
+            expected_block_time: None,
+            stale_after: Duration::from_secs(15),
+            gas_alert_gwei: None,
+            gas_spike_multiplier: None,
+            max_block_history: 1,
+            txpool_max_rows: 1,
+            txpool_fetch_list: false,
+            txpool_watch_signet_host_contracts: false,
+            txpool_stream: None,
+            fetch_receipts: false,
+            http: Default::default(),
+        });
+        metrics.probes = vec![
+            ProbeReading {
+                label: "supply".to_string(),
+                value: Ok("1,000".to_string()),
+            },
+            ProbeReading {
+                label: "owner".to_string(),
+                value: Err("execution reverted".to_string()),
+            },
+        ];
+        let widget = ProbesWidget::new(&metrics, "Host");
+        let height = widget.preferred_height(Rect::new(0, 0, 40, 50)).unwrap();
+        assert_eq!(height, 4);
+
+        let mut terminal = Terminal::new(TestBackend::new(40, height)).unwrap();
+        terminal
+            .draw(|frame| widget.render(frame, frame.area()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let row = |y: u16| (0..40).map(|x| buffer[(x, y)].symbol()).collect::<String>();
+        assert!(row(0).contains("Host Custom Probes"), "{}", row(0));
+        assert!(row(1).contains("supply 1,000"), "{}", row(1));
+        assert!(row(2).contains("owner  execution"), "{}", row(2));
+    }
+}
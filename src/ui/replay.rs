@@ -0,0 +1,88 @@
+//! The `futuresight replay` screen: the mirror's chain table at the replay clock, and how
+//! far through the recording it is
+use super::{keys_for, mirror::chains_table, shell_block};
+use crate::keymap::Action;
+use crate::replay::Replay;
+use crate::report::fmt_ts;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Gauge, Paragraph},
+};
+
+pub fn render_replay(frame: &mut Frame, replay: &Replay) {
+    let chains_height = replay.chains.len().max(1) as u16 + 3; // borders and header
+    let [header, chains, timeline, _, footer] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(chains_height),
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .areas(frame.area());
+
+    let (state, style) = if replay.finished() {
+        ("ENDED", Style::default().fg(Color::Black).bg(Color::Gray))
+    } else {
+        (
+            "PLAYING",
+            Style::default().fg(Color::Black).bg(Color::Green),
+        )
+    };
+    let line = Line::from(vec![
+        Span::styled(
+            " FUTURESIGHT REPLAY ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+        Span::styled(format!(" {} ", state), style.add_modifier(Modifier::BOLD)),
+        Span::raw(" "),
+        Span::styled(
+            format!("{}  {}×", fmt_ts(replay.position()), replay.speed),
+            Style::default().fg(Color::White),
+        ),
+        Span::raw("  "),
+        Span::styled(replay.record.clone(), Style::default().fg(Color::Gray)),
+    ]);
+    frame.render_widget(Paragraph::new(line), header);
+
+    frame.render_widget(
+        chains_table(&replay.chains, replay.position())
+            .block(shell_block("Chains".to_string(), Color::Cyan)),
+        chains,
+    );
+
+    let span = replay.end().saturating_sub(replay.start()).max(1);
+    let progress = replay.position().saturating_sub(replay.start()) as f64 / span as f64;
+    frame.render_widget(
+        Gauge::default()
+            .block(shell_block("Timeline".to_string(), Color::DarkGray))
+            .gauge_style(Style::default().fg(Color::Cyan))
+            .ratio(progress.clamp(0.0, 1.0))
+            .label(format!(
+                "{} → {}  ({}/{} samples)",
+                fmt_ts(replay.start()),
+                fmt_ts(replay.end()),
+                replay.applied(),
+                replay.total()
+            )),
+        timeline,
+    );
+
+    let spans = vec![
+        Span::styled(keys_for(Action::Quit), Style::default().fg(Color::Yellow)),
+        Span::styled(" quit", Style::default().fg(Color::Gray)),
+    ];
+    frame.render_widget(
+        Paragraph::new(Line::from(spans))
+            .block(shell_block("Flight Notes".to_string(), Color::DarkGray)),
+        footer,
+    );
+}
@@ -0,0 +1,207 @@
+//! Block history search queries typed at the `/` prompt
+use crate::data::BlockInfo;
+use crate::units;
+
+/// Syntax summary shown in the prompt title and help.
+pub const SEARCH_SYNTAX: &str = "123  100-200  0xabc  >tx:100  <gas:50  >=base:20  builder:titan";
+
+/// Whitespace-separated terms that must all match.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockQuery {
+    raw: String,
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Number(u64),
+    Range(u64, u64),
+    HashPrefix(String), // lowercase, with 0x
+    Compare(Field, Op, f64),
+    Builder(String), // lowercase substring
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Number,
+    Tx,
+    Gas,  // utilization percent
+    Base, // gwei
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl BlockQuery {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let terms = raw
+            .split_whitespace()
+            .map(parse_term)
+            .collect::<Result<Vec<_>, _>>()?;
+        if terms.is_empty() {
+            return Err("empty query".to_string());
+        }
+        Ok(Self {
+            raw: raw.trim().to_string(),
+            terms,
+        })
+    }
+
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn matches(&self, block: &BlockInfo) -> bool {
+        self.terms.iter().all(|term| term.matches(block))
+    }
+}
+
+impl Term {
+    fn matches(&self, block: &BlockInfo) -> bool {
+        match self {
+            Term::Number(number) => block.number == *number,
+            Term::Range(from, to) => (*from..=*to).contains(&block.number),
+            Term::HashPrefix(prefix) => block.hash.to_lowercase().starts_with(prefix),
+            Term::Builder(needle) => block.builder.to_lowercase().contains(needle),
+            Term::Compare(field, op, value) => {
+                let actual = match field {
+                    Field::Number => block.number as f64,
+                    Field::Tx => block.tx_count as f64,
+                    Field::Gas if block.gas_limit > 0 => {
+                        block.gas_used as f64 * 100.0 / block.gas_limit as f64
+                    }
+                    Field::Gas => return false,
+                    Field::Base => match block.base_fee_per_gas {
+                        Some(wei) => units::to_gwei(wei),
+                        None => return false,
+                    },
+                };
+                match op {
+                    Op::Eq => actual == *value,
+                    Op::Gt => actual > *value,
+                    Op::Ge => actual >= *value,
+                    Op::Lt => actual < *value,
+                    Op::Le => actual <= *value,
+                }
+            }
+        }
+    }
+}
+
+fn parse_term(term: &str) -> Result<Term, String> {
+    let number = |s: &str| {
+        s.trim_start_matches('#')
+            .parse::<u64>()
+            .map_err(|_| format!("'{}' is not a block number", s))
+    };
+
+    if term.starts_with("0x") || term.starts_with("0X") {
+        let prefix = term.to_lowercase();
+        if !prefix[2..].chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("'{}' is not a hash prefix", term));
+        }
+        return Ok(Term::HashPrefix(prefix));
+    }
+
+    if let Some((op_field, value)) = term.split_once(':') {
+        let (op, field) = match op_field {
+            f if f.starts_with(">=") => (Op::Ge, &f[2..]),
+            f if f.starts_with("<=") => (Op::Le, &f[2..]),
+            f if f.starts_with('>') => (Op::Gt, &f[1..]),
+            f if f.starts_with('<') => (Op::Lt, &f[1..]),
+            f if f.starts_with('=') => (Op::Eq, &f[1..]),
+            f => (Op::Eq, f),
+        };
+        let field = match field {
+            "builder" if op == Op::Eq => return Ok(Term::Builder(value.to_lowercase())),
+            "builder" => return Err("builder only supports builder:<name>".to_string()),
+            "blk" | "number" => Field::Number,
+            "tx" => Field::Tx,
+            "gas" => Field::Gas,
+            "base" => Field::Base,
+            other => {
+                return Err(format!(
+                    "unknown field '{}': use blk, tx, gas, base, or builder",
+                    other
+                ));
+            }
+        };
+        let value = value
+            .trim_end_matches('%')
+            .parse::<f64>()
+            .map_err(|_| format!("'{}' is not a number", value))?;
+        return Ok(Term::Compare(field, op, value));
+    }
+
+    if let Some((from, to)) = term.split_once("..").or_else(|| term.split_once('-')) {
+        let (from, to) = (number(from)?, number(to)?);
+        return Ok(Term::Range(from.min(to), from.max(to)));
+    }
+
+    number(term).map(Term::Number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlockQuery;
+    use crate::data::BlockInfo;
+    use alloy::primitives::{Address, B256};
+
+    fn block(number: u64, tx_count: usize, gas_used: u64) -> BlockInfo {
+        BlockInfo {
+            number,
+            hash: format!("0xAB{:04x}", number),
+            parent_hash: String::new(),
+            state_root: B256::ZERO,
+            timestamp: 0,
+            tx_count,
+            gas_used,
+            gas_limit: 100,
+            blobs: vec![],
+            fee_recipient: Address::ZERO,
+            extra_data: String::new(),
+            builder: "Titan".to_string(),
+            tip_revenue_wei: None,
+            base_fee_per_gas: Some(20_000_000_000),
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            withdrawals_count: None,
+            size: None,
+            calldata_bytes: None,
+            receipts: None,
+        }
+    }
+
+    #[test]
+    fn terms_combine_with_and() {
+        let b = block(150, 120, 60);
+        for (query, expected) in [
+            ("150", true),
+            ("#151", false),
+            ("100-200", true),
+            ("200..100", true),
+            ("0xab00", true),
+            ("0xab01", false),
+            (">tx:100", true),
+            (">tx:100 <gas:50", false),
+            (">=base:20 builder:tit", true),
+            ("<=blk:149", false),
+        ] {
+            let q = BlockQuery::parse(query).unwrap();
+            assert_eq!(q.matches(&b), expected, "{}", query);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_terms() {
+        for query in ["", "abc", "0xzz", "size:3", ">builder:x", ">tx:many"] {
+            assert!(BlockQuery::parse(query).is_err(), "{}", query);
+        }
+    }
+}
@@ -0,0 +1,541 @@
+//! The tx-pool panel: pending and queued counts, order flow, pool history, and the newest
+//! pool transactions
+use super::widget::MetricsWidget;
+use super::{
+    fmt_eth_short, fmt_gwei_opt, kv_span, metric_or_na, panel_accent, relative_age, shell_block,
+    short_hash, trim_middle,
+};
+use crate::address_book::AddressBook;
+use crate::bundles::{BundleSimStatus, BundleSims};
+use crate::chart::ChartView;
+use crate::config::POOL_CHART_HEIGHT;
+use crate::data::{CacheCount, SignetMetrics, TxPoolMetrics, TxPoolTx};
+use crate::record::unix_now;
+use crate::timeseries::Metric;
+use crate::txpool_stream::StreamStatus;
+use crate::units;
+use alloy::primitives::Address;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table, Wrap},
+};
+
+/// The rollup's tx-pool, with order fills counted against `host`'s blocks.
+pub struct TxPoolWidget<'a> {
+    metrics: &'a SignetMetrics,
+    label: &'static str,
+    host: Option<&'a SignetMetrics>,
+    address_book: &'a AddressBook,
+    chart: &'a ChartView,
+}
+
+impl<'a> TxPoolWidget<'a> {
+    pub fn new(
+        metrics: &'a SignetMetrics,
+        label: &'static str,
+        host: Option<&'a SignetMetrics>,
+        address_book: &'a AddressBook,
+        chart: &'a ChartView,
+    ) -> Self {
+        Self {
+            metrics,
+            label,
+            host,
+            address_book,
+            chart,
+        }
+    }
+
+    /// Tx-pool cache counts over the chart window, stacked: transactions at the bottom,
+    /// then bundles and signed orders on top, so the top line is the whole pool.
+    fn render_pool_history(&self, frame: &mut Frame, area: Rect, metrics: &SignetMetrics) {
+        let now = unix_now();
+        // Braille draws two dots per cell horizontally
+        let columns = area.width as usize * 2;
+        let counts: Vec<Vec<Option<f64>>> = [
+            Metric::PoolTransactions,
+            Metric::PoolBundles,
+            Metric::PoolOrders,
+        ]
+        .into_iter()
+        .map(|metric| match metrics.timeseries.get(metric) {
+            Some(series) => self.chart.columns(series, now, columns),
+            None => vec![None; columns],
+        })
+        .collect();
+        let mut layers: [Vec<(f64, f64)>; 3] = Default::default();
+        for column in 0..columns {
+            if counts.iter().all(|series| series[column].is_none()) {
+                continue;
+            }
+            let mut stacked = 0.0;
+            for (layer, series) in layers.iter_mut().zip(&counts) {
+                stacked += series[column].unwrap_or(0.0);
+                layer.push((column as f64, stacked));
+            }
+        }
+        let top = layers[2].iter().map(|(_, y)| *y).fold(0.0, f64::max);
+        if layers[2].is_empty() {
+            frame.render_widget(
+                Paragraph::new(format!(
+                    "Pool history · {} · (no counts in this window)",
+                    self.chart.label()
+                ))
+                .style(Style::default().fg(Color::DarkGray)),
+                area,
+            );
+            return;
+        }
+
+        // The title doubles as the legend, with each series' newest count
+        let series = [
+            ("txs", Color::Cyan),
+            ("bundles", Color::Magenta),
+            ("orders", Color::Yellow),
+        ];
+        let mut title = vec![Span::styled(
+            format!("Pool history · {}", self.chart.label()),
+            Style::default().fg(Color::DarkGray),
+        )];
+        for ((name, color), values) in series.iter().zip(&counts) {
+            let newest = values.iter().rev().find_map(|value| *value);
+            title.push(Span::styled(
+                format!(
+                    "  {} {}",
+                    name,
+                    newest.map_or("--".to_string(), |count| units::format_count(count as u64))
+                ),
+                Style::default().fg(*color),
+            ));
+        }
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        frame.render_widget(Paragraph::new(Line::from(title)), layout[0]);
+
+        let datasets = series
+            .iter()
+            .zip(&layers)
+            .map(|((_, color), data)| {
+                Dataset::default()
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(*color))
+                    .data(data)
+            })
+            .collect();
+        let axis_style = Style::default().fg(Color::DarkGray);
+        let [oldest, _, newest] = self.chart.axis_labels();
+        let chart = Chart::new(datasets)
+            .x_axis(
+                Axis::default()
+                    .style(axis_style)
+                    .bounds([0.0, columns as f64 - 1.0])
+                    .labels([oldest, newest]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(axis_style)
+                    .bounds([0.0, top.max(1.0) * 1.05])
+                    .labels(["0".to_string(), units::format_count(top as u64)]),
+            );
+        frame.render_widget(chart, layout[1]);
+    }
+
+    fn render_txpool_table(&self, frame: &mut Frame, area: Rect, txpool: &TxPoolMetrics) {
+        if area.height < 3 {
+            return;
+        }
+
+        if txpool.transactions.is_empty() {
+            let mut lines = vec![Line::from(vec![Span::styled(
+                "(no transactions in view)",
+                Style::default().fg(Color::DarkGray),
+            )])];
+            if let Some(error) = &txpool.error {
+                lines.push(Line::from(vec![Span::styled(
+                    trim_middle(error, area.width.saturating_sub(2) as usize),
+                    Style::default().fg(Color::Red),
+                )]));
+            }
+            frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), area);
+            return;
+        }
+
+        let header = Row::new(vec!["hash", "route", "value", "fee", "gas", "n", "ty"]).style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        let max_rows = area.height.saturating_sub(2) as usize;
+        let rows = txpool
+            .transactions
+            .iter()
+            .take(max_rows)
+            .map(|tx| tx_row(tx, self.address_book))
+            .collect::<Vec<_>>();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(14),
+                Constraint::Min(18),
+                Constraint::Length(10),
+                Constraint::Length(12),
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Length(4),
+            ],
+        )
+        .header(header)
+        .column_spacing(1);
+
+        frame.render_widget(table, area);
+    }
+}
+
+impl MetricsWidget for TxPoolWidget<'_> {
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        let (metrics, label, host) = (self.metrics, self.label, self.host);
+        let accent = panel_accent(label);
+        let block = shell_block(format!("{} Flow Radar", label), accent);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if inner.height < 3 {
+            return;
+        }
+
+        match &metrics.txpool {
+            Some(txpool) => {
+                let mut summary = txpool_summary_lines(
+                    txpool,
+                    metrics.txpool_stream.as_ref(),
+                    inner.width as usize,
+                );
+                summary.insert(2, order_flow_line(txpool, metrics, host, self.address_book));
+                if !metrics.bundle_sims.is_empty() {
+                    summary.insert(
+                        3,
+                        bundle_sims_line(&metrics.bundle_sims, inner.width as usize),
+                    );
+                }
+                // The history chart only when the table keeps room for a few rows
+                let chart_height = if inner.height >= summary.len() as u16 + POOL_CHART_HEIGHT + 5 {
+                    POOL_CHART_HEIGHT
+                } else {
+                    0
+                };
+                let layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(summary.len() as u16),
+                        Constraint::Length(chart_height),
+                        Constraint::Min(1),
+                    ])
+                    .split(inner);
+                frame.render_widget(Paragraph::new(summary).wrap(Wrap { trim: true }), layout[0]);
+                if chart_height > 0 {
+                    self.render_pool_history(frame, layout[1], metrics);
+                }
+                self.render_txpool_table(frame, layout[2], txpool);
+            }
+            None => {
+                let lines = vec![
+                    Line::from(vec![Span::styled(
+                        "No tx-pool service configured.",
+                        Style::default().fg(Color::Gray),
+                    )]),
+                    Line::from(vec![Span::styled(
+                        "Use --txpool-url to enable rollup flow metrics.",
+                        Style::default().fg(Color::DarkGray),
+                    )]),
+                ];
+                frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), inner);
+            }
+        }
+    }
+
+    /// Two more rows for the pool history chart in columns tall enough to spare them.
+    fn preferred_height(&self, column: Rect) -> Option<u16> {
+        Some(if column.height >= 37 { 13 } else { 11 })
+    }
+}
+
+fn txpool_summary_lines(
+    txpool: &TxPoolMetrics,
+    stream: Option<&StreamStatus>,
+    width: usize,
+) -> Vec<Line<'static>> {
+    let health_style = if txpool.healthy {
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Green)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+            .fg(Color::White)
+            .bg(Color::Red)
+            .add_modifier(Modifier::BOLD)
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!(
+                    " {} ",
+                    if txpool.healthy {
+                        "HEALTHY"
+                    } else {
+                        "DEGRADED"
+                    }
+                ),
+                health_style,
+            ),
+            Span::raw(" "),
+            stream_badge(stream),
+            Span::styled(
+                trim_middle(&txpool.base_url, width.saturating_sub(26).max(22)),
+                Style::default().fg(Color::Gray),
+            ),
+        ]),
+        Line::from(vec![
+            kv_span(
+                "Tx",
+                fmt_cache_count(&txpool.transactions_cache),
+                Color::Green,
+            ),
+            Span::raw("  "),
+            kv_span(
+                "Bundles",
+                fmt_cache_count(&txpool.bundles_cache),
+                Color::Magenta,
+            ),
+            Span::raw("  "),
+            kv_span(
+                "Orders",
+                fmt_cache_count(&txpool.signed_orders_cache),
+                Color::Blue,
+            ),
+            Span::raw("  "),
+            kv_span(
+                "Updated",
+                relative_age(txpool.last_updated.elapsed()),
+                Color::Yellow,
+            ),
+        ]),
+    ];
+
+    let stream_error = stream
+        .filter(|stream| !stream.connected)
+        .and_then(|stream| stream.error.as_ref());
+    let error = match (&txpool.error, stream_error) {
+        (Some(error), Some(stream)) => Some(format!("{}, stream: {}", error, stream)),
+        (Some(error), None) => Some(error.clone()),
+        (None, Some(stream)) => Some(format!("stream: {}", stream)),
+        (None, None) => None,
+    };
+    if let Some(error) = error {
+        lines.push(Line::from(vec![Span::styled(
+            trim_middle(&error, width.saturating_sub(2)),
+            Style::default().fg(Color::Red),
+        )]));
+    }
+
+    lines
+}
+
+/// Signed orders by state, fills within each chain's block history, and the newest fill.
+fn order_flow_line(
+    txpool: &TxPoolMetrics,
+    rollup: &SignetMetrics,
+    host: Option<&SignetMetrics>,
+    address_book: &AddressBook,
+) -> Line<'static> {
+    let filled = |metrics: &SignetMetrics| {
+        metrics
+            .block_history
+            .back()
+            .map(|oldest| metrics.order_flow.filled_since(oldest.number))
+    };
+    let mut spans = vec![
+        kv_span("Pending", metric_or_na(txpool.orders_pending), Color::Cyan),
+        Span::raw("  "),
+        kv_span(
+            "Expired",
+            metric_or_na(txpool.orders_expired),
+            Color::DarkGray,
+        ),
+        Span::raw("  "),
+        kv_span(
+            "Filled",
+            format!(
+                "{} ru / {} host",
+                metric_or_na(filled(rollup)),
+                metric_or_na(host.and_then(filled))
+            ),
+            Color::Green,
+        ),
+    ];
+    // Block numbers differ per chain, so prefer the rollup's newest fill
+    let newest = rollup
+        .order_flow
+        .fills
+        .front()
+        .map(|fill| ("ru", fill))
+        .or_else(|| host?.order_flow.fills.front().map(|fill| ("host", fill)));
+    if let Some((chain, fill)) = newest {
+        let recipient = fill
+            .outputs
+            .first()
+            .map(|output| format!(" → {}", address_book.name(output.recipient)))
+            .unwrap_or_default();
+        spans.push(Span::raw("  "));
+        spans.push(kv_span(
+            "Last",
+            format!(
+                "{} #{} {} out{}",
+                chain,
+                fill.block,
+                fill.outputs.len(),
+                recipient
+            ),
+            Color::Yellow,
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Simulation outcome counts, the failure rate, and one glyph per bundle, newest first.
+fn bundle_sims_line(sims: &BundleSims, width: usize) -> Line<'static> {
+    let failure = sims
+        .failure_pct()
+        .map(|pct| format!("{:.0}%", pct))
+        .unwrap_or_else(|| "--".to_string());
+    let mut spans = vec![
+        kv_span(
+            "Sim ok",
+            sims.count(BundleSimStatus::Ok).to_string(),
+            Color::Green,
+        ),
+        Span::raw("  "),
+        kv_span(
+            "reverted",
+            sims.count(BundleSimStatus::Reverted).to_string(),
+            Color::Red,
+        ),
+        Span::raw("  "),
+        kv_span(
+            "pending",
+            sims.count(BundleSimStatus::Pending).to_string(),
+            Color::Gray,
+        ),
+        Span::raw("  "),
+        kv_span("fail", failure, Color::Yellow),
+        Span::raw("  "),
+    ];
+    let used: usize = spans.iter().map(|span| span.content.chars().count()).sum();
+    spans.extend(
+        sims.statuses()
+            .take(width.saturating_sub(used))
+            .map(|status| match status {
+                BundleSimStatus::Ok => Span::styled("✓", Style::default().fg(Color::Green)),
+                BundleSimStatus::Reverted => Span::styled("✗", Style::default().fg(Color::Red)),
+                BundleSimStatus::Pending => Span::styled("·", Style::default().fg(Color::Gray)),
+            }),
+    );
+    Line::from(spans)
+}
+
+/// `LIVE` with the pushed event count and age while the event stream is connected, `POLL`
+/// while it is down, nothing without one.
+fn stream_badge(stream: Option<&StreamStatus>) -> Span<'static> {
+    match stream {
+        Some(stream) if stream.connected => Span::styled(
+            match stream.last_event {
+                Some(at) => format!("LIVE {} {} ", stream.events, relative_age(at.elapsed())),
+                None => "LIVE ".to_string(),
+            },
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Some(_) => Span::styled("POLL ", Style::default().fg(Color::Yellow)),
+        None => Span::raw(""),
+    }
+}
+
+fn tx_row(tx: &TxPoolTx, address_book: &AddressBook) -> Row<'static> {
+    let name = |address: &Address| match address_book.label(*address) {
+        Some(label) => label.to_string(),
+        None => short_addr(address),
+    };
+    let route = format!(
+        "{} -> {}",
+        name(&tx.from),
+        tx.to.as_ref().map(name).unwrap_or_else(|| "--".to_string())
+    );
+
+    Row::new(vec![
+        Cell::from(short_hash(&tx.hash)).style(Style::default().fg(Color::Cyan)),
+        Cell::from(trim_middle(&route, 26)).style(Style::default().fg(Color::White)),
+        Cell::from(fmt_eth_short(&tx.value)).style(Style::default().fg(Color::Yellow)),
+        Cell::from(fmt_tx_fee_gwei(tx)).style(Style::default().fg(Color::Magenta)),
+        Cell::from(
+            tx.gas_limit
+                .map(units::format_count)
+                .unwrap_or_else(|| "--".to_string()),
+        )
+        .style(Style::default().fg(Color::Gray)),
+        Cell::from(tx.nonce.to_string()).style(Style::default().fg(Color::Gray)),
+        Cell::from(
+            tx.tx_type
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| "--".to_string()),
+        )
+        .style(Style::default().fg(Color::DarkGray)),
+    ])
+}
+
+/// `visible/total` when the service holds more than it returned, `visible+` when it paged
+/// without a total.
+fn fmt_cache_count(count: &CacheCount) -> String {
+    match (count.visible, count.total) {
+        (Some(visible), Some(total)) if visible != total => format!(
+            "{}/{}",
+            units::format_count(visible),
+            units::format_count(total)
+        ),
+        (_, Some(total)) => units::format_count(total),
+        (Some(visible), None) if count.more => {
+            format!("{}+", units::format_count(visible))
+        }
+        (Some(visible), None) => units::format_count(visible),
+        (None, None) => "N/A".to_string(),
+    }
+}
+
+fn fmt_tx_fee_gwei(tx: &TxPoolTx) -> String {
+    if let Some(max_fee) = tx.max_fee_per_gas {
+        let priority = tx.max_priority_fee_per_gas.unwrap_or(0);
+        format!(
+            "{}/{}",
+            fmt_gwei_opt(Some(max_fee)),
+            fmt_gwei_opt(Some(priority))
+        )
+    } else if let Some(gas_price) = tx.gas_price {
+        fmt_gwei_opt(Some(gas_price))
+    } else {
+        "N/A".to_string()
+    }
+}
+
+fn short_addr(address: &Address) -> String {
+    trim_middle(&format!("{:#x}", address), 14)
+}
@@ -0,0 +1,12 @@
+//! The interface the dashboard's chain panels share, so a column can size and stack any
+//! enabled panel without knowing what it shows
+use ratatui::{Frame, layout::Rect};
+
+/// A panel in a chain column.
+pub trait MetricsWidget {
+    fn render(&self, frame: &mut Frame, area: Rect);
+
+    /// Rows the panel wants in a column of `column`'s size, borders included; `None` for a
+    /// panel that takes whatever height the fixed ones leave.
+    fn preferred_height(&self, column: Rect) -> Option<u16>;
+}
@@ -0,0 +1,180 @@
+//! Exact wei ↔ gwei/ETH conversion, done in integers so large values keep every digit
+pub const GWEI_DECIMALS: u32 = 9;
+pub const ETH_DECIMALS: u32 = 18;
+pub const WEI_PER_GWEI: u128 = 1_000_000_000;
+pub const WEI_PER_ETH: u128 = 1_000_000_000_000_000_000;
+
+/// `wei` in gwei with `precision` decimals, e.g. `1,234.5`.
+pub fn format_gwei(wei: u128, precision: usize) -> String {
+    format_units(wei, GWEI_DECIMALS, precision, Some(','))
+}
+
+/// `wei` in gwei with precision to suit its size: `123`, `42.1`, `0.0023`.
+pub fn format_gwei_auto(wei: u128) -> String {
+    let precision = if wei >= 100 * WEI_PER_GWEI {
+        0
+    } else if wei >= WEI_PER_GWEI / 10 || wei == 0 {
+        1
+    } else {
+        // Rollup fees are fractions of a gwei; keep two significant digits
+        (10 - wei.ilog10() as usize).min(9)
+    };
+    format_gwei(wei, precision)
+}
+
+/// `wei` in ETH with `precision` decimals, e.g. `12.3456`.
+pub fn format_eth(wei: u128, precision: usize) -> String {
+    format_units(wei, ETH_DECIMALS, precision, Some(','))
+}
+
+/// `value` scaled down by `10^decimals`, rounded half up to `precision` decimals (at most
+/// `decimals`), with `separator` between thousands of the integer part.
+pub fn format_units(
+    value: u128,
+    decimals: u32,
+    precision: usize,
+    separator: Option<char>,
+) -> String {
+    let precision = precision.min(decimals as usize);
+    let scale = 10u128.pow(decimals - precision as u32);
+    let mut scaled = value / scale;
+    if scale > 1 && value % scale >= scale / 2 {
+        scaled += 1;
+    }
+    let fraction_scale = 10u128.pow(precision as u32);
+    let whole = (scaled / fraction_scale).to_string();
+    let whole = match separator {
+        Some(separator) => group_thousands(&whole, separator),
+        None => whole,
+    };
+    if precision == 0 {
+        whole
+    } else {
+        format!(
+            "{}.{:0width$}",
+            whole,
+            scaled % fraction_scale,
+            width = precision
+        )
+    }
+}
+
+/// A decimal amount such as `0.1` scaled up by `10^decimals`, exactly: no float rounding,
+/// and more fractional digits than `decimals` is an error rather than a truncation.
+pub fn parse_units(raw: &str, decimals: u32) -> Result<u128, String> {
+    let invalid = || format!("'{}' is not a decimal number", raw);
+    let (whole, fraction) = raw.trim().split_once('.').unwrap_or((raw.trim(), ""));
+    let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty()) || !digits(whole) || !digits(fraction) {
+        return Err(invalid());
+    }
+    if fraction.len() > decimals as usize {
+        return Err(format!("'{}' has more than {} decimals", raw, decimals));
+    }
+    let scale = 10u128.pow(decimals);
+    let whole = match whole {
+        "" => 0,
+        whole => whole.parse::<u128>().map_err(|_| invalid())?,
+    };
+    let fraction = match fraction {
+        "" => 0,
+        fraction => {
+            fraction.parse::<u128>().map_err(|_| invalid())?
+                * 10u128.pow(decimals - fraction.len() as u32)
+        }
+    };
+    whole
+        .checked_mul(scale)
+        .and_then(|scaled| scaled.checked_add(fraction))
+        .ok_or_else(|| format!("'{}' is too large", raw))
+}
+
+/// A positive ETH amount, e.g. `--large-transfer-eth 0.1`, in wei.
+pub fn parse_eth(raw: &str) -> Result<u128, String> {
+    match parse_units(raw, ETH_DECIMALS)? {
+        0 => Err(format!("'{}' is not a positive number", raw)),
+        wei => Ok(wei),
+    }
+}
+
+/// A count or block number with comma thousands separators: `1,234,567`.
+pub fn format_count(value: impl Into<u128>) -> String {
+    group_thousands(&value.into().to_string(), ',')
+}
+
+/// A byte count in decimal units: `812 B`, `41.3 kB`, `12.40 MB`.
+pub fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..1_000 => format!("{} B", bytes),
+        1_000..1_000_000 => format!("{:.1} kB", bytes as f64 / 1e3),
+        1_000_000..1_000_000_000 => format!("{:.2} MB", bytes as f64 / 1e6),
+        _ => format!("{:.2} GB", bytes as f64 / 1e9),
+    }
+}
+
+/// `digits` with `separator` every three places from the right: `1234567` → `1,234,567`.
+pub fn group_thousands(digits: &str, separator: char) -> String {
+    let len = digits.chars().count();
+    let mut grouped = String::with_capacity(len + len / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (len - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Gwei as a float, for comparing against thresholds and plotting; not for display.
+pub fn to_gwei(wei: u128) -> f64 {
+    wei as f64 / WEI_PER_GWEI as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        WEI_PER_ETH, format_bytes, format_count, format_eth, format_gwei, format_gwei_auto,
+        format_units, group_thousands, parse_eth, parse_units,
+    };
+
+    #[test]
+    fn formats_without_losing_precision() {
+        assert_eq!(format_gwei(1_234_567_890_123, 1), "1,234.6");
+        assert_eq!(format_gwei(12_345_678, 4), "0.0123");
+        assert_eq!(format_gwei(0, 1), "0.0");
+        assert_eq!(format_gwei_auto(123_456_000_000), "123");
+        assert_eq!(format_gwei_auto(2_345_000), "0.0023");
+        assert_eq!(format_eth(1_500_000_000_000_000_000, 0), "2");
+        assert_eq!(format_eth(41_200_000_000_000_000, 4), "0.0412");
+        // Beyond f64's 53-bit mantissa the last wei still shows
+        assert_eq!(
+            format_units(u128::MAX, 18, 18, None),
+            "340282366920938463463.374607431768211455"
+        );
+        assert_eq!(format_units(999_999, 3, 2, Some(' ')), "1 000.00");
+        assert_eq!(format_units(7, 0, 4, None), "7");
+        assert_eq!(group_thousands("123456", '_'), "123_456");
+        assert_eq!(group_thousands("12", ','), "12");
+        assert_eq!(format_count(1_234_567u64), "1,234,567");
+        assert_eq!(format_count(999u32), "999");
+        assert_eq!(format_bytes(812), "812 B");
+        assert_eq!(format_bytes(41_320), "41.3 kB");
+        assert_eq!(format_bytes(12_400_000), "12.40 MB");
+    }
+
+    #[test]
+    fn parses_decimal_amounts_exactly() {
+        // 0.1 * 1e18 as f64 is 100000000000000005551, not 1e17
+        assert_eq!(parse_eth("0.1"), Ok(WEI_PER_ETH / 10));
+        assert_eq!(parse_eth("100"), Ok(100 * WEI_PER_ETH));
+        assert_eq!(parse_eth(".5"), Ok(WEI_PER_ETH / 2));
+        assert_eq!(parse_eth("0.000000000000000001"), Ok(1));
+        assert_eq!(parse_units("1.25", 9), Ok(1_250_000_000));
+        assert!(parse_eth("0").is_err());
+        assert!(parse_eth("0.0000000000000000001").is_err());
+        assert!(parse_eth("-1").is_err());
+        assert!(parse_eth("1e3").is_err());
+        assert!(parse_eth(".").is_err());
+        assert!(parse_eth("1000000000000000000000").is_err());
+    }
+}
@@ -0,0 +1,505 @@
+//! `watchlist.toml`: addresses, transactions, and log filters that every chain's collector
+//! checks on each new head, re-read when the file changes so scripts or another terminal
+//! can add entries while the dashboard runs
+use crate::config::WATCHLIST_ACTIVITY_ROWS;
+use crate::error::{FutureSightError, Result};
+use alloy::primitives::{Address, B256, U256, keccak256};
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    collections::VecDeque,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Watchlist {
+    #[serde(default)]
+    pub addresses: Vec<Address>,
+    #[serde(default)]
+    pub transactions: Vec<B256>,
+    #[serde(default)]
+    pub events: Vec<EventFilter>,
+}
+
+/// Logs matching an emitter and leading topics, e.g.
+/// `{ name = "USDC transfers", address = "0x…", signature = "Transfer(address,address,uint256)" }`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventFilter {
+    pub name: String,
+    pub address: Option<Address>,
+    pub signature: Option<String>, // hashed into topic0
+    #[serde(default)]
+    pub topics: Vec<B256>, // after topic0 if `signature` is set
+}
+
+impl EventFilter {
+    /// Topics in `eth_getLogs` order, the signature's hash first.
+    pub fn topics(&self) -> Vec<B256> {
+        self.signature
+            .iter()
+            .map(|signature| keccak256(signature.as_bytes()))
+            .chain(self.topics.iter().copied())
+            .collect()
+    }
+}
+
+impl Watchlist {
+    pub fn parse(raw: &str) -> Result<Self> {
+        let list: Self =
+            toml::from_str(raw).map_err(|e| FutureSightError::Config(e.to_string()))?;
+        if let Some(filter) = list
+            .events
+            .iter()
+            .find(|filter| filter.address.is_none() && filter.topics().is_empty())
+        {
+            return Err(FutureSightError::Config(format!(
+                "event '{}' needs an address, signature, or topics",
+                filter.name
+            )));
+        }
+        Ok(list)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path).map_err(|e| {
+            FutureSightError::Config(format!("cannot read watchlist '{}': {}", path.display(), e))
+        })?;
+        Self::parse(&raw).map_err(|e| {
+            FutureSightError::Config(format!("invalid watchlist '{}': {}", path.display(), e))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty() && self.transactions.is_empty() && self.events.is_empty()
+    }
+
+    /// Empty readings for this list, carrying over those of entries `previous` already had.
+    pub fn readings(&self, previous: &WatchlistReadings) -> WatchlistReadings {
+        WatchlistReadings {
+            accounts: self
+                .addresses
+                .iter()
+                .map(|address| {
+                    previous
+                        .accounts
+                        .iter()
+                        .find(|reading| reading.address == *address)
+                        .cloned()
+                        .unwrap_or_else(|| AccountReading::new(*address))
+                })
+                .collect(),
+            transactions: self
+                .transactions
+                .iter()
+                .map(|hash| {
+                    previous
+                        .transactions
+                        .iter()
+                        .find(|reading| reading.hash == *hash)
+                        .cloned()
+                        .unwrap_or_else(|| TxReading::new(*hash))
+                })
+                .collect(),
+            events: self
+                .events
+                .iter()
+                .map(|filter| {
+                    previous
+                        .events
+                        .iter()
+                        .find(|reading| reading.filter == *filter)
+                        .cloned()
+                        .unwrap_or_else(|| EventReading::new(filter.clone()))
+                })
+                .collect(),
+            activity: previous.activity.clone(),
+        }
+    }
+}
+
+/// A watched address's balance and nonce at the newest block checked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountReading {
+    pub address: Address,
+    pub balance: Option<U256>, // wei
+    pub nonce: Option<u64>,
+    pub block: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl AccountReading {
+    fn new(address: Address) -> Self {
+        Self {
+            address,
+            balance: None,
+            nonce: None,
+            block: None,
+            error: None,
+        }
+    }
+
+    /// Store the balance and nonce read at `block`. The change from the previous reading,
+    /// if either moved; the first reading is not a change.
+    pub fn update(
+        &mut self,
+        balance: U256,
+        nonce: u64,
+        block: u64,
+        at: u64,
+    ) -> Option<AccountChange> {
+        let change = match (self.balance, self.nonce, self.block) {
+            (Some(old_balance), Some(old_nonce), Some(since))
+                if old_balance != balance || old_nonce != nonce =>
+            {
+                Some(AccountChange {
+                    address: self.address,
+                    since,
+                    block,
+                    at,
+                    balance: (old_balance != balance).then_some((old_balance, balance)),
+                    nonce: (old_nonce != nonce).then_some((old_nonce, nonce)),
+                })
+            }
+            _ => None,
+        };
+        self.balance = Some(balance);
+        self.nonce = Some(nonce);
+        self.block = Some(block);
+        change
+    }
+}
+
+/// A watched address's balance or nonce moving between two readings. It happened in one
+/// of the blocks after `since`, up to and including `block`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountChange {
+    pub address: Address,
+    pub since: u64, // block of the previous reading
+    pub block: u64,
+    pub at: u64,                       // unix seconds it was seen
+    pub balance: Option<(U256, U256)>, // (before, after) wei, if it moved
+    pub nonce: Option<(u64, u64)>,     // (before, after), if it moved
+}
+
+impl AccountChange {
+    /// The only block the change can be in, when the readings were one block apart.
+    pub fn exact_block(&self) -> Option<u64> {
+        (self.block == self.since + 1).then_some(self.block)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxDirection {
+    In,
+    Out,
+    ToSelf,
+}
+
+/// A transaction a watched address sent or received, found in a full block body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddressTx {
+    pub address: Address, // the watched one
+    pub direction: TxDirection,
+    pub counterparty: Option<Address>, // None for a contract creation
+    pub value: U256,                   // wei
+    pub hash: B256,
+    pub block: u64,
+    pub at: u64, // the block's timestamp
+}
+
+/// Transactions in a block fetched with full bodies that `addresses` sent or received, in
+/// block order. `None` for hash-only bodies.
+pub fn address_txs(block: &Value, addresses: &[Address]) -> Option<Vec<AddressTx>> {
+    let quantity = |key: &str| {
+        block
+            .get(key)
+            .and_then(Value::as_str)
+            .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+    };
+    let (number, timestamp) = (quantity("number")?, quantity("timestamp").unwrap_or(0));
+    let transactions = block.get("transactions")?.as_array()?;
+    if transactions.iter().any(Value::is_string) {
+        return None;
+    }
+    let field = |tx: &Value, key: &str| -> Option<Address> { tx.get(key)?.as_str()?.parse().ok() };
+    let mut found = Vec::new();
+    for tx in transactions {
+        let (from, to) = (field(tx, "from"), field(tx, "to"));
+        let value = tx
+            .get("value")
+            .and_then(Value::as_str)
+            .and_then(|hex| U256::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+            .unwrap_or_default();
+        let hash = tx
+            .get("hash")
+            .and_then(Value::as_str)
+            .and_then(|hash| hash.parse().ok())
+            .unwrap_or_default();
+        for address in addresses {
+            let (direction, counterparty) = match (from == Some(*address), to == Some(*address)) {
+                (true, true) => (TxDirection::ToSelf, Some(*address)),
+                (true, false) => (TxDirection::Out, to),
+                (false, true) => (TxDirection::In, from),
+                (false, false) => continue,
+            };
+            found.push(AddressTx {
+                address: *address,
+                direction,
+                counterparty,
+                value,
+                hash,
+                block: number,
+                at: timestamp,
+            });
+        }
+    }
+    Some(found)
+}
+
+/// One entry of a chain's watchlist activity feed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Activity {
+    Change(AccountChange),
+    Tx(AddressTx),
+}
+
+/// Where a tracked transaction landed, from its receipt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inclusion {
+    pub block: u64,
+    pub succeeded: bool,
+    pub gas_used: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TxReading {
+    pub hash: B256,
+    pub inclusion: Option<Inclusion>, // None until a receipt turns up; not re-checked after
+    pub error: Option<String>,
+}
+
+impl TxReading {
+    fn new(hash: B256) -> Self {
+        Self {
+            hash,
+            inclusion: None,
+            error: None,
+        }
+    }
+}
+
+/// Logs matched by one filter since it was added to the watchlist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventReading {
+    pub filter: EventFilter,
+    pub matches: u64,
+    pub last_block: Option<u64>, // newest block with a match
+    pub error: Option<String>,
+}
+
+impl EventReading {
+    fn new(filter: EventFilter) -> Self {
+        Self {
+            filter,
+            matches: 0,
+            last_block: None,
+            error: None,
+        }
+    }
+}
+
+/// One chain's readings for every watchlist entry, in file order, and the account activity
+/// seen so far.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WatchlistReadings {
+    pub accounts: Vec<AccountReading>,
+    pub transactions: Vec<TxReading>,
+    pub events: Vec<EventReading>,
+    pub activity: VecDeque<Activity>, // newest first, at most WATCHLIST_ACTIVITY_ROWS
+}
+
+impl WatchlistReadings {
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.transactions.is_empty() && self.events.is_empty()
+    }
+
+    pub fn log(&mut self, activity: Activity) {
+        self.activity.push_front(activity);
+        self.activity.truncate(WATCHLIST_ACTIVITY_ROWS);
+    }
+}
+
+/// An `eth_getTransactionReceipt` result; `None` for the `null` of an unknown transaction.
+pub fn parse_receipt(receipt: &Value) -> Option<Inclusion> {
+    let quantity = |key: &str| {
+        receipt
+            .get(key)
+            .and_then(Value::as_str)
+            .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+    };
+    Some(Inclusion {
+        block: quantity("blockNumber")?,
+        succeeded: quantity("status").is_none_or(|status| status == 1),
+        gas_used: quantity("gasUsed").unwrap_or(0),
+    })
+}
+
+/// A watchlist path and the modification time of the version last loaded.
+#[derive(Debug)]
+pub struct WatchlistFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl WatchlistFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            modified: None,
+        }
+    }
+
+    /// Re-read the file if its modification time changed since the last poll. A file that
+    /// disappears yields an empty watchlist; `None` means nothing changed.
+    pub fn poll(&mut self) -> Option<Result<Watchlist>> {
+        let modified = fs::metadata(&self.path)
+            .and_then(|meta| meta.modified())
+            .ok();
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        Some(match modified {
+            Some(_) => Watchlist::load(&self.path),
+            None => Ok(Watchlist::default()),
+        })
+    }
+}
+
+/// `watchlist.toml` next to the default `config.toml`.
+pub fn default_watchlist_path() -> Option<PathBuf> {
+    crate::settings::default_config_path().map(|path| path.with_file_name("watchlist.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Activity, TxDirection, Watchlist, WatchlistReadings, address_txs, parse_receipt};
+    use crate::config::WATCHLIST_ACTIVITY_ROWS;
+    use alloy::primitives::{Address, B256, U256, keccak256};
+    use serde_json::json;
+
+    #[test]
+    fn parses_entries_and_keeps_readings_across_reloads() {
+        let list = Watchlist::parse(
+            r#"
+            addresses = ["0x00000000000000000000000000000000000000aa"]
+            transactions = ["0x1111111111111111111111111111111111111111111111111111111111111111"]
+
+            [[events]]
+            name = "transfers"
+            signature = "Transfer(address,address,uint256)"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            list.events[0].topics(),
+            [keccak256("Transfer(address,address,uint256)")]
+        );
+
+        let mut readings = list.readings(&WatchlistReadings::default());
+        readings.accounts[0].balance = Some(U256::from(5));
+        readings.events[0].matches = 3;
+        // Adding an address keeps what the existing entries already read
+        let mut grown = list.clone();
+        grown.addresses.push(Address::with_last_byte(0xbb));
+        let carried = grown.readings(&readings);
+        assert_eq!(carried.accounts[0].balance, Some(U256::from(5)));
+        assert_eq!(carried.accounts[1].balance, None);
+        assert_eq!(carried.events[0].matches, 3);
+        assert_eq!(carried.transactions[0].hash, B256::repeat_byte(0x11));
+
+        assert!(Watchlist::parse("[[events]]\nname = \"everything\"").is_err());
+        assert!(Watchlist::parse("adresses = []").is_err());
+    }
+
+    #[test]
+    fn logs_balance_and_nonce_changes() {
+        let list = Watchlist::parse("addresses = [\"0x00000000000000000000000000000000000000aa\"]")
+            .unwrap();
+        let mut readings = list.readings(&WatchlistReadings::default());
+        let account = &mut readings.accounts[0];
+        assert_eq!(account.update(U256::from(100), 3, 10, 0), None);
+        assert_eq!(account.update(U256::from(100), 3, 11, 0), None);
+        let change = account.update(U256::from(40), 4, 12, 7).unwrap();
+        assert_eq!(change.balance, Some((U256::from(100), U256::from(40))));
+        assert_eq!(change.nonce, Some((3, 4)));
+        assert_eq!(change.exact_block(), Some(12));
+        // Several blocks between readings: only the range is known
+        let change = account.update(U256::from(50), 4, 20, 9).unwrap();
+        assert_eq!(
+            (change.nonce, change.since, change.exact_block()),
+            (None, 12, None)
+        );
+
+        for _ in 0..=WATCHLIST_ACTIVITY_ROWS {
+            readings.log(Activity::Change(change.clone()));
+        }
+        assert_eq!(readings.activity.len(), WATCHLIST_ACTIVITY_ROWS);
+        // Reloading the watchlist keeps the feed
+        assert_eq!(
+            list.readings(&readings).activity.len(),
+            WATCHLIST_ACTIVITY_ROWS
+        );
+    }
+
+    #[test]
+    fn finds_transactions_of_watched_addresses() {
+        let (a, b) = (Address::with_last_byte(0xaa), Address::with_last_byte(0xbb));
+        let other = Address::with_last_byte(0xcc);
+        let block = json!({
+            "number": "0x10",
+            "timestamp": "0x64",
+            "transactions": [
+                {"hash": B256::repeat_byte(1), "from": other, "to": a, "value": "0xde0b6b3a7640000"},
+                {"hash": B256::repeat_byte(2), "from": a, "to": null, "value": "0x0"},
+                {"hash": B256::repeat_byte(3), "from": other, "to": other, "value": "0x1"},
+                {"hash": B256::repeat_byte(4), "from": a, "to": b, "value": "0x5"},
+            ]
+        });
+        let found = address_txs(&block, &[a, b]).unwrap();
+        let summary: Vec<_> = found
+            .iter()
+            .map(|tx| (tx.address, tx.direction, tx.counterparty, tx.hash))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (a, TxDirection::In, Some(other), B256::repeat_byte(1)),
+                (a, TxDirection::Out, None, B256::repeat_byte(2)),
+                (a, TxDirection::Out, Some(b), B256::repeat_byte(4)),
+                (b, TxDirection::In, Some(a), B256::repeat_byte(4)),
+            ]
+        );
+        assert_eq!(found[0].value, U256::from(10u128.pow(18)));
+        assert_eq!((found[0].block, found[0].at), (16, 100));
+        assert_eq!(
+            address_txs(&json!({"number": "0x1", "transactions": ["0x01"]}), &[a]),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_inclusion_from_receipts() {
+        let receipt = json!({"blockNumber": "0x10", "status": "0x0", "gasUsed": "0x5208"});
+        let inclusion = parse_receipt(&receipt).unwrap();
+        assert_eq!(
+            (inclusion.block, inclusion.succeeded, inclusion.gas_used),
+            (16, false, 21_000)
+        );
+        assert_eq!(parse_receipt(&json!(null)), None);
+    }
+}